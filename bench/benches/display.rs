@@ -0,0 +1,39 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use dangerous::{Expected, Input};
+
+fn bench_input_display(c: &mut Criterion) {
+    let bytes: Vec<u8> = (0..=255).cycle().take(4096).collect();
+    let input = dangerous::input(&bytes[..]);
+    c.bench_function("input_display_bytes", |b| {
+        b.iter(|| black_box(input.display().full()).to_string())
+    });
+    c.bench_function("input_display_bytes_underline", |b| {
+        b.iter(|| {
+            black_box(input.display().span(bytes[2048..2052].into(), 1024))
+                .underline()
+                .to_string()
+        })
+    });
+    let text = "hello world ".repeat(341);
+    let input = dangerous::input(text.as_str());
+    c.bench_function("input_display_str", |b| {
+        b.iter(|| black_box(input.display().full()).to_string())
+    });
+}
+
+fn bench_error_display(c: &mut Criterion) {
+    let bytes: Vec<u8> = (0..=255).cycle().take(4096).collect();
+    let error = dangerous::input(&bytes[..])
+        .read_all::<_, _, Expected>(|r| {
+            r.consume(&bytes[..2048])?;
+            r.consume(b"hello")
+        })
+        .unwrap_err();
+    c.bench_function("error_display", |b| {
+        b.iter(|| black_box(error.display().input_max_width(1024)).to_string())
+    });
+}
+
+criterion_group!(benches, bench_input_display, bench_error_display);
+criterion_main!(benches);
@@ -1,5 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
+use dangerous::input::BytePattern;
 use dangerous::{input, ByteArray, BytesReader, Input, Invalid};
 
 fn bench_consume(c: &mut Criterion) {
@@ -103,5 +104,35 @@ fn bench_peek_eq(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_peek_eq, bench_consume, bench_read_num);
+fn bench_take_while(c: &mut Criterion) {
+    c.bench_function("take_while_fn", |b| {
+        b.iter(|| {
+            input(black_box(&[b'7'; 1024]))
+                .read_all(|r: &mut BytesReader<'_, Invalid>| {
+                    r.take_while(|b: u8| b.is_ascii_digit());
+                    Ok(())
+                })
+                .unwrap();
+        })
+    });
+
+    c.bench_function("take_while_byte_pattern", |b| {
+        b.iter(|| {
+            input(black_box(&[b'7'; 1024]))
+                .read_all(|r: &mut BytesReader<'_, Invalid>| {
+                    r.take_while(BytePattern::Range(b'0', b'9'));
+                    Ok(())
+                })
+                .unwrap();
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_peek_eq,
+    bench_consume,
+    bench_read_num,
+    bench_take_while
+);
 criterion_main!(benches);
@@ -0,0 +1,123 @@
+use syn::meta::ParseNestedMeta;
+use syn::{Attribute, Error, Expr, LitStr, Result, Type};
+
+#[derive(Copy, Clone, PartialEq)]
+pub(crate) enum Endian {
+    Big,
+    Little,
+}
+
+/// Attributes on the struct or enum.
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    pub(crate) endian: Option<Endian>,
+    pub(crate) magic: Option<Expr>,
+    pub(crate) tag: Option<Type>,
+}
+
+/// Attributes on an enum variant.
+#[derive(Default)]
+pub(crate) struct VariantAttrs {
+    pub(crate) tag: Option<Expr>,
+}
+
+/// Attributes on a field.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    pub(crate) endian: Option<Endian>,
+    pub(crate) prefix: Option<Type>,
+    pub(crate) len: Option<Expr>,
+    pub(crate) pad_before: Option<Expr>,
+    pub(crate) pad_after: Option<Expr>,
+    pub(crate) eq: Option<Expr>,
+}
+
+impl ContainerAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = Self::default();
+        parse_each(attrs, |meta| {
+            if meta.path.is_ident("endian") {
+                parsed.endian = Some(parse_endian(&meta)?);
+            } else if meta.path.is_ident("magic") {
+                parsed.magic = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("tag") {
+                parsed.tag = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unsupported container attribute"));
+            }
+            Ok(())
+        })?;
+        Ok(parsed)
+    }
+}
+
+impl VariantAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = Self::default();
+        parse_each(attrs, |meta| {
+            if meta.path.is_ident("tag") {
+                parsed.tag = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unsupported variant attribute"));
+            }
+            Ok(())
+        })?;
+        Ok(parsed)
+    }
+}
+
+impl FieldAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = Self::default();
+        parse_each(attrs, |meta| {
+            if meta.path.is_ident("endian") {
+                parsed.endian = Some(parse_endian(&meta)?);
+            } else if meta.path.is_ident("prefix") {
+                parsed.prefix = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("len") {
+                parsed.len = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("pad_before") {
+                parsed.pad_before = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("pad_after") {
+                parsed.pad_after = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("eq") {
+                parsed.eq = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unsupported field attribute"));
+            }
+            Ok(())
+        })?;
+        if let (Some(_), Some(len)) = (&parsed.prefix, &parsed.len) {
+            return Err(Error::new_spanned(
+                len,
+                "`len` and `prefix` can't be used together",
+            ));
+        }
+        Ok(parsed)
+    }
+}
+
+fn parse_each<F>(attrs: &[Attribute], mut f: F) -> Result<()>
+where
+    F: FnMut(ParseNestedMeta<'_>) -> Result<()>,
+{
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("dangerous"))
+    {
+        attr.parse_nested_meta(&mut f)?;
+    }
+    Ok(())
+}
+
+fn parse_endian(meta: &ParseNestedMeta<'_>) -> Result<Endian> {
+    let value: LitStr = meta.value()?.parse()?;
+    match value.value().as_str() {
+        "big" => Ok(Endian::Big),
+        "little" => Ok(Endian::Little),
+        _ => Err(Error::new_spanned(
+            value,
+            "expected endian to be \"big\" or \"little\"",
+        )),
+    }
+}
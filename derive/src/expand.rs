@@ -0,0 +1,256 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    Data, DataEnum, DataStruct, DeriveInput, Error, Fields, GenericParam, Ident, Lifetime,
+    LifetimeParam, Result, Type,
+};
+
+use crate::attr::{ContainerAttrs, Endian, FieldAttrs, VariantAttrs};
+
+const INTEGERS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128",
+];
+
+pub(crate) fn read_binary(input: &DeriveInput) -> Result<TokenStream> {
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let name = &input.ident;
+    let context = name.to_string();
+
+    let mut generics = input.generics.clone();
+    let lifetime = match generics.lifetimes().count() {
+        0 => {
+            let lifetime = Lifetime::new("'i", Span::call_site());
+            generics.params.insert(
+                0,
+                GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+            );
+            lifetime
+        }
+        1 => generics.lifetimes().next().unwrap().lifetime.clone(),
+        _ => {
+            return Err(Error::new_spanned(
+                &input.generics,
+                "`ReadBinary` supports at most one lifetime",
+            ))
+        }
+    };
+    let bounds = generics
+        .type_params()
+        .map(|param| {
+            let ident = &param.ident;
+            quote!(#ident: ::dangerous::FromReader<#lifetime>)
+        })
+        .collect::<Vec<_>>();
+    let where_clause = generics.make_where_clause();
+    for bound in bounds {
+        where_clause.predicates.push(syn::parse2(bound)?);
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let cx = Cx {
+        lifetime: &lifetime,
+        endian: attrs.endian.unwrap_or(Endian::Little),
+    };
+    let body = match &input.data {
+        Data::Struct(data) => cx.read_struct(&attrs, data)?,
+        Data::Enum(data) => cx.read_enum(name, &attrs, data)?,
+        Data::Union(data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "`ReadBinary` does not support unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::dangerous::FromReader<#lifetime> for #name #ty_generics #where_clause {
+            fn from_reader<__E>(
+                __r: &mut ::dangerous::BytesReader<#lifetime, __E>,
+            ) -> ::core::result::Result<Self, __E>
+            where
+                __E: ::dangerous::Error<#lifetime>,
+            {
+                __r.context(#context, |__r| { #body })
+            }
+        }
+    })
+}
+
+struct Cx<'a> {
+    lifetime: &'a Lifetime,
+    endian: Endian,
+}
+
+impl Cx<'_> {
+    fn read_struct(&self, attrs: &ContainerAttrs, data: &DataStruct) -> Result<TokenStream> {
+        if let Some(tag) = &attrs.tag {
+            return Err(Error::new_spanned(tag, "`tag` is only supported on enums"));
+        }
+        let magic = attrs
+            .magic
+            .as_ref()
+            .map(|magic| quote!(__r.context("magic", |__r| __r.consume(#magic))?;));
+        let fields = self.read_fields(&quote!(Self), &data.fields)?;
+        Ok(quote! {
+            #magic
+            #fields
+        })
+    }
+
+    fn read_enum(
+        &self,
+        name: &Ident,
+        attrs: &ContainerAttrs,
+        data: &DataEnum,
+    ) -> Result<TokenStream> {
+        if let Some(magic) = &attrs.magic {
+            return Err(Error::new_spanned(
+                magic,
+                "`magic` is only supported on structs",
+            ));
+        }
+        let tag_ty = attrs
+            .tag
+            .as_ref()
+            .ok_or_else(|| Error::new_spanned(name, "`ReadBinary` enums require a `tag` type"))?;
+        if data.variants.is_empty() {
+            return Err(Error::new_spanned(
+                name,
+                "`ReadBinary` enums require at least one variant",
+            ));
+        }
+        let read_tag = self.read_value(tag_ty, None)?;
+        let expected = format!("{name} tag");
+        let mut checks = Vec::new();
+        let mut arms = Vec::new();
+        for (index, variant) in data.variants.iter().enumerate() {
+            let tag = VariantAttrs::parse(&variant.attrs)?.tag.ok_or_else(|| {
+                Error::new_spanned(&variant.ident, "`ReadBinary` variants require a `tag`")
+            })?;
+            checks.push(quote!(if __tag == (#tag) { return Ok(Some(#index)); }));
+            let ident = &variant.ident;
+            let context = ident.to_string();
+            let fields = self.read_fields(&quote!(Self::#ident), &variant.fields)?;
+            let read = quote!(__r.context(#context, |__r| { #fields }));
+            // The last variant is the catch-all arm, as the index is known to
+            // be one of the variants.
+            if index + 1 == data.variants.len() {
+                arms.push(quote!(_ => #read));
+            } else {
+                arms.push(quote!(#index => #read,));
+            }
+        }
+        Ok(quote! {
+            let __index = __r.context("tag", |__r| {
+                __r.try_expect(#expected, |__r| {
+                    let __tag = #read_tag;
+                    #(#checks)*
+                    Ok(None)
+                })
+            })?;
+            match __index {
+                #(#arms)*
+            }
+        })
+    }
+
+    /// Read the fields into bindings and construct the value from them.
+    fn read_fields(&self, path: &TokenStream, fields: &Fields) -> Result<TokenStream> {
+        let mut reads = Vec::new();
+        let mut bindings = Vec::new();
+        for (index, field) in fields.iter().enumerate() {
+            let attrs = FieldAttrs::parse(&field.attrs)?;
+            let (binding, context) = match &field.ident {
+                Some(ident) => (ident.clone(), ident.to_string()),
+                None => (format_ident!("__field{}", index), index.to_string()),
+            };
+            let read = self.read_field(&field.ty, &attrs)?;
+            reads.push(quote! {
+                let #binding = __r.context(#context, |__r| { #read })?;
+            });
+            bindings.push(binding);
+        }
+        let construct = match fields {
+            Fields::Named(_) => quote!(#path { #(#bindings),* }),
+            Fields::Unnamed(_) => quote!(#path ( #(#bindings),* )),
+            Fields::Unit => quote!(#path),
+        };
+        Ok(quote! {
+            #(#reads)*
+            Ok(#construct)
+        })
+    }
+
+    /// Read a field, returning it from the block.
+    fn read_field(&self, ty: &Type, attrs: &FieldAttrs) -> Result<TokenStream> {
+        let lifetime = self.lifetime;
+        let pad_before = attrs
+            .pad_before
+            .as_ref()
+            .map(|pad| quote!(__r.skip(#pad)?;));
+        let pad_after = attrs.pad_after.as_ref().map(|pad| quote!(__r.skip(#pad)?;));
+        let len = match (&attrs.prefix, &attrs.len) {
+            (Some(prefix), _) => {
+                let read_prefix = self.read_value(prefix, attrs.endian)?;
+                Some(quote!(::core::convert::TryFrom::try_from(#read_prefix)))
+            }
+            (None, Some(len)) => Some(quote!(::core::convert::TryFrom::try_from(#len))),
+            (None, None) => None,
+        };
+        let read = match len {
+            Some(len) => quote! {{
+                // A length that does not fit will fail to be read.
+                let __len: usize = #len.unwrap_or(usize::MAX);
+                <#ty as ::dangerous::derive::FromReaderLen<#lifetime>>::from_reader_len(__r, __len)?
+            }},
+            None => self.read_value(ty, attrs.endian)?,
+        };
+        let read = match &attrs.eq {
+            Some(eq) => {
+                let expected = quote!(#eq).to_string();
+                quote! {
+                    __r.try_expect(#expected, |__r| {
+                        let __value = #read;
+                        Ok(if __value == (#eq) { Some(__value) } else { None })
+                    })?
+                }
+            }
+            None => read,
+        };
+        Ok(quote! {
+            #pad_before
+            let __value = #read;
+            #pad_after
+            Ok(__value)
+        })
+    }
+
+    /// Read a value of a type, honouring the endianness for integers.
+    fn read_value(&self, ty: &Type, endian: Option<Endian>) -> Result<TokenStream> {
+        let lifetime = self.lifetime;
+        let integer = is_integer(ty);
+        if endian.is_some() && !integer {
+            return Err(Error::new_spanned(
+                ty,
+                "`endian` is only supported on integer fields",
+            ));
+        }
+        Ok(match endian.unwrap_or(self.endian) {
+            Endian::Big if integer => quote! {
+                <#ty>::from_be_bytes(::dangerous::ByteArray::into_dangerous(__r.take_array()?))
+            },
+            _ => quote!(<#ty as ::dangerous::FromReader<#lifetime>>::from_reader(__r)?),
+        })
+    }
+}
+
+fn is_integer(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => match path.path.get_ident() {
+            Some(ident) => INTEGERS.iter().any(|int| ident == int),
+            None => false,
+        },
+        _ => false,
+    }
+}
@@ -0,0 +1,98 @@
+//! Derive macros for [`dangerous`](https://docs.rs/dangerous).
+//!
+//! Use these through the `derive` feature of `dangerous`, which re-exports
+//! them.
+
+#![forbid(unsafe_code)]
+#![deny(
+    unused,
+    missing_docs,
+    rust_2018_idioms,
+    future_incompatible,
+    clippy::all,
+    clippy::correctness,
+    clippy::style,
+    clippy::complexity,
+    clippy::perf,
+    clippy::pedantic
+)]
+#![allow(clippy::module_name_repetitions)]
+
+mod attr;
+mod expand;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derive `FromReader` for a binary struct or enum layout.
+///
+/// Fields are read in order with `FromReader`, each within a context of the
+/// field name, and the whole value within a context of the type name.
+///
+/// # Container attributes
+///
+/// | Attribute                        | Description                                         |
+/// | -------------------------------- | --------------------------------------------------- |
+/// | `endian = "big"` / `"little"`    | Endianness of integer fields (default `"little"`).  |
+/// | `magic = <expr>`                 | Consume a prefix before the fields (structs only).  |
+/// | `tag = <type>`                   | The integer type of the variant tag (enums only).   |
+///
+/// # Variant attributes
+///
+/// | Attribute                        | Description                                         |
+/// | -------------------------------- | --------------------------------------------------- |
+/// | `tag = <expr>`                   | The tag value selecting the variant (required).     |
+///
+/// # Field attributes
+///
+/// | Attribute                        | Description                                         |
+/// | -------------------------------- | --------------------------------------------------- |
+/// | `endian = "big"` / `"little"`    | Endianness of the integer field or its prefix.      |
+/// | `prefix = <type>`                | Read an integer length, then the field of it.       |
+/// | `len = <expr>`                   | The length of the field, which may use prior fields.|
+/// | `pad_before = <expr>`            | Skip a number of bytes before the field.            |
+/// | `pad_after = <expr>`             | Skip a number of bytes after the field.             |
+/// | `eq = <expr>`                    | Expect the field to equal a value.                  |
+///
+/// Fields with a length may be `Bytes`, `String` or a `Vec` of `FromReader`
+/// values.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{Bytes, FromReader, Input, Invalid, ReadBinary};
+///
+/// #[derive(ReadBinary)]
+/// #[dangerous(magic = b"MSG", endian = "big")]
+/// struct Message<'a> {
+///     #[dangerous(eq = 1)]
+///     version: u8,
+///     #[dangerous(pad_after = 1)]
+///     kind: Kind,
+///     #[dangerous(prefix = u16)]
+///     body: Bytes<'a>,
+/// }
+///
+/// #[derive(Debug, PartialEq, ReadBinary)]
+/// #[dangerous(tag = u8)]
+/// enum Kind {
+///     #[dangerous(tag = 0)]
+///     Ping,
+///     #[dangerous(tag = 1)]
+///     Data(u32),
+/// }
+///
+/// let input = dangerous::input(b"MSG\x01\x00\x00\x00\x02hi");
+/// let message = input.read_all::<_, _, Invalid>(Message::from_reader)?;
+///
+/// assert_eq!(message.kind, Kind::Ping);
+/// assert_eq!(message.body, b"hi"[..]);
+/// # Ok::<(), Invalid>(())
+/// ```
+#[proc_macro_derive(ReadBinary, attributes(dangerous))]
+pub fn derive_read_binary(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand::read_binary(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
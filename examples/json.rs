@@ -1,5 +1,5 @@
 //! This example demonstrates a simple JSON parser (doesn't support escaping
-//! fully).
+//! fully). See `dangerous::recipes::json` for a complete one.
 //!
 //! ```
 //! echo '{ "hello": "bob" }' | cargo run --example json
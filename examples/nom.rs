@@ -24,8 +24,5 @@ fn read_imap_response<'i, E>(r: &mut BytesReader<'i, E>) -> Result<types::Respon
 where
     E: Error<'i>,
 {
-    r.try_external("IMAP response", |i| {
-        parser::parse_response(i.as_dangerous())
-            .map(|(remaining, response)| (i.len() - remaining.len(), response))
-    })
+    dangerous::interop::read_nom(r, "IMAP response", parser::parse_response)
 }
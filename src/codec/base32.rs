@@ -0,0 +1,45 @@
+//! Base32 ([RFC 4648]) encoding.
+//!
+//! [RFC 4648]: https://tools.ietf.org/html/rfc4648#section-6
+
+use alloc::string::String;
+
+use crate::input::Bytes;
+use crate::Error;
+
+use super::{Decoded, Encoding};
+
+const STANDARD: Encoding = Encoding {
+    alphabet: b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+    bits: 5,
+    group: 8,
+    partial: &[2, 4, 5, 7],
+    padded: true,
+    ignore_case: true,
+    expected: "base32 character",
+};
+
+/// Encode bytes as padded base32.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(dangerous::codec::base32::encode(b"hi"), "NBUQ====");
+/// ```
+#[must_use]
+pub fn encode(bytes: &[u8]) -> String {
+    STANDARD.encode(bytes)
+}
+
+/// Decode padded base32 of either case.
+///
+/// # Errors
+///
+/// Returns an error if the input contains a character outside of the
+/// alphabet, or is not correctly padded.
+pub fn decode<'i, E>(input: Bytes<'i>) -> Result<Decoded, E>
+where
+    E: Error<'i>,
+{
+    STANDARD.decode(input)
+}
@@ -0,0 +1,82 @@
+//! Base64 ([RFC 4648]) encoding, with the standard and URL safe alphabets.
+//!
+//! The standard alphabet is padded, while the URL safe alphabet is not, as
+//! is common for tokens such as JWTs.
+//!
+//! [RFC 4648]: https://tools.ietf.org/html/rfc4648#section-4
+
+use alloc::string::String;
+
+use crate::input::Bytes;
+use crate::Error;
+
+use super::{Decoded, Encoding};
+
+const STANDARD: Encoding = Encoding {
+    alphabet: b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+    bits: 6,
+    group: 4,
+    partial: &[2, 3],
+    padded: true,
+    ignore_case: false,
+    expected: "base64 character",
+};
+
+const URL_SAFE: Encoding = Encoding {
+    alphabet: b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+    padded: false,
+    expected: "base64url character",
+    ..STANDARD
+};
+
+/// Encode bytes as padded base64 with the standard alphabet.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(dangerous::codec::base64::encode(b"hi?"), "aGk/");
+/// assert_eq!(dangerous::codec::base64::encode(b"hi"), "aGk=");
+/// ```
+#[must_use]
+pub fn encode(bytes: &[u8]) -> String {
+    STANDARD.encode(bytes)
+}
+
+/// Decode padded base64 with the standard alphabet.
+///
+/// # Errors
+///
+/// Returns an error if the input contains a character outside of the
+/// alphabet, or is not correctly padded.
+pub fn decode<'i, E>(input: Bytes<'i>) -> Result<Decoded, E>
+where
+    E: Error<'i>,
+{
+    STANDARD.decode(input)
+}
+
+/// Encode bytes as unpadded base64 with the URL safe alphabet.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(dangerous::codec::base64::encode_url(b"hi?"), "aGk_");
+/// assert_eq!(dangerous::codec::base64::encode_url(b"hi"), "aGk");
+/// ```
+#[must_use]
+pub fn encode_url(bytes: &[u8]) -> String {
+    URL_SAFE.encode(bytes)
+}
+
+/// Decode unpadded base64 with the URL safe alphabet.
+///
+/// # Errors
+///
+/// Returns an error if the input contains a character outside of the
+/// alphabet, including padding, or has a length no encoding would produce.
+pub fn decode_url<'i, E>(input: Bytes<'i>) -> Result<Decoded, E>
+where
+    E: Error<'i>,
+{
+    URL_SAFE.decode(input)
+}
@@ -0,0 +1,56 @@
+//! Hex ([RFC 4648] base16) encoding.
+//!
+//! [RFC 4648]: https://tools.ietf.org/html/rfc4648#section-8
+
+use alloc::string::String;
+
+use crate::input::Bytes;
+use crate::Error;
+
+use super::{Decoded, Encoding};
+
+const LOWER: Encoding = Encoding {
+    alphabet: b"0123456789abcdef",
+    bits: 4,
+    group: 2,
+    partial: &[],
+    padded: false,
+    ignore_case: true,
+    expected: "hex digit",
+};
+
+const UPPER: Encoding = Encoding {
+    alphabet: b"0123456789ABCDEF",
+    ..LOWER
+};
+
+/// Encode bytes as lower case hex.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(dangerous::codec::hex::encode(b"\x01\xab"), "01ab");
+/// ```
+#[must_use]
+pub fn encode(bytes: &[u8]) -> String {
+    LOWER.encode(bytes)
+}
+
+/// Encode bytes as upper case hex.
+#[must_use]
+pub fn encode_upper(bytes: &[u8]) -> String {
+    UPPER.encode(bytes)
+}
+
+/// Decode hex of either case.
+///
+/// # Errors
+///
+/// Returns an error if the input contains a character that is not a hex
+/// digit, or has an odd length.
+pub fn decode<'i, E>(input: Bytes<'i>) -> Result<Decoded, E>
+where
+    E: Error<'i>,
+{
+    LOWER.decode(input)
+}
@@ -0,0 +1,151 @@
+//! Hex, base32 and base64 encoding and decoding.
+//!
+//! Decoding reads encoded [`Input`], so an invalid character is reported
+//! with its span within the input it came from. The decoded bytes are
+//! returned as [`Decoded`], which can be read as input in turn for formats
+//! that nest one encoding within another, such as the segments of a JWT.
+//!
+//! # Example
+//!
+//! ```
+//! use dangerous::codec::base64;
+//! use dangerous::{Input, Invalid};
+//!
+//! let decoded = base64::decode_url::<Invalid>(dangerous::input(b"eyJhbGciOiJub25lIn0"))?;
+//! let alg = decoded.input().read_all::<_, _, Invalid>(|r| {
+//!     r.consume(br#"{"alg":""#)?;
+//!     let alg = r.take_until(b'"')?;
+//!     r.consume(b"\"}")?;
+//!     Ok(alg)
+//! })?;
+//!
+//! assert_eq!(alg, b"none"[..]);
+//! assert_eq!(base64::encode_url(decoded.as_bytes()), "eyJhbGciOiJub25lIn0");
+//! # Ok::<(), Invalid>(())
+//! ```
+//!
+//! [`Input`]: crate::Input
+
+pub mod base32;
+pub mod base64;
+pub mod hex;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::input::{Bound, Bytes};
+use crate::{Error, Input};
+
+/// Bytes decoded from a text encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoded(Vec<u8>);
+
+impl Decoded {
+    /// Returns the decoded bytes as [`Input`].
+    ///
+    /// The input is bound as the decoded bytes are complete.
+    #[inline(always)]
+    pub fn input(&self) -> Bytes<'_> {
+        Bytes::new(&self.0, Bound::StartEnd)
+    }
+
+    /// Returns the decoded bytes.
+    #[must_use]
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Consumes `self` into the decoded bytes.
+    #[must_use]
+    #[inline(always)]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// An encoding of bits into characters.
+struct Encoding {
+    /// The characters for each value, in order.
+    alphabet: &'static [u8],
+    /// The number of bits each character encodes.
+    bits: u32,
+    /// The number of characters in a group that encodes a whole number of
+    /// bytes.
+    group: usize,
+    /// The number of characters a group may be cut short to.
+    partial: &'static [usize],
+    /// Whether partial groups are padded with `=`.
+    padded: bool,
+    /// Whether characters are decoded ignoring ASCII case.
+    ignore_case: bool,
+    /// The description of a valid character.
+    expected: &'static str,
+}
+
+impl Encoding {
+    fn value(&self, c: u8) -> Option<u8> {
+        self.alphabet
+            .iter()
+            .position(|&other| other == c || self.ignore_case && other.eq_ignore_ascii_case(&c))
+            .and_then(|value| u8::try_from(value).ok())
+    }
+
+    fn encode(&self, bytes: &[u8]) -> String {
+        let mut encoded = String::with_capacity(bytes.len() * 8 / self.bits as usize + self.group);
+        let mask = (1 << self.bits) - 1;
+        let mut acc = 0_u32;
+        let mut acc_bits = 0;
+        for &byte in bytes {
+            acc = (acc << 8) | u32::from(byte);
+            acc_bits += 8;
+            while acc_bits >= self.bits {
+                acc_bits -= self.bits;
+                encoded.push(self.char((acc >> acc_bits) & mask));
+            }
+        }
+        if acc_bits > 0 {
+            encoded.push(self.char((acc << (self.bits - acc_bits)) & mask));
+        }
+        if self.padded {
+            while encoded.len() % self.group != 0 {
+                encoded.push('=');
+            }
+        }
+        encoded
+    }
+
+    fn char(&self, value: u32) -> char {
+        char::from(self.alphabet[value as usize])
+    }
+
+    fn decode<'i, E>(&self, input: Bytes<'i>) -> Result<Decoded, E>
+    where
+        E: Error<'i>,
+    {
+        input.read_all(|r| {
+            let mut decoded = Vec::with_capacity(r.remaining_bytes() * self.bits as usize / 8);
+            let mut acc = 0_u32;
+            let mut acc_bits = 0;
+            let mut len = 0;
+            while !r.at_end() && !r.peek_eq(b'=') {
+                let value = r.try_expect(self.expected, |r| r.read().map(|c| self.value(c)))?;
+                acc = (acc << self.bits) | u32::from(value);
+                acc_bits += self.bits;
+                if acc_bits >= 8 {
+                    acc_bits -= 8;
+                    decoded.push((acc >> acc_bits).to_le_bytes()[0]);
+                }
+                len += 1;
+            }
+            let partial = len % self.group;
+            r.verify("whole encoded group", |_| {
+                partial == 0 || self.partial.contains(&partial)
+            })?;
+            if self.padded && partial != 0 {
+                r.consume(&b"========"[..self.group - partial])?;
+            }
+            Ok(Decoded(decoded))
+        })
+    }
+}
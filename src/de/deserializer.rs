@@ -0,0 +1,363 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor};
+
+use crate::error::ExternalSource;
+use crate::{Bytes, BytesReader, Error, Input};
+
+use super::{Format, Item};
+
+/// The error passed through `serde`.
+///
+/// Errors raised by `Deserialize` implementations are only a message, and are
+/// converted into input errors spanning the value they were raised for.
+pub(super) enum DeError<E> {
+    Input(E),
+    Custom(String),
+}
+
+impl<E> DeError<E> {
+    /// Returns the input error, converting a message into one spanning the
+    /// input consumed by the value that raised it.
+    pub(super) fn into_input<'i>(self, consumed: Bytes<'i>) -> E
+    where
+        E: Error<'i>,
+    {
+        match self {
+            Self::Input(err) => err,
+            Self::Custom(message) => consumed
+                .into_external("deserializable value", |_| {
+                    Err::<(), _>(ExternalSource::new(message))
+                })
+                .unwrap_err(),
+        }
+    }
+}
+
+impl<E> From<E> for DeError<E> {
+    fn from(err: E) -> Self {
+        Self::Input(err)
+    }
+}
+
+impl<E> fmt::Debug for DeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Input(_) => f.write_str("Input"),
+            Self::Custom(message) => f.debug_tuple("Custom").field(message).finish(),
+        }
+    }
+}
+
+impl<E> fmt::Display for DeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Input(_) => f.write_str("invalid input"),
+            Self::Custom(message) => f.write_str(message),
+        }
+    }
+}
+
+impl<E> de::StdError for DeError<E> {}
+
+impl<E> de::Error for DeError<E> {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Self::Custom(message.to_string())
+    }
+}
+
+pub(super) struct Deserializer<'i, 'r, E, F> {
+    r: &'r mut BytesReader<'i, E>,
+    format: &'r mut F,
+}
+
+impl<'i, 'r, E, F> Deserializer<'i, 'r, E, F>
+where
+    E: Error<'i>,
+    F: Format<'i>,
+{
+    pub(super) fn new(r: &'r mut BytesReader<'i, E>, format: &'r mut F) -> Self {
+        Self { r, format }
+    }
+
+    fn read_item(&mut self) -> Result<(Item<'i>, Bytes<'i>), DeError<E>> {
+        let format = &mut *self.format;
+        let (item, consumed) = self.r.take_consumed(|r| format.read_item(r));
+        Ok((item?, consumed))
+    }
+
+    /// Use the deserializer one level of nesting deeper, with the provided
+    /// context attached to any error.
+    fn enter<T>(
+        &mut self,
+        context: &'static str,
+        f: impl FnOnce(&mut Deserializer<'i, '_, E, F>) -> Result<T, DeError<E>>,
+    ) -> Result<T, DeError<E>> {
+        let format = &mut *self.format;
+        let value = self.r.enter(context, |r| {
+            let (value, consumed) = r.take_consumed(|r| f(&mut Deserializer { r, format }));
+            value.map_err(|err| err.into_input(consumed))
+        })?;
+        Ok(value)
+    }
+
+    fn visit_item<V>(
+        &mut self,
+        item: Item<'i>,
+        consumed: Bytes<'i>,
+        name: Option<&'static str>,
+        visitor: V,
+    ) -> Result<V::Value, DeError<E>>
+    where
+        V: Visitor<'i>,
+    {
+        let value: Result<_, DeError<E>> = match item {
+            Item::Seq(len) => {
+                return self.enter(name.unwrap_or("sequence"), |de| {
+                    visitor.visit_seq(Access { de, len })
+                })
+            }
+            Item::Map(len) => {
+                return self.enter(name.unwrap_or("map"), |de| {
+                    visitor.visit_map(Access { de, len })
+                })
+            }
+            Item::Some => return self.enter("option", |de| visitor.visit_some(de)),
+            Item::Unit => visitor.visit_unit(),
+            Item::Bool(v) => visitor.visit_bool(v),
+            Item::Unsigned(v) => visitor.visit_u64(v),
+            Item::Signed(v) => visitor.visit_i64(v),
+            Item::Float(v) => visitor.visit_f64(v),
+            Item::Str(v) => visitor.visit_borrowed_str(v),
+            Item::Bytes(v) => visitor.visit_borrowed_bytes(v),
+            Item::None => visitor.visit_none(),
+        };
+        value.map_err(|err| DeError::Input(err.into_input(consumed)))
+    }
+
+    fn deserialize_named<V>(
+        &mut self,
+        name: Option<&'static str>,
+        visitor: V,
+    ) -> Result<V::Value, DeError<E>>
+    where
+        V: Visitor<'i>,
+    {
+        let (item, consumed) = self.read_item()?;
+        self.visit_item(item, consumed, name, visitor)
+    }
+}
+
+impl<'de, E, F> de::Deserializer<'de> for &mut Deserializer<'de, '_, E, F>
+where
+    E: Error<'de>,
+    F: Format<'de>,
+{
+    type Error = DeError<E>;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_named(None, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.enter(name, |de| visitor.visit_newtype_struct(de))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_named(Some(name), visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_named(Some(name), visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.read_item()? {
+            (Item::Str(variant), consumed) => visitor
+                .visit_enum(BorrowedStrDeserializer::<DeError<E>>::new(variant))
+                .map_err(|err| DeError::Input(err.into_input(consumed))),
+            (Item::Map(1), _) => self.enter(name, |de| visitor.visit_enum(Access { de, len: 1 })),
+            (item, consumed) => self.visit_item(item, consumed, Some(name), visitor),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct seq tuple map identifier
+        ignored_any
+    }
+}
+
+/// Access to the contents of a sequence, map or enum.
+struct Access<'a, 'i, 'r, E, F> {
+    de: &'a mut Deserializer<'i, 'r, E, F>,
+    len: usize,
+}
+
+impl<'i, E, F> Access<'_, 'i, '_, E, F>
+where
+    E: Error<'i>,
+    F: Format<'i>,
+{
+    fn next(&mut self) -> bool {
+        if self.len == 0 {
+            false
+        } else {
+            self.len -= 1;
+            true
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        // Every value is at least one byte, so don't trust a count larger
+        // than the input left.
+        self.len.min(self.de.r.remaining_bytes())
+    }
+}
+
+impl<'i, E, F> SeqAccess<'i> for Access<'_, 'i, '_, E, F>
+where
+    E: Error<'i>,
+    F: Format<'i>,
+{
+    type Error = DeError<E>;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'i>,
+    {
+        if self.next() {
+            seed.deserialize(&mut *self.de).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining())
+    }
+}
+
+impl<'i, E, F> MapAccess<'i> for Access<'_, 'i, '_, E, F>
+where
+    E: Error<'i>,
+    F: Format<'i>,
+{
+    type Error = DeError<E>;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'i>,
+    {
+        if self.next() {
+            seed.deserialize(&mut *self.de).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'i>,
+    {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining())
+    }
+}
+
+impl<'i, E, F> EnumAccess<'i> for Access<'_, 'i, '_, E, F>
+where
+    E: Error<'i>,
+    F: Format<'i>,
+{
+    type Error = DeError<E>;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Self::Error>
+    where
+        V: DeserializeSeed<'i>,
+    {
+        let variant = seed.deserialize(&mut *self.de)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'i, E, F> VariantAccess<'i> for Access<'_, 'i, '_, E, F>
+where
+    E: Error<'i>,
+    F: Format<'i>,
+{
+    type Error = DeError<E>;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        de::Deserialize::deserialize(self.de)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'i>,
+    {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        de::Deserializer::deserialize_seq(self.de, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        de::Deserializer::deserialize_map(self.de, visitor)
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{BytesReader, Error};
+
+/// A self-describing encoding the deserializer reads values in.
+///
+/// The deserializer asks the format for the next [`Item`] whenever it needs a
+/// value, and reads the contents of sequences and maps by asking again for as
+/// many items as the header declared. Implement this to deserialize from an
+/// encoding other than [`Binary`].
+pub trait Format<'i> {
+    /// Read the header of the next value from the reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a valid value.
+    fn read_item<E>(&mut self, r: &mut BytesReader<'i, E>) -> Result<Item<'i>, E>
+    where
+        E: Error<'i>;
+}
+
+/// The header of a value read by a [`Format`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Item<'i> {
+    /// A value without contents.
+    Unit,
+    /// A boolean.
+    Bool(bool),
+    /// An unsigned integer.
+    Unsigned(u64),
+    /// A signed integer.
+    Signed(i64),
+    /// A floating point number.
+    Float(f64),
+    /// A string borrowed from the input.
+    Str(&'i str),
+    /// Bytes borrowed from the input.
+    Bytes(&'i [u8]),
+    /// A missing optional value.
+    None,
+    /// A present optional value, followed by the value.
+    Some,
+    /// A sequence, followed by the declared number of values.
+    Seq(usize),
+    /// A map, followed by the declared number of key and value pairs.
+    Map(usize),
+}
+
+/// A simple self-describing binary [`Format`].
+///
+/// Each value starts with a tag byte, followed by its contents. Numbers are
+/// little-endian, and lengths and counts are `u32`s.
+///
+/// | Tag    | Value               | Contents                          |
+/// | ------ | ------------------- | --------------------------------- |
+/// | `0x00` | unit                |                                   |
+/// | `0x01` | `false`             |                                   |
+/// | `0x02` | `true`              |                                   |
+/// | `0x03` | unsigned integer    | `u64`                             |
+/// | `0x04` | signed integer      | `i64`                             |
+/// | `0x05` | floating point      | `f64`                             |
+/// | `0x06` | string              | length, then the UTF-8 bytes      |
+/// | `0x07` | bytes               | length, then the bytes            |
+/// | `0x08` | none                |                                   |
+/// | `0x09` | some                | the value                         |
+/// | `0x0A` | sequence            | count, then the values            |
+/// | `0x0B` | map                 | count, then the keys and values   |
+///
+/// Chars are strings, structs are maps keyed by field name, unit enum
+/// variants are the variant name and all other enum variants are a map with a
+/// single entry from the variant name to its contents.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Binary;
+
+impl Binary {
+    const UNIT: u8 = 0x00;
+    const FALSE: u8 = 0x01;
+    const TRUE: u8 = 0x02;
+    const UNSIGNED: u8 = 0x03;
+    const SIGNED: u8 = 0x04;
+    const FLOAT: u8 = 0x05;
+    const STR: u8 = 0x06;
+    const BYTES: u8 = 0x07;
+    const NONE: u8 = 0x08;
+    const SOME: u8 = 0x09;
+    const SEQ: u8 = 0x0A;
+    const MAP: u8 = 0x0B;
+}
+
+impl<'i> Format<'i> for Binary {
+    fn read_item<E>(&mut self, r: &mut BytesReader<'i, E>) -> Result<Item<'i>, E>
+    where
+        E: Error<'i>,
+    {
+        let tag = r.expect("value tag", |r| {
+            r.read_opt().filter(|tag| *tag <= Self::MAP)
+        })?;
+        let item = match tag {
+            Self::UNIT => Item::Unit,
+            Self::FALSE => Item::Bool(false),
+            Self::TRUE => Item::Bool(true),
+            Self::UNSIGNED => Item::Unsigned(u64::from_le_bytes(r.take_array()?.into_dangerous())),
+            Self::SIGNED => Item::Signed(i64::from_le_bytes(r.take_array()?.into_dangerous())),
+            Self::FLOAT => Item::Float(f64::from_le_bytes(r.take_array()?.into_dangerous())),
+            Self::STR => {
+                let len = read_len(r)?;
+                Item::Str(r.take(len)?.to_dangerous_str::<E>()?)
+            }
+            Self::BYTES => {
+                let len = read_len(r)?;
+                Item::Bytes(r.take(len)?.as_dangerous())
+            }
+            Self::NONE => Item::None,
+            Self::SOME => Item::Some,
+            Self::SEQ => Item::Seq(read_len(r)?),
+            _ => Item::Map(read_len(r)?),
+        };
+        Ok(item)
+    }
+}
+
+fn read_len<'i, E>(r: &mut BytesReader<'i, E>) -> Result<usize, E>
+where
+    E: Error<'i>,
+{
+    let len = u32::from_le_bytes(r.take_array()?.into_dangerous());
+    // A length that does not fit will fail to be taken.
+    Ok(usize::try_from(len).unwrap_or(usize::MAX))
+}
@@ -0,0 +1,83 @@
+//! Deserialize `serde` types from input read with a [`Reader`].
+//!
+//! Values are read in a self-describing [`Format`], [`Binary`] by default.
+//! Errors are the reader's own, so a value that a `Deserialize`
+//! implementation rejects is reported with the span of the value, and with
+//! the names of the structs and enums it was nested within as contexts.
+//! Nested values are read with [`Reader::enter()`], so the depth of nesting
+//! is limited by the reader.
+//!
+//! # Example
+//!
+//! ```
+//! use dangerous::Expected;
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, PartialEq, Deserialize)]
+//! struct Greeting<'a> {
+//!     name: &'a str,
+//! }
+//!
+//! let bytes = b"\x0b\x01\x00\x00\x00\x06\x04\x00\x00\x00name\x06\x03\x00\x00\x00bob";
+//! let greeting: Greeting<'_> = dangerous::de::from_bytes::<_, Expected<'_>>(bytes)?;
+//!
+//! assert_eq!(greeting, Greeting { name: "bob" });
+//! # Ok::<(), Expected<'static>>(())
+//! ```
+//!
+//! [`Reader`]: crate::Reader
+//! [`Reader::enter()`]: crate::Reader::enter()
+
+mod deserializer;
+mod format;
+
+use serde::Deserialize;
+
+use crate::{BytesReader, Error, Input};
+
+use self::deserializer::Deserializer;
+
+pub use self::format::{Binary, Format, Item};
+
+/// Deserialize a `T` from all of the bytes in the [`Binary`] format.
+///
+/// # Errors
+///
+/// Returns an error if the bytes are not a valid `T`, or if not all of the
+/// bytes were read.
+pub fn from_bytes<'i, T, E>(bytes: &'i [u8]) -> Result<T, E>
+where
+    T: Deserialize<'i>,
+    E: Error<'i>,
+{
+    crate::input(bytes).read_all(from_reader)
+}
+
+/// Deserialize a `T` from the reader in the [`Binary`] format.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid `T`.
+pub fn from_reader<'i, T, E>(r: &mut BytesReader<'i, E>) -> Result<T, E>
+where
+    T: Deserialize<'i>,
+    E: Error<'i>,
+{
+    from_reader_with(r, Binary)
+}
+
+/// Deserialize a `T` from the reader in the provided [`Format`].
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid `T`.
+pub fn from_reader_with<'i, T, E, F>(r: &mut BytesReader<'i, E>, mut format: F) -> Result<T, E>
+where
+    T: Deserialize<'i>,
+    E: Error<'i>,
+    F: Format<'i>,
+{
+    let (value, consumed) =
+        r.take_consumed(|r| T::deserialize(&mut Deserializer::new(r, &mut format)));
+    value.map_err(|err| err.into_input(consumed))
+}
@@ -0,0 +1,51 @@
+//! Support for the `ReadBinary` derive, not public API.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::FromReader;
+use crate::{Bytes, BytesReader, Error, Input, String};
+
+/// Implemented for field types that can be read with a length.
+pub trait FromReaderLen<'i>: Sized {
+    /// Read a value of `len` from the reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a valid value.
+    fn from_reader_len<E>(r: &mut BytesReader<'i, E>, len: usize) -> Result<Self, E>
+    where
+        E: Error<'i>;
+}
+
+impl<'i> FromReaderLen<'i> for Bytes<'i> {
+    fn from_reader_len<E>(r: &mut BytesReader<'i, E>, len: usize) -> Result<Self, E>
+    where
+        E: Error<'i>,
+    {
+        r.take(len)
+    }
+}
+
+impl<'i> FromReaderLen<'i> for String<'i> {
+    fn from_reader_len<E>(r: &mut BytesReader<'i, E>, len: usize) -> Result<Self, E>
+    where
+        E: Error<'i>,
+    {
+        r.take(len)?.into_string()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'i, T> FromReaderLen<'i> for Vec<T>
+where
+    T: FromReader<'i>,
+{
+    fn from_reader_len<E>(r: &mut BytesReader<'i, E>, len: usize) -> Result<Self, E>
+    where
+        E: Error<'i>,
+    {
+        r.read_vec(len)
+    }
+}
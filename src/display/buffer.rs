@@ -0,0 +1,150 @@
+use core::str;
+
+use crate::fmt::{self, Write};
+
+use super::Style;
+
+const BUFFER_LEN: usize = 128;
+
+/// Batches what is written into chunks, each passed on with one `write_str`.
+///
+/// Displays write input a char or hex pair at a time, which is slow through
+/// a [`core::fmt::Formatter`] or an IO writer. Styles are passed on after
+/// what is buffered. Hex is written in the case the buffer is created with.
+pub(super) struct Buffered<'a> {
+    w: &'a mut dyn Write,
+    buf: [u8; BUFFER_LEN],
+    len: usize,
+    hex_digits: &'static [u8; 16],
+}
+
+impl<'a> Buffered<'a> {
+    pub(super) fn new(w: &'a mut dyn Write, uppercase: bool) -> Self {
+        Self {
+            w,
+            buf: [0; BUFFER_LEN],
+            len: 0,
+            hex_digits: if uppercase {
+                b"0123456789ABCDEF"
+            } else {
+                b"0123456789abcdef"
+            },
+        }
+    }
+
+    /// Passes on what is buffered.
+    pub(super) fn flush(&mut self) -> fmt::Result {
+        if self.len == 0 {
+            return Ok(());
+        }
+        let len = self.len;
+        self.len = 0;
+        // Only whole chars and strs are buffered.
+        match str::from_utf8(&self.buf[..len]) {
+            Ok(s) => self.w.write_str(s),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+
+    fn push(&mut self, bytes: &[u8]) -> fmt::Result {
+        if self.len + bytes.len() > BUFFER_LEN {
+            self.flush()?;
+        }
+        self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+        Ok(())
+    }
+}
+
+impl Write for Buffered<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.len() > BUFFER_LEN {
+            self.flush()?;
+            self.w.write_str(s)
+        } else {
+            self.push(s.as_bytes())
+        }
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.push(c.encode_utf8(&mut [0; 4]).as_bytes())
+    }
+
+    fn write_usize(&mut self, v: usize) -> fmt::Result {
+        self.flush()?;
+        self.w.write_usize(v)
+    }
+
+    fn write_hex(&mut self, b: u8) -> fmt::Result {
+        let digits = self.hex_digits;
+        self.push(&[digits[usize::from(b >> 4)], digits[usize::from(b & 0x0F)]])
+    }
+
+    fn write_style(&mut self, style: Option<Style>, escape: &str) -> fmt::Result {
+        self.flush()?;
+        self.w.write_style(style, escape)
+    }
+}
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Chunks(Vec<String>);
+
+    impl Write for Chunks {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0.push(s.into());
+            Ok(())
+        }
+
+        fn write_char(&mut self, c: char) -> fmt::Result {
+            self.0.push(c.into());
+            Ok(())
+        }
+
+        fn write_usize(&mut self, v: usize) -> fmt::Result {
+            self.0.push(v.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_batches_writes() {
+        let mut chunks = Chunks::default();
+        let mut buffered = Buffered::new(&mut chunks, false);
+        buffered.write_char('[').unwrap();
+        buffered.write_hex(0xab).unwrap();
+        buffered.write_str(" ").unwrap();
+        buffered.write_char('♥').unwrap();
+        buffered.write_style(None, "\x1b[0m").unwrap();
+        buffered.write_usize(10).unwrap();
+        buffered.write_char(']').unwrap();
+        buffered.flush().unwrap();
+        assert_eq!(chunks.0, ["[ab ♥", "\x1b[0m", "10", "]"]);
+    }
+
+    #[test]
+    fn test_uppercase_hex() {
+        let mut chunks = Chunks::default();
+        let mut buffered = Buffered::new(&mut chunks, true);
+        buffered.write_hex(0xab).unwrap();
+        buffered.write_hex(0x0c).unwrap();
+        buffered.flush().unwrap();
+        assert_eq!(chunks.0, ["AB0C"]);
+    }
+
+    #[test]
+    fn test_flushes_when_full() {
+        let mut chunks = Chunks::default();
+        let mut buffered = Buffered::new(&mut chunks, false);
+        for _ in 0..BUFFER_LEN + 1 {
+            buffered.write_char('a').unwrap();
+        }
+        buffered.flush().unwrap();
+        assert_eq!(chunks.0.len(), 2);
+        assert_eq!(chunks.0[0].len(), BUFFER_LEN);
+    }
+}
@@ -1,24 +1,62 @@
-use crate::error::{self, Context};
+use core::ops::Range;
+
+use crate::error::{self, context_key, written_eq, Context, CoreContext, LineColumn};
 use crate::fmt::{self, Write};
-use crate::input::{Bytes, Input};
+use crate::input::Input;
 
-use super::{DisplayBase, InputDisplay, PreferredFormat};
+use super::html;
+use super::input::Label;
+#[cfg(feature = "alloc")]
+use super::json;
+use super::style::{paint, Style};
+use super::{DisplayBase, InputDisplay, PreferredFormat, Preset, Theme};
 
 const DEFAULT_MAX_WIDTH: usize = 80;
+const COMPACT_MAX_WIDTH: usize = 32;
+const DEFAULT_MAX_FRAMES: usize = 64;
+#[cfg(feature = "std")]
+const MIN_TERMINAL_MAX_WIDTH: usize = 20;
 const INVALID_SPAN_ERROR: &str = "\
 note: error span is not within the error input indicating the
       concrete error being used has a bug. Consider raising an
       issue with the maintainer!
 ";
 
+/// The layout an [`ErrorDisplay`] is written in.
+///
+/// Set with [`ErrorDisplay::mode()`]. Only one mode is written, the other
+/// options of the display apply to the modes they describe.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OutputMode {
+    /// Write the error in sections over multiple lines, with excerpts of the
+    /// input and the backtrace.
+    Sections,
+    /// Write the sections with the excerpts of the input as hexdumps.
+    Hexdump,
+    /// Write the error on a single line, see [`ErrorDisplay::compact()`].
+    Compact,
+    /// Write the error in the stable format, see [`ErrorDisplay::stable()`].
+    Stable,
+    /// Write the sections as HTML, see [`ErrorDisplay::html()`].
+    Html,
+    /// Write the error as a JSON object, see [`ErrorDisplay::json()`].
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    Json,
+}
+
 /// Provides configurable [`error::Details`] formatting.
 #[derive(Clone)]
 #[must_use = "error displays must be written"]
 pub struct ErrorDisplay<'a, T> {
     error: &'a T,
-    banner: bool,
+    mode: OutputMode,
+    flags: Flags,
+    theme: Theme,
     format: PreferredFormat,
     input_max_width: usize,
+    max_frames: usize,
 }
 
 impl<'a, 'i, T> ErrorDisplay<'a, T>
@@ -35,8 +73,11 @@ where
         Self {
             error,
             format,
-            banner: false,
+            mode: OutputMode::Sections,
+            flags: Flags::COLLAPSE_REPEATED,
+            theme: Theme::new(),
             input_max_width: DEFAULT_MAX_WIDTH,
+            max_frames: DEFAULT_MAX_FRAMES,
         }
     }
 
@@ -49,18 +90,438 @@ where
         }
     }
 
+    /// Set the options bundled by a [`Preset`].
+    ///
+    /// - [`Preset::Full`] writes all of the input, the whole backtrace and
+    ///   each repeated context.
+    /// - [`Preset::Compact`] writes the error on a single line, as with
+    ///   [`compact()`](Self::compact()).
+    /// - [`Preset::Hexdump`] writes the sections of input as hexdumps, with
+    ///   the section the error points to highlighted in the rows it covers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::display::Preset;
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input(b"hello")
+    ///     .read_all::<_, _, Expected<'_>>(|r| r.consume(b"world"))
+    ///     .unwrap_err();
+    /// let formatted = error.display().preset(Preset::Hexdump).to_string();
+    ///
+    /// assert!(formatted.contains(
+    ///     "in:\n\
+    ///      00000000  68 65 6c 6c 6f                                    |hello|\n\
+    ///      \x20         ^^ ^^ ^^ ^^ ^^                                     ^^^^^\n",
+    /// ));
+    /// ```
+    pub fn preset(mut self, preset: Preset) -> Self {
+        match preset {
+            Preset::Full => {
+                if matches!(self.mode, OutputMode::Compact | OutputMode::Hexdump) {
+                    self.mode = OutputMode::Sections;
+                }
+                self.flags.set(Flags::COLLAPSE_REPEATED, false);
+                self.max_frames = usize::MAX;
+                self.input_max_width = usize::MAX;
+            }
+            Preset::Compact => self.mode = OutputMode::Compact,
+            Preset::Hexdump => self.mode = OutputMode::Hexdump,
+        }
+        self
+    }
+
+    /// Set the layout the error is written in.
+    ///
+    /// Defaults to [`OutputMode::Sections`]. The `compact()`, `stable()`,
+    /// `html()` and `json()` options set the mode they are named after.
+    pub fn mode(mut self, mode: OutputMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
     /// Set whether or not a banner should printed around the error.
+    ///
+    /// Applies to the sections, hexdump and HTML modes.
     pub fn banner(mut self, value: bool) -> Self {
-        self.banner = value;
+        self.flags.set(Flags::BANNER, value);
+        self
+    }
+
+    /// Sets the mode if `value` is `true`, or returns to the sections mode if
+    /// `value` is `false` and the mode is set.
+    fn toggle_mode(mut self, mode: OutputMode, value: bool) -> Self {
+        if value {
+            self.mode = mode;
+        } else if self.mode == mode {
+            self.mode = OutputMode::Sections;
+        }
+        self
+    }
+
+    /// Set whether or not the error should be written on a single line.
+    ///
+    /// The compact format contains the description, what was expected, the
+    /// offset of the error and a short excerpt of the input around it, suited
+    /// for log lines. The banner, backtrace and color are not written. Sets
+    /// the mode to [`OutputMode::Compact`], defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input(b"hello")
+    ///     .read_all::<_, _, Expected<'_>>(|r| r.consume(b"world"))
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.display().compact(true).to_string(),
+    ///     "failed to consume input: found a different value to the exact expected \
+    ///      (expected [77 6f 72 6c 64], at offset 0, in [68 65 6c 6c 6f])",
+    /// );
+    /// ```
+    pub fn compact(self, value: bool) -> Self {
+        self.toggle_mode(OutputMode::Compact, value)
+    }
+
+    /// Set whether or not the error should be written as a JSON object.
+    ///
+    /// The object is written from the [`Report`] of the error, with the same
+    /// fields as its `serde` serialization. Values are rendered as they would
+    /// be in the default display, and contexts past the maximum number of
+    /// frames are counted in `omitted_contexts`. Sets the mode to
+    /// [`OutputMode::Json`], defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input(b"hello")
+    ///     .read_all::<_, _, Expected<'_>>(|r| r.consume(b"world"))
+    ///     .unwrap_err();
+    ///
+    /// let json = error.display().json(true).str_hint().to_string();
+    /// assert!(json.starts_with(
+    ///     r#"{"kind":"value","reason":null,"operation":"consume input","description":"found a different value to the exact expected","expected":"exact value","expected_value":"\"world\"""#
+    /// ));
+    /// ```
+    ///
+    /// [`Report`]: crate::error::Report
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn json(self, value: bool) -> Self {
+        self.toggle_mode(OutputMode::Json, value)
+    }
+
+    /// Set whether or not the error should be written in the stable format.
+    ///
+    /// The stable format has a fixed layout that will not change across minor
+    /// versions, intended for snapshot tests of error output. Values and the
+    /// span are written in full without any width heuristics, contexts are
+    /// never collapsed and the banner and color are not written. Sets the mode
+    /// to [`OutputMode::Stable`], defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input("hello")
+    ///     .read_all::<_, _, Expected<'_>>(|r| r.context("greeting", |r| r.consume("world")))
+    ///     .unwrap_err();
+    ///
+    /// # #[cfg(feature = "full-backtrace")]
+    /// assert_eq!(
+    ///     error.display().stable(true).to_string(),
+    ///     "\
+    /// error: failed to consume input: found a different value to the exact expected
+    /// expected: \"world\"
+    /// found: \"hello\"
+    /// span: 0..5
+    /// input length: 5
+    /// context:
+    ///   1. read all input
+    ///   2. <context> (expected greeting)
+    ///   3. consume input (expected exact value)
+    /// "
+    /// );
+    /// ```
+    pub fn stable(self, value: bool) -> Self {
+        self.toggle_mode(OutputMode::Stable, value)
+    }
+
+    /// Set whether or not the output should be colored with ANSI escape
+    /// sequences.
+    ///
+    /// When enabled the description, the section of input the error points to
+    /// (and its underline), expected values and the context backtrace are
+    /// styled. Defaults to `false`.
+    #[cfg(feature = "color")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "color")))]
+    pub fn color(mut self, value: bool) -> Self {
+        self.flags.set(Flags::COLOR, value);
+        self
+    }
+
+    /// Set whether or not the error should be written as HTML.
+    ///
+    /// The error is escaped and written within a `<pre>` element of class
+    /// `dangerous-error`, with what would be colored within a `<span>` of
+    /// class `dangerous-header`, `dangerous-highlight`, `dangerous-expected`
+    /// or `dangerous-operation`, so it can be styled with CSS. The section of
+    /// input the error points to is highlighted inline in place of the
+    /// underline. Does not require the `color` feature. Sets the mode to
+    /// [`OutputMode::Html`], defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input(b"hello")
+    ///     .read_all::<_, _, Expected<'_>>(|r| r.consume(b"world"))
+    ///     .unwrap_err();
+    /// let html = error.display().html(true).to_string();
+    ///
+    /// assert!(html.starts_with(
+    ///     "<pre class=\"dangerous-error\"><span class=\"dangerous-header\">failed to consume input"
+    /// ));
+    /// assert!(html.contains(
+    ///     "&gt; [<span class=\"dangerous-highlight\">68 65 6c 6c 6f</span>]\n"
+    /// ));
+    /// ```
+    pub fn html(self, value: bool) -> Self {
+        self.toggle_mode(OutputMode::Html, value)
+    }
+
+    /// Set whether the section of input the error points to is only
+    /// highlighted inline, without the underline written below it.
+    ///
+    /// This saves a line for each section of input written in terminals.
+    /// Only takes effect when the output is colored, falling back to the
+    /// underline otherwise. Defaults to `false`.
+    #[cfg(feature = "color")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "color")))]
+    pub fn inline_highlight(mut self, value: bool) -> Self {
+        self.flags.set(Flags::INLINE_HIGHLIGHT, value);
+        self
+    }
+
+    /// Set whether the underline of the section of input the error points to
+    /// is labelled with what was expected and the offset of the error.
+    ///
+    /// The label is written right after the `^` of the error span, so it can
+    /// be read without looking back at the description. Defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input(b"hello")
+    ///     .read_all::<_, _, Expected<'_>>(|r| r.consume(b"world"))
+    ///     .unwrap_err();
+    /// let formatted = error.display().label_underline(true).to_string();
+    ///
+    /// assert!(formatted.contains(
+    ///     "> [68 65 6c 6c 6f]\n   ^^ ^^ ^^ ^^ ^^ expected exact value (offset 0)\n",
+    /// ));
+    /// ```
+    pub fn label_underline(mut self, value: bool) -> Self {
+        self.flags.set(Flags::LABEL_UNDERLINE, value);
+        self
+    }
+
+    /// Set whether or not a line summarizing where the error is within the
+    /// input is written last.
+    ///
+    /// The line is written as `span: bytes <start>..<end> of <length>`, so the
+    /// location of the error can still be found in logs that strip the input
+    /// excerpts. The compact, stable and JSON formats already include the
+    /// span, and are left as is. Defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input(b"hello")
+    ///     .read_all::<_, _, Expected<'_>>(|r| {
+    ///         r.consume(b"he")?;
+    ///         r.consume(b"yo")
+    ///     })
+    ///     .unwrap_err();
+    /// let formatted = error.display().span_summary(true).to_string();
+    ///
+    /// assert!(formatted.ends_with("\nspan: bytes 2..4 of 5"));
+    /// ```
+    pub fn span_summary(mut self, value: bool) -> Self {
+        self.flags.set(Flags::SPAN_SUMMARY, value);
+        self
+    }
+
+    /// Set whether or not consecutive repeated contexts in the backtrace are
+    /// collapsed into one entry.
+    ///
+    /// Contexts are repeated when they have the same operation description
+    /// and expected text, which is common with recursive formats. A collapsed
+    /// entry is followed by how many times it was repeated. Defaults to
+    /// `true`.
+    pub fn collapse_repeated(mut self, value: bool) -> Self {
+        self.flags.set(Flags::COLLAPSE_REPEATED, value);
+        self
+    }
+
+    /// Set the maximum number of contexts written from the backtrace.
+    ///
+    /// Errors from deeply nested input can collect a context per level of
+    /// nesting. Contexts past the maximum are not walked, and are written as
+    /// a count of the contexts omitted, so the output is bounded no matter
+    /// the depth. Walking the backtrace is iterative and never recurses.
+    /// Collapsed repeated contexts count towards the maximum. Defaults to
+    /// `64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input(b"[[[[")
+    ///     .read_all::<_, _, Expected<'_>>(|r| {
+    ///         for depth in ["one", "two", "three", "four"] {
+    ///             r.context(depth, |r| r.consume(b"["))?;
+    ///         }
+    ///         r.consume(b"]")
+    ///     })
+    ///     .unwrap_err();
+    /// let display = error.display().max_frames(1).to_string();
+    ///
+    /// assert!(display.ends_with("1. `read all input`\n  ... 1 more context omitted"));
+    /// ```
+    pub fn max_frames(mut self, value: usize) -> Self {
+        self.max_frames = value;
         self
     }
 
-    /// Set the `max-width` for wrapping error output.
+    /// Set the `max-width` of the input excerpts in the error output.
+    ///
+    /// The width is counted in displayed columns, not bytes: each byte takes
+    /// three columns formatted as hex and a char takes one as a `str`. The
+    /// excerpt of the input is centered around the error span, so the larger
+    /// the width the more input around the error is shown. Defaults to `80`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input("hello world, hello again")
+    ///     .read_all::<_, _, Expected<'_>>(|r| r.consume("hi"))
+    ///     .unwrap_err();
+    ///
+    /// let output = error.display().input_max_width(10).to_string();
+    /// assert!(output.contains("> \"hello world\" ..\n"));
+    /// ```
     pub fn input_max_width(mut self, value: usize) -> Self {
         self.input_max_width = value;
         self
     }
 
+    /// Set the `max-width` of the input excerpts to fit the terminal.
+    ///
+    /// The width of the terminal is read from the `COLUMNS` environment
+    /// variable, leaving room for the `> ` prefix of each excerpt. If it is not
+    /// set or invalid the width is left unchanged. This is opt-in so the
+    /// output doesn't silently change with the environment it is written in.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn terminal_width(self) -> Self {
+        match terminal_max_width(std::env::var("COLUMNS").ok().as_deref()) {
+            Some(width) => self.input_max_width(width),
+            None => self,
+        }
+    }
+
+    /// Writes the display to an [`std::io::Write`].
+    ///
+    /// The display is written as it is formatted, without building an
+    /// intermediate `String`. Wrap unbuffered writers such as files and
+    /// sockets with a [`std::io::BufWriter`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`std::io::Error`] the writer failed with.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_to(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        super::write_io(w, self)
+    }
+
+    /// Writes the display into a byte buffer, returning the number of bytes
+    /// written.
+    ///
+    /// Nothing is allocated, so this can be used where allocating is not
+    /// possible or safe, such as in a panic handler. The display is written
+    /// as UTF-8, and if it doesn't fit in the buffer it is cut short with the
+    /// end replaced with `...`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input(b"hello")
+    ///     .read_all::<_, _, Expected<'_>>(|r| r.consume(b"world"))
+    ///     .unwrap_err();
+    ///
+    /// let mut buf = [0; 32];
+    /// let len = error.display().compact(true).write_to_buf(&mut buf);
+    ///
+    /// assert_eq!(&buf[..len], b"failed to consume input: foun...");
+    /// ```
+    pub fn write_to_buf(&self, buf: &mut [u8]) -> usize {
+        super::write_buf(buf, self)
+    }
+
+    /// Set whether hex bytes are written in uppercase.
+    ///
+    /// Applies to the input excerpts and to any expected or found values
+    /// written as bytes. Defaults to `false`.
+    pub fn uppercase(mut self, value: bool) -> Self {
+        self.flags.set(Flags::UPPERCASE, value);
+        self
+    }
+
+    /// Set the characters and styles the error is written with.
+    ///
+    /// The theme applies to the input excerpts, their underlines and the
+    /// colors of the description, expected values and backtrace. Defaults to
+    /// [`Theme::new()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::display::Theme;
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input(b"hello")
+    ///     .read_all::<_, _, Expected<'_>>(|r| r.consume(b"world"))
+    ///     .unwrap_err();
+    /// let formatted = error
+    ///     .display()
+    ///     .theme(Theme::new().underline('~').delimiters('<', '>'))
+    ///     .to_string();
+    ///
+    /// assert!(formatted.contains("in:\n> <68 65 6c 6c 6f>\n   ~~ ~~ ~~ ~~ ~~ \n"));
+    /// ```
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
     /// Hint to the formatter that the [`crate::Input`] is a UTF-8 `str`.
     pub fn str_hint(self) -> Self {
         match self.format {
@@ -81,36 +542,86 @@ where
         let input = self.error.input();
         let root = self.error.backtrace().root();
         // Write description
-        w.write_str("failed to ")?;
-        root.operation().description(w)?;
-        w.write_str(": ")?;
-        self.error.description(w)?;
+        paint(
+            w,
+            self.flags.has(Flags::COLOR),
+            &self.theme,
+            Style::Header,
+            |w| {
+                w.write_str("failed to ")?;
+                root.operation().description(w)?;
+                w.write_str(": ")?;
+                self.error.description(w)
+            },
+        )?;
         w.write_char('\n')?;
         // Write inputs
         let input_display = self.configure_input_display(input.display());
+        let maybe_string = input.clone();
         let input = input.into_bytes();
         if let Some(expected_value) = self.error.expected() {
             let expected_display = self.configure_input_display(expected_value.display());
             w.write_str("expected:\n")?;
-            write_input(w, expected_display, false)?;
+            paint(
+                w,
+                self.flags.has(Flags::COLOR),
+                &self.theme,
+                Style::Expected,
+                |w| write_input(w, expected_display, false, None),
+            )?;
+            if let Some(found_value) = self.error.found().filter(|v| !v.as_bytes().is_empty()) {
+                w.write_str("found:\n")?;
+                write_input(
+                    w,
+                    self.configure_input_display(found_value.display()),
+                    false,
+                    None,
+                )?;
+            }
             w.write_str("in:\n")?;
         }
-        if root.span.is_within(input.span()) {
-            write_input(w, input_display.span(root.span, self.input_max_width), true)?;
+        let underline = !self.flags.has(Flags::COLOR | Flags::INLINE_HIGHLIGHT);
+        if let Some(span_range) = root.span.range_of(input.span()) {
+            let input_display = input_display.span(root.span, self.input_max_width);
+            let write_label = |w: &mut dyn Write| write_label(w, &root, span_range.start);
+            let label: Option<Label<'_>> = if self.flags.has(Flags::LABEL_UNDERLINE) {
+                Some(&write_label)
+            } else {
+                None
+            };
+            write_input(w, input_display, underline, label)?;
         } else {
             w.write_str(INVALID_SPAN_ERROR)?;
             w.write_str("input:\n")?;
-            write_input(w, input_display, false)?;
+            write_input(w, input_display, false, None)?;
+        }
+        // Write parent
+        if let Some((parent, origin)) = self.error.parent() {
+            let parent_display = self.configure_input_display(parent.display());
+            w.write_str("derived from:\n")?;
+            if origin.is_within(parent.span()) {
+                let parent_display = parent_display.span(origin, self.input_max_width);
+                write_input(w, parent_display, underline, None)?;
+            } else {
+                write_input(w, parent_display, false, None)?;
+            }
         }
         // Write additional
         w.write_str("additional:\n  ")?;
         if let Some(span_range) = root.span.range_of(input.span()) {
             if matches!(
                 self.format,
-                PreferredFormat::Str | PreferredFormat::StrCjk | PreferredFormat::BytesAscii
+                PreferredFormat::Str
+                    | PreferredFormat::StrCjk
+                    | PreferredFormat::StrLossy
+                    | PreferredFormat::BytesAscii
+                    | PreferredFormat::ByteStr
             ) {
+                let position = LineColumn::from_offset(&maybe_string, span_range.start);
                 w.write_str("error line: ")?;
-                w.write_usize(line_offset(&input, span_range.start))?;
+                w.write_usize(position.line)?;
+                w.write_str(", error column: ")?;
+                w.write_usize(position.column)?;
                 w.write_str(", ")?;
             }
             w.write_str("error offset: ")?;
@@ -124,43 +635,314 @@ where
             DisplayBase::fmt(&input.span(), w)?;
         }
         w.write_char('\n')?;
-        // Write context backtrace
+        self.write_backtrace(w)?;
+        if self.flags.has(Flags::SPAN_SUMMARY) {
+            w.write_char('\n')?;
+            write_span_summary(w, root.span.range_of(input.span()), input.len())?;
+        }
+        Ok(())
+    }
+
+    fn write_stable(&self, w: &mut dyn Write) -> fmt::Result {
+        let input = self.error.input();
+        let root = self.error.backtrace().root();
+        w.write_str("error: failed to ")?;
+        root.operation().description(w)?;
+        w.write_str(": ")?;
+        self.error.description(w)?;
+        if let Some(expected_value) = self.error.expected() {
+            w.write_str("\nexpected: ")?;
+            let expected_display = self.format_input_display(expected_value.display()).full();
+            fmt::DisplayBase::fmt(&expected_display, w)?;
+        } else if root.has_expected() {
+            w.write_str("\nexpected: ")?;
+            root.expected(w)?;
+        }
+        if let Some(found_value) = self.error.found() {
+            w.write_str("\nfound: ")?;
+            let found_display = self.format_input_display(found_value.display()).full();
+            fmt::DisplayBase::fmt(&found_display, w)?;
+        }
+        w.write_str("\nspan: ")?;
+        if let Some(span_range) = root.span.range_of(input.span()) {
+            w.write_usize(span_range.start)?;
+            w.write_str("..")?;
+            w.write_usize(span_range.end)?;
+        } else {
+            w.write_str("invalid")?;
+        }
+        w.write_str("\ninput length: ")?;
+        w.write_usize(input.as_dangerous_bytes().len())?;
+        w.write_str("\ncontext:\n")?;
+        let omitted = self.walk_frames(&mut |parent_depth, context| {
+            if context.is_child() {
+                w.write_str("     - ")?;
+            } else {
+                w.write_str("  ")?;
+                w.write_usize(parent_depth)?;
+                w.write_str(". ")?;
+            }
+            context.operation().description(w)?;
+            if context.has_expected() {
+                w.write_str(" (expected ")?;
+                context.expected(w)?;
+                w.write_char(')')?;
+            }
+            w.write_char('\n')
+        })?;
+        if omitted > 0 {
+            w.write_str("  ")?;
+            write_omitted(w, omitted)?;
+            w.write_char('\n')?;
+        }
+        Ok(())
+    }
+
+    fn write_compact(&self, w: &mut dyn Write) -> fmt::Result {
+        let input = self.error.input();
+        let root = self.error.backtrace().root();
+        w.write_str("failed to ")?;
+        root.operation().description(w)?;
+        w.write_str(": ")?;
+        self.error.description(w)?;
+        w.write_str(" (")?;
+        if let Some(expected_value) = self.error.expected() {
+            w.write_str("expected ")?;
+            let expected_display = self
+                .format_input_display(expected_value.display())
+                .head_tail(COMPACT_MAX_WIDTH);
+            fmt::DisplayBase::fmt(&expected_display.prepare(), w)?;
+            w.write_str(", ")?;
+        } else if root.has_expected() {
+            w.write_str("expected ")?;
+            root.expected(w)?;
+            w.write_str(", ")?;
+        }
+        let input_display = self.format_input_display(input.display());
+        if let Some(span_range) = root.span.range_of(input.span()) {
+            w.write_str("at offset ")?;
+            w.write_usize(span_range.start)?;
+            w.write_str(", in ")?;
+            let input_display =
+                input_display.span(root.span, self.input_max_width.min(COMPACT_MAX_WIDTH));
+            fmt::DisplayBase::fmt(&input_display.prepare(), w)?;
+        } else {
+            w.write_str("in ")?;
+            fmt::DisplayBase::fmt(&input_display.head_tail(COMPACT_MAX_WIDTH).prepare(), w)?;
+        }
+        w.write_char(')')
+    }
+
+    #[cfg(feature = "alloc")]
+    fn write_json(&self, w: &mut dyn Write) -> fmt::Result {
+        let report = error::Report::from_details(self.error);
+        w.write_char('{')?;
+        json::write_key(w, "kind")?;
+        json::write_optional_str(w, report.kind.map(error::ErrorKind::name))?;
+        w.write_char(',')?;
+        json::write_key(w, "reason")?;
+        match report.reason {
+            Some(reason) => json::write_string(w, |w| fmt::DisplayBase::fmt(&reason, w))?,
+            None => w.write_str("null")?,
+        }
+        w.write_char(',')?;
+        json::write_key(w, "operation")?;
+        json::write_string(w, |w| error::Operation::description(&report.operation, w))?;
+        w.write_char(',')?;
+        json::write_key(w, "description")?;
+        json::write_optional_str(w, Some(&report.description))?;
+        w.write_char(',')?;
+        json::write_key(w, "expected")?;
+        json::write_optional_str(w, report.expected.as_deref())?;
+        w.write_char(',')?;
+        json::write_key(w, "expected_value")?;
+        self.write_json_value(w, report.expected_value.as_ref())?;
+        w.write_char(',')?;
+        json::write_key(w, "found_value")?;
+        self.write_json_value(w, report.found_value.as_ref())?;
+        w.write_char(',')?;
+        json::write_key(w, "span")?;
+        json::write_range(w, report.span)?;
+        w.write_char(',')?;
+        json::write_key(w, "input_len")?;
+        w.write_usize(report.input_len)?;
+        w.write_char(',')?;
+        json::write_key(w, "retry_requirement")?;
+        json::write_retry_requirement(w, report.retry_requirement)?;
+        w.write_char(',')?;
+        json::write_key(w, "contexts")?;
+        w.write_char('[')?;
+        let written = report.contexts.len().min(self.max_frames);
+        for (i, context) in report.contexts[..written].iter().enumerate() {
+            if i > 0 {
+                w.write_char(',')?;
+            }
+            w.write_char('{')?;
+            json::write_key(w, "depth")?;
+            w.write_usize(context.depth)?;
+            w.write_char(',')?;
+            json::write_key(w, "operation")?;
+            json::write_optional_str(w, Some(&context.operation))?;
+            w.write_char(',')?;
+            json::write_key(w, "expected")?;
+            json::write_optional_str(w, context.expected.as_deref())?;
+            w.write_char(',')?;
+            json::write_key(w, "span")?;
+            json::write_range(w, context.span.clone())?;
+            w.write_char('}')?;
+        }
+        w.write_char(']')?;
+        let omitted = report.contexts.len() - written;
+        if omitted > 0 {
+            w.write_char(',')?;
+            json::write_key(w, "omitted_contexts")?;
+            w.write_usize(omitted)?;
+        }
+        w.write_char('}')
+    }
+
+    /// Writes a report value as a JSON string as it would be written in the
+    /// default display, or `null` if there isn't one.
+    #[cfg(feature = "alloc")]
+    fn write_json_value(
+        &self,
+        w: &mut dyn Write,
+        value: Option<&error::ReportValue>,
+    ) -> fmt::Result {
+        match value {
+            Some(value) => {
+                let value = value.as_value();
+                let display = self.format_input_display(value.display());
+                json::write_string(w, |w| fmt::DisplayBase::fmt(&display, w))
+            }
+            None => w.write_str("null"),
+        }
+    }
+
+    fn write_backtrace(&self, w: &mut dyn Write) -> fmt::Result {
         w.write_str("backtrace:")?;
         let mut child_index = 1;
         let mut last_parent_depth = 0;
-        let write_success = self.error.backtrace().walk(&mut |parent_depth, context| {
-            let mut write = || {
-                w.write_str("\n  ")?;
-                if parent_depth == last_parent_depth {
-                    w.write_str("  ")?;
-                    w.write_usize(child_index)?;
-                    child_index += 1;
-                } else {
-                    child_index = 1;
+        let mut last_key = None;
+        let mut last_index = 0;
+        let mut index = 0;
+        let mut repeated = 0;
+        let omitted = self.walk_frames(&mut |parent_depth, context| {
+            let is_child = parent_depth == last_parent_depth;
+            index += 1;
+            if self.flags.has(Flags::COLLAPSE_REPEATED) {
+                let key = (is_child, context_key(context));
+                // The key is a hash, so the frame is confirmed against the
+                // first of the run before collapsing.
+                if last_key == Some(key) && self.frame_written_eq(last_index, context) {
+                    repeated += 1;
                     last_parent_depth = parent_depth;
-                    w.write_usize(parent_depth)?;
-                }
-                w.write_str(". `")?;
-                context.operation().description(w)?;
-                w.write_char('`')?;
-                if context.has_expected() {
-                    w.write_str(" (expected ")?;
-                    context.expected(w)?;
-                    w.write_char(')')?;
+                    return Ok(());
                 }
-                fmt::Result::Ok(())
-            };
-            write().is_ok()
-        });
-        if write_success {
+                last_key = Some(key);
+                last_index = index - 1;
+                write_repeated(w, repeated)?;
+                repeated = 0;
+            }
+            w.write_str("\n  ")?;
+            if is_child {
+                w.write_str("  ")?;
+                w.write_usize(child_index)?;
+                child_index += 1;
+            } else {
+                child_index = 1;
+                last_parent_depth = parent_depth;
+                w.write_usize(parent_depth)?;
+            }
+            w.write_str(". `")?;
+            paint(
+                w,
+                self.flags.has(Flags::COLOR),
+                &self.theme,
+                Style::Operation,
+                |w| context.operation().description(w),
+            )?;
+            w.write_char('`')?;
+            if context.has_expected() {
+                w.write_str(" (expected ")?;
+                paint(
+                    w,
+                    self.flags.has(Flags::COLOR),
+                    &self.theme,
+                    Style::Expected,
+                    |w| context.expected(w),
+                )?;
+                w.write_char(')')?;
+            }
             Ok(())
-        } else {
-            Err(fmt::Error)
+        })?;
+        write_repeated(w, repeated)?;
+        if omitted > 0 {
+            w.write_str("\n  ")?;
+            write_omitted(w, omitted)?;
         }
+        Ok(())
+    }
+
+    /// Walks the backtrace writing each context with `f`, stopping once the
+    /// maximum number of frames were written.
+    ///
+    /// Returns the number of contexts omitted.
+    /// Returns `true` if the frame at `index` writes the same operation
+    /// description and expected text as `context`.
+    fn frame_written_eq(&self, index: usize, context: &dyn Context) -> bool {
+        let mut equal = false;
+        let mut frame_index = 0;
+        self.error.backtrace().walk(&mut |_, frame| {
+            if frame_index == index {
+                equal = written_eq(&|w| frame.operation().description(w), &|w| {
+                    context.operation().description(w)
+                }) && frame.has_expected() == context.has_expected()
+                    && (!frame.has_expected()
+                        || written_eq(&|w| frame.expected(w), &|w| context.expected(w)));
+                return false;
+            }
+            frame_index += 1;
+            true
+        });
+        equal
+    }
+
+    fn walk_frames(
+        &self,
+        f: &mut dyn FnMut(usize, &dyn Context) -> fmt::Result,
+    ) -> Result<usize, fmt::Error> {
+        let backtrace = self.error.backtrace();
+        let mut walked = 0;
+        let mut result = Ok(());
+        backtrace.walk(&mut |parent_depth, context| {
+            if walked == self.max_frames {
+                return false;
+            }
+            walked += 1;
+            result = f(parent_depth, context);
+            result.is_ok()
+        });
+        result.map(|()| backtrace.count().saturating_sub(walked))
+    }
+
+    /// Applies the options every input display written shares.
+    fn format_input_display<'b>(&self, display: InputDisplay<'b>) -> InputDisplay<'b> {
+        display
+            .format(self.format)
+            .uppercase(self.flags.has(Flags::UPPERCASE))
     }
 
     fn configure_input_display<'b>(&self, display: InputDisplay<'b>) -> InputDisplay<'b> {
-        display.format(self.format)
+        let display = self
+            .format_input_display(display)
+            .styled(self.flags.has(Flags::COLOR))
+            .theme(self.theme);
+        if self.mode == OutputMode::Hexdump {
+            display.hexdump()
+        } else {
+            display
+        }
     }
 }
 
@@ -169,16 +951,78 @@ where
     T: error::Details<'i>,
 {
     fn fmt(&self, w: &mut dyn Write) -> fmt::Result {
-        if self.banner {
-            w.write_str("\n-- INPUT ERROR ---------------------------------------------\n")?;
-            self.write_sections(w)?;
-            w.write_str("\n------------------------------------------------------------\n")
+        if self.mode == OutputMode::Html {
+            let display = Self {
+                mode: OutputMode::Sections,
+                flags: self.flags | Flags::COLOR | Flags::INLINE_HIGHLIGHT,
+                ..*self
+            };
+            return html::write_pre(w, "dangerous-error", |w| fmt::DisplayBase::fmt(&display, w));
+        }
+        self.write(w)
+    }
+}
+
+impl<'i, T> ErrorDisplay<'_, T>
+where
+    T: error::Details<'i>,
+{
+    fn write(&self, w: &mut dyn Write) -> fmt::Result {
+        match self.mode {
+            #[cfg(feature = "alloc")]
+            OutputMode::Json => self.write_json(w),
+            OutputMode::Stable => self.write_stable(w),
+            OutputMode::Compact => self.write_compact(w),
+            OutputMode::Sections | OutputMode::Hexdump | OutputMode::Html => {
+                if self.flags.has(Flags::BANNER) {
+                    w.write_str(
+                        "\n-- INPUT ERROR ---------------------------------------------\n",
+                    )?;
+                    self.write_sections(w)?;
+                    w.write_str("\n------------------------------------------------------------\n")
+                } else {
+                    self.write_sections(w)
+                }
+            }
+        }
+    }
+}
+
+/// The orthogonal options of an [`ErrorDisplay`], as a set of bits.
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct Flags(u8);
+
+impl Flags {
+    const BANNER: Self = Self(1);
+    const COLOR: Self = Self(1 << 1);
+    const INLINE_HIGHLIGHT: Self = Self(1 << 2);
+    const LABEL_UNDERLINE: Self = Self(1 << 3);
+    const SPAN_SUMMARY: Self = Self(1 << 4);
+    const COLLAPSE_REPEATED: Self = Self(1 << 5);
+    const UPPERCASE: Self = Self(1 << 6);
+
+    /// Returns `true` if all of the flags are set.
+    fn has(self, flags: Self) -> bool {
+        self.0 & flags.0 == flags.0
+    }
+
+    fn set(&mut self, flags: Self, value: bool) {
+        if value {
+            self.0 |= flags.0;
         } else {
-            self.write_sections(w)
+            self.0 &= !flags.0;
         }
     }
 }
 
+impl core::ops::BitOr for Flags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
 impl<'a, 'i, T> fmt::Debug for ErrorDisplay<'a, T>
 where
     T: error::Details<'i>,
@@ -197,23 +1041,77 @@ where
     }
 }
 
-fn line_offset(input: &Bytes<'_>, span_offset: usize) -> usize {
-    match input.clone().split_at_opt(span_offset) {
-        Some((before_span, _)) => before_span.count(b'\n') + 1,
-        // Will never be reached in practical usage but we handle to avoid
-        // unwrapping.
-        None => 0,
+/// Returns the `max-width` of input excerpts given the terminal columns.
+#[cfg(feature = "std")]
+fn terminal_max_width(columns: Option<&str>) -> Option<usize> {
+    let columns = columns?.trim().parse::<usize>().ok()?;
+    Some(columns.saturating_sub(2).max(MIN_TERMINAL_MAX_WIDTH))
+}
+
+fn write_repeated(w: &mut dyn Write, repeated: usize) -> fmt::Result {
+    if repeated == 0 {
+        Ok(())
+    } else {
+        w.write_str(" (repeated ")?;
+        w.write_usize(repeated + 1)?;
+        w.write_str(" times)")
+    }
+}
+
+fn write_label(w: &mut dyn Write, root: &CoreContext, offset: usize) -> fmt::Result {
+    if root.has_expected() {
+        w.write_str("expected ")?;
+        root.expected(w)?;
+        w.write_str(" (offset ")?;
+    } else {
+        w.write_str("(offset ")?;
+    }
+    w.write_usize(offset)?;
+    w.write_char(')')
+}
+
+fn write_omitted(w: &mut dyn Write, omitted: usize) -> fmt::Result {
+    w.write_str("... ")?;
+    w.write_usize(omitted)?;
+    if omitted == 1 {
+        w.write_str(" more context omitted")
+    } else {
+        w.write_str(" more contexts omitted")
     }
 }
 
-fn write_input(w: &mut dyn Write, input: InputDisplay<'_>, underline: bool) -> fmt::Result {
+fn write_span_summary(w: &mut dyn Write, span: Option<Range<usize>>, len: usize) -> fmt::Result {
+    w.write_str("span: ")?;
+    match span {
+        Some(span) => {
+            w.write_str("bytes ")?;
+            w.write_usize(span.start)?;
+            w.write_str("..")?;
+            w.write_usize(span.end)?;
+        }
+        None => w.write_str("invalid")?,
+    }
+    w.write_str(" of ")?;
+    w.write_usize(len)
+}
+
+fn write_input(
+    w: &mut dyn Write,
+    input: InputDisplay<'_>,
+    underline: bool,
+    label: Option<Label<'_>>,
+) -> fmt::Result {
+    if input.is_hexdump() {
+        fmt::DisplayBase::fmt(&input, w)?;
+        return w.write_char('\n');
+    }
     let input = input.prepare();
     w.write_str("> ")?;
     fmt::DisplayBase::fmt(&input, w)?;
     w.write_char('\n')?;
     if underline {
         w.write_str("  ")?;
-        fmt::DisplayBase::fmt(&input.underline(), w)?;
+        input.underline().write_labeled(w, label)?;
         w.write_char('\n')?;
     }
     Ok(())
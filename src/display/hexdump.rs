@@ -0,0 +1,245 @@
+// 00000000  68 65 6c 6c 6f 20 77 6f  72 6c 64 c2 20           |hello world. |
+//                                          ^^                           ^
+
+use core::ops::Range;
+
+use crate::fmt::{self, Write};
+
+use super::section::{self, SectionOpt};
+use super::style::{paint, Style};
+use super::theme::Theme;
+
+const ROW_LEN: usize = 16;
+const GROUP_LEN: usize = 8;
+
+/// Writes the input as a hexdump.
+///
+/// The widths of the section options are taken as a number of bytes, rounded
+/// up to whole rows, with omitted rows written as `...`. The bytes either side
+/// of a span are likewise rounded out to the rows they fall in.
+pub(super) fn write(
+    w: &mut dyn Write,
+    full: &[u8],
+    opt: SectionOpt,
+    color: bool,
+    theme: Theme,
+) -> fmt::Result {
+    let rows = row_count(full.len());
+    let (span, (first, second)) = match opt {
+        SectionOpt::Full => (None, (0..rows, None)),
+        SectionOpt::Head { width } => (None, (0..rows.min(row_budget(width)), None)),
+        SectionOpt::Tail { width } => (None, (rows.saturating_sub(row_budget(width))..rows, None)),
+        SectionOpt::HeadTail { width } => {
+            let budget = row_budget(width);
+            if budget >= rows {
+                (None, (0..rows, None))
+            } else {
+                let head = (budget + 1) / 2;
+                (None, (0..head, Some(rows - (budget - head)..rows)))
+            }
+        }
+        SectionOpt::Span { width, span } => {
+            let range = span.range_of(full.into());
+            let budget = row_budget(width);
+            let start = range.as_ref().map_or(0, |range| {
+                let span_rows = row_count(range.end) - range.start / ROW_LEN;
+                let before = budget.saturating_sub(span_rows) / 2;
+                (range.start / ROW_LEN).saturating_sub(before)
+            });
+            let start = start.min(rows.saturating_sub(budget));
+            (range, (start..rows.min(start + budget), None))
+        }
+        SectionOpt::SpanAround {
+            span,
+            before,
+            after,
+        } => {
+            let range = span.range_of(full.into());
+            let (start, end) = range.as_ref().map_or((0, after), |range| {
+                (
+                    range.start.saturating_sub(before),
+                    range.end.saturating_add(after),
+                )
+            });
+            let start = start / ROW_LEN;
+            let end = row_count(end).max(start + 1).min(rows);
+            (range, (start..end, None))
+        }
+        SectionOpt::SpanAroundChars {
+            span,
+            before,
+            after,
+        } => {
+            let range = span.range_of(full.into());
+            let bounds = section::char_bounds(full, range.clone().unwrap_or(0..0), before, after);
+            let start = bounds.start / ROW_LEN;
+            let end = row_count(bounds.end).max(start + 1).min(rows);
+            (range, (start..end, None))
+        }
+    };
+    let mut writer = HexdumpWriter {
+        w,
+        full,
+        span,
+        color,
+        theme,
+    };
+    if first.start > 0 {
+        writer.w.write_str("...\n")?;
+    }
+    writer.write_rows(first.clone())?;
+    if let Some(second) = second {
+        writer.w.write_str("\n...\n")?;
+        writer.write_rows(second)?;
+    } else if first.end < rows {
+        writer.w.write_str("\n...")?;
+    }
+    Ok(())
+}
+
+struct HexdumpWriter<'a> {
+    w: &'a mut dyn Write,
+    full: &'a [u8],
+    span: Option<Range<usize>>,
+    color: bool,
+    theme: Theme,
+}
+
+impl HexdumpWriter<'_> {
+    fn write_rows(&mut self, rows: Range<usize>) -> fmt::Result {
+        for row in rows.clone() {
+            if row != rows.start {
+                self.w.write_char('\n')?;
+            }
+            self.write_row(row)?;
+            if !self.color && (0..ROW_LEN).any(|i| self.is_highlighted(row * ROW_LEN + i)) {
+                self.w.write_char('\n')?;
+                self.write_row_underline(row)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_row(&mut self, row: usize) -> fmt::Result {
+        let offset = row * ROW_LEN;
+        for shift in (0..4).rev() {
+            #[allow(clippy::cast_possible_truncation)]
+            super::write_hex(self.w, (offset >> (shift * 8)) as u8)?;
+        }
+        self.w.write_str("  ")?;
+        for i in 0..ROW_LEN {
+            write_separator(self.w, i)?;
+            match self.full.get(offset + i) {
+                Some(&b) => {
+                    let highlighted = self.color && self.is_highlighted(offset + i);
+                    paint(self.w, highlighted, &self.theme, Style::Highlight, |w| {
+                        super::write_hex(w, b)
+                    })?;
+                }
+                None => self.w.write_str("  ")?,
+            }
+        }
+        self.w.write_str("  |")?;
+        for i in offset..self.full.len().min(offset + ROW_LEN) {
+            let c = ascii_char(self.full[i]);
+            let highlighted = self.color && self.is_highlighted(i);
+            paint(self.w, highlighted, &self.theme, Style::Highlight, |w| {
+                w.write_char(c)
+            })?;
+        }
+        self.w.write_char('|')
+    }
+
+    fn write_row_underline(&mut self, row: usize) -> fmt::Result {
+        let offset = row * ROW_LEN;
+        self.w.write_str("          ")?;
+        for i in 0..ROW_LEN {
+            write_separator(self.w, i)?;
+            let c = if self.is_highlighted(offset + i) {
+                self.theme.underline
+            } else {
+                ' '
+            };
+            self.w.write_char(c)?;
+            self.w.write_char(c)?;
+        }
+        self.w.write_str("   ")?;
+        let end = self.full.len().min(offset + ROW_LEN);
+        let last = (offset..end).rev().find(|&i| self.is_highlighted(i));
+        for i in offset..=last.unwrap_or(offset) {
+            self.w.write_char(if self.is_highlighted(i) {
+                self.theme.underline
+            } else {
+                ' '
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the byte at the index is within the span, or the span
+    /// is empty and points to it.
+    fn is_highlighted(&self, index: usize) -> bool {
+        index < self.full.len()
+            && self.span.as_ref().map_or(false, |span| {
+                span.contains(&index) || (span.is_empty() && span.start == index)
+            })
+    }
+}
+
+fn write_separator(w: &mut dyn Write, i: usize) -> fmt::Result {
+    if i == GROUP_LEN {
+        w.write_str("  ")
+    } else if i != 0 {
+        w.write_char(' ')
+    } else {
+        Ok(())
+    }
+}
+
+fn row_count(len: usize) -> usize {
+    len / ROW_LEN + usize::from(len % ROW_LEN != 0)
+}
+
+fn row_budget(width: usize) -> usize {
+    row_count(width).max(1)
+}
+
+fn ascii_char(b: u8) -> char {
+    if b.is_ascii_graphic() || b == b' ' {
+        b as char
+    } else {
+        '.'
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::display::InputDisplay;
+    use crate::input::Span;
+
+    #[test]
+    fn test_row_count() {
+        assert_eq!(row_count(0), 0);
+        assert_eq!(row_count(1), 1);
+        assert_eq!(row_count(16), 1);
+        assert_eq!(row_count(17), 2);
+    }
+
+    #[test]
+    fn test_span_rows_centered() {
+        let input = [0u8; 16 * 10];
+        let span = Span::from(&input[16 * 5..16 * 5 + 1]);
+        let display = InputDisplay::from_bytes(&input)
+            .hexdump()
+            .span(span, 48)
+            .to_string();
+        let offsets: Vec<&str> = display
+            .lines()
+            .filter(|line| !line.starts_with(' '))
+            .filter_map(|line| line.split(' ').next())
+            .collect();
+        assert_eq!(offsets, ["...", "00000040", "00000050", "00000060", "..."]);
+    }
+}
@@ -0,0 +1,94 @@
+use core::fmt::Result;
+
+use super::{Style, Write};
+
+/// Writes what is written by `f` as escaped HTML within a `pre` element of
+/// the provided class.
+///
+/// `f` is expected to write with color enabled. The styles it writes are
+/// translated to `span` elements with a class for each [`Style`].
+pub(super) fn write_pre(
+    w: &mut dyn Write,
+    class: &str,
+    f: impl FnOnce(&mut dyn Write) -> Result,
+) -> Result {
+    w.write_str("<pre class=\"")?;
+    w.write_str(class)?;
+    w.write_str("\">")?;
+    let mut html = Html { w, open: false };
+    f(&mut html)?;
+    html.close()?;
+    html.w.write_str("</pre>")
+}
+
+struct Html<'a> {
+    w: &'a mut dyn Write,
+    open: bool,
+}
+
+impl Html<'_> {
+    fn open(&mut self, class: &str) -> Result {
+        self.close()?;
+        self.open = true;
+        self.w.write_str("<span class=\"dangerous-")?;
+        self.w.write_str(class)?;
+        self.w.write_str("\">")
+    }
+
+    fn close(&mut self) -> Result {
+        if self.open {
+            self.open = false;
+            self.w.write_str("</span>")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Write for Html<'_> {
+    fn write_str(&mut self, s: &str) -> Result {
+        let mut last = 0;
+        for (i, c) in s.char_indices() {
+            if needs_escape(c) {
+                self.w.write_str(&s[last..i])?;
+                self.write_char(c)?;
+                last = i + c.len_utf8();
+            }
+        }
+        self.w.write_str(&s[last..])
+    }
+
+    fn write_char(&mut self, c: char) -> Result {
+        match c {
+            '&' => self.w.write_str("&amp;"),
+            '<' => self.w.write_str("&lt;"),
+            '>' => self.w.write_str("&gt;"),
+            '"' => self.w.write_str("&quot;"),
+            '\'' => self.w.write_str("&#39;"),
+            c => self.w.write_char(c),
+        }
+    }
+
+    fn write_usize(&mut self, v: usize) -> Result {
+        self.w.write_usize(v)
+    }
+
+    fn write_hex(&mut self, b: u8) -> Result {
+        super::write_hex(self.w, b)
+    }
+
+    fn write_style(&mut self, style: Option<Style>, _escape: &str) -> Result {
+        match style {
+            Some(Style::Header) => self.open("header"),
+            Some(Style::Highlight) => self.open("highlight"),
+            Some(Style::Secondary) => self.open("secondary"),
+            Some(Style::Expected) => self.open("expected"),
+            Some(Style::Operation) => self.open("operation"),
+            None => self.close(),
+        }
+    }
+}
+
+fn needs_escape(c: char) -> bool {
+    matches!(c, '&' | '<' | '>' | '"' | '\'')
+}
@@ -1,12 +1,30 @@
+use core::ops::Range;
+
 use crate::fmt::{self, Write};
 use crate::input::{Input, PrivateExt, Span};
 
+use super::buffer::Buffered;
+use super::hexdump;
+use super::html;
+use super::render::SectionRenderer;
 use super::section::{Section, SectionOpt};
-use super::unit::{byte_display_width, byte_display_write, char_display_width, char_display_write};
+use super::style::{self, Style};
+use super::theme::Theme;
+use super::unit::{
+    byte_display_width, byte_display_write, byte_str_display_width, byte_str_display_write,
+    char_display_width, char_display_write, Lossy,
+};
+use super::wrap;
 
+const RULER_STEP: usize = 8;
+const COMPACT_WIDTH: usize = 32;
 const DEFAULT_SECTION_OPTION: SectionOpt = SectionOpt::HeadTail { width: 1024 };
 
 /// Preferred [`Input`] formats.
+///
+/// The str formats escape control characters and other non-printable
+/// characters (`\n`, `\t`, `\u{1b}`), so newlines and terminal escape
+/// sequences within the input can't corrupt the display.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum PreferredFormat {
     /// Prefer displaying as a UTF-8 str.
@@ -18,6 +36,144 @@ pub enum PreferredFormat {
     Bytes,
     /// Prefer displaying as bytes with valid ASCII graphic characters.
     BytesAscii,
+    /// Prefer displaying as a str, with any bytes that are not valid UTF-8
+    /// escaped as `\xNN`.
+    ///
+    /// Unlike [`PreferredFormat::Str`], input that is mostly text with some
+    /// binary is kept as text instead of falling back to bytes.
+    ///
+    /// ```
+    /// use dangerous::display::PreferredFormat;
+    /// use dangerous::Input;
+    ///
+    /// let formatted = dangerous::input(b"hello\xffworld")
+    ///     .display()
+    ///     .format(PreferredFormat::StrLossy)
+    ///     .to_string();
+    /// assert_eq!(formatted, r#""hello\xffworld""#);
+    /// ```
+    StrLossy,
+    /// Prefer displaying as a Rust byte string literal, with any bytes that
+    /// are not printable ASCII escaped.
+    ///
+    /// The full input written in this format can be pasted into a test as
+    /// is.
+    ///
+    /// ```
+    /// use dangerous::display::PreferredFormat;
+    /// use dangerous::Input;
+    ///
+    /// let formatted = dangerous::input(b"GET \"/\"\xff\r\n")
+    ///     .display()
+    ///     .format(PreferredFormat::ByteStr)
+    ///     .to_string();
+    /// assert_eq!(formatted, r#"b"GET \"/\"\xff\r\n""#);
+    /// ```
+    ByteStr,
+}
+
+/// Separators written between groups of bytes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ByteSeparator {
+    /// Separate groups with a space (`aa bb`).
+    Space,
+    /// Separate groups with a dash (`aa-bb`).
+    Dash,
+    /// Separate groups with a colon (`aa:bb`).
+    Colon,
+    /// Write groups without a separator (`aabb`).
+    None,
+}
+
+impl ByteSeparator {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Space => " ",
+            Self::Dash => "-",
+            Self::Colon => ":",
+            Self::None => "",
+        }
+    }
+}
+
+/// Bundles of display options for common cases.
+///
+/// Set with [`InputDisplay::preset()`] and [`ErrorDisplay::preset()`]. A
+/// preset sets each of the options it bundles, and options set after it
+/// override it.
+///
+/// [`ErrorDisplay::preset()`]: crate::display::ErrorDisplay::preset()
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Preset {
+    /// Show all of the input, with the offsets of what is shown.
+    Full,
+    /// Show a short excerpt of the input, around the span if one is set.
+    Compact,
+    /// Show the input as a hexdump.
+    Hexdump,
+}
+
+/// Markers written in place of input that is not shown.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Ellipsis {
+    /// Write two dots (`..`).
+    Dots,
+    /// Write the ellipsis character (`…`).
+    Char,
+    /// Write the number of bytes not shown (`<+1024 bytes>`).
+    Count,
+}
+
+impl Ellipsis {
+    /// Returns the display width of the marker for `elided` bytes.
+    pub(super) fn width(self, elided: usize) -> usize {
+        match self {
+            Self::Dots => "..".len(),
+            Self::Char => 1,
+            Self::Count => {
+                let unit = if elided == 1 { " byte>" } else { " bytes>" };
+                "<+".len() + usize_width(elided) + unit.len()
+            }
+        }
+    }
+
+    fn write(self, w: &mut dyn Write, elided: usize) -> fmt::Result {
+        match self {
+            Self::Dots => w.write_str(".."),
+            Self::Char => w.write_char('…'),
+            Self::Count => {
+                w.write_str("<+")?;
+                w.write_usize(elided)?;
+                w.write_str(if elided == 1 { " byte>" } else { " bytes>" })
+            }
+        }
+    }
+}
+
+fn usize_width(mut v: usize) -> usize {
+    let mut width = 1;
+    while v >= 10 {
+        v /= 10;
+        width += 1;
+    }
+    width
+}
+
+/// Writes a label after the underline of a span.
+pub(super) type Label<'a> = &'a dyn Fn(&mut dyn Write) -> fmt::Result;
+
+/// Options for writing a section of input.
+#[derive(Copy, Clone)]
+pub(super) struct WriteOpt<'a> {
+    pub(super) underline: bool,
+    pub(super) color: bool,
+    pub(super) group_size: usize,
+    pub(super) separator: ByteSeparator,
+    pub(super) ellipsis: Ellipsis,
+    pub(super) secondary: Option<Span>,
+    pub(super) ruler: bool,
+    pub(super) label: Option<Label<'a>>,
+    pub(super) theme: Theme,
 }
 
 /// Provides configurable [`Input`] formatting.
@@ -47,9 +203,23 @@ pub enum PreferredFormat {
 /// ```
 #[derive(Clone)]
 #[must_use = "input displays must be written"]
+#[allow(clippy::struct_excessive_bools)]
 pub struct InputDisplay<'i> {
     input: &'i [u8],
     underline: bool,
+    color: bool,
+    html: bool,
+    hexdump: bool,
+    offsets: bool,
+    ruler: bool,
+    group_size: usize,
+    separator: ByteSeparator,
+    uppercase: bool,
+    ellipsis: Ellipsis,
+    theme: Theme,
+    wrap: Option<usize>,
+    secondary: Option<Span>,
+    label: Option<&'i str>,
     format: PreferredFormat,
     section: Option<Section<'i>>,
     section_opt: SectionOpt,
@@ -67,6 +237,19 @@ impl<'i> InputDisplay<'i> {
             input,
             format: PreferredFormat::Bytes,
             underline: false,
+            color: false,
+            html: false,
+            hexdump: false,
+            offsets: false,
+            ruler: false,
+            group_size: 1,
+            separator: ByteSeparator::Space,
+            uppercase: false,
+            ellipsis: Ellipsis::Dots,
+            theme: Theme::new(),
+            wrap: None,
+            secondary: None,
+            label: None,
             section: None,
             section_opt: DEFAULT_SECTION_OPTION,
         }
@@ -90,6 +273,250 @@ impl<'i> InputDisplay<'i> {
         self
     }
 
+    /// Set whether the section of input any provided span covers, and its
+    /// underline, should be highlighted with ANSI escape sequences.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let full = &[0xAA, 0xBB, 0xCC];
+    /// let input = dangerous::input(full);
+    /// let formatted = input.display().span(full[1..2].into(), 16).color(true).to_string();
+    ///
+    /// assert_eq!(formatted, "[aa \x1b[1;31mbb\x1b[0m cc]");
+    /// ```
+    #[cfg(feature = "color")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "color")))]
+    pub fn color(self, value: bool) -> Self {
+        self.styled(value)
+    }
+
+    /// Sets whether styles are written, which are colors unless translated
+    /// to HTML.
+    pub(super) fn styled(mut self, value: bool) -> Self {
+        self.color = value;
+        self
+    }
+
+    /// Set whether the display is written as HTML.
+    ///
+    /// The display is escaped and written within a `<pre>` element of class
+    /// `dangerous-input`, with the section of input a span covers within a
+    /// `<span>` of class `dangerous-highlight` (`dangerous-secondary` for a
+    /// secondary span) in place of the color escape sequences, so it can be
+    /// styled with CSS. Does not require the `color` feature. Defaults to
+    /// `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let full = b"<a>";
+    /// let input = dangerous::input(full);
+    /// let formatted = input
+    ///     .display()
+    ///     .str_hint()
+    ///     .span(full[1..2].into(), 16)
+    ///     .html(true)
+    ///     .to_string();
+    ///
+    /// assert_eq!(
+    ///     formatted,
+    ///     "<pre class=\"dangerous-input\">&quot;&lt;\
+    ///      <span class=\"dangerous-highlight\">a</span>&gt;&quot;</pre>",
+    /// );
+    /// ```
+    pub fn html(mut self, value: bool) -> Self {
+        self.html = value;
+        self
+    }
+
+    /// Format the [`Input`] as a hexdump.
+    ///
+    /// Each row shows the offset of its first byte, up to 16 bytes as hex and
+    /// the bytes as ASCII, with non-graphic bytes shown as `.`. The section
+    /// any provided span covers is highlighted across rows, with a row of
+    /// `^` under each row it covers, or with ANSI escape sequences if color
+    /// is enabled. The hexdump ignores the preferred format and the
+    /// underline, and the widths of the other options are taken as a number
+    /// of bytes, rounded up to whole rows, with omitted rows shown as `...`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let full = b"hello world\xC2 and more";
+    /// let input = dangerous::input(full);
+    /// let formatted = input.display().hexdump().span(full[6..12].into(), 32).to_string();
+    ///
+    /// assert_eq!(
+    ///     formatted,
+    ///     "00000000  68 65 6c 6c 6f 20 77 6f  72 6c 64 c2 20 61 6e 64  |hello world. and|\n\
+    ///      \x20                           ^^ ^^  ^^ ^^ ^^ ^^                     ^^^^^^\n\
+    ///      00000010  20 6d 6f 72 65                                    | more|",
+    /// );
+    /// ```
+    pub fn hexdump(mut self) -> Self {
+        self.hexdump = true;
+        self
+    }
+
+    /// Set whether the offsets of the section of input shown, and of any
+    /// provided span, within the full input are written after it.
+    ///
+    /// The offsets are in bytes, so they can be found in a hex editor. Nothing
+    /// is written for the underline, and the offsets are not written for a
+    /// [`hexdump()`](Self::hexdump()) which has its own offset column.
+    /// Defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let full = &[0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF];
+    /// let input = dangerous::input(full);
+    /// let span = full[5..].into();
+    /// let formatted = input.display().span(span, 16).offsets(true).to_string();
+    ///
+    /// assert_eq!(formatted, "[.. cc dd ee ff] (offsets 2..6 of 6, span 5..6)");
+    /// ```
+    pub fn offsets(mut self, value: bool) -> Self {
+        self.offsets = value;
+        self
+    }
+
+    /// Set whether a ruler of offsets is written above the bytes.
+    ///
+    /// The offset of every eighth byte within the full input is written in
+    /// hex above it, with a line break between the ruler and the bytes. The
+    /// ruler is only written for the byte formats, and not for the
+    /// underline, a [`hexdump()`](Self::hexdump()) or a display that is
+    /// [`wrap()`](Self::wrap())ped. Defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let full: Vec<u8> = (0..18).collect();
+    /// let input = dangerous::input(&full[..]);
+    /// let formatted = input.display().ruler(true).to_string();
+    ///
+    /// assert_eq!(
+    ///     formatted,
+    ///     " 00                      08                      10\n\
+    ///      [00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f 10 11]",
+    /// );
+    /// ```
+    pub fn ruler(mut self, value: bool) -> Self {
+        self.ruler = value;
+        self
+    }
+
+    /// Set how bytes are grouped and the separator written between the
+    /// groups.
+    ///
+    /// Groups of `size` bytes are aligned to the start of the full input and
+    /// the bytes within a group are written without a separator. Only applies
+    /// to the bytes formats outside of a hexdump, and a `size` of zero is
+    /// taken as one. Defaults to groups of one byte separated with a space.
+    ///
+    /// The width of a section is computed as if the bytes were separated with
+    /// a space, so grouped bytes never exceed it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::display::ByteSeparator;
+    /// use dangerous::Input;
+    ///
+    /// let input = dangerous::input(&[0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+    /// let mac = input.display().group_bytes(1, ByteSeparator::Colon).to_string();
+    /// let hash = input.display().group_bytes(4, ByteSeparator::Space).to_string();
+    ///
+    /// assert_eq!(mac, "[aa:bb:cc:dd:ee:ff]");
+    /// assert_eq!(hash, "[aabbccdd eeff]");
+    /// ```
+    pub fn group_bytes(mut self, size: usize, separator: ByteSeparator) -> Self {
+        self.group_size = size.max(1);
+        self.separator = separator;
+        self
+    }
+
+    /// Set whether hex bytes are written in uppercase.
+    ///
+    /// Applies to every byte written as hex, including the escaped bytes of a
+    /// lossy str and the offsets of a hexdump. Defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let input = dangerous::input(&[0xAA, 0xBB, 0x0C]);
+    /// let formatted = input.display().uppercase(true).to_string();
+    ///
+    /// assert_eq!(formatted, "[AA BB 0C]");
+    /// ```
+    pub fn uppercase(mut self, value: bool) -> Self {
+        self.uppercase = value;
+        self
+    }
+
+    /// Set the marker written in place of input that is not shown.
+    ///
+    /// Markers wider than the default `..` are taken from the width of the
+    /// section, assuming the widest a marker could be for the input. The
+    /// omitted rows of a [`hexdump()`](Self::hexdump()) are always written as
+    /// `...`. Defaults to [`Ellipsis::Dots`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::display::Ellipsis;
+    /// use dangerous::Input;
+    ///
+    /// let input = dangerous::input(&[0xAA; 64]);
+    /// let formatted = input.display().head(32).ellipsis(Ellipsis::Count).to_string();
+    ///
+    /// assert_eq!(formatted, "[aa aa aa aa <+60 bytes>]");
+    /// ```
+    pub fn ellipsis(mut self, ellipsis: Ellipsis) -> Self {
+        self.section = None;
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    /// Set the characters and styles the input is written with.
+    ///
+    /// Sets the [`ellipsis()`](Self::ellipsis()) to that of the theme, which
+    /// options set after it override. Defaults to [`Theme::new()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::display::Theme;
+    /// use dangerous::Input;
+    ///
+    /// let formatted = dangerous::input(b"hello")
+    ///     .display()
+    ///     .str_hint()
+    ///     .theme(Theme::new().quote('\''))
+    ///     .to_string();
+    ///
+    /// assert_eq!(formatted, "'hello'");
+    /// ```
+    pub fn theme(self, theme: Theme) -> Self {
+        Self { theme, ..self }.ellipsis(theme.ellipsis)
+    }
+
     /// Hint to the formatter that the [`Input`] is a UTF-8 `str`.
     pub fn str_hint(self) -> Self {
         match self.format {
@@ -181,6 +608,151 @@ impl<'i> InputDisplay<'i> {
         self
     }
 
+    /// Show `before` and `after` bytes of [`Input`] either side of a span.
+    ///
+    /// Unlike [`InputDisplay::span()`], the span itself is always shown in
+    /// full and the bytes either side are limited independently. The bytes
+    /// after an error are often more telling than those before it. The str
+    /// formats trim the bytes either side to whole chars.
+    ///
+    /// If the span is not within the input, `after` bytes from the head of
+    /// the input are shown.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let full = &[0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF];
+    /// let input = dangerous::input(full);
+    /// let span = full[2..3].into();
+    /// let formatted = input.display().span_around(span, 1, 2).to_string();
+    ///
+    /// assert_eq!(formatted, "[.. bb cc dd ee ..]");
+    /// ```
+    pub fn span_around(mut self, span: Span, before: usize, after: usize) -> Self {
+        self.section = None;
+        self.section_opt = SectionOpt::SpanAround {
+            span,
+            before,
+            after,
+        };
+        self
+    }
+
+    /// Show `before` and `after` chars of [`Input`] either side of a span.
+    ///
+    /// Like [`InputDisplay::span_around()`], but the input either side of the
+    /// span is counted in chars rather than bytes, so the section shows the
+    /// same amount of text whatever the encoded length of its chars. A byte
+    /// that is not part of a char of valid UTF-8 counts as one char.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let full = "♥♥♥x♥♥♥";
+    /// let input = dangerous::input(full.as_bytes());
+    /// let span = full[9..10].as_bytes().into();
+    /// let formatted = input.display().str_hint().span_around_chars(span, 2, 1).to_string();
+    ///
+    /// assert_eq!(formatted, r#".. "♥♥x♥" .."#);
+    /// ```
+    pub fn span_around_chars(mut self, span: Span, before: usize, after: usize) -> Self {
+        self.section = None;
+        self.section_opt = SectionOpt::SpanAroundChars {
+            span,
+            before,
+            after,
+        };
+        self
+    }
+
+    /// Wrap the display across lines of at most `width` columns.
+    ///
+    /// The underline of any provided span is written below each line it
+    /// points to, so the display and its underline are written together and
+    /// [`underline()`](Self::underline()) is ignored. A char is never split
+    /// across lines. Colors are not written while wrapping, and a
+    /// [`hexdump()`](Self::hexdump()) is not wrapped. A `width` of zero is
+    /// taken as one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let full = &[0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF];
+    /// let input = dangerous::input(full);
+    /// let formatted = input.display().span(full[2..4].into(), 32).wrap(9).to_string();
+    ///
+    /// assert_eq!(formatted, "[aa bb cc\n       ^^\n dd ee ff\n ^^      \n]");
+    /// ```
+    pub fn wrap(mut self, width: usize) -> Self {
+        self.wrap = Some(width.max(1));
+        self
+    }
+
+    /// Mark a secondary span along with the span the section targets.
+    ///
+    /// This relates a second position in the input to the primary span, such
+    /// as where an unclosed delimiter was opened. The secondary span is
+    /// underlined with `-` and highlighted in another color, where it falls
+    /// within the section of input selected for the primary span.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let full = b"(hello world";
+    /// let input = dangerous::input(full);
+    /// let formatted = input
+    ///     .display()
+    ///     .str_hint()
+    ///     .span(full[12..].into(), 32)
+    ///     .secondary_span(full[..1].into())
+    ///     .underline()
+    ///     .to_string();
+    ///
+    /// assert_eq!(formatted, " -           ^");
+    /// ```
+    pub fn secondary_span(mut self, span: Span) -> Self {
+        self.secondary = Some(span);
+        self
+    }
+
+    /// Set a label written after the underline of the span.
+    ///
+    /// The label is written a space after the last `^` of the span, in place
+    /// of the rest of the underline, so it sits right next to the input it
+    /// describes. Anything the underline would have marked after the span is
+    /// not written. The label is not written if the span is not shown, or if
+    /// the display is [`wrap()`](Self::wrap())ped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let full = b"GET / HTTP/1.1\n";
+    /// let input = dangerous::input(full);
+    /// let formatted = input
+    ///     .display()
+    ///     .str_hint()
+    ///     .span(full[14..].into(), 32)
+    ///     .label("expected CRLF")
+    ///     .underline()
+    ///     .to_string();
+    ///
+    /// assert_eq!(formatted, "               ^^ expected CRLF");
+    /// ```
+    pub fn label(mut self, label: &'i str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
     /// Shows the all of the elements in the [`Input`].
     ///
     /// # Example
@@ -199,9 +771,148 @@ impl<'i> InputDisplay<'i> {
         self
     }
 
+    /// Set the options bundled by a [`Preset`].
+    ///
+    /// - [`Preset::Full`] shows the full input with its offsets.
+    /// - [`Preset::Compact`] shows `32` columns of input, around the span if
+    ///   one is set, without the offsets, ruler or wrapping.
+    /// - [`Preset::Hexdump`] shows the input as a [`hexdump()`](Self::hexdump()).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::display::Preset;
+    /// use dangerous::Input;
+    ///
+    /// let full: Vec<u8> = (0..64).collect();
+    /// let input = dangerous::input(&full[..]);
+    /// let formatted = input
+    ///     .display()
+    ///     .span(full[40..41].into(), 80)
+    ///     .preset(Preset::Compact)
+    ///     .to_string();
+    ///
+    /// assert_eq!(formatted, "[.. 25 26 27 28 29 2a 2b 2c ..]");
+    /// ```
+    pub fn preset(mut self, preset: Preset) -> Self {
+        self.section = None;
+        match preset {
+            Preset::Full => {
+                self.section_opt = SectionOpt::Full;
+                self.hexdump = false;
+                self.offsets = true;
+            }
+            Preset::Compact => {
+                self.section_opt = match self.section_opt {
+                    SectionOpt::Span { span, .. }
+                    | SectionOpt::SpanAround { span, .. }
+                    | SectionOpt::SpanAroundChars { span, .. } => SectionOpt::Span {
+                        width: COMPACT_WIDTH,
+                        span,
+                    },
+                    _ => SectionOpt::HeadTail {
+                        width: COMPACT_WIDTH,
+                    },
+                };
+                self.hexdump = false;
+                self.offsets = false;
+                self.ruler = false;
+                self.wrap = None;
+            }
+            Preset::Hexdump => self.hexdump = true,
+        }
+        self
+    }
+
+    /// Returns `true` if the input is written as a hexdump.
+    pub(super) fn is_hexdump(&self) -> bool {
+        self.hexdump
+    }
+
+    /// Renders the computed section of input with a [`SectionRenderer`].
+    ///
+    /// The section is selected as it would be for writing, so applications
+    /// can draw it in their own UI. The hexdump, underline, color and byte
+    /// options are left to the renderer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::display::SectionRenderer;
+    /// use dangerous::Input;
+    ///
+    /// struct Markup(String);
+    ///
+    /// impl SectionRenderer for Markup {
+    ///     fn visible(&mut self, bytes: &[u8], _offset: usize, in_span: bool) {
+    ///         let text = String::from_utf8_lossy(bytes);
+    ///         if in_span {
+    ///             self.0.push_str(&format!("<b>{}</b>", text));
+    ///         } else {
+    ///             self.0.push_str(&text);
+    ///         }
+    ///     }
+    ///
+    ///     fn more(&mut self, elided: usize, _in_span: bool) {
+    ///         self.0.push_str(&format!("<+{}>", elided));
+    ///     }
+    /// }
+    ///
+    /// let full = b"hello world, and goodbye";
+    /// let mut markup = Markup(String::new());
+    /// dangerous::input(full)
+    ///     .display()
+    ///     .str_hint()
+    ///     .span(full[6..11].into(), 20)
+    ///     .render(&mut markup);
+    ///
+    /// assert_eq!(markup.0, "hello <b>world</b>, an<+9>");
+    /// ```
+    pub fn render(&self, renderer: &mut dyn SectionRenderer) {
+        match &self.section {
+            Some(section) => section.render(renderer),
+            None => self.clone().prepare().render(renderer),
+        }
+    }
+
+    /// Writes the display to an [`std::io::Write`].
+    ///
+    /// The display is written as it is formatted, without building an
+    /// intermediate `String`. It is written in many small pieces, so wrap
+    /// unbuffered writers such as files and sockets with a
+    /// [`std::io::BufWriter`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let mut out = Vec::new();
+    /// dangerous::input(&[0xAA, 0xBB]).display().write_to(&mut out)?;
+    ///
+    /// assert_eq!(out, b"[aa bb]");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`std::io::Error`] the writer failed with.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_to(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        super::write_io(w, self)
+    }
+
     /// Compute the sections of input to display.
     pub fn prepare(mut self) -> Self {
-        let computed = self.section_opt.compute(self.input, self.format);
+        let extra = self
+            .ellipsis
+            .width(self.input.len())
+            .saturating_sub(Ellipsis::Dots.width(0));
+        let computed = self
+            .section_opt
+            .reserve(extra * 2)
+            .compute(self.input, self.format);
         self.section = Some(computed);
         self
     }
@@ -209,10 +920,72 @@ impl<'i> InputDisplay<'i> {
 
 impl<'i> fmt::DisplayBase for InputDisplay<'i> {
     fn fmt(&self, w: &mut dyn Write) -> fmt::Result {
-        match &self.section {
-            None => self.clone().prepare().fmt(w),
-            Some(section) => section.write(w, self.underline),
+        let write_label = |w: &mut dyn Write| w.write_str(self.label.unwrap_or_default());
+        let label: Option<Label<'_>> = if self.label.is_some() {
+            Some(&write_label)
+        } else {
+            None
+        };
+        self.write_labeled(w, label)
+    }
+}
+
+impl InputDisplay<'_> {
+    /// Writes the display with a label after the underline of the span, in
+    /// place of any label set.
+    pub(super) fn write_labeled(&self, w: &mut dyn Write, label: Option<Label<'_>>) -> fmt::Result {
+        if self.html {
+            let display = Self {
+                html: false,
+                color: true,
+                ..self.clone()
+            };
+            return html::write_pre(w, "dangerous-input", |w| display.write_labeled(w, label));
+        }
+        let mut buffered = Buffered::new(w, self.uppercase);
+        self.write(&mut buffered, label)?;
+        buffered.flush()
+    }
+
+    fn write(&self, w: &mut dyn Write, label: Option<Label<'_>>) -> fmt::Result {
+        if self.hexdump {
+            return hexdump::write(w, self.input, self.section_opt, self.color, self.theme);
         }
+        let section = match &self.section {
+            None => return self.clone().prepare().write(w, label),
+            Some(section) => section,
+        };
+        let opt = WriteOpt {
+            underline: self.underline,
+            color: self.color && self.wrap.is_none(),
+            group_size: self.group_size,
+            separator: self.separator,
+            ellipsis: self.ellipsis,
+            secondary: self.secondary,
+            ruler: false,
+            label: label.filter(|_| self.wrap.is_none()),
+            theme: self.theme,
+        };
+        let line = |w: &mut dyn Write, underline: bool| {
+            section.write(w, WriteOpt { underline, ..opt })?;
+            if self.offsets && !underline {
+                section.write_offsets(w)?;
+            }
+            Ok(())
+        };
+        if let Some(width) = self.wrap {
+            let cjk = self.format == PreferredFormat::StrCjk;
+            return wrap::write(w, width, cjk, &line);
+        }
+        let is_bytes = matches!(
+            section.format(),
+            PreferredFormat::Bytes | PreferredFormat::BytesAscii
+        );
+        if self.ruler && !self.underline && is_bytes {
+            section.write(w, WriteOpt { ruler: true, ..opt })?;
+            w.write_char('\n')?;
+        }
+        line(w, self.underline)
     }
 }
 
@@ -230,11 +1003,38 @@ impl<'i> fmt::Display for InputDisplay<'i> {
 
 ///////////////////////////////////////////////////////////////////////////////
 
+/// How a unit of input is marked by the spans.
+#[derive(Copy, Clone, PartialEq)]
+enum Mark {
+    None,
+    Primary,
+    Secondary,
+}
+
+#[allow(clippy::struct_excessive_bools)]
 pub(super) struct InputWriter<'a> {
     w: &'a mut dyn Write,
     underline: bool,
+    color: bool,
+    style: Option<Style>,
+    theme: Theme,
+    group_size: usize,
+    separator: ByteSeparator,
+    ellipsis: Ellipsis,
     full: &'a [u8],
     span: Option<Span>,
+    secondary: Option<Span>,
+    ruler: bool,
+    /// Columns of spaces taken by a ruler label wider than its byte.
+    overflow: usize,
+    /// Columns of spaces held back until something other than a space is
+    /// written, so a ruler or a label has no trailing spaces before it.
+    pending: usize,
+    label: Option<Label<'a>>,
+    /// Whether the underline of the span was written and a label is waiting
+    /// to be written after it.
+    in_underline: bool,
+    labeled: bool,
 }
 
 impl<'a> InputWriter<'a> {
@@ -242,16 +1042,42 @@ impl<'a> InputWriter<'a> {
         w: &'a mut dyn Write,
         full: &'a [u8],
         span: Option<Span>,
-        underline: bool,
+        opt: WriteOpt<'a>,
     ) -> Self {
+        // The ruler is written as an underline with nothing to mark.
+        let (span, secondary) = if opt.ruler {
+            (None, None)
+        } else {
+            (span, opt.secondary)
+        };
         Self {
             w,
-            underline,
+            underline: opt.underline || opt.ruler,
+            color: opt.color,
+            style: None,
+            theme: opt.theme,
+            group_size: opt.group_size,
+            separator: opt.separator,
+            ellipsis: opt.ellipsis,
             full,
             span,
+            secondary,
+            ruler: opt.ruler,
+            overflow: 0,
+            pending: 0,
+            label: opt.label,
+            in_underline: false,
+            labeled: false,
         }
     }
 
+    pub(super) fn finish(&mut self) -> fmt::Result {
+        if self.in_underline {
+            self.write_label()?;
+        }
+        self.highlight(false)
+    }
+
     ///////////////////////////////////////////////////////////////////////////
     // Bytes
 
@@ -270,7 +1096,7 @@ impl<'a> InputWriter<'a> {
         self.write_bytes_open(left)?;
         self.write_bytes(left, show_ascii)?;
         self.write_space(1)?;
-        self.write_more(is_span_overlapping_end(left, self.span))?;
+        self.write_more_between(left, right)?;
         self.write_space(1)?;
         self.write_bytes(right, show_ascii)?;
         self.write_bytes_close(right)
@@ -278,104 +1104,152 @@ impl<'a> InputWriter<'a> {
 
     fn write_bytes_open(&mut self, bytes: &[u8]) -> fmt::Result {
         if has_more_before(bytes, self.full) {
-            self.write_delim('[', false)?;
-            self.write_more(is_span_overlapping_start(bytes, self.span))?;
+            self.write_delim(self.theme.open, false)?;
+            self.write_more(
+                is_span_overlapping_start(bytes, self.span),
+                self.range_of(bytes).start,
+            )?;
             self.write_space(1)
         } else {
-            self.write_delim('[', is_span_pointing_to_start(bytes, self.span))
+            self.write_delim(self.theme.open, is_span_pointing_to_start(bytes, self.span))
         }
     }
 
     fn write_bytes_close(&mut self, bytes: &[u8]) -> fmt::Result {
         if has_more_after(bytes, self.full) {
             self.write_space(1)?;
-            self.write_more(is_span_overlapping_end(bytes, self.span))?;
-            self.write_delim(']', false)
+            self.write_more(
+                is_span_overlapping_end(bytes, self.span),
+                self.full.len() - self.range_of(bytes).end,
+            )?;
+            self.write_delim(self.theme.close, false)
         } else {
-            self.write_delim(']', is_span_pointing_to_end(bytes, self.span))
+            self.write_delim(self.theme.close, is_span_pointing_to_end(bytes, self.span))
         }
     }
 
     fn write_bytes(&mut self, bytes: &[u8], show_ascii: bool) -> fmt::Result {
+        let offset = Span::from(bytes)
+            .range_of(self.full.into())
+            .map_or(0, |range| range.start);
         let mut iter = bytes.iter().copied();
         if let Some(byte) = iter.next() {
             self.write_byte(byte, bytes, show_ascii)?;
         }
         for (i, byte) in (1..bytes.len()).zip(iter) {
-            self.write_space(1)?;
+            if !self.underline && self.mark(&bytes[i..]) == Mark::None {
+                self.highlight(false)?;
+            }
+            if (offset + i) % self.group_size == 0 {
+                self.write_separator()?;
+            }
             self.write_byte(byte, &bytes[i..], show_ascii)?;
         }
         Ok(())
     }
 
-    fn write_byte(&mut self, byte: u8, remaining: &[u8], show_ascii: bool) -> fmt::Result {
+    fn write_separator(&mut self) -> fmt::Result {
+        let separator = self.separator.as_str();
         if self.underline {
-            let byte_display_width = byte_display_width(byte, show_ascii);
-            if is_section_start_span_or_within(remaining, self.span) {
-                self.write_underline(byte_display_width)
-            } else {
-                self.write_space(byte_display_width)
-            }
+            self.write_space(separator.len())
         } else {
+            self.w.write_str(separator)
+        }
+    }
+
+    fn write_byte(&mut self, byte: u8, remaining: &[u8], show_ascii: bool) -> fmt::Result {
+        let mark = self.mark(remaining);
+        let offset = self.range_of(remaining).start;
+        if self.ruler && offset % RULER_STEP == 0 {
+            self.write_ruler_label(offset, byte_display_width(byte, show_ascii))
+        } else if self.underline {
+            self.write_mark(mark, byte_display_width(byte, show_ascii))
+        } else {
+            self.paint_mark(mark)?;
             byte_display_write(byte, show_ascii, self.w)
         }
     }
 
+    /// Writes the offset in hex over `len` columns, or over as many as it
+    /// needs, taking them from the columns of spaces that follow.
+    fn write_ruler_label(&mut self, offset: usize, len: usize) -> fmt::Result {
+        let bytes = offset.to_be_bytes();
+        let skip = bytes
+            .iter()
+            .take_while(|&&b| b == 0)
+            .count()
+            .min(bytes.len() - 1);
+        self.write_pending()?;
+        let mut width = 0;
+        for &b in &bytes[skip..] {
+            super::write_hex(self.w, b)?;
+            width += 2;
+        }
+        let pad = len.saturating_sub(width);
+        self.overflow += width.saturating_sub(len);
+        self.write_space(pad)
+    }
+
     ///////////////////////////////////////////////////////////////////////////
     // Str
 
     pub(super) fn write_str_side(&mut self, side: &str, cjk: bool) -> fmt::Result {
-        self.write_str_open(side)?;
+        self.write_str_open(side.as_bytes())?;
         self.write_str(side, cjk)?;
-        self.write_str_close(side)
+        self.write_str_close(side.as_bytes())
     }
 
     pub(super) fn write_str_sides(&mut self, left: &str, right: &str, cjk: bool) -> fmt::Result {
-        self.write_str_open(left)?;
+        self.write_str_open(left.as_bytes())?;
         self.write_str(left, cjk)?;
-        self.write_delim('"', false)?;
+        self.write_delim(self.theme.quote, false)?;
         self.write_space(1)?;
-        self.write_more(is_span_overlapping_end(left.as_bytes(), self.span))?;
+        self.write_more_between(left.as_bytes(), right.as_bytes())?;
         self.write_space(1)?;
-        self.write_delim('"', false)?;
+        self.write_delim(self.theme.quote, false)?;
         self.write_str(right, cjk)?;
-        self.write_str_close(right)
+        self.write_str_close(right.as_bytes())
     }
 
-    fn write_str_open(&mut self, s: &str) -> fmt::Result {
-        let bytes = s.as_bytes();
+    fn write_str_open(&mut self, bytes: &[u8]) -> fmt::Result {
         if has_more_before(bytes, self.full) {
-            self.write_more(is_span_overlapping_start(bytes, self.span))?;
+            self.write_more(
+                is_span_overlapping_start(bytes, self.span),
+                self.range_of(bytes).start,
+            )?;
             self.write_space(1)?;
-            self.write_delim('"', false)
+            self.write_delim(self.theme.quote, false)
         } else {
-            self.write_delim('"', is_span_pointing_to_start(bytes, self.span))
+            self.write_delim(
+                self.theme.quote,
+                is_span_pointing_to_start(bytes, self.span),
+            )
         }
     }
 
-    fn write_str_close(&mut self, s: &str) -> fmt::Result {
-        let bytes = s.as_bytes();
+    fn write_str_close(&mut self, bytes: &[u8]) -> fmt::Result {
         if has_more_after(bytes, self.full) {
-            self.write_delim('"', false)?;
+            self.write_delim(self.theme.quote, false)?;
             self.write_space(1)?;
-            self.write_more(is_span_overlapping_end(bytes, self.span))
+            self.write_more(
+                is_span_overlapping_end(bytes, self.span),
+                self.full.len() - self.range_of(bytes).end,
+            )
         } else {
-            self.write_delim('"', is_span_pointing_to_end(bytes, self.span))
+            self.write_delim(self.theme.quote, is_span_pointing_to_end(bytes, self.span))
         }
     }
 
     fn write_str(&mut self, s: &str, cjk: bool) -> fmt::Result {
         let bytes = s.as_bytes();
         if self.underline {
-            if is_span_start_within_section(bytes, self.span) {
+            if is_span_start_within_section(bytes, self.span)
+                || is_span_start_within_section(bytes, self.secondary)
+            {
                 let mut offset = 0;
                 for c in s.chars() {
-                    let char_display_width = char_display_width(c, cjk);
-                    if is_section_start_span_or_within(&bytes[offset..], self.span) {
-                        self.write_underline(char_display_width)?;
-                    } else {
-                        self.write_space(char_display_width)?;
-                    }
+                    let mark = self.mark(&bytes[offset..]);
+                    self.write_mark(mark, char_display_width(c, cjk))?;
                     offset += c.len_utf8();
                 }
             } else {
@@ -383,6 +1257,14 @@ impl<'a> InputWriter<'a> {
                     self.write_space(char_display_width(c, cjk))?;
                 }
             }
+        } else if self.color {
+            let mut offset = 0;
+            for c in s.chars() {
+                let mark = self.mark(&bytes[offset..]);
+                self.paint_mark(mark)?;
+                char_display_write(c, self.w)?;
+                offset += c.len_utf8();
+            }
         } else {
             for c in s.chars() {
                 char_display_write(c, self.w)?;
@@ -391,21 +1273,121 @@ impl<'a> InputWriter<'a> {
         Ok(())
     }
 
+    ///////////////////////////////////////////////////////////////////////////
+    // Str lossy
+
+    pub(super) fn write_lossy_side(&mut self, side: &[u8]) -> fmt::Result {
+        self.write_str_open(side)?;
+        self.write_lossy(side)?;
+        self.write_str_close(side)
+    }
+
+    pub(super) fn write_lossy_sides(&mut self, left: &[u8], right: &[u8]) -> fmt::Result {
+        self.write_str_open(left)?;
+        self.write_lossy(left)?;
+        self.write_delim(self.theme.quote, false)?;
+        self.write_space(1)?;
+        self.write_more_between(left, right)?;
+        self.write_space(1)?;
+        self.write_delim(self.theme.quote, false)?;
+        self.write_lossy(right)?;
+        self.write_str_close(right)
+    }
+
+    fn write_lossy(&mut self, bytes: &[u8]) -> fmt::Result {
+        let mut offset = 0;
+        while let Some((unit, len)) = Lossy::front(&bytes[offset..]) {
+            let mark = self.mark(&bytes[offset..]);
+            if self.underline {
+                self.write_mark(mark, unit.display_width(false))?;
+            } else {
+                self.paint_mark(mark)?;
+                unit.display_write(self.w)?;
+            }
+            offset += len;
+        }
+        Ok(())
+    }
+
+    ///////////////////////////////////////////////////////////////////////////
+    // Byte str
+
+    pub(super) fn write_byte_str_side(&mut self, side: &[u8]) -> fmt::Result {
+        self.write_byte_str_open(side)?;
+        self.write_byte_str(side)?;
+        self.write_str_close(side)
+    }
+
+    pub(super) fn write_byte_str_sides(&mut self, left: &[u8], right: &[u8]) -> fmt::Result {
+        self.write_byte_str_open(left)?;
+        self.write_byte_str(left)?;
+        self.write_delim(self.theme.quote, false)?;
+        self.write_space(1)?;
+        self.write_more_between(left, right)?;
+        self.write_space(1)?;
+        self.write_delim('b', false)?;
+        self.write_delim(self.theme.quote, false)?;
+        self.write_byte_str(right)?;
+        self.write_str_close(right)
+    }
+
+    fn write_byte_str_open(&mut self, bytes: &[u8]) -> fmt::Result {
+        if has_more_before(bytes, self.full) {
+            self.write_more(
+                is_span_overlapping_start(bytes, self.span),
+                self.range_of(bytes).start,
+            )?;
+            self.write_space(1)?;
+            self.write_delim('b', false)?;
+            self.write_delim(self.theme.quote, false)
+        } else {
+            self.write_delim('b', false)?;
+            self.write_delim(
+                self.theme.quote,
+                is_span_pointing_to_start(bytes, self.span),
+            )
+        }
+    }
+
+    fn write_byte_str(&mut self, bytes: &[u8]) -> fmt::Result {
+        for (i, &b) in bytes.iter().enumerate() {
+            let mark = self.mark(&bytes[i..]);
+            if self.underline {
+                self.write_mark(mark, byte_str_display_width(b))?;
+            } else {
+                self.paint_mark(mark)?;
+                byte_str_display_write(b, self.w)?;
+            }
+        }
+        Ok(())
+    }
+
     ///////////////////////////////////////////////////////////////////////////
     // Private
 
-    fn write_more(&mut self, highlight: bool) -> fmt::Result {
+    fn write_more(&mut self, highlight: bool, elided: usize) -> fmt::Result {
         if self.underline {
+            let width = self.ellipsis.width(elided);
             if highlight {
-                self.write_underline(2)
+                self.write_underline(width)
             } else {
-                self.write_space(2)
+                self.write_space(width)
             }
         } else {
-            self.w.write_str("..")
+            self.highlight(highlight)?;
+            self.ellipsis.write(self.w, elided)
         }
     }
 
+    fn write_more_between(&mut self, left: &[u8], right: &[u8]) -> fmt::Result {
+        let elided = self.range_of(right).start - self.range_of(left).end;
+        self.write_more(is_span_overlapping_end(left, self.span), elided)
+    }
+
+    fn range_of(&self, bytes: &[u8]) -> Range<usize> {
+        Span::from(bytes).range_of(self.full.into()).unwrap_or(0..0)
+    }
+
     fn write_delim(&mut self, delim: char, highlighted: bool) -> fmt::Result {
         if self.underline {
             if highlighted {
@@ -414,6 +1396,7 @@ impl<'a> InputWriter<'a> {
                 self.write_space(1)
             }
         } else {
+            self.highlight(highlighted)?;
             self.w.write_char(delim)
         }
     }
@@ -423,15 +1406,93 @@ impl<'a> InputWriter<'a> {
     }
 
     fn write_underline(&mut self, len: usize) -> fmt::Result {
-        self.write_char_len('^', len)
+        self.highlight(true)?;
+        self.write_char_len(self.theme.underline, len)?;
+        self.in_underline |= self.label.is_some() && len > 0;
+        Ok(())
+    }
+
+    fn write_label(&mut self) -> fmt::Result {
+        if let Some(label) = self.label.filter(|_| !self.labeled) {
+            self.labeled = true;
+            self.pending = 0;
+            self.w.write_char(' ')?;
+            label(self.w)?;
+        }
+        Ok(())
+    }
+
+    /// Returns how the unit of input at the start of `remaining` is marked.
+    fn mark(&self, remaining: &[u8]) -> Mark {
+        if is_section_start_span_or_within(remaining, self.span) {
+            Mark::Primary
+        } else if is_section_start_span_or_within(remaining, self.secondary) {
+            Mark::Secondary
+        } else {
+            Mark::None
+        }
+    }
+
+    fn write_mark(&mut self, mark: Mark, len: usize) -> fmt::Result {
+        match mark {
+            Mark::None => self.write_space(len),
+            Mark::Primary => self.write_underline(len),
+            Mark::Secondary => {
+                self.set_style(Some(Style::Secondary))?;
+                self.write_char_len(self.theme.secondary_underline, len)
+            }
+        }
+    }
+
+    fn paint_mark(&mut self, mark: Mark) -> fmt::Result {
+        self.set_style(match mark {
+            Mark::None => None,
+            Mark::Primary => Some(Style::Highlight),
+            Mark::Secondary => Some(Style::Secondary),
+        })
+    }
+
+    /// Switches the highlight style on or off if color is enabled.
+    fn highlight(&mut self, on: bool) -> fmt::Result {
+        self.set_style(if on { Some(Style::Highlight) } else { None })
+    }
+
+    fn set_style(&mut self, next: Option<Style>) -> fmt::Result {
+        if !self.color || self.style == next {
+            return Ok(());
+        }
+        self.style = next;
+        let escape = next.map_or(style::RESET, |next| self.theme.escape(next));
+        self.w.write_style(next, escape)
     }
 
     fn write_char_len(&mut self, c: char, len: usize) -> fmt::Result {
-        for _ in 0..len {
+        if self.labeled || len == 0 {
+            return Ok(());
+        }
+        let skip = len.min(self.overflow);
+        self.overflow -= skip;
+        if c == ' ' && (self.ruler || self.in_underline) {
+            self.pending += len - skip;
+            return Ok(());
+        }
+        if self.in_underline && c != self.theme.underline {
+            return self.write_label();
+        }
+        self.write_pending()?;
+        for _ in skip..len {
             self.w.write_char(c)?;
         }
         Ok(())
     }
+
+    fn write_pending(&mut self) -> fmt::Result {
+        for _ in 0..self.pending {
+            self.w.write_char(' ')?;
+        }
+        self.pending = 0;
+        Ok(())
+    }
 }
 
 fn has_more_before(bytes: &[u8], full: &[u8]) -> bool {
@@ -0,0 +1,110 @@
+use core::fmt::Result;
+use core::ops::Range;
+
+use crate::error::RetryRequirement;
+
+use super::Write;
+
+/// Writes a JSON string, escaping what is written by `f` within it.
+pub(super) fn write_string(w: &mut dyn Write, f: impl FnOnce(&mut dyn Write) -> Result) -> Result {
+    w.write_char('"')?;
+    f(&mut Escaped(w))?;
+    w.write_char('"')
+}
+
+/// Writes a JSON string of `s`, or `null` if there isn't one.
+pub(super) fn write_optional_str(w: &mut dyn Write, s: Option<&str>) -> Result {
+    match s {
+        Some(s) => write_string(w, |w| w.write_str(s)),
+        None => w.write_str("null"),
+    }
+}
+
+/// Writes a JSON object key followed by the `:` separator.
+pub(super) fn write_key(w: &mut dyn Write, key: &str) -> Result {
+    w.write_char('"')?;
+    w.write_str(key)?;
+    w.write_str("\":")
+}
+
+/// Writes a span range as a JSON object of `start` and `end` offsets, or
+/// `null` if there isn't one.
+pub(super) fn write_range(w: &mut dyn Write, range: Option<Range<usize>>) -> Result {
+    match range {
+        Some(range) => {
+            w.write_str("{\"start\":")?;
+            w.write_usize(range.start)?;
+            w.write_str(",\"end\":")?;
+            w.write_usize(range.end)?;
+            w.write_char('}')
+        }
+        None => w.write_str("null"),
+    }
+}
+
+/// Writes a retry requirement as a JSON object of its `continue_after`,
+/// `total_hint` and `exact` fields, or `null` if there isn't one.
+pub(super) fn write_retry_requirement(
+    w: &mut dyn Write,
+    requirement: Option<RetryRequirement>,
+) -> Result {
+    match requirement {
+        Some(requirement) => {
+            w.write_str("{\"continue_after\":")?;
+            w.write_usize(requirement.continue_after())?;
+            w.write_str(",\"total_hint\":")?;
+            match requirement.total_hint() {
+                Some(total) => w.write_usize(total)?,
+                None => w.write_str("null")?,
+            }
+            w.write_str(",\"exact\":")?;
+            w.write_str(if requirement.is_exact() {
+                "true"
+            } else {
+                "false"
+            })?;
+            w.write_char('}')
+        }
+        None => w.write_str("null"),
+    }
+}
+
+struct Escaped<'a>(&'a mut dyn Write);
+
+impl Write for Escaped<'_> {
+    fn write_str(&mut self, s: &str) -> Result {
+        let mut last = 0;
+        for (i, c) in s.char_indices() {
+            if needs_escape(c) {
+                self.0.write_str(&s[last..i])?;
+                self.write_char(c)?;
+                last = i + c.len_utf8();
+            }
+        }
+        self.0.write_str(&s[last..])
+    }
+
+    fn write_char(&mut self, c: char) -> Result {
+        match c {
+            '"' => self.0.write_str("\\\""),
+            '\\' => self.0.write_str("\\\\"),
+            '\n' => self.0.write_str("\\n"),
+            '\r' => self.0.write_str("\\r"),
+            '\t' => self.0.write_str("\\t"),
+            c if needs_escape(c) => {
+                self.0.write_str("\\u00")?;
+                #[allow(clippy::cast_possible_truncation)]
+                super::write_hex(self.0, c as u8)
+            }
+            c => self.0.write_char(c),
+        }
+    }
+
+    fn write_usize(&mut self, v: usize) -> Result {
+        self.0.write_usize(v)
+    }
+}
+
+fn needs_escape(c: char) -> bool {
+    c == '"' || c == '\\' || c.is_ascii_control()
+}
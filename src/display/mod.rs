@@ -1,14 +1,28 @@
 //! Display support.
 
+mod buffer;
 mod error;
+mod hexdump;
+mod html;
 mod input;
+#[cfg(feature = "alloc")]
+mod json;
+mod render;
 mod section;
+mod style;
+mod theme;
 mod unit;
+mod width;
+mod wrap;
 
 use core::fmt::{Formatter, Result};
 
-pub use self::error::ErrorDisplay;
-pub use self::input::{InputDisplay, PreferredFormat};
+pub use self::error::{ErrorDisplay, OutputMode};
+pub use self::input::{ByteSeparator, Ellipsis, InputDisplay, PreferredFormat, Preset};
+pub use self::render::SectionRenderer;
+pub use self::style::Style;
+pub use self::theme::Theme;
+pub use self::width::{display_width, unit_widths, UnitWidth, UnitWidths};
 
 /// Library specific display trait that accepts a [`Write`] without requiring a
 /// formatter.
@@ -68,6 +82,10 @@ pub trait Write {
     /// # Errors
     ///
     /// Returns a [`core::fmt::Error`] if failed to write.
+    #[deprecated(
+        since = "0.11.0",
+        note = "input displays batch hex without calling this, use `InputDisplay::uppercase()` to change its case"
+    )]
     fn write_hex(&mut self, b: u8) -> Result {
         fn digit(b: u8) -> char {
             if b > 9 {
@@ -79,6 +97,22 @@ pub trait Write {
         self.write_char(digit(b >> 4))?;
         self.write_char(digit(b & 0x0F))
     }
+
+    /// Switches the style of what is written next, or back to no style if
+    /// `style` is `None`.
+    ///
+    /// Only called when the output is colored. `escape` is the ANSI escape
+    /// sequence the [`Theme`] sets for the style, or the reset sequence.
+    /// Writers that mark styled output in another way, such as HTML, can
+    /// override this to use `style` instead. Writers wrapping another writer
+    /// should pass it on.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`core::fmt::Error`] if failed to write.
+    fn write_style(&mut self, _style: Option<Style>, escape: &str) -> Result {
+        self.write_str(escape)
+    }
 }
 
 impl<T> Write for &mut T
@@ -98,7 +132,11 @@ where
     }
 
     fn write_hex(&mut self, b: u8) -> Result {
-        (**self).write_hex(b)
+        write_hex(*self, b)
+    }
+
+    fn write_style(&mut self, style: Option<Style>, escape: &str) -> Result {
+        (**self).write_style(style, escape)
     }
 }
 
@@ -116,8 +154,159 @@ impl<'a> Write for Formatter<'a> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl Write for alloc::string::String {
+    fn write_str(&mut self, s: &str) -> Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> Result {
+        self.push(c);
+        Ok(())
+    }
+
+    fn write_usize(&mut self, v: usize) -> Result {
+        core::fmt::Write::write_fmt(self, format_args!("{}", v))
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 
+/// Writes a byte as hex with [`Write::write_hex()`], which the writers within
+/// displays still implement to batch or escape hex.
+fn write_hex(w: &mut dyn Write, b: u8) -> Result {
+    #[allow(deprecated)]
+    w.write_hex(b)
+}
+
+/// Writes the decimal digits of `v` to the end of `buf`, returning them.
+pub(crate) fn usize_digits(mut v: usize, buf: &mut [u8; 20]) -> &[u8] {
+    let mut pos = buf.len();
+    loop {
+        pos -= 1;
+        #[allow(clippy::cast_possible_truncation)]
+        let digit = (v % 10) as u8;
+        buf[pos] = b'0' + digit;
+        v /= 10;
+        if v == 0 {
+            break;
+        }
+    }
+    &buf[pos..]
+}
+
+/// Writes a [`core::fmt::Display`] value to a [`Write`].
+pub(crate) fn write_display(w: &mut dyn Write, value: &dyn core::fmt::Display) -> Result {
+    struct Adapter<'a>(&'a mut dyn Write);
+
+    impl core::fmt::Write for Adapter<'_> {
+        fn write_str(&mut self, s: &str) -> Result {
+            self.0.write_str(s)
+        }
+    }
+
+    core::fmt::write(&mut Adapter(w), format_args!("{}", value))
+}
+
+/// Writes a [`DisplayBase`] value to a [`std::io::Write`].
+///
+/// The first IO error hit is returned over the [`core::fmt::Error`] it is
+/// surfaced as while formatting.
+#[cfg(feature = "std")]
+pub(crate) fn write_io(w: &mut dyn std::io::Write, value: &dyn DisplayBase) -> std::io::Result<()> {
+    struct Adapter<'a> {
+        inner: &'a mut dyn std::io::Write,
+        error: Option<std::io::Error>,
+    }
+
+    impl Adapter<'_> {
+        fn write_bytes(&mut self, bytes: &[u8]) -> Result {
+            self.inner.write_all(bytes).map_err(|err| {
+                self.error = Some(err);
+                core::fmt::Error
+            })
+        }
+    }
+
+    impl Write for Adapter<'_> {
+        fn write_str(&mut self, s: &str) -> Result {
+            self.write_bytes(s.as_bytes())
+        }
+
+        fn write_char(&mut self, c: char) -> Result {
+            self.write_bytes(c.encode_utf8(&mut [0; 4]).as_bytes())
+        }
+
+        fn write_usize(&mut self, v: usize) -> Result {
+            self.write_bytes(usize_digits(v, &mut [0; 20]))
+        }
+    }
+
+    let mut adapter = Adapter {
+        inner: w,
+        error: None,
+    };
+    match value.fmt(&mut adapter) {
+        Ok(()) => Ok(()),
+        Err(_) => Err(adapter
+            .error
+            .take()
+            .unwrap_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "formatter error"))),
+    }
+}
+
+/// Writes a [`DisplayBase`] value into a byte buffer, returning the number of
+/// bytes written.
+///
+/// The value is written as UTF-8. If it doesn't fit, it is cut short on a char
+/// boundary and the end replaced with `...` if there is room.
+pub(crate) fn write_buf(buf: &mut [u8], value: &dyn DisplayBase) -> usize {
+    const ELLIPSIS: &str = "...";
+
+    struct Buffer<'a> {
+        buf: &'a mut [u8],
+        len: usize,
+    }
+
+    impl Write for Buffer<'_> {
+        fn write_str(&mut self, s: &str) -> Result {
+            let remaining = self.buf.len() - self.len;
+            let mut end = s.len().min(remaining);
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            self.buf[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+            self.len += end;
+            if end == s.len() {
+                Ok(())
+            } else {
+                Err(core::fmt::Error)
+            }
+        }
+
+        fn write_char(&mut self, c: char) -> Result {
+            self.write_str(c.encode_utf8(&mut [0; 4]))
+        }
+
+        fn write_usize(&mut self, v: usize) -> Result {
+            write_display(self, &v)
+        }
+    }
+
+    let mut buffer = Buffer { buf, len: 0 };
+    if value.fmt(&mut buffer).is_err() && buffer.buf.len() >= ELLIPSIS.len() {
+        // Step back from the end to a char boundary with room for the ellipsis.
+        let mut end = buffer.len.min(buffer.buf.len() - ELLIPSIS.len());
+        while end > 0 && buffer.buf[end] & 0xC0 == 0x80 {
+            end -= 1;
+        }
+        buffer.buf[end..end + ELLIPSIS.len()].copy_from_slice(ELLIPSIS.as_bytes());
+        buffer.len = end + ELLIPSIS.len();
+    }
+    buffer.len
+}
+
 pub(crate) fn byte_count(w: &mut dyn Write, count: usize) -> Result {
     match count {
         0 => w.write_str("no bytes"),
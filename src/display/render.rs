@@ -0,0 +1,92 @@
+use core::ops::Range;
+
+use super::input::PreferredFormat;
+
+/// Renders the sections of input an [`InputDisplay`] selects.
+///
+/// The renderer is called with the runs of input to show in order, along with
+/// markers where input is not shown, leaving how they are drawn up to the
+/// application. Runs are split where the span starts and ends, so each run is
+/// either wholly within the span or wholly outside it.
+///
+/// See [`InputDisplay::render()`], and [`unit_widths()`] for the columns each
+/// run takes when written by the crate.
+///
+/// [`InputDisplay`]: super::InputDisplay
+/// [`InputDisplay::render()`]: super::InputDisplay::render()
+/// [`unit_widths()`]: super::unit_widths()
+pub trait SectionRenderer {
+    /// Called once before anything else with the format the section was
+    /// computed with.
+    ///
+    /// This can differ from the preferred format, as the str formats fall
+    /// back to bytes when the input is not valid UTF-8.
+    ///
+    /// Defaults to doing nothing.
+    fn start(&mut self, format: PreferredFormat) {
+        let _ = format;
+    }
+
+    /// Called with a run of input to show, at `offset` within the full input.
+    ///
+    /// An empty span is passed as an empty run where it points.
+    fn visible(&mut self, bytes: &[u8], offset: usize, in_span: bool);
+
+    /// Called in place of `elided` bytes of input that are not shown.
+    ///
+    /// `in_span` is `true` if the span overlaps the input not shown.
+    fn more(&mut self, elided: usize, in_span: bool);
+}
+
+/// Renders the visible sides of a section.
+pub(super) fn render(
+    r: &mut dyn SectionRenderer,
+    full: &[u8],
+    sides: (Range<usize>, Option<Range<usize>>),
+    span: Option<&Range<usize>>,
+) {
+    let (left, right) = sides;
+    let overlaps =
+        |start: usize, end: usize| span.map_or(false, |span| span.start < end && span.end > start);
+    if left.start > 0 {
+        r.more(left.start, overlaps(0, left.start));
+    }
+    render_runs(r, full, left.clone(), span);
+    let end = match right {
+        Some(right) => {
+            r.more(right.start - left.end, overlaps(left.end, right.start));
+            render_runs(r, full, right.clone(), span);
+            right.end
+        }
+        None => left.end,
+    };
+    if end < full.len() {
+        r.more(full.len() - end, overlaps(end, full.len()));
+    }
+}
+
+fn render_runs(
+    r: &mut dyn SectionRenderer,
+    full: &[u8],
+    range: Range<usize>,
+    span: Option<&Range<usize>>,
+) {
+    let span = match span {
+        Some(span) if span.start <= range.end && span.end >= range.start => span,
+        _ => {
+            r.visible(&full[range.clone()], range.start, false);
+            return;
+        }
+    };
+    let start = span.start.max(range.start);
+    let end = span.end.min(range.end);
+    if range.start < start {
+        r.visible(&full[range.start..start], range.start, false);
+    }
+    if start < end || span.is_empty() {
+        r.visible(&full[start..end], start, true);
+    }
+    if end < range.end {
+        r.visible(&full[end..range.end], end, false);
+    }
+}
@@ -5,14 +5,16 @@
 // | head-tail | `"a" .. "a"` | `[97 .. 97]` | `['a' .. 'a']` |
 // | span      | `.. "a" ..`  | `[.. 97 ..]` | `[.. 'a' ..]`  |
 
+use core::ops::Range;
 use core::str;
 
 use crate::fmt::{self, Write};
 use crate::input::Span;
 use crate::util::utf8;
 
-use super::input::{InputWriter, PreferredFormat};
-use super::unit::UnitIter;
+use super::input::{InputWriter, PreferredFormat, WriteOpt};
+use super::render::{self, SectionRenderer};
+use super::unit::{Lossy, UnitIter};
 
 const MIN_WIDTH: usize = 16;
 const SPACE_COST: usize = 1;
@@ -20,17 +22,57 @@ const DELIM_PAIR_COST: usize = 2;
 const SIDE_HAS_MORE_COST: usize = ".. ".len();
 const HEAD_TAIL_HAS_MORE_COST: usize = SIDE_HAS_MORE_COST;
 const STR_HEAD_TAIL_HAS_MORE_COST: usize = SIDE_HAS_MORE_COST + DELIM_PAIR_COST + SPACE_COST;
+const BYTE_STR_PREFIX_COST: usize = "b".len();
 
 #[derive(Copy, Clone)]
 pub(super) enum SectionOpt {
     Full,
-    Head { width: usize },
-    Tail { width: usize },
-    HeadTail { width: usize },
-    Span { width: usize, span: Span },
+    Head {
+        width: usize,
+    },
+    Tail {
+        width: usize,
+    },
+    HeadTail {
+        width: usize,
+    },
+    Span {
+        width: usize,
+        span: Span,
+    },
+    SpanAround {
+        span: Span,
+        before: usize,
+        after: usize,
+    },
+    SpanAroundChars {
+        span: Span,
+        before: usize,
+        after: usize,
+    },
 }
 
 impl SectionOpt {
+    /// Returns the option with `width` less of the width given to the input.
+    pub(super) fn reserve(self, width: usize) -> Self {
+        match self {
+            Self::Full | Self::SpanAround { .. } | Self::SpanAroundChars { .. } => self,
+            Self::Head { width: w } => Self::Head {
+                width: w.saturating_sub(width),
+            },
+            Self::Tail { width: w } => Self::Tail {
+                width: w.saturating_sub(width),
+            },
+            Self::HeadTail { width: w } => Self::HeadTail {
+                width: w.saturating_sub(width),
+            },
+            Self::Span { width: w, span } => Self::Span {
+                width: w.saturating_sub(width),
+                span,
+            },
+        }
+    }
+
     pub(super) fn compute(self, input: &[u8], format: PreferredFormat) -> Section<'_> {
         match self {
             Self::Full => Section::from_full(input, format),
@@ -38,6 +80,16 @@ impl SectionOpt {
             Self::Tail { width } => Section::from_tail(input, width, format),
             Self::HeadTail { width } => Section::from_head_tail(input, width, format),
             Self::Span { width, span } => Section::from_span(input, span, width, format),
+            Self::SpanAround {
+                span,
+                before,
+                after,
+            } => Section::from_span_around(input, span, before, after, format),
+            Self::SpanAroundChars {
+                span,
+                before,
+                after,
+            } => Section::from_span_around_chars(input, span, before, after, format),
         }
     }
 }
@@ -61,6 +113,14 @@ enum Visible<'a> {
     BytesPair(&'a [u8], &'a [u8]),
     // head-tail-bytes-ascii
     BytesAsciiPair(&'a [u8], &'a [u8]),
+    // str-lossy, head-str-lossy, tail-str-lossy, span-str-lossy
+    StrLossy(&'a [u8]),
+    // head-tail-str-lossy
+    StrLossyPair(&'a [u8], &'a [u8]),
+    // byte-str, head-byte-str, tail-byte-str, span-byte-str
+    ByteStr(&'a [u8]),
+    // head-tail-byte-str
+    ByteStrPair(&'a [u8], &'a [u8]),
 }
 
 #[derive(Clone)]
@@ -89,6 +149,8 @@ impl<'a> Section<'a> {
                     Visible::BytesAscii(full)
                 }
             }
+            PreferredFormat::StrLossy => Visible::StrLossy(full),
+            PreferredFormat::ByteStr => Visible::ByteStr(full),
         };
         Self {
             full,
@@ -104,6 +166,8 @@ impl<'a> Section<'a> {
             PreferredFormat::BytesAscii => take_bytes_head(full, width, true),
             PreferredFormat::Str => take_str_head(full, width, false),
             PreferredFormat::StrCjk => take_str_head(full, width, true),
+            PreferredFormat::StrLossy => take_lossy_head(full, width),
+            PreferredFormat::ByteStr => take_byte_str_head(full, width),
         };
         Self {
             full,
@@ -119,6 +183,8 @@ impl<'a> Section<'a> {
             PreferredFormat::BytesAscii => take_bytes_tail(full, width, true),
             PreferredFormat::Str => take_str_tail(full, width, false),
             PreferredFormat::StrCjk => take_str_tail(full, width, true),
+            PreferredFormat::StrLossy => take_lossy_tail(full, width),
+            PreferredFormat::ByteStr => take_byte_str_tail(full, width),
         };
         Self {
             full,
@@ -134,6 +200,8 @@ impl<'a> Section<'a> {
             PreferredFormat::BytesAscii => take_bytes_head_tail(full, width, true),
             PreferredFormat::Str => take_str_head_tail(full, width, false),
             PreferredFormat::StrCjk => take_str_head_tail(full, width, true),
+            PreferredFormat::StrLossy => take_lossy_head_tail(full, width),
+            PreferredFormat::ByteStr => take_byte_str_head_tail(full, width),
         };
         Self {
             full,
@@ -161,6 +229,8 @@ impl<'a> Section<'a> {
                     PreferredFormat::BytesAscii => take_bytes_head(full, width, true),
                     PreferredFormat::Str => take_str_head(full, width, false),
                     PreferredFormat::StrCjk => take_str_head(full, width, true),
+                    PreferredFormat::StrLossy => take_lossy_head(full, width),
+                    PreferredFormat::ByteStr => take_byte_str_head(full, width),
                 };
                 return Self {
                     full,
@@ -173,6 +243,8 @@ impl<'a> Section<'a> {
                     PreferredFormat::BytesAscii => take_bytes_tail(full, width, true),
                     PreferredFormat::Str => take_str_tail(full, width, false),
                     PreferredFormat::StrCjk => take_str_tail(full, width, true),
+                    PreferredFormat::StrLossy => take_lossy_tail(full, width),
+                    PreferredFormat::ByteStr => take_byte_str_tail(full, width),
                 };
                 return Self {
                     full,
@@ -199,6 +271,8 @@ impl<'a> Section<'a> {
             PreferredFormat::BytesAscii => take_bytes_span(full, span_offset, width, true),
             PreferredFormat::Str => take_str_span(full, span_offset, width, false),
             PreferredFormat::StrCjk => take_str_span(full, span_offset, width, true),
+            PreferredFormat::StrLossy => take_lossy_span(full, span_offset, width),
+            PreferredFormat::ByteStr => take_byte_str_span(full, span_offset, width),
         };
         Self {
             full,
@@ -207,8 +281,81 @@ impl<'a> Section<'a> {
         }
     }
 
-    pub(super) fn write(&self, w: &mut dyn Write, underline: bool) -> fmt::Result {
-        let mut writer = InputWriter::new(w, self.full, self.span, underline);
+    /// Takes `before` and `after` bytes either side of the span, trimmed to
+    /// whole chars for the str formats.
+    ///
+    /// If the span is not within the input, `after` bytes from the head of the
+    /// input are taken.
+    pub(super) fn from_span_around(
+        full: &'a [u8],
+        span: Span,
+        before: usize,
+        after: usize,
+        format: PreferredFormat,
+    ) -> Self {
+        let (range, span) = match span.range_of(full.into()) {
+            Some(range) => (range, Some(span)),
+            None => (0..0, None),
+        };
+        let mut start = range.start.saturating_sub(before);
+        let mut end = full.len().min(range.end.saturating_add(after));
+        if !matches!(format, PreferredFormat::Bytes | PreferredFormat::BytesAscii) {
+            while start < range.start && utf8::char_len(full[start]) == 0 {
+                start += 1;
+            }
+            while end > range.end && end < full.len() && utf8::char_len(full[end]) == 0 {
+                end -= 1;
+            }
+        }
+        Self::from_bounds(full, start..end, span, format)
+    }
+
+    /// Takes `before` and `after` chars either side of the span.
+    ///
+    /// If the span is not within the input, `after` chars from the head of the
+    /// input are taken.
+    pub(super) fn from_span_around_chars(
+        full: &'a [u8],
+        span: Span,
+        before: usize,
+        after: usize,
+        format: PreferredFormat,
+    ) -> Self {
+        let (range, span) = match span.range_of(full.into()) {
+            Some(range) => (range, Some(span)),
+            None => (0..0, None),
+        };
+        Self::from_bounds(full, char_bounds(full, range, before, after), span, format)
+    }
+
+    fn from_bounds(
+        full: &'a [u8],
+        bounds: Range<usize>,
+        span: Option<Span>,
+        format: PreferredFormat,
+    ) -> Self {
+        let bytes = &full[bounds];
+        let visible = match format {
+            PreferredFormat::Bytes => Visible::Bytes(bytes),
+            PreferredFormat::BytesAscii => Visible::BytesAscii(bytes),
+            PreferredFormat::Str => {
+                str::from_utf8(bytes).map_or(Visible::BytesAscii(bytes), Visible::Str)
+            }
+            PreferredFormat::StrCjk => {
+                str::from_utf8(bytes).map_or(Visible::BytesAscii(bytes), Visible::StrCjk)
+            }
+            PreferredFormat::StrLossy => Visible::StrLossy(bytes),
+            PreferredFormat::ByteStr => Visible::ByteStr(bytes),
+        };
+        Self {
+            full,
+            visible,
+            span,
+        }
+    }
+
+    pub(super) fn write(&self, w: &mut dyn Write, opt: WriteOpt<'_>) -> fmt::Result {
+        let mut writer = InputWriter::new(w, self.full, self.span, opt);
         match self.visible {
             Visible::Bytes(bytes) => writer.write_bytes_side(bytes, false),
             Visible::BytesAscii(bytes) => writer.write_bytes_side(bytes, true),
@@ -218,10 +365,112 @@ impl<'a> Section<'a> {
             Visible::BytesAsciiPair(left, right) => writer.write_bytes_sides(left, right, true),
             Visible::StrPair(left, right) => writer.write_str_sides(left, right, false),
             Visible::StrCjkPair(left, right) => writer.write_str_sides(left, right, true),
+            Visible::StrLossy(bytes) => writer.write_lossy_side(bytes),
+            Visible::StrLossyPair(left, right) => writer.write_lossy_sides(left, right),
+            Visible::ByteStr(bytes) => writer.write_byte_str_side(bytes),
+            Visible::ByteStrPair(left, right) => writer.write_byte_str_sides(left, right),
+        }?;
+        writer.finish()
+    }
+
+    /// Writes the offsets of the visible input, and of the span if any, within
+    /// the full input.
+    pub(super) fn write_offsets(&self, w: &mut dyn Write) -> fmt::Result {
+        let (left, right) = self.sides();
+        w.write_str(" (offsets ")?;
+        self.write_range(w, left.into())?;
+        if let Some(right) = right {
+            w.write_str(" and ")?;
+            self.write_range(w, right.into())?;
+        }
+        w.write_str(" of ")?;
+        w.write_usize(self.full.len())?;
+        if let Some(span) = self.span {
+            w.write_str(", span ")?;
+            self.write_range(w, span)?;
+        }
+        w.write_char(')')
+    }
+
+    pub(super) fn render(&self, r: &mut dyn SectionRenderer) {
+        r.start(self.format());
+        let (left, right) = self.sides();
+        let range_of = |bytes: &[u8]| Span::from(bytes).range_of(self.full.into());
+        let left = range_of(left).unwrap_or(0..0);
+        let right = right.and_then(range_of);
+        let span = self.span.and_then(|span| span.range_of(self.full.into()));
+        render::render(r, self.full, (left, right), span.as_ref());
+    }
+
+    pub(super) fn format(&self) -> PreferredFormat {
+        match self.visible {
+            Visible::Str(_) | Visible::StrPair(..) => PreferredFormat::Str,
+            Visible::StrCjk(_) | Visible::StrCjkPair(..) => PreferredFormat::StrCjk,
+            Visible::Bytes(_) | Visible::BytesPair(..) => PreferredFormat::Bytes,
+            Visible::BytesAscii(_) | Visible::BytesAsciiPair(..) => PreferredFormat::BytesAscii,
+            Visible::StrLossy(_) | Visible::StrLossyPair(..) => PreferredFormat::StrLossy,
+            Visible::ByteStr(_) | Visible::ByteStrPair(..) => PreferredFormat::ByteStr,
+        }
+    }
+
+    /// Returns the visible input as bytes, split where input is not shown.
+    fn sides(&self) -> (&'a [u8], Option<&'a [u8]>) {
+        match self.visible {
+            Visible::Bytes(bytes)
+            | Visible::BytesAscii(bytes)
+            | Visible::StrLossy(bytes)
+            | Visible::ByteStr(bytes) => (bytes, None),
+            Visible::Str(s) | Visible::StrCjk(s) => (s.as_bytes(), None),
+            Visible::BytesPair(left, right)
+            | Visible::BytesAsciiPair(left, right)
+            | Visible::StrLossyPair(left, right)
+            | Visible::ByteStrPair(left, right) => (left, Some(right)),
+            Visible::StrPair(left, right) | Visible::StrCjkPair(left, right) => {
+                (left.as_bytes(), Some(right.as_bytes()))
+            }
+        }
+    }
+
+    fn write_range(&self, w: &mut dyn Write, span: Span) -> fmt::Result {
+        match span.range_of(self.full.into()) {
+            Some(range) => {
+                w.write_usize(range.start)?;
+                w.write_str("..")?;
+                w.write_usize(range.end)
+            }
+            None => w.write_str("invalid"),
         }
     }
 }
 
+/// Returns the bounds of `before` chars before the range and `after` chars
+/// after it.
+///
+/// A byte that is not part of a char of valid UTF-8 counts as one char, so
+/// the bounds never fall within a char.
+pub(super) fn char_bounds(
+    full: &[u8],
+    range: Range<usize>,
+    before: usize,
+    after: usize,
+) -> Range<usize> {
+    let mut start = range.start;
+    for _ in 0..before {
+        match Lossy::back(&full[..start]) {
+            Some((_, len)) => start -= len,
+            None => break,
+        }
+    }
+    let mut end = range.end;
+    for _ in 0..after {
+        match Lossy::front(&full[end..]) {
+            Some((_, len)) => end += len,
+            None => break,
+        }
+    }
+    start..end
+}
+
 fn init_width(width: usize) -> usize {
     // account for `[]` or `""`
     if width < MIN_WIDTH {
@@ -356,6 +605,70 @@ fn take_bytes_head_tail(bytes: &[u8], width: usize, show_ascii: bool) -> Visible
     }
 }
 
+fn take_lossy_span(bytes: &[u8], span_offset: usize, width: usize) -> Visible<'_> {
+    let iter = UnitIter::new_lossy(bytes);
+    let (start, end) = take_span(iter, span_offset, width, false).unwrap();
+    Visible::StrLossy(&bytes[start..end])
+}
+
+fn take_lossy_head(bytes: &[u8], width: usize) -> Visible<'_> {
+    let iter = UnitIter::new_lossy(bytes);
+    let (len, _) = take_head(iter, width, false).unwrap();
+    Visible::StrLossy(&bytes[..len])
+}
+
+fn take_lossy_tail(bytes: &[u8], width: usize) -> Visible<'_> {
+    let iter = UnitIter::new_lossy(bytes);
+    let (len, _) = take_tail(iter, width, false).unwrap();
+    Visible::StrLossy(&bytes[bytes.len() - len..])
+}
+
+fn take_lossy_head_tail(bytes: &[u8], width: usize) -> Visible<'_> {
+    let iter = UnitIter::new_lossy(bytes);
+    let (start, end) = take_head_tail(iter, width, false, STR_HEAD_TAIL_HAS_MORE_COST).unwrap();
+    if start == end {
+        Visible::StrLossy(bytes)
+    } else {
+        Visible::StrLossyPair(&bytes[..start], &bytes[end..])
+    }
+}
+
+// The widths given to a byte string are less the `b` prefix of each literal.
+
+fn take_byte_str_span(bytes: &[u8], span_offset: usize, width: usize) -> Visible<'_> {
+    let iter = UnitIter::new_byte_str(bytes);
+    let (start, end) = take_span(iter, span_offset, width - BYTE_STR_PREFIX_COST, false).unwrap();
+    Visible::ByteStr(&bytes[start..end])
+}
+
+fn take_byte_str_head(bytes: &[u8], width: usize) -> Visible<'_> {
+    let iter = UnitIter::new_byte_str(bytes);
+    let (len, _) = take_head(iter, width - BYTE_STR_PREFIX_COST, false).unwrap();
+    Visible::ByteStr(&bytes[..len])
+}
+
+fn take_byte_str_tail(bytes: &[u8], width: usize) -> Visible<'_> {
+    let iter = UnitIter::new_byte_str(bytes);
+    let (len, _) = take_tail(iter, width - BYTE_STR_PREFIX_COST, false).unwrap();
+    Visible::ByteStr(&bytes[bytes.len() - len..])
+}
+
+fn take_byte_str_head_tail(bytes: &[u8], width: usize) -> Visible<'_> {
+    let iter = UnitIter::new_byte_str(bytes);
+    let (start, end) = take_head_tail(
+        iter,
+        width - BYTE_STR_PREFIX_COST,
+        false,
+        STR_HEAD_TAIL_HAS_MORE_COST + BYTE_STR_PREFIX_COST,
+    )
+    .unwrap();
+    if start == end {
+        Visible::ByteStr(bytes)
+    } else {
+        Visible::ByteStrPair(&bytes[..start], &bytes[end..])
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 
 /// Returns `Result<(length, remaining), ()>`
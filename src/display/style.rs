@@ -0,0 +1,54 @@
+//! Styles marking sections of output, and the ANSI escape sequences written
+//! for them when color is enabled.
+
+use crate::fmt::{Result, Write};
+
+use super::Theme;
+
+pub(super) const RESET: &str = "\x1b[0m";
+/// The error description.
+pub(super) const HEADER: &str = "\x1b[1m";
+/// The section of input the error span covers and its underline.
+pub(super) const HIGHLIGHT: &str = "\x1b[1;31m";
+/// The section of input a secondary span covers and its underline.
+pub(super) const SECONDARY: &str = "\x1b[1;34m";
+/// Expected values and context expectations.
+pub(super) const EXPECTED: &str = "\x1b[32m";
+/// Operations within the context backtrace.
+pub(super) const OPERATION: &str = "\x1b[36m";
+
+/// What a section of colored output marks.
+///
+/// Passed to [`Write::write_style()`], so writers can style output by what it
+/// marks instead of by the escape sequence written for it.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Style {
+    /// The error description.
+    Header,
+    /// The section of input the error span covers and its underline.
+    Highlight,
+    /// The section of input a secondary span covers and its underline.
+    Secondary,
+    /// Expected values and context expectations.
+    Expected,
+    /// Operations within the context backtrace.
+    Operation,
+}
+
+/// Writes `f` wrapped in the provided style if `color` is enabled.
+pub(super) fn paint(
+    w: &mut dyn Write,
+    color: bool,
+    theme: &Theme,
+    style: Style,
+    f: impl FnOnce(&mut dyn Write) -> Result,
+) -> Result {
+    if color {
+        w.write_style(Some(style), theme.escape(style))?;
+        f(w)?;
+        w.write_style(None, RESET)
+    } else {
+        f(w)
+    }
+}
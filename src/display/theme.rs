@@ -0,0 +1,156 @@
+use super::style::{self, Style};
+use super::Ellipsis;
+
+/// The characters and styles displays are written with.
+///
+/// Every character of a theme is expected to be one column wide, as the
+/// underline of a span is aligned to the input column by column.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::display::Theme;
+/// use dangerous::Input;
+///
+/// let theme = Theme::new().underline('~').delimiters('<', '>');
+/// let input = dangerous::input(b"hello");
+/// let display = input.display().theme(theme).span(input.span(), 16);
+///
+/// assert_eq!(display.to_string(), "<68 65 6c 6c 6f>");
+/// assert_eq!(display.underline().to_string(), " ~~ ~~ ~~ ~~ ~~ ");
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[must_use]
+pub struct Theme {
+    pub(super) underline: char,
+    pub(super) secondary_underline: char,
+    pub(super) open: char,
+    pub(super) close: char,
+    pub(super) quote: char,
+    pub(super) ellipsis: Ellipsis,
+    pub(super) header: &'static str,
+    pub(super) highlight: &'static str,
+    pub(super) secondary: &'static str,
+    pub(super) expected: &'static str,
+    pub(super) operation: &'static str,
+}
+
+impl Theme {
+    /// Create the default `Theme`.
+    pub const fn new() -> Self {
+        Self {
+            underline: '^',
+            secondary_underline: '-',
+            open: '[',
+            close: ']',
+            quote: '"',
+            ellipsis: Ellipsis::Dots,
+            header: style::HEADER,
+            highlight: style::HIGHLIGHT,
+            secondary: style::SECONDARY,
+            expected: style::EXPECTED,
+            operation: style::OPERATION,
+        }
+    }
+
+    /// Set the character the span is underlined with.
+    ///
+    /// Defaults to `^`.
+    pub fn underline(mut self, c: char) -> Self {
+        self.underline = c;
+        self
+    }
+
+    /// Set the character a secondary span is underlined with.
+    ///
+    /// Defaults to `-`.
+    pub fn secondary_underline(mut self, c: char) -> Self {
+        self.secondary_underline = c;
+        self
+    }
+
+    /// Set the characters bytes are delimited with.
+    ///
+    /// Defaults to `[` and `]`.
+    pub fn delimiters(mut self, open: char, close: char) -> Self {
+        self.open = open;
+        self.close = close;
+        self
+    }
+
+    /// Set the character strings are quoted with.
+    ///
+    /// Defaults to `"`.
+    pub fn quote(mut self, c: char) -> Self {
+        self.quote = c;
+        self
+    }
+
+    /// Set the marker written in place of input that is not shown.
+    ///
+    /// Defaults to [`Ellipsis::Dots`].
+    pub fn ellipsis(mut self, ellipsis: Ellipsis) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    /// Set the ANSI escape sequence the error description is colored with.
+    ///
+    /// Defaults to bold.
+    pub fn header_style(mut self, style: &'static str) -> Self {
+        self.header = style;
+        self
+    }
+
+    /// Set the ANSI escape sequence the span and its underline are colored
+    /// with.
+    ///
+    /// Defaults to bold red.
+    pub fn highlight_style(mut self, style: &'static str) -> Self {
+        self.highlight = style;
+        self
+    }
+
+    /// Set the ANSI escape sequence a secondary span and its underline are
+    /// colored with.
+    ///
+    /// Defaults to bold blue.
+    pub fn secondary_style(mut self, style: &'static str) -> Self {
+        self.secondary = style;
+        self
+    }
+
+    /// Set the ANSI escape sequence expected values are colored with.
+    ///
+    /// Defaults to green.
+    pub fn expected_style(mut self, style: &'static str) -> Self {
+        self.expected = style;
+        self
+    }
+
+    /// Set the ANSI escape sequence the operations of a context backtrace are
+    /// colored with.
+    ///
+    /// Defaults to cyan.
+    pub fn operation_style(mut self, style: &'static str) -> Self {
+        self.operation = style;
+        self
+    }
+
+    /// Returns the ANSI escape sequence set for the style.
+    pub(super) fn escape(&self, style: Style) -> &'static str {
+        match style {
+            Style::Header => self.header,
+            Style::Highlight => self.highlight,
+            Style::Secondary => self.secondary,
+            Style::Expected => self.expected,
+            Style::Operation => self.operation,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,8 +1,12 @@
 #[cfg(feature = "unicode")]
 use unicode_width::UnicodeWidthChar;
 
+use core::str;
+
 use crate::fmt::{self, Write};
-use crate::util::utf8::CharIter;
+use crate::util::utf8::{self, CharIter};
+
+use super::PreferredFormat;
 
 ///////////////////////////////////////////////////////////////////////////////
 // Byte display
@@ -32,10 +36,10 @@ pub(super) fn byte_display_write(b: u8, show_ascii: bool, w: &mut dyn Write) ->
                 w.write_char(b as char)?;
                 w.write_char('\'')
             }
-            b => w.write_hex(b),
+            b => super::write_hex(w, b),
         }
     } else {
-        w.write_hex(b)
+        super::write_hex(w, b)
     }
 }
 
@@ -60,6 +64,54 @@ fn byte_next_back(bytes: &mut &[u8], show_ascii: bool) -> Option<Result<Unit, ()
     }
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// Byte string display
+
+pub(super) fn byte_str_display_width(b: u8) -> usize {
+    match b {
+        b'\"' | b'\\' | b'\n' | b'\r' | b'\t' | b'\0' => "\\x".len(),
+        b' '..=b'~' => "x".len(),
+        _ => "\\xNN".len(),
+    }
+}
+
+/// Writes a byte as it would be written within a Rust byte string literal.
+pub(super) fn byte_str_display_write(b: u8, w: &mut dyn Write) -> fmt::Result {
+    match b {
+        b'\"' => w.write_str("\\\""),
+        b'\\' => w.write_str("\\\\"),
+        b'\n' => w.write_str("\\n"),
+        b'\r' => w.write_str("\\r"),
+        b'\t' => w.write_str("\\t"),
+        b'\0' => w.write_str("\\0"),
+        b' '..=b'~' => w.write_char(b as char),
+        b => {
+            w.write_str("\\x")?;
+            super::write_hex(w, b)
+        }
+    }
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn byte_str_next_front(bytes: &mut &[u8], _: bool) -> Option<Result<Unit, ()>> {
+    let (&b, rest) = bytes.split_first()?;
+    *bytes = rest;
+    Some(Ok(Unit {
+        display_cost: byte_str_display_width(b),
+        len_utf8: 1,
+    }))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn byte_str_next_back(bytes: &mut &[u8], _: bool) -> Option<Result<Unit, ()>> {
+    let (&b, rest) = bytes.split_last()?;
+    *bytes = rest;
+    Some(Ok(Unit {
+        display_cost: byte_str_display_width(b),
+        len_utf8: 1,
+    }))
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Char display
 
@@ -68,6 +120,11 @@ pub(super) fn char_display_width(c: char, cjk: bool) -> usize {
         .fold(0, |acc, c| acc + unicode_width(c, cjk))
 }
 
+/// Returns the number of columns a char written to a display takes.
+pub(super) fn char_width(c: char, cjk: bool) -> usize {
+    unicode_width(c, cjk)
+}
+
 pub(super) fn char_display_write(c: char, w: &mut dyn Write) -> fmt::Result {
     for c in c.escape_debug() {
         w.write_char(c)?;
@@ -75,9 +132,17 @@ pub(super) fn char_display_write(c: char, w: &mut dyn Write) -> fmt::Result {
     Ok(())
 }
 
+/// Returns the number of columns a char takes in a terminal.
+///
+/// Combining marks and zero-width chars are escaped before they get here, but
+/// emoji skin tone modifiers are printable and drawn combined with the emoji
+/// before them, so they take no columns of their own.
 #[cfg(feature = "unicode")]
 #[inline]
 fn unicode_width(c: char, cjk: bool) -> usize {
+    if matches!(c, '\u{1F3FB}'..='\u{1F3FF}') {
+        return 0;
+    }
     if cjk { c.width_cjk() } else { c.width() }.unwrap_or(1)
 }
 
@@ -105,6 +170,90 @@ fn char_next_back(bytes: &mut &[u8], cjk: bool) -> Option<Result<Unit, ()>> {
     result
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// Lossy display
+
+/// A char of valid UTF-8, or a byte that is not part of one.
+#[derive(Copy, Clone)]
+pub(super) enum Lossy {
+    Char(char),
+    Byte(u8),
+}
+
+impl Lossy {
+    /// Returns the first lossy unit and its length in bytes.
+    pub(super) fn front(bytes: &[u8]) -> Option<(Self, usize)> {
+        let first = *bytes.first()?;
+        let len = utf8::char_len(first);
+        if len > 0 && len <= bytes.len() {
+            if let Ok(s) = str::from_utf8(&bytes[..len]) {
+                if let Some(c) = s.chars().next() {
+                    return Some((Self::Char(c), len));
+                }
+            }
+        }
+        Some((Self::Byte(first), 1))
+    }
+
+    /// Returns the last lossy unit and its length in bytes.
+    pub(super) fn back(bytes: &[u8]) -> Option<(Self, usize)> {
+        let last = *bytes.last()?;
+        let min_start = bytes.len().saturating_sub(4);
+        let start = (min_start..bytes.len())
+            .rev()
+            .find(|&i| !is_continuation(bytes[i]));
+        if let Some(start) = start {
+            if let Some((unit @ Self::Char(_), len)) = Self::front(&bytes[start..]) {
+                if start + len == bytes.len() {
+                    return Some((unit, len));
+                }
+            }
+        }
+        Some((Self::Byte(last), 1))
+    }
+
+    pub(super) fn display_width(self, cjk: bool) -> usize {
+        match self {
+            Self::Char(c) => char_display_width(c, cjk),
+            Self::Byte(_) => "\\xNN".len(),
+        }
+    }
+
+    pub(super) fn display_write(self, w: &mut dyn Write) -> fmt::Result {
+        match self {
+            Self::Char(c) => char_display_write(c, w),
+            Self::Byte(b) => {
+                w.write_str("\\x")?;
+                super::write_hex(w, b)
+            }
+        }
+    }
+}
+
+fn is_continuation(b: u8) -> bool {
+    b & 0xC0 == 0x80
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn lossy_next_front(bytes: &mut &[u8], cjk: bool) -> Option<Result<Unit, ()>> {
+    let (unit, len) = Lossy::front(bytes)?;
+    *bytes = &bytes[len..];
+    Some(Ok(Unit {
+        display_cost: unit.display_width(cjk),
+        len_utf8: len,
+    }))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn lossy_next_back(bytes: &mut &[u8], cjk: bool) -> Option<Result<Unit, ()>> {
+    let (unit, len) = Lossy::back(bytes)?;
+    *bytes = &bytes[..bytes.len() - len];
+    Some(Ok(Unit {
+        display_cost: unit.display_width(cjk),
+        len_utf8: len,
+    }))
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Unit iterator
 
@@ -159,6 +308,42 @@ impl<'a> UnitIter<'a> {
         }
     }
 
+    pub(super) fn new_lossy(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            modifier: false,
+            next_front: lossy_next_front,
+            next_back: lossy_next_back,
+        }
+    }
+
+    pub(super) fn new_byte_str(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            modifier: false,
+            next_front: byte_str_next_front,
+            next_back: byte_str_next_back,
+        }
+    }
+
+    /// Creates an iterator over the units of `bytes` written in `format`.
+    ///
+    /// Unlike [`UnitIter::new_char()`], bytes that are not valid UTF-8 are
+    /// taken as escapes for the str formats, so the iterator never fails.
+    pub(super) fn new_format(bytes: &'a [u8], format: PreferredFormat) -> Self {
+        match format {
+            PreferredFormat::Bytes => Self::new_byte(bytes, false),
+            PreferredFormat::BytesAscii => Self::new_byte(bytes, true),
+            PreferredFormat::Str | PreferredFormat::StrCjk | PreferredFormat::StrLossy => Self {
+                bytes,
+                modifier: format == PreferredFormat::StrCjk,
+                next_front: lossy_next_front,
+                next_back: lossy_next_back,
+            },
+            PreferredFormat::ByteStr => Self::new_byte_str(bytes),
+        }
+    }
+
     pub(super) fn has_next(&self) -> bool {
         !self.bytes.is_empty()
     }
@@ -0,0 +1,100 @@
+use core::iter::FusedIterator;
+
+use super::unit::{Unit, UnitIter};
+use super::PreferredFormat;
+
+/// The width of a unit of input written by an [`InputDisplay`].
+///
+/// A unit is a byte for the byte formats and a char for the str formats.
+///
+/// [`InputDisplay`]: super::InputDisplay
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UnitWidth {
+    /// The number of bytes of input the unit covers.
+    pub len: usize,
+    /// The number of columns the unit takes when written.
+    pub width: usize,
+}
+
+/// Returns an iterator over the widths of the units of `bytes` written in
+/// `format`.
+///
+/// This is what the underline of a span is aligned with, so a
+/// [`SectionRenderer`] can use it to draw its own underline under each run.
+/// Bytes that are not valid UTF-8 are taken as the `\xNN` escapes of
+/// [`PreferredFormat::StrLossy`] for the str formats.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::display::{self, PreferredFormat, UnitWidth};
+///
+/// let widths: Vec<_> = display::unit_widths("a\n♥".as_bytes(), PreferredFormat::Str).collect();
+///
+/// assert_eq!(
+///     widths,
+///     [
+///         UnitWidth { len: 1, width: 1 },
+///         UnitWidth { len: 1, width: 2 },
+///         UnitWidth { len: 3, width: 1 },
+///     ]
+/// );
+/// ```
+///
+/// [`SectionRenderer`]: super::SectionRenderer
+pub fn unit_widths(bytes: &[u8], format: PreferredFormat) -> UnitWidths<'_> {
+    UnitWidths(UnitIter::new_format(bytes, format))
+}
+
+/// Returns the number of columns `bytes` take when written in `format`.
+///
+/// The bytes of the byte formats are counted with a space between each, as
+/// they are written by default. Delimiters, quotes and ellipses around the
+/// input are not counted.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::display::{self, PreferredFormat};
+///
+/// assert_eq!(display::display_width(b"hi", PreferredFormat::Bytes), 5);
+/// assert_eq!(display::display_width(b"hi", PreferredFormat::Str), 2);
+/// ```
+#[must_use]
+pub fn display_width(bytes: &[u8], format: PreferredFormat) -> usize {
+    let separators = match format {
+        PreferredFormat::Bytes | PreferredFormat::BytesAscii => bytes.len().saturating_sub(1),
+        _ => 0,
+    };
+    unit_widths(bytes, format).fold(separators, |acc, unit| acc + unit.width)
+}
+
+/// An iterator over the widths of the units of input.
+///
+/// See [`unit_widths()`].
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct UnitWidths<'a>(UnitIter<'a>);
+
+impl Iterator for UnitWidths<'_> {
+    type Item = UnitWidth;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_front()?.ok().map(to_width)
+    }
+}
+
+impl DoubleEndedIterator for UnitWidths<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()?.ok().map(to_width)
+    }
+}
+
+impl FusedIterator for UnitWidths<'_> {}
+
+fn to_width(unit: Unit) -> UnitWidth {
+    UnitWidth {
+        len: unit.len_utf8,
+        width: unit.display_cost,
+    }
+}
@@ -0,0 +1,97 @@
+use crate::fmt::{self, Write};
+
+use super::unit::char_width;
+
+/// Writes a line of display wrapped to lines of at most `width` columns,
+/// with the underline for each line written below it.
+///
+/// The line and underline are written by `line` once per line, only keeping
+/// what falls within the columns of that line, so nothing is buffered. A char
+/// is never split across lines, and a char wider than the width is given a
+/// line of its own. Underlines with nothing to point to are skipped.
+pub(super) fn write(
+    w: &mut dyn Write,
+    width: usize,
+    cjk: bool,
+    line: &dyn Fn(&mut dyn Write, bool) -> fmt::Result,
+) -> fmt::Result {
+    let mut start = 0;
+    loop {
+        let mut measure = Window::new(None, start, start + width, cjk);
+        line(&mut measure, false)?;
+        let total = measure.column;
+        let end = measure.overflow.unwrap_or(start + width).min(total);
+        if start > 0 {
+            w.write_char('\n')?;
+        }
+        line(&mut Window::new(Some(w), start, end, cjk), false)?;
+        let mut underline = Window::new(None, start, end, cjk);
+        line(&mut underline, true)?;
+        if underline.marked {
+            w.write_char('\n')?;
+            line(&mut Window::new(Some(w), start, end, cjk), true)?;
+        }
+        if end >= total {
+            return Ok(());
+        }
+        start = end;
+    }
+}
+
+/// Passes through the chars written that start within a range of columns.
+struct Window<'a> {
+    w: Option<&'a mut dyn Write>,
+    start: usize,
+    end: usize,
+    cjk: bool,
+    column: usize,
+    overflow: Option<usize>,
+    marked: bool,
+}
+
+impl<'a> Window<'a> {
+    fn new(w: Option<&'a mut dyn Write>, start: usize, end: usize, cjk: bool) -> Self {
+        Self {
+            w,
+            start,
+            end,
+            cjk,
+            column: 0,
+            overflow: None,
+            marked: false,
+        }
+    }
+}
+
+impl Write for Window<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.write_char(c)?;
+        }
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        let width = char_width(c, self.cjk);
+        let column = self.column;
+        self.column += width;
+        if column < self.start || column >= self.end {
+            return Ok(());
+        }
+        if column + width > self.end && column != self.start {
+            self.overflow.get_or_insert(column);
+            return Ok(());
+        }
+        if c != ' ' {
+            self.marked = true;
+        }
+        match &mut self.w {
+            Some(w) => w.write_char(c),
+            None => Ok(()),
+        }
+    }
+
+    fn write_usize(&mut self, v: usize) -> fmt::Result {
+        super::write_display(self, &v)
+    }
+}
@@ -1,9 +1,23 @@
 #[cfg(feature = "alloc")]
-use alloc::{boxed::Box, vec::Vec};
-#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, collections::VecDeque, string::String, vec::Vec};
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+use core::any::Any;
+#[cfg(any(feature = "alloc", feature = "heapless"))]
 use core::iter;
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+#[cfg(all(feature = "smallvec", feature = "alloc"))]
+use smallvec::SmallVec;
+
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+use crate::fmt;
 
-use super::{Context, CoreContext};
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+use super::Operation;
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+use super::OperationId;
+use super::{written_eq, Context, CoreContext};
+use crate::input::Span;
 
 /// Implemented for walkable stacks of [`Context`]s collected from an error.
 pub trait Backtrace: 'static {
@@ -49,6 +63,235 @@ pub trait BacktraceBuilder {
 /// depth` value.
 pub type BacktraceWalker<'a> = dyn FnMut(usize, &dyn Context) -> bool + 'a;
 
+impl dyn Backtrace + '_ {
+    /// Walk the contexts of type `C` in the backtrace, starting with the
+    /// highest context to the root.
+    ///
+    /// Contexts of other types are skipped. See [`Backtrace::walk()`] for the
+    /// parameters and return value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::any::Any;
+    /// use core::fmt;
+    /// use dangerous::display::Write;
+    /// use dangerous::error::{Context, Details, Operation};
+    /// use dangerous::{Expected, Input};
+    ///
+    /// #[derive(Clone)]
+    /// struct Field(&'static str);
+    ///
+    /// impl Context for Field {
+    ///     fn operation(&self) -> &dyn Operation {
+    ///         self
+    ///     }
+    ///
+    ///     fn as_any(&self) -> &dyn Any {
+    ///         self
+    ///     }
+    /// }
+    ///
+    /// impl Operation for Field {
+    ///     fn description(&self, w: &mut dyn Write) -> fmt::Result {
+    ///         w.write_str("read field ")?;
+    ///         w.write_str(self.0)
+    ///     }
+    ///
+    ///     fn as_any(&self) -> &dyn Any {
+    ///         self
+    ///     }
+    /// }
+    ///
+    /// let error = dangerous::input(b"a")
+    ///     .read_all::<_, _, Expected<'_>>(|r| {
+    ///         r.context(Field("user"), |r| r.context(Field("name"), |r| r.take(2)))
+    ///     })
+    ///     .unwrap_err();
+    ///
+    /// let mut fields = Vec::new();
+    /// error.backtrace().walk_of(&mut |_, field: &Field| {
+    ///     fields.push(field.0);
+    ///     true
+    /// });
+    /// assert_eq!(fields, ["user", "name"]);
+    /// ```
+    pub fn walk_of<C>(&self, f: &mut dyn FnMut(usize, &C) -> bool) -> bool
+    where
+        C: Context,
+    {
+        self.walk(
+            &mut |depth, context| match context.as_any().downcast_ref::<C>() {
+                Some(context) => f(depth, context),
+                None => true,
+            },
+        )
+    }
+
+    /// Returns the first context of type `C` in the backtrace, starting with
+    /// the highest context to the root.
+    #[must_use]
+    pub fn find_context<C>(&self) -> Option<C>
+    where
+        C: Context + Clone,
+    {
+        let mut found = None;
+        self.walk_of(&mut |_, context: &C| {
+            found = Some(context.clone());
+            false
+        });
+        found
+    }
+
+    /// Returns the contexts of type `C` in the backtrace, starting with the
+    /// highest context to the root.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[must_use]
+    pub fn contexts_of<C>(&self) -> Vec<C>
+    where
+        C: Context + Clone,
+    {
+        let mut contexts = Vec::new();
+        self.walk_of(&mut |_, context: &C| {
+            contexts.push(context.clone());
+            true
+        });
+        contexts
+    }
+}
+
+/// Returns `true` if the backtraces are equal, comparing the spans of the
+/// contexts by their offsets within the provided inputs.
+///
+/// Contexts are compared by their parent depth, whether they are a child, the
+/// [`OperationId`] of their operation, the description and expected text they
+/// write and their span.
+///
+/// [`OperationId`]: super::OperationId
+pub(crate) fn backtrace_eq(
+    a: &dyn Backtrace,
+    a_input: Span,
+    b: &dyn Backtrace,
+    b_input: Span,
+) -> bool {
+    a.count() == b.count()
+        && a.root().eq_within(a_input, &b.root(), b_input)
+        && frames_eq(a, a_input, b, b_input)
+}
+
+/// Compares the walked contexts of the backtraces.
+///
+/// Contexts can't be held past the walk that provides them, so the contexts
+/// of `b` are written out in one walk and compared as `a` is walked.
+#[cfg(feature = "alloc")]
+fn frames_eq(a: &dyn Backtrace, a_input: Span, b: &dyn Backtrace, b_input: Span) -> bool {
+    let mut b_frames = Vec::with_capacity(b.count());
+    b.walk(&mut |depth, context| {
+        b_frames.push(WrittenFrame::new(depth, context, b_input));
+        true
+    });
+    let mut b_frames = b_frames.iter();
+    let walked = a.walk(&mut |depth, context| {
+        b_frames
+            .next()
+            .map_or(false, |frame| frame.eq_context(depth, context, a_input))
+    });
+    walked && b_frames.next().is_none()
+}
+
+/// Compares the walked contexts of the backtraces.
+///
+/// Without `alloc` the contexts of `b` can't be written out, so `b` is walked
+/// up to each context of `a`. Backtraces available without `alloc` hold a
+/// bounded number of contexts.
+#[cfg(not(feature = "alloc"))]
+fn frames_eq(a: &dyn Backtrace, a_input: Span, b: &dyn Backtrace, b_input: Span) -> bool {
+    let mut b_len = 0;
+    b.walk(&mut |_, _| {
+        b_len += 1;
+        true
+    });
+    let mut index = 0;
+    let walked = a.walk(&mut |a_depth, a_context| {
+        let mut equal = false;
+        let mut b_index = 0;
+        b.walk(&mut |b_depth, b_context| {
+            if b_index == index {
+                equal = a_depth == b_depth
+                    && a_context.is_child() == b_context.is_child()
+                    && a_context.operation().id() == b_context.operation().id()
+                    && written_eq(&|w| a_context.operation().description(w), &|w| {
+                        b_context.operation().description(w)
+                    })
+                    && a_context.has_expected() == b_context.has_expected()
+                    && (!a_context.has_expected()
+                        || written_eq(&|w| a_context.expected(w), &|w| b_context.expected(w)))
+                    && a_context.span().and_then(|span| span.range_of(a_input))
+                        == b_context.span().and_then(|span| span.range_of(b_input));
+                return false;
+            }
+            b_index += 1;
+            true
+        });
+        index += 1;
+        equal
+    });
+    walked && index == b_len
+}
+
+/// A context written out for comparison.
+#[cfg(feature = "alloc")]
+struct WrittenFrame {
+    depth: usize,
+    is_child: bool,
+    id: OperationId,
+    description: String,
+    expected: Option<String>,
+    span: Option<Range<usize>>,
+}
+
+#[cfg(feature = "alloc")]
+impl WrittenFrame {
+    fn new(depth: usize, context: &dyn Context, input: Span) -> Self {
+        let mut description = String::new();
+        // Failing to write the description leaves what was written.
+        let _ = context.operation().description(&mut description);
+        let expected = if context.has_expected() {
+            let mut expected = String::new();
+            let _ = context.expected(&mut expected);
+            Some(expected)
+        } else {
+            None
+        };
+        Self {
+            depth,
+            is_child: context.is_child(),
+            id: context.operation().id(),
+            description,
+            expected,
+            span: context.span().and_then(|span| span.range_of(input)),
+        }
+    }
+
+    fn eq_context(&self, depth: usize, context: &dyn Context, input: Span) -> bool {
+        self.depth == depth
+            && self.is_child == context.is_child()
+            && self.id == context.operation().id()
+            && written_eq(&|w| context.operation().description(w), &|w| {
+                w.write_str(&self.description)
+            })
+            && match &self.expected {
+                Some(expected) => {
+                    context.has_expected()
+                        && written_eq(&|w| context.expected(w), &|w| w.write_str(expected))
+                }
+                None => !context.has_expected(),
+            }
+            && context.span().and_then(|span| span.range_of(input)) == self.span
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Root context backtrace
 
@@ -122,27 +365,603 @@ impl Backtrace for FullBacktrace {
             let context: &dyn Context = context.as_ref();
             context
         });
-        let items_iter = iter::once(root_as_dyn).chain(stack_iter).rev();
-        let child_iter = &mut items_iter.clone().filter(|context| context.is_child());
-        let mut depth = 0;
-        let mut children_skipped = 0;
-        // Starts from the top context, with children before their parent.
-        for context in items_iter {
-            if context.is_child() {
-                children_skipped += 1;
-            } else {
-                depth += 1;
-                if !f(depth, context) {
+        walk_contexts(iter::once(root_as_dyn).chain(stack_iter).rev(), f)
+    }
+}
+
+/// Walks contexts ordered from the top of the stack to the bottom, with
+/// children walked after their parent.
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+fn walk_contexts<'a, I>(items_iter: I, f: &mut BacktraceWalker<'_>) -> bool
+where
+    I: Iterator<Item = &'a dyn Context> + Clone,
+{
+    let child_iter = &mut items_iter.clone().filter(|context| context.is_child());
+    let mut depth = 0;
+    let mut children_skipped = 0;
+    // Starts from the top context, with children before their parent.
+    for context in items_iter {
+        if context.is_child() {
+            children_skipped += 1;
+        } else {
+            depth += 1;
+            if !f(depth, context) {
+                return false;
+            }
+            for child in child_iter.take(children_skipped) {
+                if !f(depth, child) {
                     return false;
                 }
-                for child in child_iter.take(children_skipped) {
-                    if !f(depth, child) {
-                        return false;
-                    }
-                }
-                children_skipped = 0;
             }
+            children_skipped = 0;
         }
-        true
+    }
+    true
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Limited backtrace
+
+/// A [`Backtrace`] that collects at most `MAX` [`Context`]s after the root.
+///
+/// Contexts are pushed from the source of the error up, so the contexts
+/// closest to the error are kept and any pushed past the limit are counted
+/// and dropped. When contexts were dropped, the walk starts with a context
+/// noting how many were truncated.
+///
+/// Useful for deeply recursive grammars, where an error bubbling up through
+/// hundreds of frames would otherwise allocate a context for each.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::error::{Backtrace, Details, LimitedBacktrace};
+/// use dangerous::{BytesReader, Expected, Input};
+///
+/// type Error<'i> = Expected<'i, LimitedBacktrace<2>>;
+///
+/// fn nested<'i>(r: &mut BytesReader<'i, Error<'i>>) -> Result<(), Error<'i>> {
+///     r.context("nested", |r| {
+///         if r.at_end() {
+///             r.consume(b"end")
+///         } else {
+///             r.skip(1)?;
+///             nested(r)
+///         }
+///     })
+/// }
+///
+/// let error = dangerous::input(b"\x00\x00\x00\x00")
+///     .read_all::<_, _, Error<'_>>(nested)
+///     .unwrap_err();
+///
+/// assert_eq!(error.backtrace().count(), 4);
+/// assert!(error.to_string().contains("<truncated 4 contexts>"));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct LimitedBacktrace<const MAX: usize> {
+    inner: FullBacktrace,
+    truncated: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: usize> LimitedBacktrace<MAX> {
+    /// Returns the number of contexts that were dropped.
+    #[must_use]
+    pub fn truncated(&self) -> usize {
+        self.truncated
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: usize> BacktraceBuilder for LimitedBacktrace<MAX> {
+    fn from_root(context: CoreContext) -> Self {
+        Self {
+            inner: FullBacktrace {
+                root: context,
+                stack: Vec::with_capacity(MAX.min(32)),
+            },
+            truncated: 0,
+        }
+    }
+
+    fn push(&mut self, context: impl Context) {
+        if self.inner.stack.len() < MAX {
+            self.inner.push(context);
+        } else {
+            self.truncated += 1;
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: usize> Backtrace for LimitedBacktrace<MAX> {
+    fn root(&self) -> CoreContext {
+        self.inner.root()
+    }
+
+    fn count(&self) -> usize {
+        if self.truncated == 0 {
+            self.inner.count()
+        } else {
+            self.inner.count() + 1
+        }
+    }
+
+    fn walk<'a>(&'a self, f: &mut BacktraceWalker<'a>) -> bool {
+        if self.truncated == 0 {
+            return self.inner.walk(f);
+        }
+        if !f(1, &TruncatedContext(self.truncated)) {
+            return false;
+        }
+        self.inner.walk(&mut |depth, context| f(depth + 1, context))
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Pruned backtrace
+
+/// A [`Backtrace`] that collects at most the `N` innermost and `N` outermost
+/// [`Context`]s after the root.
+///
+/// Contexts are pushed from the source of the error up. The first `N` pushed
+/// are kept, being closest to the error, along with the last `N` pushed,
+/// describing where the error was reached from. Contexts pushed in between
+/// are counted and dropped as they are replaced. When contexts were dropped,
+/// the walk notes how many between the outermost and innermost contexts.
+///
+/// Unlike [`LimitedBacktrace`], the entry points of a deeply recursive
+/// grammar are not lost, while memory use stays bounded by `2 * N` contexts.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::error::{Backtrace, Details, PrunedBacktrace};
+/// use dangerous::{BytesReader, Expected, Input};
+///
+/// type Error<'i> = Expected<'i, PrunedBacktrace<1>>;
+///
+/// fn nested<'i>(r: &mut BytesReader<'i, Error<'i>>) -> Result<(), Error<'i>> {
+///     r.context("nested", |r| {
+///         if r.at_end() {
+///             r.consume(b"end")
+///         } else {
+///             r.skip(1)?;
+///             nested(r)
+///         }
+///     })
+/// }
+///
+/// let error = dangerous::input(b"\x00\x00\x00\x00")
+///     .read_all::<_, _, Error<'_>>(nested)
+///     .unwrap_err();
+///
+/// assert_eq!(error.backtrace().count(), 4);
+/// assert!(error.to_string().contains("<truncated 4 contexts>"));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct PrunedBacktrace<const N: usize> {
+    inner: FullBacktrace,
+    outer: VecDeque<Box<dyn Context>>,
+    pruned: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> PrunedBacktrace<N> {
+    /// Returns the number of contexts that were dropped.
+    #[must_use]
+    pub fn pruned(&self) -> usize {
+        self.pruned
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> BacktraceBuilder for PrunedBacktrace<N> {
+    fn from_root(context: CoreContext) -> Self {
+        Self {
+            inner: FullBacktrace {
+                root: context,
+                stack: Vec::with_capacity(N.min(32)),
+            },
+            outer: VecDeque::new(),
+            pruned: 0,
+        }
+    }
+
+    fn push(&mut self, context: impl Context) {
+        if self.inner.stack.len() < N {
+            self.inner.push(context);
+        } else if N > 0 {
+            if self.outer.len() == N {
+                self.outer.pop_front();
+                self.pruned += 1;
+            }
+            self.outer.push_back(Box::new(context));
+        } else {
+            self.pruned += 1;
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> Backtrace for PrunedBacktrace<N> {
+    fn root(&self) -> CoreContext {
+        self.inner.root()
+    }
+
+    fn count(&self) -> usize {
+        let count = self.inner.count() + self.outer.len();
+        if self.pruned == 0 {
+            count
+        } else {
+            count + 1
+        }
+    }
+
+    fn walk<'a>(&'a self, f: &mut BacktraceWalker<'a>) -> bool {
+        let pruned = TruncatedContext(self.pruned);
+        let pruned_as_dyn: &dyn Context = &pruned;
+        let pruned_iter = iter::once(pruned_as_dyn).filter(|_| self.pruned > 0);
+        let outer_iter = self.outer.iter().map(|context| {
+            let context: &dyn Context = context.as_ref();
+            context
+        });
+        let inner_iter = self.inner.stack.iter().map(|context| {
+            let context: &dyn Context = context.as_ref();
+            context
+        });
+        let root_as_dyn: &dyn Context = &self.inner.root;
+        let items_iter = iter::once(root_as_dyn)
+            .chain(inner_iter)
+            .chain(pruned_iter)
+            .chain(outer_iter)
+            .rev();
+        walk_contexts(items_iter, f)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Heapless backtrace
+
+/// A [`Backtrace`] that collects at most `N` [`Context`]s after the root
+/// without allocating.
+///
+/// Contexts are stored inline with [`heapless::Vec`], so the full backtrace
+/// can be captured on `no_std` targets without `alloc`. As contexts can't be
+/// boxed, the parts of each that can be copied are kept: core contexts and
+/// `&'static str` contexts as they are, and for any other context its span
+/// and [`OperationId`]. Like [`LimitedBacktrace`], the contexts closest to
+/// the error are kept and any pushed past the capacity are counted and
+/// dropped.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::error::{Backtrace, Details, HeaplessBacktrace};
+/// use dangerous::{Expected, Input};
+///
+/// type Error<'i> = Expected<'i, HeaplessBacktrace<4>>;
+///
+/// let error = dangerous::input(b"hello")
+///     .read_all::<_, _, Error<'_>>(|r| r.context("greeting", |r| r.consume(b"world")))
+///     .unwrap_err();
+///
+/// assert_eq!(error.backtrace().count(), 3);
+/// assert!(error.to_string().contains("greeting"));
+/// ```
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub struct HeaplessBacktrace<const N: usize> {
+    root: CoreContext,
+    stack: heapless::Vec<CompactContext, N>,
+    truncated: usize,
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> HeaplessBacktrace<N> {
+    /// Returns the number of contexts that were dropped.
+    #[must_use]
+    pub fn truncated(&self) -> usize {
+        self.truncated
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> BacktraceBuilder for HeaplessBacktrace<N> {
+    fn from_root(context: CoreContext) -> Self {
+        Self {
+            root: context,
+            stack: heapless::Vec::new(),
+            truncated: 0,
+        }
+    }
+
+    fn push(&mut self, context: impl Context) {
+        if self.stack.push(CompactContext::new(&context)).is_err() {
+            self.truncated += 1;
+        }
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> Backtrace for HeaplessBacktrace<N> {
+    fn root(&self) -> CoreContext {
+        self.root
+    }
+
+    fn count(&self) -> usize {
+        let count = self.stack.len() + 1;
+        if self.truncated == 0 {
+            count
+        } else {
+            count + 1
+        }
+    }
+
+    fn walk<'a>(&'a self, f: &mut BacktraceWalker<'a>) -> bool {
+        let root_as_dyn: &dyn Context = &self.root;
+        let stack_iter = self.stack.iter().map(|context| {
+            let context: &dyn Context = context;
+            context
+        });
+        let items_iter = iter::once(root_as_dyn).chain(stack_iter).rev();
+        if self.truncated == 0 {
+            return walk_contexts(items_iter, f);
+        }
+        if !f(1, &TruncatedContext(self.truncated)) {
+            return false;
+        }
+        walk_contexts(items_iter, &mut |depth, context| f(depth + 1, context))
+    }
+}
+
+/// The parts of a [`Context`] that can be copied, kept by
+/// [`HeaplessBacktrace`] and [`SmallVecBacktrace`].
+#[cfg(any(feature = "heapless", all(feature = "smallvec", feature = "alloc")))]
+struct CompactContext {
+    kind: CompactContextKind,
+    is_child: bool,
+}
+
+#[cfg(any(feature = "heapless", all(feature = "smallvec", feature = "alloc")))]
+enum CompactContextKind {
+    Core(CoreContext),
+    Expected(&'static str),
+    #[cfg(feature = "heapless")]
+    Other(Option<Span>, IdOperation),
+}
+
+#[cfg(any(feature = "heapless", all(feature = "smallvec", feature = "alloc")))]
+impl CompactContext {
+    #[cfg(feature = "heapless")]
+    fn new(context: &dyn Context) -> Self {
+        Self::try_new(context).unwrap_or_else(|| Self {
+            kind: CompactContextKind::Other(context.span(), IdOperation(context.operation().id())),
+            is_child: context.is_child(),
+        })
+    }
+
+    /// Returns the context if it is a core or `&'static str` context, both of
+    /// which are kept whole.
+    fn try_new(context: &dyn Context) -> Option<Self> {
+        let any = context.as_any();
+        let kind = if let Some(core) = any.downcast_ref::<CoreContext>() {
+            CompactContextKind::Core(*core)
+        } else if let Some(expected) = any.downcast_ref::<&'static str>() {
+            CompactContextKind::Expected(expected)
+        } else {
+            return None;
+        };
+        Some(Self {
+            kind,
+            is_child: context.is_child(),
+        })
+    }
+}
+
+#[cfg(any(feature = "heapless", all(feature = "smallvec", feature = "alloc")))]
+impl Context for CompactContext {
+    fn span(&self) -> Option<Span> {
+        match &self.kind {
+            CompactContextKind::Core(context) => context.span(),
+            CompactContextKind::Expected(_) => None,
+            #[cfg(feature = "heapless")]
+            CompactContextKind::Other(span, _) => *span,
+        }
+    }
+
+    fn operation(&self) -> &dyn Operation {
+        match &self.kind {
+            CompactContextKind::Core(context) => context.operation(),
+            CompactContextKind::Expected(expected) => expected.operation(),
+            #[cfg(feature = "heapless")]
+            CompactContextKind::Other(_, operation) => operation,
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        match &self.kind {
+            CompactContextKind::Core(context) => context,
+            CompactContextKind::Expected(expected) => expected,
+            #[cfg(feature = "heapless")]
+            CompactContextKind::Other(..) => self,
+        }
+    }
+
+    fn has_expected(&self) -> bool {
+        match &self.kind {
+            CompactContextKind::Core(context) => context.has_expected(),
+            CompactContextKind::Expected(_) => true,
+            #[cfg(feature = "heapless")]
+            CompactContextKind::Other(..) => false,
+        }
+    }
+
+    fn expected(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        match &self.kind {
+            CompactContextKind::Core(context) => context.expected(w),
+            CompactContextKind::Expected(expected) => w.write_str(expected),
+            #[cfg(feature = "heapless")]
+            CompactContextKind::Other(..) => Err(fmt::Error),
+        }
+    }
+
+    fn is_child(&self) -> bool {
+        self.is_child
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// SmallVec backtrace
+
+/// A [`Backtrace`] that stores up to `N` [`Context`]s after the root inline,
+/// only allocating for deeper stacks.
+///
+/// [`FullBacktrace`] boxes every context and reserves room for its stack up
+/// front, which adds up when errors are created and thrown away while
+/// speculatively parsing. Here core contexts and `&'static str` contexts are
+/// stored inline in a [`smallvec::SmallVec`], so the common case of a handful
+/// of contexts doesn't allocate. Any other context is boxed, and once more
+/// than `N` contexts are pushed the stack moves to the heap. Unlike
+/// [`HeaplessBacktrace`], no context is ever dropped.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::error::{Backtrace, Details, SmallVecBacktrace};
+/// use dangerous::{Expected, Input};
+///
+/// type Error<'i> = Expected<'i, SmallVecBacktrace<6>>;
+///
+/// let error = dangerous::input(b"hello")
+///     .read_all::<_, _, Error<'_>>(|r| r.context("greeting", |r| r.consume(b"world")))
+///     .unwrap_err();
+///
+/// assert_eq!(error.backtrace().count(), 3);
+/// assert!(error.to_string().contains("greeting"));
+/// ```
+#[cfg(all(feature = "smallvec", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "smallvec", feature = "alloc"))))]
+pub struct SmallVecBacktrace<const N: usize> {
+    root: CoreContext,
+    stack: SmallVec<[SmallVecContext; N]>,
+}
+
+#[cfg(all(feature = "smallvec", feature = "alloc"))]
+impl<const N: usize> SmallVecBacktrace<N> {
+    /// Returns `true` if the contexts no longer fit inline and were moved to
+    /// the heap.
+    #[must_use]
+    pub fn spilled(&self) -> bool {
+        self.stack.spilled()
+    }
+}
+
+#[cfg(all(feature = "smallvec", feature = "alloc"))]
+impl<const N: usize> BacktraceBuilder for SmallVecBacktrace<N> {
+    fn from_root(context: CoreContext) -> Self {
+        Self {
+            root: context,
+            stack: SmallVec::new(),
+        }
+    }
+
+    fn push(&mut self, context: impl Context) {
+        let context = match CompactContext::try_new(&context) {
+            Some(compact) => SmallVecContext::Compact(compact),
+            None => SmallVecContext::Boxed(Box::new(context)),
+        };
+        self.stack.push(context);
+    }
+}
+
+#[cfg(all(feature = "smallvec", feature = "alloc"))]
+impl<const N: usize> Backtrace for SmallVecBacktrace<N> {
+    fn root(&self) -> CoreContext {
+        self.root
+    }
+
+    fn count(&self) -> usize {
+        self.stack.len() + 1
+    }
+
+    fn walk<'a>(&'a self, f: &mut BacktraceWalker<'a>) -> bool {
+        let root_as_dyn: &dyn Context = &self.root;
+        let stack_iter = self.stack.iter().map(SmallVecContext::as_dyn);
+        walk_contexts(iter::once(root_as_dyn).chain(stack_iter).rev(), f)
+    }
+}
+
+/// A context kept by [`SmallVecBacktrace`].
+#[cfg(all(feature = "smallvec", feature = "alloc"))]
+enum SmallVecContext {
+    Compact(CompactContext),
+    Boxed(Box<dyn Context>),
+}
+
+#[cfg(all(feature = "smallvec", feature = "alloc"))]
+impl SmallVecContext {
+    fn as_dyn(&self) -> &dyn Context {
+        match self {
+            Self::Compact(context) => context,
+            Self::Boxed(context) => context.as_ref(),
+        }
+    }
+}
+
+/// An operation only known by its identifier.
+#[cfg(feature = "heapless")]
+struct IdOperation(OperationId);
+
+#[cfg(feature = "heapless")]
+impl Operation for IdOperation {
+    fn description(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        match self.0 {
+            OperationId::Core(operation) => w.write_str(operation.description()),
+            OperationId::Custom(operation) => w.write_str(operation),
+            OperationId::Unknown => w.write_str("<unknown>"),
+        }
+    }
+
+    fn id(&self) -> OperationId {
+        self.0
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+struct TruncatedContext(usize);
+
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+impl Context for TruncatedContext {
+    fn operation(&self) -> &dyn Operation {
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+impl Operation for TruncatedContext {
+    fn description(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_str("<truncated ")?;
+        w.write_usize(self.0)?;
+        w.write_str(if self.0 == 1 {
+            " context>"
+        } else {
+            " contexts>"
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
     }
 }
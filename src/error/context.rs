@@ -1,5 +1,6 @@
 use core::any::Any;
 
+use crate::display;
 use crate::fmt;
 use crate::input::{Input, MaybeString, Span, Token, TokenType};
 
@@ -15,6 +16,14 @@ pub trait Context: 'static + Send + Sync {
     /// Returns the operation that failed in this context.
     fn operation(&self) -> &dyn Operation;
 
+    /// Return a reference of self as [`Any`].
+    ///
+    /// This allows typed contexts to be retrieved from a [`Backtrace`] with
+    /// `walk_of()`, `find_context()` and `contexts_of()`.
+    ///
+    /// [`Backtrace`]: crate::error::Backtrace
+    fn as_any(&self) -> &dyn Any;
+
     /// Returns `true` if there is an expected value.
     fn has_expected(&self) -> bool {
         false
@@ -54,10 +63,60 @@ pub trait Operation: Any + Send + Sync {
     /// Returns a [`fmt::Error`] if failed to write to the formatter.
     fn description(&self, w: &mut dyn fmt::Write) -> fmt::Result;
 
+    /// Returns the identifier of the operation.
+    ///
+    /// Defaults to [`OperationId::Unknown`]. Implement this to allow the
+    /// operation to be matched without comparing descriptions.
+    fn id(&self) -> OperationId {
+        OperationId::Unknown
+    }
+
     /// Return a reference of self as [`Any`].
     fn as_any(&self) -> &dyn Any;
 }
 
+/// Identifies an [`Operation`] so it can be compared, matched and localized
+/// without relying on its description.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::error::{CoreOperation, Details, OperationId};
+/// use dangerous::{Expected, Input};
+///
+/// fn localize(id: OperationId) -> &'static str {
+///     match id {
+///         OperationId::Core(CoreOperation::Consume) => "consommer l'entrée",
+///         OperationId::Custom("header") => "lire l'en-tête",
+///         _ => "opération inconnue",
+///     }
+/// }
+///
+/// let error = dangerous::input(b"hello")
+///     .read_all::<_, _, Expected<'_>>(|r| r.consume(b"world"))
+///     .unwrap_err();
+/// let id = error.backtrace().root().operation.into();
+///
+/// assert_eq!(localize(id), "consommer l'entrée");
+/// assert_eq!(localize(OperationId::Custom("header")), "lire l'en-tête");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OperationId {
+    /// A core operation used by `dangerous`.
+    Core(CoreOperation),
+    /// An operation defined outside of `dangerous`.
+    Custom(&'static str),
+    /// The operation didn't provide an identifier.
+    Unknown,
+}
+
+impl From<CoreOperation> for OperationId {
+    fn from(operation: CoreOperation) -> Self {
+        Self::Core(operation)
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Basic expected context
 
@@ -66,6 +125,10 @@ impl Context for &'static str {
         &CoreOperation::Context
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn has_expected(&self) -> bool {
         true
     }
@@ -80,6 +143,10 @@ impl Operation for &'static str {
         w.write_str(self)
     }
 
+    fn id(&self) -> OperationId {
+        OperationId::Custom(self)
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -109,6 +176,10 @@ where
         }
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn has_expected(&self) -> bool {
         self.expected.is_some()
     }
@@ -177,6 +248,10 @@ impl Context for CoreContext {
         &self.operation
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn has_expected(&self) -> bool {
         self.expected != CoreExpected::Unknown
     }
@@ -186,6 +261,16 @@ impl Context for CoreContext {
     }
 }
 
+impl CoreContext {
+    /// Returns `true` if the contexts are equal, comparing the spans by their
+    /// offsets within the provided inputs.
+    pub(crate) fn eq_within(&self, input: Span, other: &Self, other_input: Span) -> bool {
+        self.operation == other.operation
+            && self.expected == other.expected
+            && self.span.range_of(input) == other.span.range_of(other_input)
+    }
+}
+
 impl fmt::Debug for CoreContext {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("CoreContext")
@@ -214,13 +299,14 @@ impl<'i> fmt::Debug for DebugFor<'i> {
 
 /// Core operations used by `dangerous`.
 #[allow(missing_docs)]
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
 pub enum CoreOperation {
     // Context
     Context,
     // Entry
     ReadAll,
     ReadPartial,
+    ReadSlice,
     // Consuming
     Consume,
     // Skipping
@@ -241,6 +327,8 @@ pub enum CoreOperation {
     TakeConsumed,
     TakeStrWhile,
     TakeRemainingStr,
+    TakePodSlice,
+    TakeStrEncoded,
     // Peeking
     Peek,
     PeekByte,
@@ -248,6 +336,15 @@ pub enum CoreOperation {
     // Reading
     ReadByte,
     ReadChar,
+    // Lexing
+    Lex,
+    // Grammars
+    Rule,
+    // Nesting
+    Enter,
+    // Repeating
+    Repeat,
+    BurnFuel,
     // Errors
     RecoverIf,
     Verify,
@@ -264,6 +361,10 @@ impl Operation for CoreOperation {
         w.write_str(CoreOperation::description(*self))
     }
 
+    fn id(&self) -> OperationId {
+        OperationId::Core(*self)
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -284,11 +385,12 @@ impl CoreOperation {
         }
     }
 
-    fn description(self) -> &'static str {
+    pub(crate) fn description(self) -> &'static str {
         match self {
             Self::Context => "<context>",
             Self::ReadAll => "read all input",
             Self::ReadPartial => "read a partial length of input",
+            Self::ReadSlice => "read within one slice of vectored input",
             Self::Consume => "consume input",
             Self::Skip => "skip a length of input",
             Self::SkipWhile => "skip input while a pattern matches",
@@ -305,11 +407,18 @@ impl CoreOperation {
             Self::TakeConsumed => "take input that was consumed",
             Self::TakeStrWhile => "take UTF-8 input while a condition remains true",
             Self::TakeRemainingStr => "take remaining string within bytes",
+            Self::TakePodSlice => "take a slice of plain old data",
+            Self::TakeStrEncoded => "take a length of encoded string input",
             Self::Peek => "peek a length of input",
             Self::PeekByte => "peek a byte",
             Self::PeekChar => "peek a char",
             Self::ReadByte => "read a byte",
             Self::ReadChar => "read a char",
+            Self::Lex => "lex a token",
+            Self::Rule => "read a grammar rule",
+            Self::Enter => "enter nested input",
+            Self::Repeat => "repeat a read",
+            Self::BurnFuel => "burn iteration fuel",
             Self::RecoverIf => "recover if a condition returns true",
             Self::Verify => "read and verify input",
             Self::Expect => "read and expect a value",
@@ -346,6 +455,10 @@ pub enum CoreExpected {
     Valid(&'static str),
     /// Enough input for a given description of a value was expected.
     EnoughInputFor(&'static str),
+    /// Nesting no deeper than the contained maximum depth was expected.
+    MaxDepth(usize),
+    /// Enough fuel for the contained number of iterations was expected.
+    Fuel(usize),
 }
 
 impl fmt::DisplayBase for CoreExpected {
@@ -361,6 +474,19 @@ impl fmt::DisplayBase for CoreExpected {
                 w.write_str("enough input for ")?;
                 w.write_str(expected)
             }
+            Self::MaxDepth(depth) => {
+                w.write_str("a nesting depth of at most ")?;
+                w.write_usize(depth)
+            }
+            Self::Fuel(iterations) => {
+                w.write_str("fuel for ")?;
+                w.write_usize(iterations)?;
+                if iterations == 1 {
+                    w.write_str(" iteration")
+                } else {
+                    w.write_str(" iterations")
+                }
+            }
         }
     }
 }
@@ -388,6 +514,7 @@ where
     E: WithContext<'i>,
 {
     const PASSTHROUGH: bool = E::PASSTHROUGH;
+    const NO_CONTEXT: bool = E::NO_CONTEXT;
 
     #[inline(always)]
     fn with_input(self, _input: impl Input<'i>) -> Self {
@@ -410,6 +537,10 @@ where
         self.0.operation()
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self.0.as_any()
+    }
+
     fn has_expected(&self) -> bool {
         self.0.has_expected()
     }
@@ -425,6 +556,117 @@ where
 
 ///////////////////////////////////////////////////////////////////////////////
 
+/// Returns a key identifying the written operation description and expected
+/// text of a context.
+///
+/// The text is hashed with FNV-1a as it is written so no allocation is
+/// required.
+pub(crate) fn context_key(context: &dyn Context) -> u64 {
+    struct Hasher(u64);
+
+    impl Hasher {
+        fn write_bytes(&mut self, bytes: &[u8]) {
+            for byte in bytes {
+                self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+    }
+
+    impl fmt::Write for Hasher {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.write_bytes(s.as_bytes());
+            Ok(())
+        }
+
+        fn write_char(&mut self, c: char) -> fmt::Result {
+            self.write_bytes(c.encode_utf8(&mut [0; 4]).as_bytes());
+            Ok(())
+        }
+
+        fn write_usize(&mut self, v: usize) -> fmt::Result {
+            self.write_bytes(&v.to_le_bytes());
+            Ok(())
+        }
+    }
+
+    let mut hasher = Hasher(0xcbf2_9ce4_8422_2325);
+    let _ = context.operation().description(&mut hasher);
+    if context.has_expected() {
+        let _ = fmt::Write::write_char(&mut hasher, '\0');
+        let _ = context.expected(&mut hasher);
+    }
+    hasher.0
+}
+
+/// A function that writes text, such as an operation description.
+pub(crate) type WriteFn<'a> = dyn Fn(&mut dyn fmt::Write) -> fmt::Result + 'a;
+
+/// Returns `true` if `a` and `b` write the same text.
+///
+/// Each part `a` writes is checked against the same offsets of what `b`
+/// writes, so neither text has to be collected. If `a` fails to write, the
+/// texts are not equal.
+pub(crate) fn written_eq(a: &WriteFn<'_>, b: &WriteFn<'_>) -> bool {
+    let mut offset = 0;
+    let a_written = a(&mut WriteBytes(|part: &[u8]| {
+        if !part.is_empty() {
+            let mut skip = offset;
+            let mut matched = 0;
+            // `b` is stopped with an error once the part is matched.
+            let _ = b(&mut WriteBytes(|bytes: &[u8]| {
+                if skip >= bytes.len() {
+                    skip -= bytes.len();
+                    return Ok(());
+                }
+                let bytes = &bytes[skip..];
+                skip = 0;
+                let expected = &part[matched..];
+                let len = bytes.len().min(expected.len());
+                if bytes[..len] != expected[..len] {
+                    return Err(fmt::Error);
+                }
+                matched += len;
+                if matched == part.len() {
+                    Err(fmt::Error)
+                } else {
+                    Ok(())
+                }
+            }));
+            if matched != part.len() {
+                return Err(fmt::Error);
+            }
+            offset += part.len();
+        }
+        Ok(())
+    }));
+    let mut b_len = 0;
+    let _ = b(&mut WriteBytes(|bytes: &[u8]| {
+        b_len += bytes.len();
+        Ok(())
+    }));
+    a_written.is_ok() && offset == b_len
+}
+
+/// Passes each part written on to a function as bytes.
+struct WriteBytes<F>(F);
+
+impl<F> fmt::Write for WriteBytes<F>
+where
+    F: FnMut(&[u8]) -> fmt::Result,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        (self.0)(s.as_bytes())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        (self.0)(c.encode_utf8(&mut [0; 4]).as_bytes())
+    }
+
+    fn write_usize(&mut self, v: usize) -> fmt::Result {
+        (self.0)(display::usize_digits(v, &mut [0; 20]))
+    }
+}
+
 #[inline(always)]
 pub(crate) fn with_context<'i, F, T, E>(
     context: impl Context,
@@ -435,6 +677,9 @@ where
     E: WithContext<'i>,
     F: FnOnce() -> Result<T, E>,
 {
+    if E::NO_CONTEXT {
+        return f();
+    }
     match f() {
         Ok(ok) => Ok(ok),
         Err(err) => Err(err.with_context(context).with_input(input)),
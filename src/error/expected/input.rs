@@ -0,0 +1,50 @@
+use core::marker::PhantomData;
+
+use crate::input::{Bound, Bytes, MaybeString, Span, String};
+
+/// The input of an [`Expected`](super::Expected) error, stored as its span.
+///
+/// A [`MaybeString`] is rebuilt from the span on demand, which keeps the
+/// error smaller than storing the input itself.
+#[derive(Copy, Clone)]
+pub(crate) struct InputSpan<'i> {
+    span: Span,
+    bound: Bound,
+    is_string: bool,
+    marker: PhantomData<&'i [u8]>,
+}
+
+impl<'i> InputSpan<'i> {
+    #[inline(always)]
+    pub(crate) fn new(input: &MaybeString<'i>) -> Self {
+        Self {
+            span: input.span(),
+            bound: input.bound(),
+            is_string: input.is_string(),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn span(self) -> Span {
+        self.span
+    }
+
+    #[inline(always)]
+    pub(crate) fn as_dangerous_bytes(self) -> &'i [u8] {
+        // SAFETY: the span was taken from input borrowed for `'i` in `new()`,
+        // which `marker` keeps borrowed.
+        unsafe { self.span.as_slice() }
+    }
+
+    pub(crate) fn to_maybe_string(self) -> MaybeString<'i> {
+        let bytes = Bytes::new(self.as_dangerous_bytes(), self.bound);
+        if self.is_string {
+            // SAFETY: the span was taken from a `String` in `new()`, so the
+            // bytes are valid UTF-8.
+            MaybeString::String(unsafe { String::from_utf8_unchecked(bytes) })
+        } else {
+            MaybeString::Bytes(bytes)
+        }
+    }
+}
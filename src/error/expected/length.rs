@@ -1,5 +1,7 @@
+use core::ops::Range;
+
 use crate::display::byte_count;
-use crate::error::{CoreContext, Length, RetryRequirement, ToRetryRequirement};
+use crate::error::{CoreContext, CoreExpected, Length, RetryRequirement, ToRetryRequirement};
 use crate::fmt;
 use crate::input::MaybeString;
 
@@ -38,6 +40,46 @@ impl<'i> ExpectedLength<'i> {
     pub fn input(&self) -> MaybeString<'i> {
         self.input.clone()
     }
+
+    /// Returns the span of the error as offsets into the
+    /// [`input()`](Self::input()).
+    ///
+    /// Unlike the [`Span`](crate::Span) of the [`context()`](Self::context()),
+    /// the offsets don't borrow from the input and remain meaningful once it
+    /// is copied or dropped. Returns `None` if the span is not within the
+    /// input.
+    #[must_use]
+    #[inline(always)]
+    pub fn span_range(&self) -> Option<Range<usize>> {
+        self.context.span.range_of(self.input.span())
+    }
+}
+
+impl ExpectedLength<'_> {
+    /// Returns `true` if the minimum length is exactly what the operation
+    /// required.
+    ///
+    /// Non-empty input and a char of a `str` can be satisfied by more than
+    /// the minimum.
+    fn is_exact_requirement(&self) -> bool {
+        match self.context.expected {
+            CoreExpected::NonEmpty => false,
+            CoreExpected::EnoughInputFor("token") => !self.input.is_string(),
+            _ => true,
+        }
+    }
+}
+
+impl PartialEq for ExpectedLength<'_> {
+    /// Compares the errors structurally, with the spans compared by their
+    /// offsets within the inputs.
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self.input == other.input
+            && self
+                .context
+                .eq_within(self.input.span(), &other.context, other.input.span())
+    }
 }
 
 impl<'i> fmt::Debug for ExpectedLength<'i> {
@@ -52,14 +94,18 @@ impl<'i> fmt::Debug for ExpectedLength<'i> {
 
 impl<'i> fmt::DisplayBase for ExpectedLength<'i> {
     fn fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
-        w.write_str("found ")?;
-        byte_count(w, self.context.span.len())?;
-        w.write_str(" when ")?;
-        self.len.fmt(w)?;
-        w.write_str(" was expected")
+        write_description(w, self.context.span.len(), self.len)
     }
 }
 
+pub(crate) fn write_description(w: &mut dyn fmt::Write, found: usize, len: Length) -> fmt::Result {
+    w.write_str("found ")?;
+    byte_count(w, found)?;
+    w.write_str(" when ")?;
+    fmt::DisplayBase::fmt(&len, w)?;
+    w.write_str(" was expected")
+}
+
 impl<'i> fmt::Display for ExpectedLength<'i> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::DisplayBase::fmt(self, f)
@@ -75,6 +121,7 @@ impl<'i> ToRetryRequirement for ExpectedLength<'i> {
             let had = self.context.span.len();
             let needed = self.len().min();
             RetryRequirement::from_had_and_needed(had, needed)
+                .map(|req| req.with_exact(self.is_exact_requirement()))
         }
     }
 
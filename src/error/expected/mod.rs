@@ -1,20 +1,33 @@
+mod input;
 mod length;
+#[cfg(feature = "alloc")]
+mod owned;
 mod valid;
 mod value;
 
 pub use self::length::ExpectedLength;
-pub use self::valid::ExpectedValid;
+#[cfg(feature = "alloc")]
+pub use self::owned::OwnedExpected;
+pub use self::valid::{ExpectedValid, Reason};
 pub use self::value::ExpectedValue;
 
+pub(crate) use self::input::InputSpan;
+
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
+use core::ops::Range;
+
+#[cfg(feature = "alloc")]
+use crate::error::Report;
 
 use crate::display::ErrorDisplay;
 use crate::error::{
-    Backtrace, BacktraceBuilder, Context, Details, RetryRequirement, ToRetryRequirement, Value,
-    WithContext,
+    backtrace_eq, span_range, Backtrace, BacktraceBuilder, Context, CoreContext, CoreOperation,
+    Details, Length, LineColumn, RetryRequirement, ToRetryRequirement, Value, WithContext,
 };
 use crate::fmt;
+#[cfg(feature = "alloc")]
+use crate::input::Span;
 use crate::input::{Input, MaybeString};
 
 #[cfg(feature = "full-backtrace")]
@@ -28,26 +41,90 @@ type ExpectedBacktrace = crate::error::RootBacktrace;
 ///   all contexts with [`Expected`].
 /// - It is generally recommended for better performance to box `Expected` if
 ///   the structures being returned from parsing are smaller than or equal to
-///   `~128 bytes`. This is because the `Expected` structure is `192 - 216
+///   `~128 bytes`. This is because the `Expected` structure is `136 - 168
 ///   bytes` large on 64 bit systems and successful parses may be hindered by
-///   the time to move the `Result<T, Expected>` value. By boxing `Expected` the
-///   size becomes only `8 bytes`. When in doubt, write a benchmark.
+///   the time to move the `Result<T, Expected>` value. By boxing `Expected`
+///   with [`BoxedExpected`] the size becomes only `8 bytes`. When in doubt,
+///   write a benchmark.
 ///
 /// See [`crate::error`] for additional documentation around the error system.
 #[must_use = "error must be handled"]
 pub struct Expected<'i, S = ExpectedBacktrace> {
-    input: MaybeString<'i>,
+    /// The input the error is within, widened by [`WithContext::with_input()`].
+    input: InputSpan<'i>,
     trace: S,
     kind: ExpectedKind<'i>,
+    /// Computed from the error the kind was created from, with any hint
+    /// set with [`ToRetryRequirement::with_total_hint()`].
+    retry_requirement: Option<RetryRequirement>,
+    #[cfg(feature = "alloc")]
+    parent: Option<Box<(MaybeString<'i>, Span)>>,
+}
+
+/// A boxed [`Expected`], for keeping `Result<T, E>` small on the `Ok` path.
+///
+/// Can be used anywhere [`Expected`] is as the error type of a parse.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::error::BoxedExpected;
+/// use dangerous::Input;
+///
+/// let error = dangerous::input(b"hello")
+///     .read_all::<_, _, BoxedExpected<'_>>(|r| r.consume(b"world"))
+///     .unwrap_err();
+///
+/// assert_eq!(core::mem::size_of_val(&error), core::mem::size_of::<usize>());
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type BoxedExpected<'i, S = ExpectedBacktrace> = Box<Expected<'i, S>>;
+
+/// The kind of [`Expected`] error.
+///
+/// Allows callers to branch on what went wrong without matching on the
+/// display output. For example, input that was cut short is reported as
+/// [`ErrorKind::Length`] (see [`ToRetryRequirement`] for whether more input
+/// would help), while malformed input is reported as [`ErrorKind::Valid`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// An exact value was expected but a different value was found.
+    Value,
+    /// A valid value was expected but the input was malformed.
+    Valid,
+    /// A length of input was expected but not available.
+    Length,
 }
 
+/// What was expected, with the context kept as the root of the backtrace.
+#[derive(PartialEq)]
 enum ExpectedKind<'i> {
     /// An exact value was expected in a context.
-    Value(ExpectedValue<'i>),
+    Value {
+        expected: Value<'i>,
+        is_string: bool,
+    },
     /// A valid value was expected in a context.
-    Valid(ExpectedValid<'i>),
+    Valid {
+        reason: Option<Reason>,
+        found: Option<Value<'i>>,
+    },
     /// A length was expected in a context.
-    Length(ExpectedLength<'i>),
+    Length(Length),
+}
+
+impl ErrorKind {
+    /// Returns the lowercase name of the kind.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Value => "value",
+            Self::Valid => "valid",
+            Self::Length => "length",
+        }
+    }
 }
 
 impl<'i, S> Expected<'i, S>
@@ -58,6 +135,197 @@ where
     pub fn display(&self) -> ErrorDisplay<'_, Self> {
         ErrorDisplay::new(self)
     }
+
+    /// Returns the kind of error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::error::{CoreOperation, ErrorKind};
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input(b"a")
+    ///     .read_all::<_, _, Expected<'_>>(|r| r.take(2))
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(error.kind(), ErrorKind::Length);
+    /// assert_eq!(error.operation(), CoreOperation::Take);
+    /// ```
+    #[must_use]
+    pub fn kind(&self) -> ErrorKind {
+        match self.kind {
+            ExpectedKind::Value { .. } => ErrorKind::Value,
+            ExpectedKind::Valid { .. } => ErrorKind::Valid,
+            ExpectedKind::Length(_) => ErrorKind::Length,
+        }
+    }
+
+    /// Returns the structured reason the input was not valid, if known.
+    ///
+    /// Only [`ErrorKind::Valid`] errors may have a reason.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::error::Reason;
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input(b"\xc2 ")
+    ///     .read_all::<_, _, Expected<'_>>(|r| r.take_remaining_str())
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(error.reason(), Some(Reason::InvalidUtf8));
+    /// ```
+    #[must_use]
+    pub fn reason(&self) -> Option<Reason> {
+        match self.kind {
+            ExpectedKind::Valid { reason, .. } => reason,
+            ExpectedKind::Value { .. } | ExpectedKind::Length(_) => None,
+        }
+    }
+
+    /// Returns the root operation that failed.
+    #[must_use]
+    pub fn operation(&self) -> CoreOperation {
+        self.trace.root().operation
+    }
+
+    /// Returns the line and column the error occurred at within the input.
+    ///
+    /// The position is computed on each call. Returns `None` if the error
+    /// span is not within the input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input("a\nbé!")
+    ///     .read_all::<_, _, Expected<'_>>(|r| {
+    ///         r.take_until_consume('\n')?;
+    ///         r.consume("bé?")
+    ///     })
+    ///     .unwrap_err();
+    /// let position = error.line_column().unwrap();
+    ///
+    /// assert_eq!((position.line, position.column), (2, 1));
+    /// assert_eq!(position.to_string(), "line 2, column 1");
+    /// ```
+    #[must_use]
+    pub fn line_column(&self) -> Option<LineColumn> {
+        LineColumn::from_details(self)
+    }
+
+    /// Returns the span of the error as offsets into the input.
+    ///
+    /// The offsets remain meaningful once the input is copied, such as with
+    /// [`into_owned()`](Self::into_owned()). Returns `None` if the error span
+    /// is not within the input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input(b"hello")
+    ///     .read_all::<_, _, Expected<'_>>(|r| {
+    ///         r.skip(2)?;
+    ///         r.consume(b"lo")
+    ///     })
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(error.span_range(), Some(2..4));
+    /// ```
+    #[must_use]
+    pub fn span_range(&self) -> Option<Range<usize>> {
+        span_range(self)
+    }
+
+    /// Returns `Self` linked to the parent input the input was derived from.
+    ///
+    /// When a parser reads input derived from an outer input, such as after
+    /// decoding base64, the error only points into the derived input. Linking
+    /// the parent with the `origin` span of the parent the derived input was
+    /// decoded from lets the error display show both. Parents of the parent
+    /// added later with [`WithContext::with_input()`] replace it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input, Invalid};
+    ///
+    /// let outer = dangerous::input(b"id=MTIz");
+    /// let (_, encoded) = outer.clone().split_at::<Invalid>(3).unwrap();
+    /// // Imagine base64 decoding `encoded` here.
+    /// let decoded = b"123".to_vec();
+    /// let error = dangerous::input(&decoded[..])
+    ///     .read_all::<_, _, Expected<'_>>(|r| r.consume(b"124"))
+    ///     .map_err(|err| err.with_parent(outer.clone(), encoded.span()))
+    ///     .unwrap_err();
+    ///
+    /// assert!(error.to_string().contains("derived from:"));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn with_parent(mut self, parent: impl Input<'i>, origin: Span) -> Self {
+        self.parent = Some(Box::new((parent.into_maybe_string(), origin)));
+        self
+    }
+
+    /// Copies the input into owned storage, dropping the `'i` lifetime.
+    ///
+    /// See [`OwnedExpected`] for more information.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn into_owned(self) -> OwnedExpected {
+        OwnedExpected::from_details(self.kind(), self.reason(), &self)
+    }
+
+    /// Writes the error on a single line into a byte buffer, returning the
+    /// number of bytes written.
+    ///
+    /// This is the compact display of the error written without allocating,
+    /// for panic handlers and logging where a `String` can't be built. If the
+    /// message doesn't fit, it is cut short with the end replaced with `...`.
+    /// See [`ErrorDisplay::write_to_buf()`] to write other displays.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let error = dangerous::input(b"hello")
+    ///     .read_all::<_, _, Expected<'_>>(|r| r.consume(b"world"))
+    ///     .unwrap_err();
+    ///
+    /// let mut buf = [0; 64];
+    /// let len = error.write_to_buf(&mut buf);
+    ///
+    /// assert_eq!(
+    ///     &buf[..len],
+    ///     &b"failed to consume input: found a different value to the exact..."[..],
+    /// );
+    /// ```
+    pub fn write_to_buf(&self, buf: &mut [u8]) -> usize {
+        self.display().compact(true).write_to_buf(buf)
+    }
+
+    /// Returns a [`Report`] of the error, decoupled from formatting.
+    ///
+    /// See [`Report`] for more information.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_report(&self) -> Report {
+        Report::from_details(self)
+    }
+}
+
+#[cfg(feature = "miette")]
+impl<'i, S> Expected<'i, S> {
+    #[inline(always)]
+    pub(crate) fn input_span(&self) -> &InputSpan<'i> {
+        &self.input
+    }
 }
 
 impl<'i, S> Expected<'i, S>
@@ -67,7 +335,14 @@ where
     #[inline(always)]
     fn add_input(&mut self, input: impl Input<'i>) {
         if self.input.span().is_within(input.span()) {
-            self.input = input.into_maybe_string();
+            self.input = InputSpan::new(&input.into_maybe_string());
+            return;
+        }
+        #[cfg(feature = "alloc")]
+        if let Some(parent) = &mut self.parent {
+            if parent.0.span().is_within(input.span()) {
+                parent.0 = input.into_maybe_string();
+            }
         }
     }
 
@@ -76,16 +351,19 @@ where
         self.trace.push(context);
     }
 
-    fn from_kind(kind: ExpectedKind<'i>) -> Self {
-        let (input, context) = match &kind {
-            ExpectedKind::Valid(err) => (err.input(), err.context()),
-            ExpectedKind::Value(err) => (err.input(), err.context()),
-            ExpectedKind::Length(err) => (err.input(), err.context()),
-        };
+    fn from_parts(
+        input: &MaybeString<'i>,
+        context: CoreContext,
+        retry_requirement: Option<RetryRequirement>,
+        kind: ExpectedKind<'i>,
+    ) -> Self {
         Self {
-            kind,
-            input,
+            input: InputSpan::new(input),
             trace: S::from_root(context),
+            kind,
+            retry_requirement,
+            #[cfg(feature = "alloc")]
+            parent: None,
         }
     }
 }
@@ -95,44 +373,159 @@ where
     S: Backtrace,
 {
     fn input(&self) -> MaybeString<'i> {
-        self.input.clone()
+        self.input.to_maybe_string()
     }
 
     fn expected(&self) -> Option<Value<'i>> {
-        match &self.kind {
-            ExpectedKind::Value(err) => Some(err.expected()),
-            ExpectedKind::Valid(_) | ExpectedKind::Length(_) => None,
+        match self.kind {
+            ExpectedKind::Value { expected, .. } => Some(expected),
+            ExpectedKind::Valid { .. } | ExpectedKind::Length(_) => None,
         }
     }
 
+    fn found(&self) -> Option<Value<'i>> {
+        match self.kind {
+            ExpectedKind::Value { is_string, .. } => value::found_in(
+                self.trace.root().span,
+                self.input.as_dangerous_bytes(),
+                is_string,
+            ),
+            ExpectedKind::Valid { found, .. } => found,
+            ExpectedKind::Length(_) => None,
+        }
+    }
+
+    fn kind(&self) -> Option<ErrorKind> {
+        Some(Expected::kind(self))
+    }
+
+    fn reason(&self) -> Option<Reason> {
+        Expected::reason(self)
+    }
+
+    fn retry_requirement(&self) -> Option<RetryRequirement> {
+        self.to_retry_requirement()
+    }
+
     fn description(&self, f: &mut dyn fmt::Write) -> fmt::Result {
-        match &self.kind {
-            ExpectedKind::Value(err) => fmt::DisplayBase::fmt(err, f),
-            ExpectedKind::Valid(err) => fmt::DisplayBase::fmt(err, f),
-            ExpectedKind::Length(err) => fmt::DisplayBase::fmt(err, f),
+        match self.kind {
+            ExpectedKind::Value { .. } => value::write_description(f, self.is_fatal()),
+            ExpectedKind::Valid { found, .. } => {
+                valid::write_description(f, self.trace.root().expected, found)
+            }
+            ExpectedKind::Length(len) => {
+                length::write_description(f, self.trace.root().span.len(), len)
+            }
         }
     }
 
+    #[cfg(feature = "alloc")]
+    fn parent(&self) -> Option<(MaybeString<'i>, Span)> {
+        self.parent.as_deref().cloned()
+    }
+
     fn backtrace(&self) -> &dyn Backtrace {
         &self.trace
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<'i, S> Details<'i> for Box<Expected<'i, S>>
+where
+    S: Backtrace,
+{
+    fn input(&self) -> MaybeString<'i> {
+        (**self).input()
+    }
+
+    fn expected(&self) -> Option<Value<'_>> {
+        (**self).expected()
+    }
+
+    fn found(&self) -> Option<Value<'_>> {
+        (**self).found()
+    }
+
+    fn kind(&self) -> Option<ErrorKind> {
+        Details::kind(&**self)
+    }
+
+    fn reason(&self) -> Option<Reason> {
+        Details::reason(&**self)
+    }
+
+    fn retry_requirement(&self) -> Option<RetryRequirement> {
+        Details::retry_requirement(&**self)
+    }
+
+    fn description(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).description(w)
+    }
+
+    fn parent(&self) -> Option<(MaybeString<'i>, Span)> {
+        (**self).parent()
+    }
+
+    fn backtrace(&self) -> &dyn Backtrace {
+        (**self).backtrace()
+    }
+}
+
+impl<S> PartialEq for Expected<'_, S>
+where
+    S: Backtrace,
+{
+    /// Compares the errors structurally, ignoring the identity of the input.
+    ///
+    /// The spans of the error and its contexts are compared by their offsets
+    /// within the inputs, so errors from reading equal inputs are equal.
+    /// Contexts are compared by their operation, their written description
+    /// and expected text and their span.
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.retry_requirement == other.retry_requirement
+            && self.input.to_maybe_string() == other.input.to_maybe_string()
+            && self.parent_eq(other)
+            && backtrace_eq(
+                &self.trace,
+                self.input.span(),
+                &other.trace,
+                other.input.span(),
+            )
+    }
+}
+
+impl<S> Expected<'_, S> {
+    #[cfg(feature = "alloc")]
+    fn parent_eq(&self, other: &Self) -> bool {
+        match (&self.parent, &other.parent) {
+            (Some(a), Some(b)) => {
+                a.0 == b.0 && a.1.range_of(a.0.span()) == b.1.range_of(b.0.span())
+            }
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[inline(always)]
+    fn parent_eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
 impl<'i, S> ToRetryRequirement for Expected<'i, S> {
     fn to_retry_requirement(&self) -> Option<RetryRequirement> {
-        match &self.kind {
-            ExpectedKind::Value(err) => err.to_retry_requirement(),
-            ExpectedKind::Valid(err) => err.to_retry_requirement(),
-            ExpectedKind::Length(err) => err.to_retry_requirement(),
-        }
+        self.retry_requirement
     }
 
     fn is_fatal(&self) -> bool {
-        match &self.kind {
-            ExpectedKind::Value(err) => err.is_fatal(),
-            ExpectedKind::Valid(err) => err.is_fatal(),
-            ExpectedKind::Length(err) => err.is_fatal(),
-        }
+        self.retry_requirement.is_none()
+    }
+
+    fn with_total_hint(mut self, total: usize) -> Self {
+        self.retry_requirement = self.retry_requirement.with_total_hint(total);
+        self
     }
 }
 
@@ -145,6 +538,11 @@ impl<'i, S> ToRetryRequirement for Box<Expected<'i, S>> {
     fn is_fatal(&self) -> bool {
         (**self).is_fatal()
     }
+
+    fn with_total_hint(mut self, total: usize) -> Self {
+        self.retry_requirement = self.retry_requirement.with_total_hint(total);
+        self
+    }
 }
 
 impl<'i, S> WithContext<'i> for Expected<'i, S>
@@ -205,7 +603,13 @@ where
     S: BacktraceBuilder,
 {
     fn from(err: ExpectedLength<'i>) -> Self {
-        Self::from_kind(ExpectedKind::Length(err))
+        let retry_requirement = err.to_retry_requirement();
+        Self::from_parts(
+            &err.input,
+            err.context,
+            retry_requirement,
+            ExpectedKind::Length(err.len),
+        )
     }
 }
 
@@ -224,7 +628,12 @@ where
     S: BacktraceBuilder,
 {
     fn from(err: ExpectedValid<'i>) -> Self {
-        Self::from_kind(ExpectedKind::Valid(err))
+        let retry_requirement = err.to_retry_requirement();
+        let kind = ExpectedKind::Valid {
+            reason: err.reason,
+            found: err.found,
+        };
+        Self::from_parts(&err.input, err.context, retry_requirement, kind)
     }
 }
 
@@ -243,7 +652,12 @@ where
     S: BacktraceBuilder,
 {
     fn from(err: ExpectedValue<'i>) -> Self {
-        Self::from_kind(ExpectedKind::Value(err))
+        let retry_requirement = err.to_retry_requirement();
+        let kind = ExpectedKind::Value {
+            expected: err.expected,
+            is_string: err.input.is_string(),
+        };
+        Self::from_parts(&err.input, err.context, retry_requirement, kind)
     }
 }
 
@@ -265,13 +679,25 @@ mod tests {
     #[cfg(all(target_pointer_width = "64", not(feature = "full-backtrace")))]
     fn test_expected_size() {
         // Update the docs if this value changes.
-        assert_eq!(core::mem::size_of::<Expected<'_>>(), 192);
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(core::mem::size_of::<Expected<'_>>(), 136);
+        #[cfg(feature = "alloc")]
+        assert_eq!(core::mem::size_of::<Expected<'_>>(), 144);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_boxed_expected_result_size() {
+        assert_eq!(
+            core::mem::size_of::<Result<(), BoxedExpected<'_>>>(),
+            core::mem::size_of::<usize>()
+        );
     }
 
     #[test]
     #[cfg(all(target_pointer_width = "64", feature = "full-backtrace"))]
     fn test_expected_size() {
         // Update the docs if this value changes.
-        assert_eq!(core::mem::size_of::<Expected<'_>>(), 216);
+        assert_eq!(core::mem::size_of::<Expected<'_>>(), 168);
     }
 }
@@ -0,0 +1,354 @@
+use alloc::boxed::Box;
+use alloc::string::String as StringBuf;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::ops::Range;
+use core::str;
+
+use crate::display::ErrorDisplay;
+use crate::error::{
+    span_range, Backtrace, BacktraceWalker, Context, CoreContext, CoreOperation, Details,
+    ErrorKind, LineColumn, Operation, Reason, Report, ReportValue, RetryRequirement,
+    ToRetryRequirement, Value,
+};
+use crate::fmt;
+use crate::input::{Bound, Bytes, MaybeString, Span, String};
+
+/// An [`Expected`] error with the input copied into owned storage, created
+/// with [`Expected::into_owned()`].
+///
+/// The input, the expected value and the description are copied, and the
+/// spans of all contexts collected are moved to point into the owned input.
+/// This drops the `'i` lifetime so the error can outlive the buffer it was
+/// created from and be sent across threads.
+///
+/// [`Details`] is implemented for `&OwnedExpected`.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::error::OwnedExpected;
+/// use dangerous::{Expected, Input};
+///
+/// fn parse(buf: Vec<u8>) -> Result<u8, OwnedExpected> {
+///     dangerous::input(&buf[..])
+///         .read_all::<_, _, Expected<'_>>(|r| r.read())
+///         .map_err(Expected::into_owned)
+/// }
+///
+/// let error = parse(vec![1, 2]).unwrap_err();
+///
+/// assert!(error.to_string().starts_with("failed to read all input"));
+/// ```
+///
+/// [`Expected`]: crate::Expected
+/// [`Expected::into_owned()`]: crate::Expected::into_owned()
+#[must_use = "error must be handled"]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct OwnedExpected {
+    kind: ErrorKind,
+    reason: Option<Reason>,
+    input: Box<[u8]>,
+    input_bound: Bound,
+    input_is_str: bool,
+    expected: Option<ReportValue>,
+    found: Option<ReportValue>,
+    description: Box<str>,
+    retry_requirement: Option<RetryRequirement>,
+    trace: OwnedBacktrace,
+}
+
+impl OwnedExpected {
+    pub(crate) fn from_details<'i, T>(kind: ErrorKind, reason: Option<Reason>, error: &T) -> Self
+    where
+        T: Details<'i> + ToRetryRequirement,
+    {
+        let input = error.input();
+        let input_span = input.span();
+        let owned_input: Box<[u8]> = input.as_dangerous_bytes().into();
+        let rebase = |span| rebase_span(span, input_span, &owned_input);
+        let mut description = StringBuf::new();
+        // Failing to write the description leaves what was written.
+        let _ = error.description(&mut description);
+        let trace = OwnedBacktrace::from_backtrace(error.backtrace(), &rebase);
+        Self {
+            kind,
+            reason,
+            input_bound: input.bound(),
+            input_is_str: input.is_string(),
+            input: owned_input,
+            expected: error.expected().map(ReportValue::from_value),
+            found: error.found().map(ReportValue::from_value),
+            description: description.into_boxed_str(),
+            retry_requirement: error.to_retry_requirement(),
+            trace,
+        }
+    }
+
+    /// Returns the kind of error.
+    #[must_use]
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the structured reason the input was not valid, if known.
+    #[must_use]
+    pub fn reason(&self) -> Option<Reason> {
+        self.reason
+    }
+
+    /// Returns the root operation that failed.
+    #[must_use]
+    pub fn operation(&self) -> CoreOperation {
+        self.trace.root.operation
+    }
+
+    /// Returns the line and column the error occurred at within the input.
+    #[must_use]
+    pub fn line_column(&self) -> Option<LineColumn> {
+        LineColumn::from_details(&self)
+    }
+
+    /// Returns the span of the error as offsets into the owned input.
+    #[must_use]
+    pub fn span_range(&self) -> Option<Range<usize>> {
+        span_range(&self)
+    }
+
+    /// Returns a [`Report`] of the error, decoupled from formatting.
+    #[must_use]
+    pub fn to_report(&self) -> Report {
+        Report::from_details(&self)
+    }
+}
+
+impl Clone for OwnedExpected {
+    /// Clones the error, moving the spans to point into the cloned input.
+    fn clone(&self) -> Self {
+        let input = self.input.clone();
+        let trace = self
+            .trace
+            .rebased(&|span| rebase_span(span, Span::from(&*self.input), &input));
+        Self {
+            kind: self.kind,
+            reason: self.reason,
+            input,
+            input_bound: self.input_bound,
+            input_is_str: self.input_is_str,
+            expected: self.expected.clone(),
+            found: self.found.clone(),
+            description: self.description.clone(),
+            retry_requirement: self.retry_requirement,
+            trace,
+        }
+    }
+}
+
+impl<'a> Details<'a> for &'a OwnedExpected {
+    fn input(&self) -> MaybeString<'a> {
+        if self.input_is_str {
+            // SAFETY: the owned input was copied from input known to be UTF-8.
+            let utf8 = unsafe { str::from_utf8_unchecked(&self.input) };
+            MaybeString::String(String::new(utf8, self.input_bound))
+        } else {
+            MaybeString::Bytes(Bytes::new(&self.input, self.input_bound))
+        }
+    }
+
+    fn expected(&self) -> Option<Value<'_>> {
+        self.expected.as_ref().map(ReportValue::as_value)
+    }
+
+    fn found(&self) -> Option<Value<'_>> {
+        self.found.as_ref().map(ReportValue::as_value)
+    }
+
+    fn kind(&self) -> Option<ErrorKind> {
+        Some(self.kind)
+    }
+
+    fn reason(&self) -> Option<Reason> {
+        self.reason
+    }
+
+    fn retry_requirement(&self) -> Option<RetryRequirement> {
+        self.retry_requirement
+    }
+
+    fn description(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_str(&self.description)
+    }
+
+    fn backtrace(&self) -> &dyn Backtrace {
+        &self.trace
+    }
+}
+
+impl ToRetryRequirement for OwnedExpected {
+    fn to_retry_requirement(&self) -> Option<RetryRequirement> {
+        self.retry_requirement
+    }
+
+    fn with_total_hint(mut self, total: usize) -> Self {
+        self.retry_requirement = self.retry_requirement.with_total_hint(total);
+        self
+    }
+}
+
+impl fmt::Debug for OwnedExpected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ErrorDisplay::from_formatter(&self, f).banner(true).fmt(f)
+    }
+}
+
+impl fmt::Display for OwnedExpected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ErrorDisplay::from_formatter(&self, f).fmt(f)
+    }
+}
+
+/// Moves a span within the input to the same offsets within its copy.
+///
+/// Spans outside of the input are left as they are.
+fn rebase_span(span: Span, input: Span, copy: &[u8]) -> Span {
+    span.range_of(input)
+        .and_then(|range| copy.get(range))
+        .map_or(span, Span::from)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+struct OwnedBacktrace {
+    root: CoreContext,
+    contexts: Vec<(usize, OwnedContext)>,
+}
+
+impl OwnedBacktrace {
+    fn from_backtrace(backtrace: &dyn Backtrace, rebase: &dyn Fn(Span) -> Span) -> Self {
+        let mut root = backtrace.root();
+        root.span = rebase(root.span);
+        let mut contexts = Vec::with_capacity(backtrace.count());
+        backtrace.walk(&mut |depth, context| {
+            contexts.push((depth, OwnedContext::from_context(context, rebase)));
+            true
+        });
+        Self { root, contexts }
+    }
+
+    fn rebased(&self, rebase: &dyn Fn(Span) -> Span) -> Self {
+        let mut root = self.root;
+        root.span = rebase(root.span);
+        let contexts = self
+            .contexts
+            .iter()
+            .map(|(depth, context)| {
+                let mut context = context.clone();
+                context.span = context.span.map(rebase);
+                (*depth, context)
+            })
+            .collect();
+        Self { root, contexts }
+    }
+}
+
+impl Backtrace for OwnedBacktrace {
+    fn root(&self) -> CoreContext {
+        self.root
+    }
+
+    fn count(&self) -> usize {
+        self.contexts.len()
+    }
+
+    fn walk<'a>(&'a self, f: &mut BacktraceWalker<'a>) -> bool {
+        self.contexts
+            .iter()
+            .all(|(depth, context)| f(*depth, context))
+    }
+}
+
+#[derive(Clone)]
+struct OwnedContext {
+    span: Option<Span>,
+    operation: OwnedOperation,
+    expected: Option<Box<str>>,
+    is_child: bool,
+}
+
+impl OwnedContext {
+    fn from_context(context: &dyn Context, rebase: &dyn Fn(Span) -> Span) -> Self {
+        let operation = context.operation();
+        let operation = if let Some(core) = operation.as_any().downcast_ref::<CoreOperation>() {
+            OwnedOperation::Core(*core)
+        } else {
+            let mut description = StringBuf::new();
+            let _ = operation.description(&mut description);
+            OwnedOperation::Other(description.into_boxed_str())
+        };
+        let expected = if context.has_expected() {
+            let mut expected = StringBuf::new();
+            let _ = context.expected(&mut expected);
+            Some(expected.into_boxed_str())
+        } else {
+            None
+        };
+        Self {
+            span: context.span().map(rebase),
+            operation,
+            expected,
+            is_child: context.is_child(),
+        }
+    }
+}
+
+impl Context for OwnedContext {
+    fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    fn operation(&self) -> &dyn Operation {
+        if let OwnedOperation::Core(core) = &self.operation {
+            core
+        } else {
+            &self.operation
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn has_expected(&self) -> bool {
+        self.expected.is_some()
+    }
+
+    fn expected(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        match &self.expected {
+            Some(expected) => w.write_str(expected),
+            None => Err(fmt::Error),
+        }
+    }
+
+    fn is_child(&self) -> bool {
+        self.is_child
+    }
+}
+
+#[derive(Clone)]
+enum OwnedOperation {
+    Core(CoreOperation),
+    Other(Box<str>),
+}
+
+impl Operation for OwnedOperation {
+    fn description(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        match self {
+            Self::Core(core) => Operation::description(core, w),
+            Self::Other(description) => w.write_str(description),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -1,4 +1,6 @@
-use crate::error::{CoreContext, RetryRequirement, ToRetryRequirement};
+use core::ops::Range;
+
+use crate::error::{CoreContext, CoreExpected, RetryRequirement, ToRetryRequirement, Value};
 use crate::fmt;
 use crate::input::MaybeString;
 
@@ -7,6 +9,8 @@ use crate::input::MaybeString;
 #[must_use = "error must be handled"]
 pub struct ExpectedValid<'i> {
     pub(crate) retry_requirement: Option<RetryRequirement>,
+    pub(crate) reason: Option<Reason>,
+    pub(crate) found: Option<Value<'i>>,
     pub(crate) context: CoreContext,
     pub(crate) input: MaybeString<'i>,
 }
@@ -19,12 +23,119 @@ impl<'i> ExpectedValid<'i> {
         self.context
     }
 
+    /// The structured reason the input was not valid, if known.
+    ///
+    /// Set by the operation that failed, or by an [`External`] error with
+    /// [`External::reason()`].
+    ///
+    /// [`External`]: crate::error::External
+    /// [`External::reason()`]: crate::error::External::reason()
+    #[must_use]
+    #[inline(always)]
+    pub fn reason(&self) -> Option<Reason> {
+        self.reason
+    }
+
+    /// The value that was found instead of a valid one, if known.
+    ///
+    /// This is provided by an [`External`] error with [`External::found()`].
+    ///
+    /// [`External`]: crate::error::External
+    /// [`External::found()`]: crate::error::External::found()
+    #[must_use]
+    #[inline(always)]
+    pub fn found(&self) -> Option<Value<'i>> {
+        self.found
+    }
+
     /// The [`Input`](crate::Input) provided in the context when the error
     /// occurred.
     #[inline(always)]
     pub fn input(&self) -> MaybeString<'i> {
         self.input.clone()
     }
+
+    /// Returns the span of the error as offsets into the
+    /// [`input()`](Self::input()).
+    ///
+    /// Unlike the [`Span`](crate::Span) of the [`context()`](Self::context()),
+    /// the offsets don't borrow from the input and remain meaningful once it
+    /// is copied or dropped. Returns `None` if the span is not within the
+    /// input.
+    #[must_use]
+    #[inline(always)]
+    pub fn span_range(&self) -> Option<Range<usize>> {
+        self.context.span.range_of(self.input.span())
+    }
+}
+
+/// A structured reason for why input was not valid.
+///
+/// Allows consumers to branch on why an [`ExpectedValid`] error occurred
+/// without parsing the expected description.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Reason {
+    /// The input was not valid UTF-8.
+    InvalidUtf8,
+    /// An index did not sit on a token boundary.
+    InvalidTokenBoundary,
+    /// A numeric value overflowed.
+    Overflow,
+    /// A tag or discriminant did not match a known variant.
+    UnknownVariant,
+    /// Nested input exceeded the maximum depth of a reader.
+    DepthExceeded,
+    /// A reader ran out of iteration fuel.
+    FuelExhausted,
+    /// Input was not aligned for the type being read.
+    Misaligned,
+    /// A char did not start any token a lexer could match.
+    UnexpectedChar,
+    /// Input was not valid in the text encoding it was decoded with.
+    InvalidEncoding,
+    /// A code specific to the format being read.
+    Code(u32),
+}
+
+impl fmt::DisplayBase for Reason {
+    fn fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        match *self {
+            Self::InvalidUtf8 => w.write_str("invalid utf-8"),
+            Self::InvalidTokenBoundary => w.write_str("invalid token boundary"),
+            Self::Overflow => w.write_str("overflow"),
+            Self::UnknownVariant => w.write_str("unknown variant"),
+            Self::DepthExceeded => w.write_str("maximum depth exceeded"),
+            Self::FuelExhausted => w.write_str("fuel exhausted"),
+            Self::Misaligned => w.write_str("misaligned"),
+            Self::UnexpectedChar => w.write_str("unexpected character"),
+            Self::InvalidEncoding => w.write_str("invalid encoding"),
+            Self::Code(code) => {
+                w.write_str("code ")?;
+                w.write_usize(code as usize)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::DisplayBase::fmt(self, f)
+    }
+}
+
+impl PartialEq for ExpectedValid<'_> {
+    /// Compares the errors structurally, with the spans compared by their
+    /// offsets within the inputs.
+    fn eq(&self, other: &Self) -> bool {
+        self.retry_requirement == other.retry_requirement
+            && self.reason == other.reason
+            && self.found == other.found
+            && self.input == other.input
+            && self
+                .context
+                .eq_within(self.input.span(), &other.context, other.input.span())
+    }
 }
 
 impl<'i> fmt::Debug for ExpectedValid<'i> {
@@ -32,6 +143,12 @@ impl<'i> fmt::Debug for ExpectedValid<'i> {
         let mut debug = f.debug_struct("ExpectedValid");
 
         debug.field("retry_requirement", &self.retry_requirement);
+        if let Some(reason) = &self.reason {
+            debug.field("reason", reason);
+        }
+        if let Some(found) = &self.found {
+            debug.field("found", found);
+        }
         debug.field("context", &self.context().debug_for(self.input()));
         debug.field("input", &self.input());
 
@@ -41,9 +158,22 @@ impl<'i> fmt::Debug for ExpectedValid<'i> {
 
 impl<'i> fmt::DisplayBase for ExpectedValid<'i> {
     fn fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
-        w.write_str("expected ")?;
-        self.context.expected.fmt(w)
+        write_description(w, self.context.expected, self.found)
+    }
+}
+
+pub(crate) fn write_description(
+    w: &mut dyn fmt::Write,
+    expected: CoreExpected,
+    found: Option<Value<'_>>,
+) -> fmt::Result {
+    w.write_str("expected ")?;
+    fmt::DisplayBase::fmt(&expected, w)?;
+    if let Some(found) = found {
+        w.write_str(", found ")?;
+        fmt::DisplayBase::fmt(&found.display(), w)?;
     }
+    Ok(())
 }
 
 impl<'i> fmt::Display for ExpectedValid<'i> {
@@ -1,7 +1,10 @@
+use core::ops::Range;
+
 use crate::error::Value;
 use crate::error::{CoreContext, RetryRequirement, ToRetryRequirement};
 use crate::fmt;
-use crate::input::MaybeString;
+use crate::input::{MaybeString, Span};
+use crate::util::fast;
 
 /// An error representing a failed exact value requirement of
 /// [`Input`](crate::Input).
@@ -19,6 +22,20 @@ impl<'i> ExpectedValue<'i> {
         self.expected
     }
 
+    /// The value that was found instead of the expected.
+    ///
+    /// This is the input within the context span, which may be shorter than
+    /// the expected value if there was not enough input. Returns `None` if the
+    /// span is not within the input.
+    #[must_use]
+    pub fn found(&self) -> Option<Value<'i>> {
+        found_in(
+            self.context.span,
+            self.input.as_dangerous_bytes(),
+            self.input.is_string(),
+        )
+    }
+
     /// The [`CoreContext`] around the error.
     #[must_use]
     #[inline(always)]
@@ -31,6 +48,31 @@ impl<'i> ExpectedValue<'i> {
     pub fn input(&self) -> MaybeString<'i> {
         self.input.clone()
     }
+
+    /// Returns the span of the error as offsets into the
+    /// [`input()`](Self::input()).
+    ///
+    /// Unlike the [`Span`](crate::Span) of the [`context()`](Self::context()),
+    /// the offsets don't borrow from the input and remain meaningful once it
+    /// is copied or dropped. Returns `None` if the span is not within the
+    /// input.
+    #[must_use]
+    #[inline(always)]
+    pub fn span_range(&self) -> Option<Range<usize>> {
+        self.context.span.range_of(self.input.span())
+    }
+}
+
+impl PartialEq for ExpectedValue<'_> {
+    /// Compares the errors structurally, with the spans compared by their
+    /// offsets within the inputs.
+    fn eq(&self, other: &Self) -> bool {
+        self.expected == other.expected
+            && self.input == other.input
+            && self
+                .context
+                .eq_within(self.input.span(), &other.context, other.input.span())
+    }
 }
 
 impl<'i> fmt::Debug for ExpectedValue<'i> {
@@ -45,11 +87,7 @@ impl<'i> fmt::Debug for ExpectedValue<'i> {
 
 impl<'i> fmt::DisplayBase for ExpectedValue<'i> {
     fn fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
-        if self.is_fatal() {
-            w.write_str("found a different value to the exact expected")
-        } else {
-            w.write_str("not enough input to match expected value")
-        }
+        write_description(w, self.is_fatal())
     }
 }
 
@@ -59,6 +97,25 @@ impl<'i> fmt::Display for ExpectedValue<'i> {
     }
 }
 
+/// Returns the value found within `span` of the input `bytes`.
+pub(crate) fn found_in(span: Span, bytes: &[u8], is_string: bool) -> Option<Value<'_>> {
+    let found = span.of(bytes)?;
+    if is_string {
+        if let Ok(found) = fast::from_utf8(found) {
+            return Some(Value::from(found));
+        }
+    }
+    Some(Value::from(found))
+}
+
+pub(crate) fn write_description(w: &mut dyn fmt::Write, is_fatal: bool) -> fmt::Result {
+    if is_fatal {
+        w.write_str("found a different value to the exact expected")
+    } else {
+        w.write_str("not enough input to match expected value")
+    }
+}
+
 impl<'i> ToRetryRequirement for ExpectedValue<'i> {
     #[inline]
     fn to_retry_requirement(&self) -> Option<RetryRequirement> {
@@ -67,7 +124,8 @@ impl<'i> ToRetryRequirement for ExpectedValue<'i> {
         } else {
             let needed = self.expected().as_bytes().len();
             let had = self.context.span.len();
-            RetryRequirement::from_had_and_needed(had, needed)
+            // The rest of the value is all that is required to match it.
+            RetryRequirement::from_had_and_needed(had, needed).map(|req| req.with_exact(true))
         }
     }
 
@@ -2,8 +2,8 @@ use crate::fmt;
 use crate::input::Input;
 
 use super::{
-    Context, ExpectedLength, ExpectedValid, ExpectedValue, RetryRequirement, ToRetryRequirement,
-    WithContext,
+    Context, ExpectedLength, ExpectedValid, ExpectedValue, NoContext, RetryRequirement,
+    ToRetryRequirement, WithContext,
 };
 
 /// An error that has no details around what went wrong and cannot be retried.
@@ -44,8 +44,11 @@ impl fmt::Display for Fatal {
     }
 }
 
+impl NoContext<'_> for Fatal {}
+
 impl<'i> WithContext<'i> for Fatal {
     const PASSTHROUGH: bool = true;
+    const NO_CONTEXT: bool = true;
 
     #[inline(always)]
     fn with_input(self, _input: impl Input<'i>) -> Self {
@@ -0,0 +1,169 @@
+#[cfg(feature = "error-hook")]
+use core::mem;
+#[cfg(feature = "error-hook")]
+use core::ptr;
+#[cfg(feature = "error-hook")]
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+#[cfg(feature = "error-hook")]
+use super::{
+    CoreContext, ErrorKind, ExpectedLength, ExpectedValid, ExpectedValue, ToRetryRequirement,
+};
+
+/// An error created while processing input, passed to the [`ErrorHook`].
+///
+/// See [`set_error_hook()`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone)]
+#[cfg(feature = "error-hook")]
+#[cfg_attr(docsrs, doc(cfg(feature = "error-hook")))]
+pub struct ErrorEvent {
+    /// The kind of error created.
+    pub kind: ErrorKind,
+    /// The context of the operation that failed.
+    pub context: CoreContext,
+    /// Whether the error can be retried with more input.
+    pub fatal: bool,
+}
+
+/// A function called whenever an error is created while processing input.
+///
+/// See [`set_error_hook()`].
+#[cfg(feature = "error-hook")]
+#[cfg_attr(docsrs, doc(cfg(feature = "error-hook")))]
+pub type ErrorHook = fn(&ErrorEvent);
+
+#[cfg(feature = "error-hook")]
+static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Sets the global hook called whenever an error is created, replacing any
+/// previous hook.
+///
+/// The hook is called as the error is created, before it is converted into
+/// the error type in use and before it may be discarded by a combinator such
+/// as [`Reader::recover()`]. This allows failures to be counted and sampled
+/// for monitoring regardless of the error type. Passing `None` removes the
+/// hook.
+///
+/// # Example
+///
+/// ```
+/// use core::sync::atomic::{AtomicUsize, Ordering};
+/// use dangerous::error::{set_error_hook, ErrorEvent, ErrorKind};
+/// use dangerous::{Fatal, Input};
+///
+/// static LENGTH_ERRORS: AtomicUsize = AtomicUsize::new(0);
+///
+/// fn count(event: &ErrorEvent) {
+///     if event.kind == ErrorKind::Length {
+///         LENGTH_ERRORS.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// set_error_hook(Some(count));
+/// let value = dangerous::input(b"a").read_all::<_, _, Fatal>(|r| {
+///     let recovered = r.recover(|r| r.take(2)).is_none();
+///     r.skip(1)?;
+///     Ok(recovered)
+/// });
+/// set_error_hook(None);
+///
+/// assert_eq!(value, Ok(true));
+/// assert_eq!(LENGTH_ERRORS.load(Ordering::Relaxed), 1);
+/// ```
+///
+/// [`Reader::recover()`]: crate::Reader::recover()
+#[cfg(feature = "error-hook")]
+#[cfg_attr(docsrs, doc(cfg(feature = "error-hook")))]
+pub fn set_error_hook(hook: Option<ErrorHook>) {
+    let hook = hook.map_or(ptr::null_mut(), |hook| hook as *mut ());
+    HOOK.store(hook, Ordering::Release);
+}
+
+/// Notifies the error hook, if any, of an error being created.
+#[cfg(feature = "error-hook")]
+#[inline(always)]
+pub(crate) fn notify<T>(error: T) -> T
+where
+    T: Created,
+{
+    let hook = HOOK.load(Ordering::Acquire);
+    if !hook.is_null() {
+        // SAFETY: only `ErrorHook` function pointers are stored in `HOOK`.
+        let hook = unsafe { mem::transmute::<*mut (), ErrorHook>(hook) };
+        hook(&ErrorEvent {
+            kind: T::KIND,
+            context: error.context(),
+            fatal: error.is_fatal(),
+        });
+    }
+    error
+}
+
+/// Without the `error-hook` feature there is nothing to notify.
+#[cfg(not(feature = "error-hook"))]
+#[inline(always)]
+pub(crate) fn notify<T>(error: T) -> T {
+    error
+}
+
+/// Creates an error, notifying the error hook if any.
+///
+/// Errors are the exception when reading input, so this is kept cold and out
+/// of line, leaving the success paths of the callers small enough to inline.
+#[cfg(feature = "error-hook")]
+#[cold]
+#[inline(never)]
+pub(crate) fn create<T, E>(error: T) -> E
+where
+    T: Created,
+    E: From<T>,
+{
+    E::from(notify(error))
+}
+
+/// Creates an error, kept cold and out of line.
+#[cfg(not(feature = "error-hook"))]
+#[cold]
+#[inline(never)]
+pub(crate) fn create<T, E>(error: T) -> E
+where
+    E: From<T>,
+{
+    E::from(notify(error))
+}
+
+/// Implemented for the errors created while processing input.
+#[cfg(feature = "error-hook")]
+pub(crate) trait Created: ToRetryRequirement {
+    const KIND: ErrorKind;
+
+    fn context(&self) -> CoreContext;
+}
+
+#[cfg(feature = "error-hook")]
+impl Created for ExpectedValue<'_> {
+    const KIND: ErrorKind = ErrorKind::Value;
+
+    fn context(&self) -> CoreContext {
+        self.context
+    }
+}
+
+#[cfg(feature = "error-hook")]
+impl Created for ExpectedValid<'_> {
+    const KIND: ErrorKind = ErrorKind::Valid;
+
+    fn context(&self) -> CoreContext {
+        self.context
+    }
+}
+
+#[cfg(feature = "error-hook")]
+impl Created for ExpectedLength<'_> {
+    const KIND: ErrorKind = ErrorKind::Length;
+
+    fn context(&self) -> CoreContext {
+        self.context
+    }
+}
@@ -1,9 +1,12 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
 use crate::fmt;
 use crate::input::Input;
 
 use super::{
-    Backtrace, Context, Expected, ExpectedLength, ExpectedValid, ExpectedValue, RetryRequirement,
-    ToRetryRequirement, WithContext,
+    Backtrace, Context, Expected, ExpectedLength, ExpectedValid, ExpectedValue, NoContext,
+    RetryRequirement, ToRetryRequirement, WithContext,
 };
 
 /// An error that has no details around what went wrong other than a
@@ -37,7 +40,7 @@ pub struct Invalid {
 impl Invalid {
     /// Create a fatal `Invalid` error.
     #[inline(always)]
-    pub fn fatal() -> Self {
+    pub const fn fatal() -> Self {
         Self {
             retry_requirement: None,
         }
@@ -45,7 +48,7 @@ impl Invalid {
 
     /// Create a retryable `Invalid` error.
     #[inline(always)]
-    pub fn retry(requirement: RetryRequirement) -> Self {
+    pub const fn retry(requirement: RetryRequirement) -> Self {
         Self {
             retry_requirement: Some(requirement),
         }
@@ -75,10 +78,20 @@ impl ToRetryRequirement for Invalid {
     fn to_retry_requirement(&self) -> Option<RetryRequirement> {
         self.retry_requirement
     }
+
+    #[inline(always)]
+    fn with_total_hint(self, total: usize) -> Self {
+        Self {
+            retry_requirement: self.retry_requirement.with_total_hint(total),
+        }
+    }
 }
 
+impl NoContext<'_> for Invalid {}
+
 impl<'i> WithContext<'i> for Invalid {
     const PASSTHROUGH: bool = true;
+    const NO_CONTEXT: bool = true;
 
     #[inline(always)]
     fn with_input(self, _input: impl Input<'i>) -> Self {
@@ -101,6 +114,17 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<'i, S> From<Box<Expected<'i, S>>> for Invalid
+where
+    S: Backtrace,
+{
+    #[inline(always)]
+    fn from(err: Box<Expected<'i, S>>) -> Self {
+        err.to_retry_requirement().into()
+    }
+}
+
 impl<'i> From<ExpectedValue<'i>> for Invalid {
     #[inline(always)]
     fn from(err: ExpectedValue<'i>) -> Self {
@@ -0,0 +1,204 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use crate::fmt;
+use crate::input::Input;
+
+use super::{
+    Backtrace, Context, CoreContext, CoreOperation, Details, ErrorKind, Expected, ExpectedLength,
+    ExpectedValid, ExpectedValue, Invalid, NoContext, Operation, RetryRequirement,
+    ToRetryRequirement, WithContext,
+};
+
+/// An [`Invalid`] error with a hint of what went wrong.
+///
+/// Along with the [`RetryRequirement`] if the error is not fatal, the
+/// [`ErrorKind`] and root [`CoreContext`] of the error are kept. Like
+/// [`Invalid`], the input and any contexts added while the error bubbles up
+/// are ignored, so the error is cheap to construct and copy. This suits
+/// streaming parsers which need retry semantics and would like to log what
+/// failed, without paying for an [`Expected`] error.
+///
+/// See [`crate::error`] for additional documentation around the error system.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::error::{CoreOperation, ErrorKind, InvalidHint};
+/// use dangerous::Input;
+///
+/// let error: InvalidHint = dangerous::input(b"a").read_all(|r| {
+///     r.consume(b"ab")
+/// }).unwrap_err();
+///
+/// assert_eq!(error.kind(), ErrorKind::Value);
+/// assert_eq!(error.operation(), CoreOperation::Consume);
+/// assert_eq!(
+///     error.to_string(),
+///     "invalid input: failed to consume input (expected exact value): \
+///      needs 1 byte more to continue processing",
+/// );
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[must_use = "error must be handled"]
+pub struct InvalidHint {
+    kind: ErrorKind,
+    context: CoreContext,
+    retry_requirement: Option<RetryRequirement>,
+}
+
+impl InvalidHint {
+    /// Returns the kind of error.
+    #[must_use]
+    #[inline(always)]
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the root [`CoreContext`] of the error.
+    ///
+    /// The span of the context points into the input the error was created
+    /// from.
+    #[must_use]
+    #[inline(always)]
+    pub fn context(&self) -> CoreContext {
+        self.context
+    }
+
+    /// Returns the root operation that failed.
+    #[must_use]
+    #[inline(always)]
+    pub fn operation(&self) -> CoreOperation {
+        self.context.operation
+    }
+}
+
+impl PartialEq for InvalidHint {
+    /// Compares the errors without their spans, as the input they point into
+    /// is not kept.
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.retry_requirement == other.retry_requirement
+            && self.context.operation == other.context.operation
+            && self.context.expected == other.context.expected
+    }
+}
+
+impl fmt::DisplayBase for InvalidHint {
+    fn fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_str("invalid input: failed to ")?;
+        Operation::description(&self.context.operation, w)?;
+        if self.context.has_expected() {
+            w.write_str(" (expected ")?;
+            self.context.expected(w)?;
+            w.write_char(')')?;
+        }
+        if let Some(retry_requirement) = self.retry_requirement {
+            w.write_str(": needs ")?;
+            fmt::DisplayBase::fmt(&retry_requirement, w)?;
+            w.write_str(" to continue processing")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for InvalidHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::DisplayBase::fmt(self, f)
+    }
+}
+
+impl ToRetryRequirement for InvalidHint {
+    #[inline(always)]
+    fn to_retry_requirement(&self) -> Option<RetryRequirement> {
+        self.retry_requirement
+    }
+
+    #[inline(always)]
+    fn with_total_hint(mut self, total: usize) -> Self {
+        self.retry_requirement = self.retry_requirement.with_total_hint(total);
+        self
+    }
+}
+
+impl NoContext<'_> for InvalidHint {}
+
+impl<'i> WithContext<'i> for InvalidHint {
+    const PASSTHROUGH: bool = true;
+    const NO_CONTEXT: bool = true;
+
+    #[inline(always)]
+    fn with_input(self, _input: impl Input<'i>) -> Self {
+        self
+    }
+
+    #[inline(always)]
+    fn with_context(self, _context: impl Context) -> Self {
+        self
+    }
+}
+
+impl<'i, S> From<Expected<'i, S>> for InvalidHint
+where
+    S: Backtrace,
+{
+    #[inline(always)]
+    fn from(err: Expected<'i, S>) -> Self {
+        Self {
+            kind: err.kind(),
+            context: err.backtrace().root(),
+            retry_requirement: err.to_retry_requirement(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'i, S> From<Box<Expected<'i, S>>> for InvalidHint
+where
+    S: Backtrace,
+{
+    #[inline(always)]
+    fn from(err: Box<Expected<'i, S>>) -> Self {
+        Self::from(*err)
+    }
+}
+
+impl<'i> From<ExpectedValue<'i>> for InvalidHint {
+    #[inline(always)]
+    fn from(err: ExpectedValue<'i>) -> Self {
+        Self {
+            kind: ErrorKind::Value,
+            context: err.context(),
+            retry_requirement: err.to_retry_requirement(),
+        }
+    }
+}
+
+impl<'i> From<ExpectedLength<'i>> for InvalidHint {
+    #[inline(always)]
+    fn from(err: ExpectedLength<'i>) -> Self {
+        Self {
+            kind: ErrorKind::Length,
+            context: err.context(),
+            retry_requirement: err.to_retry_requirement(),
+        }
+    }
+}
+
+impl<'i> From<ExpectedValid<'i>> for InvalidHint {
+    #[inline(always)]
+    fn from(err: ExpectedValid<'i>) -> Self {
+        Self {
+            kind: ErrorKind::Valid,
+            context: err.context(),
+            retry_requirement: err.to_retry_requirement(),
+        }
+    }
+}
+
+impl From<InvalidHint> for Invalid {
+    #[inline(always)]
+    fn from(err: InvalidHint) -> Self {
+        err.retry_requirement.into()
+    }
+}
@@ -1,7 +1,8 @@
 //! Error support.
 //!
 //! - If you want the fastest error which has no debugging information,
-//!   [`Fatal`] or [`Invalid`] (retryable) has you covered.
+//!   [`Fatal`] or [`Invalid`] (retryable) has you covered. [`InvalidHint`]
+//!   additionally keeps the kind and root context of the error.
 //! - If you want an error that is still designed to be fast, but also includes
 //!   debugging information, [`Expected`] will meet your uh, expectations... If
 //!   the feature `full-backtrace` is enabled, [`Expected`] uses
@@ -23,24 +24,54 @@ mod backtrace;
 mod context;
 mod expected;
 mod fatal;
+pub(crate) mod hook;
 mod invalid;
+mod invalid_hint;
 mod length;
+#[cfg(feature = "alloc")]
+mod multi;
+mod position;
+#[cfg(feature = "alloc")]
+mod report;
 mod retry;
+mod source;
 mod traits;
 mod value;
 
-#[cfg(feature = "alloc")]
-pub use self::backtrace::FullBacktrace;
+#[cfg(feature = "heapless")]
+pub use self::backtrace::HeaplessBacktrace;
+#[cfg(all(feature = "smallvec", feature = "alloc"))]
+pub use self::backtrace::SmallVecBacktrace;
 pub use self::backtrace::{Backtrace, BacktraceBuilder, BacktraceWalker, RootBacktrace};
+#[cfg(feature = "alloc")]
+pub use self::backtrace::{FullBacktrace, LimitedBacktrace, PrunedBacktrace};
 pub use self::context::{
-    Context, CoreContext, CoreExpected, CoreOperation, ExternalContext, Operation, WithChildContext,
+    Context, CoreContext, CoreExpected, CoreOperation, ExternalContext, Operation, OperationId,
+    WithChildContext,
+};
+#[cfg(feature = "alloc")]
+pub use self::expected::{BoxedExpected, OwnedExpected};
+pub use self::expected::{
+    ErrorKind, Expected, ExpectedLength, ExpectedValid, ExpectedValue, Reason,
 };
-pub use self::expected::{Expected, ExpectedLength, ExpectedValid, ExpectedValue};
 pub use self::fatal::Fatal;
+#[cfg(feature = "error-hook")]
+pub use self::hook::{set_error_hook, ErrorEvent, ErrorHook};
 pub use self::invalid::Invalid;
+pub use self::invalid_hint::InvalidHint;
 pub use self::length::Length;
+#[cfg(feature = "alloc")]
+pub use self::multi::MultiError;
+pub(crate) use self::position::span_range;
+pub use self::position::LineColumn;
+#[cfg(feature = "alloc")]
+pub use self::report::{Report, ReportContext, ReportValue};
 pub use self::retry::{RetryRequirement, ToRetryRequirement};
-pub use self::traits::{Details, Error, External, WithContext};
+pub use self::source::ExternalSource;
+pub use self::traits::{Details, Error, External, NoContext, WithContext};
 pub use self::value::Value;
 
-pub(crate) use self::context::with_context;
+pub(crate) use self::backtrace::backtrace_eq;
+pub(crate) use self::context::{context_key, with_context, written_eq};
+#[cfg(feature = "miette")]
+pub(crate) use self::expected::InputSpan;
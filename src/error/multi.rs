@@ -0,0 +1,212 @@
+use alloc::vec::{self, Vec};
+use core::iter::FromIterator;
+use core::slice;
+
+use crate::display::ErrorDisplay;
+use crate::fmt;
+use crate::input::Input;
+
+use super::{Details, WithContext};
+
+/// A collection of errors found while reading the same input.
+///
+/// Pairs with [`Reader::recover_into()`] to report every problem found in a
+/// document in one pass, instead of stopping at the first. Each error is
+/// displayed with its span against the input, in the order it was pushed.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::error::MultiError;
+/// use dangerous::{Expected, Input};
+///
+/// let input = dangerous::input(b"1,x,3,y");
+/// let mut errors = MultiError::<Expected<'_>>::new();
+/// let digits = input.clone().read_all(|r| {
+///     let mut digits = Vec::new();
+///     loop {
+///         digits.extend(r.recover_into(&mut errors, |r| {
+///             r.expect("digit", |r| r.read_opt().and_then(|b| (b as char).to_digit(10)))
+///         }));
+///         r.skip_until_opt(b',');
+///         if !r.consume_opt(b',') {
+///             return Ok(digits);
+///         }
+///     }
+/// });
+/// let errors = errors.with_input(input);
+///
+/// assert_eq!(digits.unwrap(), [1, 3]);
+/// assert_eq!(errors.len(), 2);
+/// assert!(errors.to_string().starts_with("error 1 of 2:"));
+/// ```
+///
+/// [`Reader::recover_into()`]: crate::Reader::recover_into()
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct MultiError<E> {
+    errors: Vec<E>,
+}
+
+impl<E> MultiError<E> {
+    /// Create a new empty `MultiError`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    /// Push an error onto the collection.
+    pub fn push(&mut self, error: E) {
+        self.errors.push(error);
+    }
+
+    /// Returns the number of errors collected.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns `true` if no errors were collected.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns an iterator over the errors collected.
+    pub fn iter(&self) -> slice::Iter<'_, E> {
+        self.errors.iter()
+    }
+
+    /// Returns the errors collected as a slice.
+    #[must_use]
+    pub fn as_slice(&self) -> &[E] {
+        &self.errors
+    }
+
+    /// Consumes `self` returning the errors collected.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<E> {
+        self.errors
+    }
+
+    /// Returns `Ok(value)` if no errors were collected, `Err(self)` if not.
+    ///
+    /// # Errors
+    ///
+    /// Returns `self` if any errors were collected.
+    pub fn into_result<T>(self, value: T) -> Result<T, Self> {
+        if self.is_empty() {
+            Ok(value)
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<'i, E> MultiError<E>
+where
+    E: WithContext<'i>,
+{
+    /// Returns `Self` with every error given the parent [`Input`].
+    ///
+    /// Errors recovered from deep within a read only know the input they
+    /// failed on. Providing the input of the whole document once reading is
+    /// complete has every error displayed against it.
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn with_input(self, input: impl Input<'i>) -> Self {
+        self.errors
+            .into_iter()
+            .map(|error| error.with_input(input.clone()))
+            .collect()
+    }
+}
+
+impl<E> Default for MultiError<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> From<Vec<E>> for MultiError<E> {
+    fn from(errors: Vec<E>) -> Self {
+        Self { errors }
+    }
+}
+
+impl<E> FromIterator<E> for MultiError<E> {
+    fn from_iter<T: IntoIterator<Item = E>>(iter: T) -> Self {
+        Self {
+            errors: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<E> Extend<E> for MultiError<E> {
+    fn extend<T: IntoIterator<Item = E>>(&mut self, iter: T) {
+        self.errors.extend(iter);
+    }
+}
+
+impl<E> IntoIterator for MultiError<E> {
+    type Item = E;
+    type IntoIter = vec::IntoIter<E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<'a, E> IntoIterator for &'a MultiError<E> {
+    type Item = &'a E;
+    type IntoIter = slice::Iter<'a, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.iter()
+    }
+}
+
+impl<'i, E> MultiError<E>
+where
+    E: Details<'i>,
+{
+    fn write_errors(&self, w: &mut dyn fmt::Write, str_hint: bool) -> fmt::Result {
+        let count = self.errors.len();
+        for (index, error) in self.errors.iter().enumerate() {
+            if index > 0 {
+                w.write_str("\n\n")?;
+            }
+            w.write_str("error ")?;
+            w.write_usize(index + 1)?;
+            w.write_str(" of ")?;
+            w.write_usize(count)?;
+            w.write_str(":\n")?;
+            let display = ErrorDisplay::new(error);
+            if str_hint {
+                fmt::DisplayBase::fmt(&display.str_hint(), w)?;
+            } else {
+                fmt::DisplayBase::fmt(&display, w)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'i, E> fmt::DisplayBase for MultiError<E>
+where
+    E: Details<'i>,
+{
+    fn fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        self.write_errors(w, false)
+    }
+}
+
+impl<'i, E> fmt::Display for MultiError<E>
+where
+    E: Details<'i>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str_hint = f.alternate();
+        self.write_errors(f, str_hint)
+    }
+}
@@ -0,0 +1,68 @@
+use core::ops::Range;
+
+use crate::fmt;
+use crate::input::MaybeString;
+use crate::util::fast;
+
+use super::Details;
+
+/// A line and column position within input.
+///
+/// Both the line and column start at `1`. Lines are separated by `\n`. For
+/// UTF-8 `str` input the column is counted in chars, otherwise it is counted
+/// in bytes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LineColumn {
+    /// The line number, starting at `1`.
+    pub line: usize,
+    /// The column number, starting at `1`.
+    pub column: usize,
+}
+
+/// Returns the range of the root span of an error within its input.
+pub(crate) fn span_range(details: &dyn Details<'_>) -> Option<Range<usize>> {
+    let input = details.input();
+    details.backtrace().root().span.range_of(input.span())
+}
+
+impl LineColumn {
+    /// Returns the position of the root span of an error within its input.
+    pub(crate) fn from_details(details: &dyn Details<'_>) -> Option<Self> {
+        span_range(details).map(|range| Self::from_offset(&details.input(), range.start))
+    }
+
+    pub(crate) fn from_offset(input: &MaybeString<'_>, offset: usize) -> Self {
+        let bytes = input.as_dangerous_bytes();
+        let before = bytes.get(..offset).unwrap_or(bytes);
+        let line_start = before
+            .iter()
+            .rposition(|b| *b == b'\n')
+            .map_or(0, |i| i + 1);
+        let line = &before[line_start..];
+        let column = if input.is_string() {
+            // Count the bytes that don't continue a UTF-8 code point.
+            line.iter().filter(|b| (**b & 0xC0) != 0x80).count()
+        } else {
+            line.len()
+        };
+        Self {
+            line: fast::count_u8(b'\n', before) + 1,
+            column: column + 1,
+        }
+    }
+}
+
+impl fmt::DisplayBase for LineColumn {
+    fn fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_str("line ")?;
+        w.write_usize(self.line)?;
+        w.write_str(", column ")?;
+        w.write_usize(self.column)
+    }
+}
+
+impl fmt::Display for LineColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::DisplayBase::fmt(self, f)
+    }
+}
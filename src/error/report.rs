@@ -0,0 +1,162 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::input::Span;
+use crate::util::fast;
+
+use super::{
+    Backtrace, Context, CoreOperation, Details, ErrorKind, Reason, RetryRequirement, Value,
+};
+
+/// A plain description of an [`Expected`] error, decoupled from formatting.
+///
+/// Created with [`Expected::to_report()`] or [`OwnedExpected::to_report()`].
+/// The JSON display and the `serde` implementations of the errors are both
+/// written from the report. All spans are converted to offsets from the start of the input, so the
+/// report does not borrow from the input and is suitable for handing to
+/// external diagnostic tooling.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::error::{CoreOperation, ErrorKind};
+/// use dangerous::{Expected, Input};
+///
+/// let error = dangerous::input(b"hello")
+///     .read_all::<_, _, Expected<'_>>(|r| r.context("greeting", |r| r.consume(b"world")))
+///     .unwrap_err();
+/// let report = error.to_report();
+///
+/// assert_eq!(report.kind, Some(ErrorKind::Value));
+/// assert_eq!(report.operation, CoreOperation::Consume);
+/// assert_eq!(report.span, Some(0..5));
+/// assert_eq!(report.input_len, 5);
+/// assert_eq!(report.contexts[1].expected.as_deref(), Some("greeting"));
+/// ```
+///
+/// [`Expected`]: crate::Expected
+/// [`Expected::to_report()`]: crate::Expected::to_report()
+/// [`OwnedExpected::to_report()`]: crate::error::OwnedExpected::to_report()
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct Report {
+    /// The kind of error, if known.
+    pub kind: Option<ErrorKind>,
+    /// The structured reason the input was not valid, if known.
+    pub reason: Option<Reason>,
+    /// The root operation that failed.
+    pub operation: CoreOperation,
+    /// The description of what went wrong.
+    pub description: String,
+    /// What the root operation expected, if known.
+    pub expected: Option<String>,
+    /// The exact value that was expected, if any.
+    pub expected_value: Option<ReportValue>,
+    /// The value found instead of what was expected, if known.
+    pub found_value: Option<ReportValue>,
+    /// The offsets of the input that caused the error, if within the input.
+    pub span: Option<Range<usize>>,
+    /// The length of the input.
+    pub input_len: usize,
+    /// The requirement to retry processing the input, if any.
+    pub retry_requirement: Option<RetryRequirement>,
+    /// The contexts collected, from the highest context to the root.
+    pub contexts: Vec<ReportContext>,
+}
+
+/// A [`Context`] within a [`Report`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct ReportContext {
+    /// The parent depth of the context, see [`BacktraceWalker`].
+    ///
+    /// [`BacktraceWalker`]: crate::error::BacktraceWalker
+    pub depth: usize,
+    /// The description of the operation.
+    pub operation: String,
+    /// What the context expected, if known.
+    pub expected: Option<String>,
+    /// The offsets of the input the context covers, if known.
+    pub span: Option<Range<usize>>,
+}
+
+/// A [`Value`] within a [`Report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub enum ReportValue {
+    /// A value of bytes.
+    Bytes(Vec<u8>),
+    /// A value known to be UTF-8.
+    String(String),
+}
+
+impl ReportValue {
+    pub(crate) fn from_value(value: Value<'_>) -> Self {
+        if value.is_str() {
+            if let Ok(value) = fast::from_utf8(value.as_bytes()) {
+                return Self::String(value.into());
+            }
+        }
+        Self::Bytes(value.as_bytes().into())
+    }
+
+    /// Returns the report value as a [`Value`].
+    pub fn as_value(&self) -> Value<'_> {
+        match self {
+            Self::Bytes(v) => Value::from(&v[..]),
+            Self::String(v) => Value::from(&v[..]),
+        }
+    }
+}
+
+impl Report {
+    pub(crate) fn from_details(details: &dyn Details<'_>) -> Self {
+        let input = details.input();
+        let input_span = input.span();
+        let root = details.backtrace().root();
+        let mut description = String::new();
+        // Failing to write the description leaves what was written.
+        let _ = details.description(&mut description);
+        Self {
+            kind: details.kind(),
+            reason: details.reason(),
+            operation: root.operation,
+            description,
+            expected: expected_of(&root),
+            expected_value: details.expected().map(ReportValue::from_value),
+            found_value: details.found().map(ReportValue::from_value),
+            span: root.span.range_of(input_span),
+            input_len: input.as_dangerous_bytes().len(),
+            retry_requirement: details.retry_requirement(),
+            contexts: contexts_of(details.backtrace(), input_span),
+        }
+    }
+}
+
+fn expected_of(context: &dyn Context) -> Option<String> {
+    if context.has_expected() {
+        let mut expected = String::new();
+        context.expected(&mut expected).ok().map(|()| expected)
+    } else {
+        None
+    }
+}
+
+fn contexts_of(backtrace: &dyn Backtrace, input: Span) -> Vec<ReportContext> {
+    let mut contexts = Vec::with_capacity(backtrace.count());
+    backtrace.walk(&mut |depth, context| {
+        let mut operation = String::new();
+        let _ = context.operation().description(&mut operation);
+        contexts.push(ReportContext {
+            depth,
+            operation,
+            expected: expected_of(context),
+            span: context.span().and_then(|span| span.range_of(input)),
+        });
+        true
+    });
+    contexts
+}
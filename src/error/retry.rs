@@ -8,9 +8,28 @@ use crate::fmt;
 /// Although the value allows you to estimate how much more input you need till
 /// you can continue processing the input, it is a very granular value and may
 /// result in a lot of wasted reprocessing of input if not handled correctly.
+///
+/// If the total length of the input is known ahead of time (for example from a
+/// length prefix), it can be attached as a hint with [`with_total_hint()`] so
+/// that streaming drivers can reserve and read everything they need at once.
+///
+/// A requirement is either for at least `continue_after` more bytes (the
+/// default), or, if [`is_exact()`] returns `true`, for exactly that many more.
+/// An exact requirement is known to satisfy the operation that failed, for
+/// example the body of a length-prefixed frame, so one precise read can be
+/// made. An at-least requirement comes from a token of unknown length, where
+/// more input may be needed after it arrives, so speculative reads may be
+/// preferred.
+///
+/// [`with_total_hint()`]: Self::with_total_hint()
+/// [`is_exact()`]: Self::is_exact()
 #[must_use]
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub struct RetryRequirement(NonZeroUsize);
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct RetryRequirement {
+    continue_after: NonZeroUsize,
+    total_hint: Option<NonZeroUsize>,
+    exact: bool,
+}
 
 impl RetryRequirement {
     /// Create a new `RetryRequirement`.
@@ -23,6 +42,28 @@ impl RetryRequirement {
         NonZeroUsize::new(value).map(Self::from_continue_after)
     }
 
+    /// Create a new exact `RetryRequirement`.
+    ///
+    /// If the provided value is `0`, this signifies processing can't be
+    /// retried. If the provided value is greater than `0`, this signifies the
+    /// exact amount of additional input bytes required to complete the
+    /// operation that failed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::error::RetryRequirement;
+    ///
+    /// let req = RetryRequirement::exactly(4).unwrap();
+    ///
+    /// assert!(req.is_exact());
+    /// assert_eq!(req.continue_after(), 4);
+    /// ```
+    #[must_use]
+    pub fn exactly(value: usize) -> Option<Self> {
+        Self::new(value).map(|req| req.with_exact(true))
+    }
+
     /// Create a retry requirement from a count of how many bytes we had and
     /// how many we needed.
     #[must_use]
@@ -33,7 +74,71 @@ impl RetryRequirement {
     /// Create a retry requirement from a count of how many bytes are required
     /// to continue processing input.
     pub fn from_continue_after(continue_after: NonZeroUsize) -> Self {
-        Self(continue_after)
+        Self {
+            continue_after,
+            total_hint: None,
+            exact: false,
+        }
+    }
+
+    /// Set whether the requirement is for exactly `continue_after` more bytes,
+    /// rather than at least.
+    pub fn with_exact(mut self, value: bool) -> Self {
+        self.exact = value;
+        self
+    }
+
+    /// Returns `true` if exactly [`continue_after()`] more bytes are required
+    /// to complete the operation that failed, or `false` if at least that
+    /// many are.
+    ///
+    /// [`continue_after()`]: Self::continue_after()
+    #[must_use]
+    pub fn is_exact(self) -> bool {
+        self.exact
+    }
+
+    /// Attach a hint of the total length of input expected, measured from the
+    /// start of the input being processed.
+    ///
+    /// If the provided value is `0`, any existing hint is removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::error::RetryRequirement;
+    ///
+    /// let req = RetryRequirement::new(1).unwrap().with_total_hint(1024);
+    ///
+    /// assert_eq!(req.continue_after(), 1);
+    /// assert_eq!(req.total_hint(), Some(1024));
+    /// assert_eq!(req.reserve_hint(512), 512);
+    /// ```
+    pub fn with_total_hint(mut self, total: usize) -> Self {
+        self.total_hint = NonZeroUsize::new(total);
+        self
+    }
+
+    /// The hint of the total length of input expected, if known.
+    #[must_use]
+    pub fn total_hint(self) -> Option<usize> {
+        self.total_hint.map(NonZeroUsize::get)
+    }
+
+    /// Returns how many additional bytes should be reserved given the length
+    /// of input already available.
+    ///
+    /// This is the greater of [`continue_after()`] and what remains of the
+    /// [`total_hint()`], if any.
+    ///
+    /// [`continue_after()`]: Self::continue_after()
+    /// [`total_hint()`]: Self::total_hint()
+    #[must_use]
+    pub fn reserve_hint(self, had: usize) -> usize {
+        match self.total_hint() {
+            Some(total) => total.saturating_sub(had).max(self.continue_after()),
+            None => self.continue_after(),
+        }
     }
 
     /// An indicator of how many bytes are required to continue processing input, if
@@ -44,13 +149,32 @@ impl RetryRequirement {
     /// result in a lot of wasted reprocessing of input if not handled correctly.
     #[must_use]
     pub fn continue_after(self) -> usize {
-        self.0.get()
+        self.continue_after.get()
     }
 
     /// Returns a `NonZeroUsize` wrapped variant of `continue_after`.
     #[must_use]
     pub fn continue_after_non_zero(self) -> NonZeroUsize {
-        self.0
+        self.continue_after
+    }
+}
+
+impl fmt::Debug for RetryRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.total_hint {
+            None if !self.exact => f
+                .debug_tuple("RetryRequirement")
+                .field(&self.continue_after)
+                .finish(),
+            total_hint => {
+                let mut debug = f.debug_struct("RetryRequirement");
+                debug.field("continue_after", &self.continue_after);
+                if let Some(total_hint) = total_hint {
+                    debug.field("total_hint", &total_hint);
+                }
+                debug.field("exact", &self.exact).finish()
+            }
+        }
     }
 }
 
@@ -83,12 +207,33 @@ pub trait ToRetryRequirement {
     fn is_fatal(&self) -> bool {
         self.to_retry_requirement().is_none()
     }
+
+    /// Returns `Self` with a hint of the total length of input expected
+    /// attached to its requirement, if it has one.
+    ///
+    /// Used by parsers that learn the total length before all of it is
+    /// available, such as from a length prefix. See
+    /// [`RetryRequirement::with_total_hint()`].
+    ///
+    /// Defaults to returning `self` unchanged, for errors that can't carry a
+    /// hint.
+    #[must_use]
+    fn with_total_hint(self, _total: usize) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
 }
 
 impl ToRetryRequirement for RetryRequirement {
     fn to_retry_requirement(&self) -> Option<RetryRequirement> {
         Some(*self)
     }
+
+    fn with_total_hint(self, total: usize) -> Self {
+        RetryRequirement::with_total_hint(self, total)
+    }
 }
 
 impl<T> ToRetryRequirement for Option<T>
@@ -103,4 +248,8 @@ where
     fn is_fatal(&self) -> bool {
         self.is_none()
     }
+
+    fn with_total_hint(self, total: usize) -> Self {
+        self.map(|err| err.with_total_hint(total))
+    }
 }
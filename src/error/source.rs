@@ -0,0 +1,143 @@
+use core::any::Any;
+
+use crate::display::write_display;
+use crate::fmt;
+use crate::input::Span;
+
+use super::{Context, External, Operation, Reason, RetryRequirement, WithContext};
+
+/// An [`External`] error carrying the error of a downstream conversion.
+///
+/// Without a source, an external error is reported only as `expected valid
+/// <value>`. Wrapping the error from a `TryFrom` conversion, a checksum
+/// library or similar with `ExternalSource` keeps its description in the
+/// backtrace, so the real reason for the failure is reported.
+///
+/// Any source that implements [`Display`](core::fmt::Display) can be used,
+/// including `Box<dyn std::error::Error + Send + Sync>`.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::error::ExternalSource;
+/// use dangerous::{Expected, Input};
+///
+/// let error = dangerous::input(b"abc")
+///     .read_all::<_, _, Expected<'_>>(|r| {
+///         r.try_external("small length", |i| {
+///             u8::try_from(i.len() * 100)
+///                 .map(|len| (i.len(), len))
+///                 .map_err(ExternalSource::new)
+///         })
+///     })
+///     .unwrap_err();
+///
+/// assert!(error
+///     .to_string()
+///     .contains("out of range integral type conversion attempted"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExternalSource<S> {
+    source: S,
+    span: Option<Span>,
+    reason: Option<Reason>,
+    retry_requirement: Option<RetryRequirement>,
+}
+
+impl<S> ExternalSource<S> {
+    /// Create a new `ExternalSource` given the source error.
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            span: None,
+            reason: None,
+            retry_requirement: None,
+        }
+    }
+
+    /// Set the section of input that caused the error.
+    ///
+    /// If not set, the whole input provided to the external operation is used.
+    #[must_use]
+    pub fn span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Set the structured reason the input was not valid.
+    #[must_use]
+    pub fn reason(mut self, reason: Reason) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+
+    /// Set the requirement to retry processing the input.
+    #[must_use]
+    pub fn retry_requirement(mut self, requirement: Option<RetryRequirement>) -> Self {
+        self.retry_requirement = requirement;
+        self
+    }
+
+    /// Returns a reference to the source error.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Consumes `self` returning the source error.
+    pub fn into_source(self) -> S {
+        self.source
+    }
+}
+
+impl<'i, S> External<'i> for ExternalSource<S>
+where
+    S: fmt::Display + Send + Sync + 'static,
+{
+    fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    fn retry_requirement(&self) -> Option<RetryRequirement> {
+        self.retry_requirement
+    }
+
+    fn reason(&self) -> Option<Reason> {
+        self.reason
+    }
+
+    fn push_backtrace<E>(self, error: E) -> E
+    where
+        E: WithContext<'i>,
+    {
+        error.with_context(SourceContext(self.source))
+    }
+}
+
+/// A context whose operation describes the source error.
+struct SourceContext<S>(S);
+
+impl<S> Context for SourceContext<S>
+where
+    S: fmt::Display + Send + Sync + 'static,
+{
+    fn operation(&self) -> &dyn Operation {
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl<S> Operation for SourceContext<S>
+where
+    S: fmt::Display + Send + Sync + 'static,
+{
+    fn description(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        write_display(w, &self.0)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -2,8 +2,8 @@ use crate::fmt;
 use crate::input::{Input, MaybeString, Span};
 
 use super::{
-    Backtrace, Context, ExpectedLength, ExpectedValid, ExpectedValue, RetryRequirement,
-    ToRetryRequirement, Value,
+    Backtrace, Context, ErrorKind, ExpectedLength, ExpectedValid, ExpectedValue, Reason,
+    RetryRequirement, ToRetryRequirement, Value,
 };
 
 /// Auto-trait for [`WithContext`], [`ToRetryRequirement`] and
@@ -37,6 +37,14 @@ pub trait WithContext<'i>: Sized {
     /// collected backtrace.
     const PASSTHROUGH: bool = false;
 
+    /// If `true` indicates the error discards both contexts and inputs.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// When set, the reader skips building contexts for the error altogether.
+    /// Errors setting this should also implement [`NoContext`].
+    const NO_CONTEXT: bool = false;
+
     /// Returns `Self` with a parent [`Input`].
     #[must_use]
     fn with_input(self, input: impl Input<'i>) -> Self;
@@ -48,6 +56,36 @@ pub trait WithContext<'i>: Sized {
     fn with_context(self, context: impl Context) -> Self;
 }
 
+/// Marker for errors that discard any provided contexts and inputs.
+///
+/// Implemented by [`Invalid`], [`InvalidHint`] and [`Fatal`], for which
+/// [`WithContext::NO_CONTEXT`] is `true`. Generic parsers can require this to
+/// guarantee the context machinery compiles away.
+///
+/// This trait is sealed so the guarantee holds, errors of your own can set
+/// [`WithContext::NO_CONTEXT`] instead.
+///
+/// [`Invalid`]: crate::Invalid
+/// [`InvalidHint`]: crate::error::InvalidHint
+/// [`Fatal`]: crate::error::Fatal
+pub trait NoContext<'i>: WithContext<'i> + NoContextPrivate {}
+
+/// Seals [`NoContext`] to the errors within the crate that discard contexts.
+pub trait NoContextPrivate {}
+
+impl NoContextPrivate for super::Invalid {}
+
+impl NoContextPrivate for super::InvalidHint {}
+
+impl NoContextPrivate for super::Fatal {}
+
+// Every sealed `NoContext` error must discard contexts.
+const _: () = assert!(
+    <super::Invalid as WithContext<'static>>::NO_CONTEXT
+        && <super::InvalidHint as WithContext<'static>>::NO_CONTEXT
+        && <super::Fatal as WithContext<'static>>::NO_CONTEXT
+);
+
 /// Required details around an error to produce a verbose report on what went
 /// wrong when processing input.
 ///
@@ -69,6 +107,34 @@ pub trait Details<'i> {
     /// The expected value, if applicable.
     fn expected(&self) -> Option<Value<'_>>;
 
+    /// The value found instead of what was expected, if known.
+    ///
+    /// Defaults to `None`.
+    fn found(&self) -> Option<Value<'_>> {
+        None
+    }
+
+    /// The kind of error, if known.
+    ///
+    /// Defaults to `None`.
+    fn kind(&self) -> Option<ErrorKind> {
+        None
+    }
+
+    /// The structured reason the input was not valid, if known.
+    ///
+    /// Defaults to `None`.
+    fn reason(&self) -> Option<Reason> {
+        None
+    }
+
+    /// The requirement to retry processing the input, if any.
+    ///
+    /// Defaults to `None`.
+    fn retry_requirement(&self) -> Option<RetryRequirement> {
+        None
+    }
+
     /// The description of what went wrong while processing the input.
     ///
     /// Descriptions should be simple and written in lowercase.
@@ -78,6 +144,14 @@ pub trait Details<'i> {
     /// Returns a [`fmt::Error`] if failed to write to the formatter.
     fn description(&self, w: &mut dyn fmt::Write) -> fmt::Result;
 
+    /// The parent input the input was derived from, along with the span of
+    /// the parent the input originated from, if linked.
+    ///
+    /// Defaults to `None`.
+    fn parent(&self) -> Option<(MaybeString<'i>, Span)> {
+        None
+    }
+
     /// The walkable [`Backtrace`] to the original context around the error
     /// that occurred.
     fn backtrace(&self) -> &dyn Backtrace;
@@ -109,6 +183,21 @@ pub trait External<'i>: Sized {
         None
     }
 
+    /// The structured reason the input was not valid, if known.
+    ///
+    /// Defaults to `None`.
+    fn reason(&self) -> Option<Reason> {
+        None
+    }
+
+    /// The value that was found instead of a valid one, if known.
+    ///
+    /// This can be a section of the input (the tag that was encountered), or
+    /// a description of it, rendered as `expected <value>, found <found>`.
+    fn found(&self) -> Option<Value<'i>> {
+        None
+    }
+
     /// Pushes a child backtrace to the base error generated.
     ///
     /// Push from the bottom of the trace (from the source of the error) up.
@@ -30,16 +30,30 @@ impl<'i> Value<'i> {
         }
     }
 
+    /// Returns `true` if the value is known to be UTF-8.
+    pub(crate) fn is_str(&self) -> bool {
+        matches!(self.0, ValueInner::Char(_) | ValueInner::String(_))
+    }
+
     /// Returns an [`InputDisplay`] for formatting.
     pub fn display(&self) -> InputDisplay<'_> {
         let display = Bytes::new(self.as_bytes(), Bound::StartEnd).display();
-        match self.0 {
-            ValueInner::Byte(_) | ValueInner::Bytes(_) => display,
-            ValueInner::Char(_) | ValueInner::String(_) => display.str_hint(),
+        if self.is_str() {
+            display.str_hint()
+        } else {
+            display
         }
     }
 }
 
+impl PartialEq for Value<'_> {
+    /// Compares the bytes of the values and whether they are known to be
+    /// UTF-8.
+    fn eq(&self, other: &Self) -> bool {
+        self.is_str() == other.is_str() && self.as_bytes() == other.as_bytes()
+    }
+}
+
 impl<'i> fmt::Debug for Value<'i> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let name = match self.0 {
@@ -0,0 +1,178 @@
+//! Fuzzing and property testing support.
+//!
+//! Parsers are most likely to go wrong close to the edges of a format: a
+//! length prefix that is one off, a field cut short or trailing input after
+//! the end. A [`Mutation`] makes one such edit to an otherwise valid encoding,
+//! so structured inputs can be generated valid and then mutated, instead of
+//! hoping random bytes happen to get past the first field.
+//!
+//! With the `arbitrary` feature, [`Bytes`], [`String`] and [`Mutation`]
+//! implement `arbitrary::Arbitrary`. With the `proptest` feature,
+//! `mutation()` and `mutated()` provide the equivalent strategies.
+//!
+//! # Example
+//!
+//! ```
+//! use dangerous::fuzz::Mutation;
+//!
+//! let mut bytes = vec![3, b'a', b'b', b'c'];
+//! Mutation::Adjust(0, 1).apply(&mut bytes);
+//! Mutation::Truncate(3).apply(&mut bytes);
+//!
+//! assert_eq!(bytes, [4, b'a', b'b']);
+//! ```
+//!
+//! [`Bytes`]: crate::Bytes
+//! [`String`]: crate::String
+
+use alloc::vec::Vec;
+
+/// An edit made to bytes to create an input close to a valid one.
+///
+/// Offsets wrap around the length of the bytes, so any mutation can be
+/// applied to any bytes without panicking. Mutations of an offset are ignored
+/// if the bytes are empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Mutation {
+    /// Cut the bytes short at an offset.
+    Truncate(usize),
+    /// Add to the byte at an offset, wrapping on overflow.
+    ///
+    /// Adjusting by one either side of a length or count is the most likely
+    /// way to find an off by one error.
+    Adjust(usize, i8),
+    /// Replace the byte at an offset.
+    Replace(usize, u8),
+    /// Insert a byte at an offset.
+    Insert(usize, u8),
+    /// Remove the byte at an offset.
+    Remove(usize),
+    /// Append bytes after the end.
+    Append(Vec<u8>),
+}
+
+impl Mutation {
+    /// Apply the mutation to the bytes.
+    pub fn apply(&self, bytes: &mut Vec<u8>) {
+        match *self {
+            Self::Truncate(offset) => bytes.truncate(offset % (bytes.len() + 1)),
+            Self::Adjust(offset, amount) => {
+                if let Some(byte) = byte_at(bytes, offset) {
+                    *byte = byte.wrapping_add(u8::from_ne_bytes(amount.to_ne_bytes()));
+                }
+            }
+            Self::Replace(offset, value) => {
+                if let Some(byte) = byte_at(bytes, offset) {
+                    *byte = value;
+                }
+            }
+            Self::Insert(offset, value) => bytes.insert(offset % (bytes.len() + 1), value),
+            Self::Remove(offset) => {
+                if !bytes.is_empty() {
+                    bytes.remove(offset % bytes.len());
+                }
+            }
+            Self::Append(ref tail) => bytes.extend_from_slice(tail),
+        }
+    }
+}
+
+fn byte_at(bytes: &mut [u8], offset: usize) -> Option<&mut u8> {
+    if bytes.is_empty() {
+        None
+    } else {
+        let len = bytes.len();
+        bytes.get_mut(offset % len)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// arbitrary support
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Mutation {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=5)? {
+            0 => Self::Truncate(u.arbitrary()?),
+            1 => Self::Adjust(u.arbitrary()?, u.arbitrary()?),
+            2 => Self::Replace(u.arbitrary()?, u.arbitrary()?),
+            3 => Self::Insert(u.arbitrary()?, u.arbitrary()?),
+            4 => Self::Remove(u.arbitrary()?),
+            _ => Self::Append(u.arbitrary()?),
+        })
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// proptest support
+
+#[cfg(all(feature = "proptest", feature = "std"))]
+mod strategy {
+    use alloc::vec::Vec;
+
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    use super::Mutation;
+
+    /// A strategy generating a single [`Mutation`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+    pub fn mutation() -> impl Strategy<Value = Mutation> {
+        prop_oneof![
+            any::<usize>().prop_map(Mutation::Truncate),
+            (any::<usize>(), prop_oneof![Just(-1), Just(1), any::<i8>()])
+                .prop_map(|(offset, amount)| Mutation::Adjust(offset, amount)),
+            (any::<usize>(), any::<u8>())
+                .prop_map(|(offset, value)| Mutation::Replace(offset, value)),
+            (any::<usize>(), any::<u8>())
+                .prop_map(|(offset, value)| Mutation::Insert(offset, value)),
+            any::<usize>().prop_map(Mutation::Remove),
+            vec(any::<u8>(), 1..8).prop_map(Mutation::Append),
+        ]
+    }
+
+    /// A strategy generating valid encodings with up to a few mutations
+    /// applied.
+    ///
+    /// Unmutated encodings are generated too, so the same property can check
+    /// both that valid input is read and that invalid input is not panicked
+    /// on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    /// use proptest::prelude::*;
+    ///
+    /// fn encode(value: &[u8]) -> Vec<u8> {
+    ///     let mut bytes = vec![value.len() as u8];
+    ///     bytes.extend_from_slice(value);
+    ///     bytes
+    /// }
+    ///
+    /// let valid = prop::collection::vec(any::<u8>(), 0..16).prop_map(|value| encode(&value));
+    ///
+    /// proptest!(|(bytes in dangerous::fuzz::mutated(valid))| {
+    ///     let _ = dangerous::input(bytes.as_slice()).read_all::<_, _, Invalid>(|r| {
+    ///         let len = r.read()?;
+    ///         r.take(len.into())
+    ///     });
+    /// });
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+    pub fn mutated<S>(valid: S) -> impl Strategy<Value = Vec<u8>>
+    where
+        S: Strategy<Value = Vec<u8>>,
+    {
+        (valid, vec(mutation(), 0..4)).prop_map(|(mut bytes, mutations)| {
+            for mutation in &mutations {
+                mutation.apply(&mut bytes);
+            }
+            bytes
+        })
+    }
+}
+
+#[cfg(all(feature = "proptest", feature = "std"))]
+pub use self::strategy::{mutated, mutation};
@@ -0,0 +1,252 @@
+//! Recursive descent grammars built from named rules.
+//!
+//! A [`Rule`] pairs a name with the function that reads it. Reading a rule
+//! attaches a [`RuleContext`] with its name, so an error carries the stack of
+//! rules that were being read when it happened. Rules that can contain
+//! themselves are created with [`Rule::recursive()`], which guards each level
+//! with [`Reader::enter()`] so deeply nested input fails with
+//! [`Reason::DepthExceeded`] instead of overflowing the stack.
+//!
+//! # Example
+//!
+//! ```
+//! use dangerous::error::Details;
+//! use dangerous::grammar::{self, Rule};
+//! use dangerous::{Bytes, Expected, Input};
+//!
+//! type SumRule<'i> = Rule<'i, Bytes<'i>, u32, Expected<'i>>;
+//!
+//! fn value<'i>() -> SumRule<'i> {
+//!     Rule::new("value", |r| grammar::choice(r, "digit or list", &[digit(), list()]))
+//! }
+//!
+//! fn digit<'i>() -> SumRule<'i> {
+//!     Rule::new("digit", |r| r.try_expect("digit", |r| Ok(char::from(r.read()?).to_digit(10))))
+//! }
+//!
+//! fn list<'i>() -> SumRule<'i> {
+//!     Rule::recursive("list", |r| {
+//!         r.consume(b'[')?;
+//!         let mut sum = 0;
+//!         while !r.peek_eq(b']') {
+//!             sum += value().read(r)?;
+//!             r.consume_opt(b',');
+//!         }
+//!         r.consume(b']')?;
+//!         Ok(sum)
+//!     })
+//! }
+//!
+//! assert_eq!(value().parse(dangerous::input(b"[1,[2,3],4]"))?, 10);
+//!
+//! let error = value().parse(dangerous::input(b"[1,[x]]")).unwrap_err();
+//! let rules = error
+//!     .backtrace()
+//!     .contexts_of::<grammar::RuleContext>()
+//!     .into_iter()
+//!     .map(|context| context.name)
+//!     .collect::<Vec<_>>();
+//!
+//! assert_eq!(rules, ["value", "list", "value", "list", "value"]);
+//! # Ok::<(), Expected<'static>>(())
+//! ```
+//!
+//! [`Reader::enter()`]: crate::Reader::enter()
+//! [`Reason::DepthExceeded`]: crate::error::Reason::DepthExceeded
+
+use core::any::Any;
+use core::cell::Cell;
+
+use crate::error::{
+    Context, CoreOperation, ExpectedLength, ExpectedValid, Operation, ToRetryRequirement,
+    WithContext,
+};
+use crate::fmt;
+use crate::input::Input;
+use crate::reader::Reader;
+
+/// A named rule of a grammar.
+///
+/// See the [module documentation](self) for an example.
+pub struct Rule<'i, I, T, E> {
+    name: &'static str,
+    recursive: bool,
+    read: fn(&mut Reader<'i, I, E>) -> Result<T, E>,
+}
+
+impl<'i, I, T, E> Rule<'i, I, T, E>
+where
+    I: Input<'i>,
+{
+    /// Creates a rule with a name and the function that reads it.
+    #[must_use]
+    pub fn new(name: &'static str, read: fn(&mut Reader<'i, I, E>) -> Result<T, E>) -> Self {
+        Self {
+            name,
+            recursive: false,
+            read,
+        }
+    }
+
+    /// Creates a rule that may contain itself.
+    ///
+    /// Each time the rule is read it enters a level of nesting, failing once
+    /// the maximum depth of the [`Reader`] is reached.
+    ///
+    /// [`Reader`]: crate::Reader
+    #[must_use]
+    pub fn recursive(name: &'static str, read: fn(&mut Reader<'i, I, E>) -> Result<T, E>) -> Self {
+        Self {
+            name,
+            recursive: true,
+            read,
+        }
+    }
+
+    /// Returns the name of the rule.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns `true` if the rule was created with [`Rule::recursive()`].
+    #[must_use]
+    pub fn is_recursive(&self) -> bool {
+        self.recursive
+    }
+
+    /// Read the rule from a reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the rule with a [`RuleContext`]
+    /// attached, or [`ExpectedValid`] if the rule is recursive and the
+    /// maximum depth was reached.
+    pub fn read(&self, r: &mut Reader<'i, I, E>) -> Result<T, E>
+    where
+        E: WithContext<'i>,
+        E: From<ExpectedValid<'i>>,
+    {
+        let context = RuleContext { name: self.name };
+        if self.recursive {
+            r.enter(context, self.read)
+        } else {
+            r.context(context, self.read)
+        }
+    }
+
+    /// Read the rule from a reader, returning `None` and resetting the reader
+    /// if it failed.
+    pub fn read_opt(&self, r: &mut Reader<'i, I, E>) -> Option<T>
+    where
+        E: WithContext<'i>,
+        E: From<ExpectedValid<'i>>,
+    {
+        r.recover(|r| self.read(r))
+    }
+
+    /// Read the rule from all of the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the rule, or [`ExpectedLength`] if the
+    /// rule did not read all of the input.
+    pub fn parse(&self, input: I) -> Result<T, E>
+    where
+        E: WithContext<'i>,
+        E: From<ExpectedValid<'i>>,
+        E: From<ExpectedLength<'i>>,
+    {
+        input.read_all(|r| self.read(r))
+    }
+}
+
+impl<I, T, E> Clone for Rule<'_, I, T, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I, T, E> Copy for Rule<'_, I, T, E> {}
+
+impl<I, T, E> fmt::Debug for Rule<'_, I, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rule")
+            .field("name", &self.name)
+            .field("recursive", &self.recursive)
+            .finish()
+    }
+}
+
+/// Read the first of a set of rules that succeeds.
+///
+/// Each rule is tried in order, with the reader reset after a rule fails
+/// without reading any input. Once a rule has read input it is committed to,
+/// so its error points at where the input went wrong rather than at the start
+/// of the choice. A rule failing because it needs more input is not skipped
+/// either, as a later rule can't be chosen until it is known whether the
+/// earlier one matches.
+///
+/// # Errors
+///
+/// Returns [`ExpectedValid`] if no rule succeeded, the error of a committed
+/// rule or one that needs more input, or the error of a recursive rule if the
+/// maximum depth was reached.
+pub fn choice<'i, I, T, E>(
+    r: &mut Reader<'i, I, E>,
+    expected: &'static str,
+    rules: &[Rule<'i, I, T, E>],
+) -> Result<T, E>
+where
+    I: Input<'i>,
+    E: WithContext<'i>,
+    E: From<ExpectedValid<'i>>,
+    E: ToRetryRequirement,
+{
+    r.try_expect(expected, |r| {
+        for rule in rules {
+            if rule.recursive && r.at_max_depth() {
+                return rule.read(r).map(Some);
+            }
+            let start = r.remaining_bytes();
+            let committed = Cell::new(false);
+            let value = r.recover_if(
+                |r| {
+                    let value = rule.read(r);
+                    committed.set(r.remaining_bytes() != start);
+                    value
+                },
+                |err| err.is_fatal() && !committed.get(),
+            )?;
+            if value.is_some() {
+                return Ok(value);
+            }
+        }
+        Ok(None)
+    })
+}
+
+/// The [`Context`] of a [`Rule`] being read.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RuleContext {
+    /// The name of the rule.
+    pub name: &'static str,
+}
+
+impl Context for RuleContext {
+    fn operation(&self) -> &dyn Operation {
+        &CoreOperation::Rule
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn has_expected(&self) -> bool {
+        true
+    }
+
+    fn expected(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_str(self.name)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::input::{Bytes, Pattern};
+use crate::util::fast;
+
+/// A simple byte predicate that can be searched for as a [`Pattern`].
+///
+/// Unlike a closure, the search knows the shape of the predicate and checks
+/// a machine word of input at a time, which is considerably faster over long
+/// runs of input on targets without SIMD.
+///
+/// As a range may cover bytes that are part of a UTF-8 char, byte patterns can
+/// only be searched for in [`Bytes`].
+///
+/// # Example
+///
+/// ```
+/// use dangerous::input::BytePattern;
+/// use dangerous::{Input, Invalid};
+///
+/// let (digits, rest) = dangerous::input(b"0042 rest").read_all::<_, _, Invalid>(|r| {
+///     r.skip_while(BytePattern::Eq(b'0'));
+///     let digits = r.take_while(BytePattern::Range(b'0', b'9'));
+///     r.skip(1)?;
+///     Ok((digits, r.take_remaining()))
+/// })?;
+///
+/// assert_eq!(digits, b"42"[..]);
+/// assert_eq!(rest, b"rest"[..]);
+/// # Ok::<(), Invalid>(())
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BytePattern {
+    /// Bytes equal to the value.
+    Eq(u8),
+    /// Bytes within the inclusive range of the start and end values.
+    ///
+    /// A range with a start after its end is empty.
+    Range(u8, u8),
+}
+
+impl BytePattern {
+    /// Returns `true` if the byte is matched by the pattern.
+    #[must_use]
+    #[inline]
+    pub fn matches(self, byte: u8) -> bool {
+        let (start, end) = self.bounds();
+        start <= byte && byte <= end
+    }
+
+    #[inline(always)]
+    fn bounds(self) -> (u8, u8) {
+        match self {
+            Self::Eq(byte) => (byte, byte),
+            Self::Range(start, end) => (start, end),
+        }
+    }
+}
+
+unsafe impl<'i> Pattern<Bytes<'i>> for BytePattern {
+    fn find_match(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        let (start, end) = self.bounds();
+        fast::find_range_match(start, end, input.as_dangerous()).map(|index| (index, 1))
+    }
+
+    fn find_reject(self, input: &Bytes<'i>) -> Option<usize> {
+        let (start, end) = self.bounds();
+        fast::find_range_reject(start, end, input.as_dangerous())
+    }
+}
@@ -2,13 +2,18 @@ mod array;
 mod pattern;
 mod prefix;
 
+#[cfg(all(feature = "encoding_rs", feature = "alloc"))]
+use alloc::{borrow::Cow, string::String as StringBuf};
 use core::slice::Iter as SliceIter;
 use core::{iter, str};
 
+#[cfg(all(feature = "encoding_rs", feature = "alloc"))]
+use encoding_rs::{DecoderResult, Encoding};
+
 use crate::display::InputDisplay;
 use crate::error::{
-    with_context, CoreContext, CoreExpected, CoreOperation, ExpectedLength, ExpectedValid, Length,
-    WithContext,
+    hook, with_context, CoreContext, CoreExpected, CoreOperation, ExpectedLength, ExpectedValid,
+    ExpectedValue, Length, Reason, Value, WithContext,
 };
 use crate::fmt;
 use crate::util::{fast, slice, utf8};
@@ -21,6 +26,13 @@ pub use self::array::ByteArray;
 #[derive(Clone)]
 #[must_use = "input must be consumed"]
 pub struct Bytes<'i> {
+    // NOTE: `Bound` makes this three words rather than two. It can't be
+    // folded into the slice: a `u8` pointer has no alignment bits to spare
+    // and a slice length only guarantees its top bit is unused, while the
+    // three states of `Bound` need two. Packing it anyway would cap inputs at
+    // a quarter of the address space (1 GiB on 32-bit targets, reachable with
+    // a memory map) and replace the slice with raw parts, so the field is
+    // kept as is.
     value: &'i [u8],
     bound: Bound,
 }
@@ -46,13 +58,28 @@ impl<'i> Bytes<'i> {
 
     /// Returns the occurrences of `needle` within the underlying byte slice.
     ///
-    /// It is recommended to enable the `bytecount` dependency when using this
-    /// function for better performance.
+    /// The input is counted a machine word at a time, or with the `simd`
+    /// feature, with the widest SIMD instructions detected at runtime.
     #[must_use]
     pub fn count(&self, needle: u8) -> usize {
         fast::count_u8(needle, self.as_dangerous())
     }
 
+    /// Returns the occurrences of any of `needles` within the underlying byte
+    /// slice, in a single pass over it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let input = dangerous::input(b"a,b;c,d");
+    ///
+    /// assert_eq!(input.count_any(b",;"), 3);
+    /// ```
+    #[must_use]
+    pub fn count_any<const N: usize>(&self, needles: &[u8; N]) -> usize {
+        fast::count_any_u8(needles, self.as_dangerous())
+    }
+
     /// Returns the underlying byte slice.
     ///
     /// The naming of this function is to a degree hyperbole, and should not be
@@ -79,7 +106,7 @@ impl<'i> Bytes<'i> {
         E: From<ExpectedValid<'i>>,
         E: From<ExpectedLength<'i>>,
     {
-        str::from_utf8(self.as_dangerous()).map_err(|err| {
+        fast::from_utf8(self.as_dangerous()).map_err(|err| {
             self.clone().map_utf8_error(
                 err.error_len(),
                 err.valid_up_to(),
@@ -87,6 +114,41 @@ impl<'i> Bytes<'i> {
             )
         })
     }
+
+    /// Decodes the underlying byte slice from a text encoding into a `str`.
+    ///
+    /// The input is borrowed if the encoding is UTF-8, or if it is ASCII
+    /// compatible and the input is all ASCII. Otherwise it is decoded into an
+    /// owned string. No BOM is sniffed or stripped.
+    ///
+    /// See [`Bytes::as_dangerous`] for naming.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Invalid;
+    /// use encoding_rs::SHIFT_JIS;
+    ///
+    /// let input = dangerous::input(b"\x82\xb1\x82\xf1\x82\xc9\x82\xbf\x82\xcd");
+    ///
+    /// assert_eq!(input.to_dangerous_str_with::<Invalid>(SHIFT_JIS)?, "こんにちは");
+    /// # Ok::<(), Invalid>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValid`] spanning the malformed sequence if the input
+    /// is not valid in the encoding. For UTF-8 the errors are the same as
+    /// [`Bytes::to_dangerous_str()`].
+    #[cfg(all(feature = "encoding_rs", feature = "alloc"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "encoding_rs", feature = "alloc"))))]
+    pub fn to_dangerous_str_with<E>(&self, encoding: &'static Encoding) -> Result<Cow<'i, str>, E>
+    where
+        E: From<ExpectedValid<'i>>,
+        E: From<ExpectedLength<'i>>,
+    {
+        self.clone().decode_str(encoding, CoreOperation::IntoString)
+    }
 }
 
 impl<'i> Input<'i> for Bytes<'i> {
@@ -143,6 +205,21 @@ impl<'i> Input<'i> for Bytes<'i> {
 ///////////////////////////////////////////////////////////////////////////////
 
 impl<'i> Bytes<'i> {
+    #[inline(always)]
+    pub(crate) fn split_str_remaining<E>(
+        self,
+        operation: CoreOperation,
+    ) -> Result<(String<'i>, Bytes<'i>), E>
+    where
+        E: From<ExpectedValid<'i>>,
+        E: From<ExpectedLength<'i>>,
+    {
+        match fast::from_utf8(self.as_dangerous()) {
+            Ok(s) => Ok((String::new(s, self.bound()), self.end())),
+            Err(err) => Err(self.map_utf8_error(err.error_len(), err.valid_up_to(), operation)),
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn split_str_while<F, E>(
         self,
@@ -262,7 +339,129 @@ impl<'i> Bytes<'i> {
         })
     }
 
-    fn map_utf8_error<E>(
+    /// Returns the input from a found match onwards, with the bound as is.
+    ///
+    /// Unlike a split, the skipped input is never constructed.
+    #[inline(always)]
+    pub(crate) fn skip_to_match<E>(
+        self,
+        index: Option<usize>,
+        expected: Value<'i>,
+        operation: CoreOperation,
+    ) -> Result<Bytes<'i>, E>
+    where
+        E: From<ExpectedValue<'i>>,
+    {
+        match index.and_then(|index| self.value.get(index..)) {
+            Some(tail) => Ok(Bytes::new(tail, self.bound)),
+            None => Err(hook::create(ExpectedValue {
+                expected,
+                context: CoreContext {
+                    span: self.span(),
+                    operation,
+                    expected: CoreExpected::PatternMatch,
+                },
+                input: self.into_maybe_string(),
+            })),
+        }
+    }
+
+    #[cfg(feature = "bytemuck")]
+    pub(crate) fn split_pod_slice<T, E>(
+        self,
+        count: usize,
+        operation: CoreOperation,
+    ) -> Result<(&'i [T], Bytes<'i>), E>
+    where
+        T: bytemuck::Pod,
+        E: From<ExpectedValid<'i>>,
+        E: From<ExpectedLength<'i>>,
+    {
+        // An overflowing length could never be met, so saturating it still
+        // returns a length error.
+        let len = count.saturating_mul(core::mem::size_of::<T>());
+        let (head, tail) = self.split_at_for::<E>(len, operation)?;
+        // Nothing is reinterpreted when empty, so alignment does not matter.
+        if head.is_empty() {
+            return Ok((&[], tail));
+        }
+        match bytemuck::try_cast_slice(head.as_dangerous()) {
+            Ok(values) => Ok((values, tail)),
+            Err(_) => Err(hook::create(ExpectedValid {
+                retry_requirement: None,
+                reason: Some(Reason::Misaligned),
+                found: None,
+                context: CoreContext {
+                    span: head.span(),
+                    operation,
+                    expected: CoreExpected::Valid("aligned slice"),
+                },
+                input: head.into_maybe_string(),
+            })),
+        }
+    }
+
+    #[cfg(all(feature = "encoding_rs", feature = "alloc"))]
+    pub(crate) fn decode_str<E>(
+        self,
+        encoding: &'static Encoding,
+        operation: CoreOperation,
+    ) -> Result<Cow<'i, str>, E>
+    where
+        E: From<ExpectedValid<'i>>,
+        E: From<ExpectedLength<'i>>,
+    {
+        let bytes = self.as_dangerous();
+        if encoding == encoding_rs::UTF_8 {
+            return fast::from_utf8(bytes).map(Cow::Borrowed).map_err(|err| {
+                self.clone()
+                    .map_utf8_error(err.error_len(), err.valid_up_to(), operation)
+            });
+        }
+        // ASCII is the same in any ASCII compatible encoding and UTF-8.
+        if encoding.is_ascii_compatible() && bytes.is_ascii() {
+            if let Ok(s) = fast::from_utf8(bytes) {
+                return Ok(Cow::Borrowed(s));
+            }
+        }
+        let mut decoder = encoding.new_decoder_without_bom_handling();
+        let mut output = StringBuf::new();
+        let mut read = 0;
+        loop {
+            let src = bytes.get(read..).unwrap_or_default();
+            output.reserve(
+                decoder
+                    .max_utf8_buffer_length_without_replacement(src.len())
+                    .unwrap_or(src.len()),
+            );
+            let (result, len) =
+                decoder.decode_to_string_without_replacement(src, &mut output, true);
+            read += len;
+            match result {
+                DecoderResult::InputEmpty => return Ok(Cow::Owned(output)),
+                DecoderResult::OutputFull => {}
+                DecoderResult::Malformed(bad_len, good_len) => {
+                    // The malformed sequence is followed by the good bytes
+                    // that were read to find where it ended.
+                    let end = read.saturating_sub(usize::from(good_len));
+                    let start = end.saturating_sub(usize::from(bad_len));
+                    return Err(hook::create(ExpectedValid {
+                        retry_requirement: None,
+                        reason: Some(Reason::InvalidEncoding),
+                        found: None,
+                        context: CoreContext {
+                            span: bytes.get(start..end).unwrap_or(bytes).into(),
+                            operation,
+                            expected: CoreExpected::Valid(encoding.name()),
+                        },
+                        input: self.into_maybe_string(),
+                    }));
+                }
+            }
+        }
+    }
+
+    pub(crate) fn map_utf8_error<E>(
         self,
         error_len: Option<usize>,
         valid_up_to: usize,
@@ -280,7 +479,7 @@ impl<'i> Bytes<'i> {
                 // least one byte in an invalid codepoint) so it is safe to
                 // get without checking bounds.
                 let first_invalid = unsafe { slice::first_unchecked(invalid) };
-                E::from(ExpectedLength {
+                hook::create(ExpectedLength {
                     len: Length::AtLeast(utf8::char_len(first_invalid)),
                     context: CoreContext {
                         span: invalid.into(),
@@ -292,8 +491,10 @@ impl<'i> Bytes<'i> {
             }
             Some(error_len) => {
                 let error_end = valid_up_to + error_len;
-                E::from(ExpectedValid {
+                hook::create(ExpectedValid {
                     retry_requirement: None,
+                    reason: Some(Reason::InvalidUtf8),
+                    found: None,
                     context: CoreContext {
                         span: bytes[valid_up_to..error_end].into(),
                         operation,
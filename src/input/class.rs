@@ -0,0 +1,87 @@
+use crate::input::{Bytes, Pattern, String};
+use crate::util::fast;
+
+/// A class of ASCII characters that can be searched for as a [`Pattern`].
+///
+/// Skipping and taking over a class is common in text protocols, such as
+/// skipping whitespace between tokens or taking the digits of a number. With
+/// the `simd` feature, classes are searched 16 bytes at a time on `x86_64`.
+///
+/// Only ASCII characters are within a class, so a class may be searched for
+/// in both [`Bytes`] and [`String`].
+///
+/// # Example
+///
+/// ```
+/// use dangerous::input::AsciiClass;
+/// use dangerous::{Input, Invalid};
+///
+/// let (method, path) = dangerous::input(b"GET   /index.html").read_all::<_, _, Invalid>(|r| {
+///     let method = r.take_while(AsciiClass::Token);
+///     r.skip_while(AsciiClass::Whitespace);
+///     Ok((method, r.take_remaining()))
+/// })?;
+///
+/// assert_eq!(method, b"GET"[..]);
+/// assert_eq!(path, b"/index.html"[..]);
+/// # Ok::<(), Invalid>(())
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AsciiClass {
+    /// ASCII whitespace, as defined by [`u8::is_ascii_whitespace()`].
+    Whitespace,
+    /// ASCII decimal digits `0-9`.
+    Digit,
+    /// ASCII hexadecimal digits `0-9`, `a-f` and `A-F`.
+    HexDigit,
+    /// ASCII letters `a-z` and `A-Z`.
+    Alphabetic,
+    /// ASCII letters and decimal digits.
+    Alphanumeric,
+    /// Token characters as defined by HTTP (`tchar` in RFC 7230), being
+    /// ASCII letters, decimal digits and ``!#$%&'*+-.^_`|~``.
+    Token,
+}
+
+impl AsciiClass {
+    /// Returns `true` if the byte is within the class.
+    #[must_use]
+    #[inline]
+    pub fn matches(self, byte: u8) -> bool {
+        match self {
+            Self::Whitespace => byte.is_ascii_whitespace(),
+            Self::Digit => byte.is_ascii_digit(),
+            Self::HexDigit => byte.is_ascii_hexdigit(),
+            Self::Alphabetic => byte.is_ascii_alphabetic(),
+            Self::Alphanumeric => byte.is_ascii_alphanumeric(),
+            Self::Token => byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Pattern
+
+unsafe impl<'i> Pattern<Bytes<'i>> for AsciiClass {
+    fn find_match(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        fast::find_class_match(self, input.as_dangerous()).map(|index| (index, 1))
+    }
+
+    fn find_reject(self, input: &Bytes<'i>) -> Option<usize> {
+        fast::find_class_reject(self, input.as_dangerous())
+    }
+}
+
+// A match is always an ASCII char, and a reject is either an ASCII char or the
+// first byte of a non-ASCII char, as continuation bytes are never in a class.
+// Both are char boundaries.
+unsafe impl<'i> Pattern<String<'i>> for AsciiClass {
+    fn find_match(self, input: &String<'i>) -> Option<(usize, usize)> {
+        fast::find_class_match(self, input.as_dangerous().as_bytes()).map(|index| (index, 1))
+    }
+
+    fn find_reject(self, input: &String<'i>) -> Option<usize> {
+        fast::find_class_reject(self, input.as_dangerous().as_bytes())
+    }
+}
@@ -0,0 +1,143 @@
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use memmap2::{Mmap, MmapOptions};
+
+use crate::fmt;
+
+use super::{Bound, Bytes};
+
+/// Memory maps a file for use as [`Input`](crate::Input).
+///
+/// This is shorthand for [`MappedFile::open()`].
+///
+/// # Errors
+///
+/// Returns an error if the file could not be opened or mapped.
+///
+/// # Safety
+///
+/// See [`MappedFile::open()`].
+///
+/// # Example
+///
+/// ```no_run
+/// use dangerous::{Input, Invalid};
+///
+/// let file = unsafe { dangerous::input_file("data.bin")? };
+/// let magic: Result<_, Invalid> = file.input().read_partial(|r| r.take_array::<4>());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "memmap2")))]
+pub unsafe fn input_file(path: impl AsRef<Path>) -> io::Result<MappedFile> {
+    MappedFile::open(path)
+}
+
+/// A memory mapped file that can be processed as [`Input`](crate::Input)
+/// without reading it into memory.
+///
+/// The file path and the offset the mapping starts at within the file are kept
+/// alongside the mapping, so positions within the input can be related back to
+/// the source.
+#[cfg_attr(docsrs, doc(cfg(feature = "memmap2")))]
+pub struct MappedFile {
+    map: Mmap,
+    path: PathBuf,
+    offset: u64,
+}
+
+impl MappedFile {
+    /// Memory maps the whole file at the given path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be opened or mapped.
+    ///
+    /// # Safety
+    ///
+    /// The returned input borrows the mapped memory directly. The caller must
+    /// ensure the underlying file is not modified or truncated (by this or any
+    /// other process) while the mapping is alive, otherwise the behaviour is
+    /// undefined.
+    pub unsafe fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::open_with(path, &MmapOptions::new(), 0)
+    }
+
+    /// Memory maps `len` bytes of the file at the given path, starting at
+    /// `offset` bytes from the start of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be opened or mapped.
+    ///
+    /// # Safety
+    ///
+    /// See [`MappedFile::open()`].
+    pub unsafe fn open_range(path: impl AsRef<Path>, offset: u64, len: usize) -> io::Result<Self> {
+        let mut options = MmapOptions::new();
+        options.offset(offset).len(len);
+        Self::open_with(path, &options, offset)
+    }
+
+    unsafe fn open_with(
+        path: impl AsRef<Path>,
+        options: &MmapOptions,
+        offset: u64,
+    ) -> io::Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        let map = options.map(&file)?;
+        Ok(Self {
+            map,
+            path: path.to_path_buf(),
+            offset,
+        })
+    }
+
+    /// Returns the [`Input`](crate::Input) over the mapped file.
+    ///
+    /// The input is bound as the full length of the mapping is known.
+    #[inline(always)]
+    pub fn input(&self) -> Bytes<'_> {
+        Bytes::new(&self.map, Bound::StartEnd)
+    }
+
+    /// Returns the path of the mapped file.
+    #[must_use]
+    #[inline(always)]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the offset within the file the mapping starts at.
+    #[must_use]
+    #[inline(always)]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the length of the mapping.
+    #[must_use]
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the mapping is empty.
+    #[must_use]
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl fmt::Debug for MappedFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedFile")
+            .field("path", &self.path)
+            .field("offset", &self.offset)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -2,24 +2,36 @@
 
 mod bound;
 mod byte_len;
+mod byte_pattern;
 mod bytes;
+mod class;
 mod entry;
+#[cfg(all(feature = "memmap2", feature = "std"))]
+mod file;
 mod pattern;
 mod prefix;
 mod span;
 mod string;
 mod token;
 mod traits;
+#[cfg(feature = "alloc")]
+mod vectored;
 
 pub use self::bound::Bound;
 pub use self::byte_len::ByteLength;
+pub use self::byte_pattern::BytePattern;
 pub use self::bytes::{ByteArray, Bytes};
+pub use self::class::AsciiClass;
+#[cfg(all(feature = "memmap2", feature = "std"))]
+pub use self::file::{input_file, MappedFile};
 pub use self::pattern::Pattern;
 pub use self::prefix::Prefix;
-pub use self::span::Span;
+pub use self::span::{Span, SpanOffsets};
 pub use self::string::{MaybeString, String};
 pub use self::token::{Token, TokenType};
 pub use self::traits::Input;
+#[cfg(feature = "alloc")]
+pub use self::vectored::{VectoredInput, VectoredReader};
 
 pub(crate) use self::entry::IntoInput;
 pub(crate) use self::traits::{Private, PrivateExt};
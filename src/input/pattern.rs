@@ -2,7 +2,8 @@
 /// [`Input`](crate::Input).
 ///
 /// You can search for a `char` or `&str` within either `Bytes` or `String`, but
-/// only a `u8` and `&[u8]` within `Bytes`.
+/// only a `u8`, `&[u8]` and [`BytePattern`] within `Bytes`. An [`AsciiClass`],
+/// such as whitespace or digits, can be searched for within either.
 ///
 /// Empty slices are invalid patterns and have the following behaviour:
 ///
@@ -10,7 +11,7 @@
 /// - Finding a reject of a empty slice pattern will return `Some(0)`.
 ///
 /// With the `simd` feature enabled pattern searches are SIMD optimised where
-/// possible.
+/// possible, including searches for an [`AsciiClass`] on `x86_64`.
 ///
 /// With the `regex` feature enabled, you can search for regex patterns.
 ///
@@ -18,6 +19,9 @@
 ///
 /// The implementation must return valid indexes and lengths for splitting input
 /// as these are not checked.
+///
+/// [`AsciiClass`]: crate::input::AsciiClass
+/// [`BytePattern`]: crate::input::BytePattern
 pub unsafe trait Pattern<I> {
     /// Returns the byte index and byte length of the first match and `None` if
     /// there was no match.
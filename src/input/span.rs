@@ -4,7 +4,7 @@ use core::slice;
 
 use crate::display::InputDisplay;
 use crate::fmt;
-use crate::input::{Input, MaybeString};
+use crate::input::{Input, MaybeString, PrivateExt};
 
 /// Range of [`Input`].
 ///
@@ -14,6 +14,10 @@ use crate::input::{Input, MaybeString};
 /// You can create a span from either [`Input::span()`] or from a raw slice via
 /// [`Span::from()`].
 ///
+/// As a span only has meaning for the input it points into, it can't be
+/// persisted as is. Use [`Span::offsets_in()`] to get [`SpanOffsets`] that
+/// can be.
+///
 /// [`Input`]: crate::Input  
 /// [`Input::span()`]: crate::Input::span()
 #[must_use]
@@ -225,6 +229,26 @@ impl Span {
         }
     }
 
+    /// Returns the [`SpanOffsets`] of `self` within the `parent`. `None` is
+    /// returned if `self` is not within in the `parent`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::input::SpanOffsets;
+    /// use dangerous::Span;
+    ///
+    /// let parent = &[1, 2, 3, 4][..];
+    /// let sub = Span::from(&parent[1..2]);
+    ///
+    /// assert_eq!(sub.offsets_in(parent.into()), Some(SpanOffsets::from(1..2)))
+    /// ```
+    #[must_use]
+    #[inline(always)]
+    pub fn offsets_in(self, parent: Span) -> Option<SpanOffsets> {
+        self.range_of(parent).map(SpanOffsets::from)
+    }
+
     /// Returns `None` if the span is empty, `Some(Self)` if not.
     ///
     /// # Example
@@ -259,6 +283,17 @@ impl Span {
             span: self,
         }
     }
+
+    /// Returns the bytes spanned.
+    ///
+    /// # Safety
+    ///
+    /// Caller must ensure the span was created from a slice that is valid for
+    /// `'a`.
+    #[inline(always)]
+    pub(crate) unsafe fn as_slice<'a>(self) -> &'a [u8] {
+        slice::from_raw_parts(self.start.as_ptr(), self.len())
+    }
 }
 
 impl fmt::DisplayBase for Span {
@@ -336,6 +371,76 @@ impl<'a> fmt::Debug for DebugFor<'a> {
     }
 }
 
+///////////////////////////////////////////////////////////////////////////////
+
+/// Byte offsets of a [`Span`] within its input.
+///
+/// Unlike a span, the offsets don't point into the input, so they can be
+/// persisted (with the `serde` feature) and resolved against the input with
+/// [`SpanOffsets::span_in()`] once it is loaded again.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::Input;
+///
+/// let input = dangerous::input(b"hello world");
+/// let world = input.clone().split_at::<dangerous::Invalid>(6).unwrap().1;
+/// let offsets = world.span().offsets_in(input.span()).unwrap();
+///
+/// assert_eq!(offsets.start..offsets.end, 6..11);
+/// assert_eq!(offsets.span_in(&input), Some(world.span()));
+/// ```
+#[must_use]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SpanOffsets {
+    /// The offset of the start of the span.
+    pub start: usize,
+    /// The offset of the end of the span.
+    pub end: usize,
+}
+
+impl SpanOffsets {
+    /// Returns the [`Span`] of the offsets within the `input` or `None` if
+    /// they are not within the input or do not align with start and end
+    /// token boundaries.
+    #[must_use]
+    pub fn span_in<'i, I>(self, input: &I) -> Option<Span>
+    where
+        I: Input<'i>,
+    {
+        if self.start > self.end
+            || self.end > input.byte_len()
+            || input.verify_token_boundary(self.start).is_err()
+            || input.verify_token_boundary(self.end).is_err()
+        {
+            return None;
+        }
+        Some(Span::from(
+            &input.as_dangerous_bytes()[self.start..self.end],
+        ))
+    }
+
+    /// Returns the offsets as a [`Range`].
+    #[must_use]
+    #[inline(always)]
+    pub fn range(self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+impl From<Range<usize>> for SpanOffsets {
+    #[inline(always)]
+    fn from(range: Range<usize>) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
 pub trait Parent: Sized {
     fn extract(self, span: Span) -> Option<Self>;
 }
@@ -91,3 +91,12 @@ impl<'i> fmt::Display for MaybeString<'i> {
         self.display().fmt(f)
     }
 }
+
+impl PartialEq for MaybeString<'_> {
+    /// Compares the content of the inputs and whether they are known to be
+    /// UTF-8, not their identity.
+    fn eq(&self, other: &Self) -> bool {
+        self.is_string() == other.is_string()
+            && self.as_dangerous_bytes() == other.as_dangerous_bytes()
+    }
+}
@@ -6,7 +6,7 @@ use core::str;
 
 use crate::display::InputDisplay;
 use crate::error::{
-    CoreContext, CoreExpected, CoreOperation, ExpectedLength, ExpectedValid, Length,
+    hook, CoreContext, CoreExpected, CoreOperation, ExpectedLength, ExpectedValid, Length,
 };
 use crate::fmt;
 use crate::util::{fast, slice, utf8};
@@ -31,8 +31,8 @@ impl<'i> String<'i> {
 
     /// Returns the number of UTF-8 characters in the string.
     ///
-    /// It is recommended to enable the `bytecount` dependency when using this
-    /// function for better performance.
+    /// The input is counted a machine word at a time, or with the `simd`
+    /// feature, with the widest SIMD instructions detected at runtime.
     #[must_use]
     pub fn num_chars(&self) -> usize {
         fast::num_chars(self.as_dangerous())
@@ -67,7 +67,7 @@ impl<'i> String<'i> {
         E: From<ExpectedLength<'i>>,
     {
         if self.is_empty() {
-            Err(E::from(ExpectedLength {
+            Err(hook::create(ExpectedLength {
                 len: Length::AtLeast(1),
                 context: CoreContext {
                     span: self.span(),
@@ -2,8 +2,8 @@ use core::convert::Infallible;
 
 use crate::display::InputDisplay;
 use crate::error::{
-    with_context, CoreContext, CoreExpected, CoreOperation, ExpectedLength, ExpectedValid,
-    ExpectedValue, External, Length, Value, WithChildContext, WithContext,
+    hook, with_context, CoreContext, CoreExpected, CoreOperation, ExpectedLength, ExpectedValid,
+    ExpectedValue, External, Length, Reason, Value, WithChildContext, WithContext,
 };
 use crate::fmt::{Debug, Display, DisplayBase};
 use crate::input::pattern::Pattern;
@@ -194,7 +194,7 @@ pub trait Input<'i>: Private<'i, Self::Token> {
             f,
         ) {
             Ok(ok) if r.at_end() => Ok(ok),
-            Ok(_) => Err(E::from(ExpectedLength {
+            Ok(_) => Err(hook::create(ExpectedLength {
                 len: Length::Exactly(0),
                 context: CoreContext {
                     span: r.take_remaining().span(),
@@ -273,7 +273,7 @@ pub trait Input<'i>: Private<'i, Self::Token> {
         E: From<ExpectedLength<'i>>,
     {
         if self.is_empty() {
-            Err(E::from(ExpectedLength {
+            Err(hook::create(ExpectedLength {
                 len: Length::AtLeast(1),
 
                 context: CoreContext {
@@ -348,7 +348,7 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
         E: From<ExpectedLength<'i>>,
     {
         self.clone().split_at_opt(mid).ok_or_else(|| {
-            E::from(ExpectedLength {
+            hook::create(ExpectedLength {
                 len: Length::AtLeast(mid),
 
                 context: CoreContext {
@@ -374,7 +374,7 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
         E: From<ExpectedLength<'i>>,
     {
         if self.byte_len() < mid {
-            Err(E::from(ExpectedLength {
+            Err(hook::create(ExpectedLength {
                 len: Length::AtLeast(mid),
 
                 context: CoreContext {
@@ -391,8 +391,10 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
                     // boundary.
                     Ok(unsafe { self.split_at_byte_unchecked(mid) })
                 }
-                Err(expected) => Err(E::from(ExpectedValid {
+                Err(expected) => Err(hook::create(ExpectedValid {
                     retry_requirement: None,
+                    reason: Some(Reason::InvalidTokenBoundary),
+                    found: None,
                     context: CoreContext {
                         span: self.as_dangerous_bytes()[mid..mid].into(),
                         operation,
@@ -427,7 +429,7 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
         E: From<ExpectedLength<'i>>,
     {
         self.clone().split_token_opt().ok_or_else(|| {
-            E::from(ExpectedLength {
+            hook::create(ExpectedLength {
                 len: Length::AtLeast(1),
 
                 context: CoreContext {
@@ -477,7 +479,7 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
                 } else {
                     bytes
                 };
-                Err(E::from(ExpectedValue {
+                Err(hook::create(ExpectedValue {
                     expected: prefix.into(),
                     context: CoreContext {
                         span: actual.into(),
@@ -524,7 +526,7 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
         P: Pattern<Self> + Into<Value<'i>> + Copy,
     {
         self.clone().split_until_opt(pattern).ok_or_else(|| {
-            E::from(ExpectedValue {
+            hook::create(ExpectedValue {
                 expected: pattern.into(),
                 context: CoreContext {
                     span: self.span(),
@@ -554,7 +556,7 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
         self.clone()
             .split_until_consume_opt(pattern)
             .ok_or_else(|| {
-                E::from(ExpectedValue {
+                hook::create(ExpectedValue {
                     expected: pattern.into(),
                     context: CoreContext {
                         span: self.span(),
@@ -702,8 +704,10 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
         } else {
             let tail = reader.take_remaining();
             let span = self.as_dangerous_bytes()[..self.byte_len() - tail.byte_len()].into();
-            Err(E::from(ExpectedValid {
+            Err(hook::create(ExpectedValid {
                 retry_requirement: None,
+                reason: None,
+                found: None,
                 context: CoreContext {
                     span,
                     expected: CoreExpected::Valid(expected),
@@ -748,8 +752,10 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
                 // operation.
                 context.span =
                     self.as_dangerous_bytes()[..self.byte_len() - tail.byte_len()].into();
-                Err(E::from(ExpectedValid {
+                Err(hook::create(ExpectedValid {
                     retry_requirement: None,
+                    reason: None,
+                    found: None,
                     context,
                     input: self.into_maybe_string(),
                 }))
@@ -806,8 +812,10 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
         E: From<ExpectedValid<'i>>,
         Ex: External<'i>,
     {
-        let error = E::from(ExpectedValid {
+        let error: E = hook::create(ExpectedValid {
             retry_requirement: external.retry_requirement(),
+            reason: external.reason(),
+            found: external.found(),
             context: CoreContext {
                 span: external.span().unwrap_or_else(|| self.span()),
                 expected: CoreExpected::Valid(expected),
@@ -0,0 +1,194 @@
+use alloc::borrow::Cow;
+use core::ops::Deref;
+
+use crate::error::{CoreContext, CoreOperation, WithContext};
+use crate::fmt;
+use crate::BytesReader;
+
+use super::{Bytes, Input};
+
+/// [`Input`] collected from multiple slices, such as the [`IoSlice`]s filled
+/// by a vectored read.
+///
+/// `Input` is always backed by a single contiguous slice, so the slices are
+/// read in turn with a [`VectoredReader`], without copying. Each read is made
+/// within one slice, and a read that would continue into the next slice
+/// fails. If a format can't be split along the slices, the slices can instead
+/// be joined with [`to_contiguous()`], which copies them.
+///
+/// # Example
+///
+/// ```
+/// use std::io::IoSlice;
+/// use dangerous::input::VectoredInput;
+/// use dangerous::Invalid;
+///
+/// let slices = [IoSlice::new(&[5]), IoSlice::new(b"hello")];
+/// let vectored = VectoredInput::new(&slices);
+/// let mut r = vectored.reader();
+///
+/// let len = r.read_partial::<_, _, Invalid>(|r| r.read())?;
+/// let body = r.read_partial::<_, _, Invalid>(|r| r.take(len.into()))?;
+///
+/// assert_eq!(body.as_dangerous().as_ptr(), slices[1].as_ptr());
+/// assert!(r.at_end());
+/// # Ok::<(), Invalid>(())
+/// ```
+///
+/// [`IoSlice`]: std::io::IoSlice
+/// [`to_contiguous()`]: Self::to_contiguous()
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct VectoredInput<'s, S> {
+    slices: &'s [S],
+}
+
+impl<'s, S> VectoredInput<'s, S>
+where
+    S: Deref<Target = [u8]>,
+{
+    /// Create a new `VectoredInput` from a list of slices.
+    pub fn new(slices: &'s [S]) -> Self {
+        Self { slices }
+    }
+
+    /// Returns the total length of the slices.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.slices.iter().map(|slice| slice.len()).sum()
+    }
+
+    /// Returns `true` if all of the slices are empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.slices.iter().all(|slice| slice.is_empty())
+    }
+
+    /// Returns a [`VectoredReader`] over the slices.
+    #[must_use]
+    pub fn reader(&self) -> VectoredReader<'s, S> {
+        VectoredReader {
+            current: crate::input(&[]).into_bound(),
+            rest: self.slices,
+        }
+    }
+
+    /// Returns the slices joined into one contiguous slice.
+    ///
+    /// If at most one of the slices is non-empty it is borrowed, otherwise
+    /// the slices are copied into one owned buffer.
+    #[must_use]
+    pub fn to_contiguous(&self) -> Cow<'s, [u8]> {
+        let mut non_empty = self.slices.iter().filter(|slice| !slice.is_empty());
+        match (non_empty.next(), non_empty.next()) {
+            (None, _) => Cow::Borrowed(&[][..]),
+            (Some(slice), None) => Cow::Borrowed(&**slice),
+            (Some(_), Some(_)) => {
+                let mut buf = alloc::vec::Vec::with_capacity(self.len());
+                for slice in self.slices {
+                    buf.extend_from_slice(slice);
+                }
+                Cow::Owned(buf)
+            }
+        }
+    }
+}
+
+impl<S> fmt::Debug for VectoredInput<'_, S>
+where
+    S: Deref<Target = [u8]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VectoredInput")
+            .field("slices", &self.slices.len())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Reads the slices of a [`VectoredInput`] in turn.
+///
+/// Created with [`VectoredInput::reader()`].
+///
+/// Each read is made with a [`BytesReader`] over what remains of the current
+/// slice, so values borrow the slices directly. The input of each slice is
+/// bound, so a read that needs more than what is left of it returns a fatal
+/// error rather than asking for input that is already there. While more
+/// slices follow, the error has a context with the operation
+/// [`CoreOperation::ReadSlice`].
+///
+/// [`CoreOperation::ReadSlice`]: crate::error::CoreOperation::ReadSlice
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct VectoredReader<'s, S> {
+    current: Bytes<'s>,
+    rest: &'s [S],
+}
+
+impl<'s, S> VectoredReader<'s, S>
+where
+    S: Deref<Target = [u8]>,
+{
+    /// Read a part of the current slice, moving on to the next slice once it
+    /// is consumed.
+    ///
+    /// Nothing is consumed if the read fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the provided function, which is fatal as
+    /// the input of the slice is bound.
+    pub fn read_partial<F, T, E>(&mut self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut BytesReader<'s, E>) -> Result<T, E>,
+        E: WithContext<'s>,
+    {
+        self.skip_empty();
+        match self.current.clone().read_partial(f) {
+            Ok((value, remaining)) => {
+                self.current = remaining;
+                Ok(value)
+            }
+            Err(err) if self.rest.iter().any(|slice| !slice.is_empty()) => Err(err.with_context(
+                CoreContext::from_operation(CoreOperation::ReadSlice, self.current.span()),
+            )),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the number of bytes left to read across the slices.
+    #[must_use]
+    pub fn remaining_len(&self) -> usize {
+        self.current.len() + self.rest.iter().map(|slice| slice.len()).sum::<usize>()
+    }
+
+    /// Returns `true` if there is no more input to read in any slice.
+    #[must_use]
+    pub fn at_end(&self) -> bool {
+        self.current.is_empty() && self.rest.iter().all(|slice| slice.is_empty())
+    }
+
+    /// Moves past the current slice while it is consumed and more follow.
+    fn skip_empty(&mut self) {
+        while self.current.is_empty() {
+            let (slice, rest) = match self.rest.split_first() {
+                Some(split) => split,
+                None => break,
+            };
+            self.rest = rest;
+            self.current = crate::input(&**slice).into_bound();
+        }
+    }
+}
+
+impl<S> fmt::Debug for VectoredReader<'_, S>
+where
+    S: Deref<Target = [u8]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VectoredReader")
+            .field("current", &self.current)
+            .field("remaining_len", &self.remaining_len())
+            .finish()
+    }
+}
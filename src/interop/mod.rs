@@ -0,0 +1,17 @@
+//! Adapters to and from other parser crates.
+//!
+//! A grammar written with another parser crate can be migrated to `dangerous`
+//! a parser at a time: existing parsers can be run within read functions, and
+//! migrated read functions can be run where a parser is still expected.
+//! Retry requirements are translated in both directions, so streaming parsers
+//! keep working across the boundary.
+
+#[cfg(feature = "nom")]
+mod nom;
+#[cfg(feature = "winnow")]
+mod winnow;
+
+#[cfg(feature = "nom")]
+pub use self::nom::{nom_parser, read_nom, NomInput};
+#[cfg(feature = "winnow")]
+pub use self::winnow::{read_winnow, StreamInput};
@@ -0,0 +1,145 @@
+use nom::error::{ErrorKind, FromExternalError, ParseError};
+use nom::{IResult, Needed, Parser};
+
+use crate::error::External;
+use crate::{Bytes, Error, Input, Reader, String};
+
+/// Implemented for [`Input`]s `nom` can parse.
+///
+/// [`Bytes`] are parsed as `&[u8]` and [`String`] as `&str`.
+pub trait NomInput<'i>: Input<'i> {
+    /// The input as `nom` parses it.
+    type Nom: Copy;
+
+    /// Returns the input as `nom` parses it.
+    fn as_nom(&self) -> Self::Nom;
+
+    /// Returns the input `nom` left unparsed as an `Input`.
+    fn from_nom(nom: Self::Nom) -> Self;
+
+    /// Returns the length of the input in bytes as `nom` parses it.
+    fn nom_len(nom: Self::Nom) -> usize;
+}
+
+impl<'i> NomInput<'i> for Bytes<'i> {
+    type Nom = &'i [u8];
+
+    fn as_nom(&self) -> Self::Nom {
+        self.as_dangerous()
+    }
+
+    fn from_nom(nom: Self::Nom) -> Self {
+        crate::input(nom)
+    }
+
+    fn nom_len(nom: Self::Nom) -> usize {
+        nom.len()
+    }
+}
+
+impl<'i> NomInput<'i> for String<'i> {
+    type Nom = &'i str;
+
+    fn as_nom(&self) -> Self::Nom {
+        self.as_dangerous()
+    }
+
+    fn from_nom(nom: Self::Nom) -> Self {
+        crate::input(nom)
+    }
+
+    fn nom_len(nom: Self::Nom) -> usize {
+        nom.len()
+    }
+}
+
+/// Read a value with a `nom` parser.
+///
+/// The parser is given the remaining input of the reader, and the reader is
+/// advanced past what it parsed.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{Expected, Input};
+/// use nom::bytes::streaming::tag;
+///
+/// let result = dangerous::input(b"hello!").read_all::<_, _, Expected<'_>>(|r| {
+///     let hello = dangerous::interop::read_nom(r, "greeting", tag::<_, _, nom::error::Error<_>>("hello"))?;
+///     r.consume(b'!')?;
+///     Ok(hello)
+/// });
+///
+/// assert_eq!(result.unwrap(), b"hello");
+/// ```
+///
+/// # Errors
+///
+/// Returns [`ExpectedValid`] spanning the input the parser failed on if it
+/// returns an error, which will require a retry if the parser returned
+/// `Incomplete`.
+///
+/// [`ExpectedValid`]: crate::error::ExpectedValid
+pub fn read_nom<'i, I, E, P, T, Ex>(
+    r: &mut Reader<'i, I, E>,
+    expected: &'static str,
+    mut parser: P,
+) -> Result<T, E>
+where
+    I: NomInput<'i>,
+    E: Error<'i>,
+    P: Parser<I::Nom, T, Ex>,
+    Ex: External<'i>,
+{
+    r.try_external(expected, |i| {
+        let nom = i.as_nom();
+        parser
+            .parse(nom)
+            .map(|(remaining, value)| (I::nom_len(nom) - I::nom_len(remaining), value))
+    })
+}
+
+/// Returns a `nom` parser running the provided read function.
+///
+/// The parser returns what the read function left unread. An error that
+/// requires a retry is returned as `Incomplete`, and any other as a
+/// recoverable `nom` error created with [`FromExternalError`] from the input
+/// given to the parser.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{BytesReader, Error, Invalid};
+/// use nom::multi::many1;
+///
+/// fn read_digit<'i, E>(r: &mut BytesReader<'i, E>) -> Result<u8, E>
+/// where
+///     E: Error<'i>,
+/// {
+///     r.try_expect("digit", |r| Ok(Some(r.read()?).filter(u8::is_ascii_digit)))
+/// }
+///
+/// let mut digits = many1(dangerous::interop::nom_parser::<_, _, _, Invalid, nom::error::Error<_>>(
+///     read_digit,
+/// ));
+///
+/// assert_eq!(digits(&b"12a"[..]), Ok((&b"a"[..], vec![b'1', b'2'])));
+/// assert!(matches!(digits(&b""[..]), Err(nom::Err::Incomplete(_))));
+/// ```
+pub fn nom_parser<'i, I, F, T, E, Ex>(mut f: F) -> impl FnMut(I::Nom) -> IResult<I::Nom, T, Ex>
+where
+    I: NomInput<'i>,
+    F: FnMut(&mut Reader<'i, I, E>) -> Result<T, E>,
+    E: Error<'i>,
+    Ex: ParseError<I::Nom> + FromExternalError<I::Nom, E>,
+{
+    move |nom| match I::from_nom(nom).read_partial(&mut f) {
+        Ok((value, remaining)) => Ok((remaining.as_nom(), value)),
+        Err(err) => Err(match err.to_retry_requirement() {
+            Some(requirement) => {
+                nom::Err::Incomplete(Needed::Size(requirement.continue_after_non_zero()))
+            }
+            None => nom::Err::Error(Ex::from_external_error(nom, ErrorKind::Verify, err)),
+        }),
+    }
+}
@@ -0,0 +1,450 @@
+use winnow::error::{AddContext, FromExternalError, Needed, ParserError};
+use winnow::stream::{
+    AsBStr, AsBytes, Compare, CompareResult, FindSlice, Offset, SliceLen, Stream, StreamIsPartial,
+};
+use winnow::{Parser, Partial};
+
+use crate::error::{
+    hook, BacktraceBuilder, CoreContext, CoreExpected, CoreOperation, ExpectedLength,
+    ExpectedValid, External, Length, RetryRequirement, WithContext,
+};
+use crate::input::PrivateExt;
+use crate::{Bytes, Expected, Input, Invalid, Reader, String, ToRetryRequirement};
+
+/// Implemented for `winnow` streams of `dangerous` input.
+///
+/// [`Bytes`] and [`String`] are complete streams. Wrap them in
+/// [`Partial`](winnow::Partial) for streaming parsers, whose incomplete errors
+/// are created with the retry requirement of the input needed.
+pub trait StreamInput<'i>: Stream {
+    /// The input the stream is over.
+    type Input: Input<'i>;
+
+    /// Returns the input remaining in the stream.
+    fn remaining(&self) -> Self::Input;
+}
+
+impl<'i> StreamInput<'i> for Bytes<'i> {
+    type Input = Self;
+
+    fn remaining(&self) -> Self::Input {
+        self.clone()
+    }
+}
+
+impl<'i> StreamInput<'i> for String<'i> {
+    type Input = Self;
+
+    fn remaining(&self) -> Self::Input {
+        self.clone()
+    }
+}
+
+impl<'i, I> StreamInput<'i> for Partial<I>
+where
+    I: StreamInput<'i>,
+{
+    type Input = I::Input;
+
+    fn remaining(&self) -> Self::Input {
+        (**self).remaining()
+    }
+}
+
+/// Read a value with a `winnow` parser.
+///
+/// The parser is given the remaining input of the reader as a stream, and
+/// the reader is advanced past what it parsed.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{Expected, Input};
+/// use winnow::ascii::digit1;
+/// use winnow::Parser;
+///
+/// let result = dangerous::input(b"123!").read_all::<_, _, Expected<'_>>(|r| {
+///     let digits = dangerous::interop::read_winnow(r, digit1)?;
+///     r.consume(b'!')?;
+///     Ok(digits)
+/// });
+///
+/// assert_eq!(result.unwrap(), b"123"[..]);
+/// ```
+///
+/// # Errors
+///
+/// Returns any error the parser does.
+pub fn read_winnow<'i, I, E, P, T>(r: &mut Reader<'i, I, E>, mut parser: P) -> Result<T, E>
+where
+    I: Input<'i> + Stream,
+    P: Parser<I, T, E>,
+{
+    r.try_advance(|mut input| {
+        let value = parser.parse_next(&mut input)?;
+        Ok((value, input))
+    })
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Streams
+
+fn split_off<'i, I>(input: &mut I, offset: usize) -> I
+where
+    I: Input<'i>,
+{
+    match input.clone().split_at_byte_opt(offset) {
+        Some((head, tail)) => {
+            *input = tail;
+            head
+        }
+        None => panic!("offset {} is out of bounds of the stream", offset),
+    }
+}
+
+impl<'i> Stream for Bytes<'i> {
+    type Token = u8;
+    type Slice = Self;
+    type IterOffsets = <&'i [u8] as Stream>::IterOffsets;
+    type Checkpoint = Self;
+
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        self.as_dangerous().iter_offsets()
+    }
+
+    fn eof_offset(&self) -> usize {
+        self.len()
+    }
+
+    fn next_token(&mut self) -> Option<Self::Token> {
+        let token = self.peek_token()?;
+        let _ = split_off(self, 1);
+        Some(token)
+    }
+
+    fn peek_token(&self) -> Option<Self::Token> {
+        self.as_dangerous().first().copied()
+    }
+
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        self.as_dangerous().offset_for(predicate)
+    }
+
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        self.as_dangerous().offset_at(tokens)
+    }
+
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        split_off(self, offset)
+    }
+
+    fn peek_slice(&self, offset: usize) -> Self::Slice {
+        split_off(&mut self.clone(), offset)
+    }
+
+    fn checkpoint(&self) -> Self::Checkpoint {
+        self.clone()
+    }
+
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        *self = checkpoint.clone();
+    }
+
+    fn trace(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+impl<'i> Stream for String<'i> {
+    type Token = char;
+    type Slice = Self;
+    type IterOffsets = <&'i str as Stream>::IterOffsets;
+    type Checkpoint = Self;
+
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        self.as_dangerous().iter_offsets()
+    }
+
+    fn eof_offset(&self) -> usize {
+        self.as_dangerous().len()
+    }
+
+    fn next_token(&mut self) -> Option<Self::Token> {
+        let token = self.peek_token()?;
+        let _ = split_off(self, token.len_utf8());
+        Some(token)
+    }
+
+    fn peek_token(&self) -> Option<Self::Token> {
+        self.as_dangerous().chars().next()
+    }
+
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        self.as_dangerous().offset_for(predicate)
+    }
+
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        self.as_dangerous().offset_at(tokens)
+    }
+
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        split_off(self, offset)
+    }
+
+    fn peek_slice(&self, offset: usize) -> Self::Slice {
+        split_off(&mut self.clone(), offset)
+    }
+
+    fn checkpoint(&self) -> Self::Checkpoint {
+        self.clone()
+    }
+
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        *self = checkpoint.clone();
+    }
+
+    fn trace(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+impl StreamIsPartial for Bytes<'_> {
+    type PartialState = ();
+
+    fn complete(&mut self) -> Self::PartialState {}
+
+    fn restore_partial(&mut self, _state: Self::PartialState) {}
+
+    fn is_partial_supported() -> bool {
+        false
+    }
+}
+
+impl StreamIsPartial for String<'_> {
+    type PartialState = ();
+
+    fn complete(&mut self) -> Self::PartialState {}
+
+    fn restore_partial(&mut self, _state: Self::PartialState) {}
+
+    fn is_partial_supported() -> bool {
+        false
+    }
+}
+
+impl Offset for Bytes<'_> {
+    fn offset_from(&self, start: &Self) -> usize {
+        self.as_dangerous().offset_from(&start.as_dangerous())
+    }
+}
+
+impl Offset for String<'_> {
+    fn offset_from(&self, start: &Self) -> usize {
+        self.as_dangerous().offset_from(&start.as_dangerous())
+    }
+}
+
+impl SliceLen for Bytes<'_> {
+    fn slice_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl SliceLen for String<'_> {
+    fn slice_len(&self) -> usize {
+        self.as_dangerous().len()
+    }
+}
+
+impl AsBytes for Bytes<'_> {
+    fn as_bytes(&self) -> &[u8] {
+        self.as_dangerous()
+    }
+}
+
+impl AsBStr for Bytes<'_> {
+    fn as_bstr(&self) -> &[u8] {
+        self.as_dangerous()
+    }
+}
+
+impl AsBStr for String<'_> {
+    fn as_bstr(&self) -> &[u8] {
+        self.as_dangerous().as_bytes()
+    }
+}
+
+impl<'i, T> Compare<T> for Bytes<'i>
+where
+    &'i [u8]: Compare<T>,
+{
+    fn compare(&self, t: T) -> CompareResult {
+        self.as_dangerous().compare(t)
+    }
+}
+
+impl<'i, T> Compare<T> for String<'i>
+where
+    &'i str: Compare<T>,
+{
+    fn compare(&self, t: T) -> CompareResult {
+        self.as_dangerous().compare(t)
+    }
+}
+
+impl<'i, T> FindSlice<T> for Bytes<'i>
+where
+    &'i [u8]: FindSlice<T>,
+{
+    fn find_slice(&self, substr: T) -> Option<core::ops::Range<usize>> {
+        self.as_dangerous().find_slice(substr)
+    }
+}
+
+impl<'i, T> FindSlice<T> for String<'i>
+where
+    &'i str: FindSlice<T>,
+{
+    fn find_slice(&self, substr: T) -> Option<core::ops::Range<usize>> {
+        self.as_dangerous().find_slice(substr)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Errors
+
+fn expected_valid<'i, I>(input: I) -> ExpectedValid<'i>
+where
+    I: Input<'i>,
+{
+    hook::notify(ExpectedValid {
+        retry_requirement: None,
+        reason: None,
+        found: None,
+        context: CoreContext {
+            span: input.span().start(),
+            operation: CoreOperation::ExpectExternal,
+            expected: CoreExpected::Valid("value"),
+        },
+        input: input.into_maybe_string(),
+    })
+}
+
+fn expected_length<'i, I>(input: I, needed: Needed) -> ExpectedLength<'i>
+where
+    I: Input<'i>,
+{
+    let needed = match needed {
+        Needed::Size(needed) => needed.get(),
+        Needed::Unknown => 1,
+    };
+    hook::notify(ExpectedLength {
+        len: Length::AtLeast(input.byte_len() + needed),
+        context: CoreContext {
+            span: input.span(),
+            operation: CoreOperation::ExpectExternal,
+            expected: CoreExpected::EnoughInputFor("value"),
+        },
+        input: input.into_maybe_string(),
+    })
+}
+
+fn to_needed(requirement: Option<RetryRequirement>) -> Option<Needed> {
+    requirement.map(|requirement| Needed::Size(requirement.continue_after_non_zero()))
+}
+
+impl<'i, S, I> ParserError<I> for Expected<'i, S>
+where
+    S: BacktraceBuilder,
+    I: StreamInput<'i>,
+{
+    type Inner = Self;
+
+    fn from_input(input: &I) -> Self {
+        Self::from(expected_valid(input.remaining()))
+    }
+
+    fn incomplete(input: &I, needed: Needed) -> Self {
+        Self::from(expected_length(input.remaining(), needed))
+    }
+
+    fn into_inner(self) -> Result<Self::Inner, Self> {
+        Ok(self)
+    }
+
+    fn is_incomplete(&self) -> bool {
+        self.to_retry_requirement().is_some()
+    }
+
+    fn needed(&self) -> Option<Needed> {
+        to_needed(self.to_retry_requirement())
+    }
+}
+
+impl<'i, S, I> AddContext<I> for Expected<'i, S>
+where
+    S: BacktraceBuilder,
+    I: StreamInput<'i>,
+{
+    fn add_context(self, _input: &I, _token_start: &I::Checkpoint, context: &'static str) -> Self {
+        self.with_context(context)
+    }
+}
+
+impl<'i, S, I, Ex> FromExternalError<I, Ex> for Expected<'i, S>
+where
+    S: BacktraceBuilder,
+    I: StreamInput<'i>,
+    Ex: External<'i>,
+{
+    fn from_external_error(input: &I, external: Ex) -> Self {
+        input
+            .remaining()
+            .map_external_error(external, "value", CoreOperation::ExpectExternal)
+    }
+}
+
+impl<'i, I> ParserError<I> for Invalid
+where
+    I: StreamInput<'i>,
+{
+    type Inner = Self;
+
+    fn from_input(_input: &I) -> Self {
+        Self::fatal()
+    }
+
+    fn incomplete(input: &I, needed: Needed) -> Self {
+        Self::from(expected_length(input.remaining(), needed).to_retry_requirement())
+    }
+
+    fn into_inner(self) -> Result<Self::Inner, Self> {
+        Ok(self)
+    }
+
+    fn is_incomplete(&self) -> bool {
+        self.to_retry_requirement().is_some()
+    }
+
+    fn needed(&self) -> Option<Needed> {
+        to_needed(self.to_retry_requirement())
+    }
+}
+
+impl<'i, I> AddContext<I> for Invalid where I: StreamInput<'i> {}
+
+impl<'i, I, Ex> FromExternalError<I, Ex> for Invalid
+where
+    I: StreamInput<'i>,
+    Ex: External<'i>,
+{
+    fn from_external_error(_input: &I, external: Ex) -> Self {
+        Self::from(external.retry_requirement())
+    }
+}
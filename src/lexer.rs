@@ -0,0 +1,394 @@
+//! Splitting input into tokens with a set of rules.
+//!
+//! A [`Lexer`] is built from [`TokenRule`]s, each matching a kind of token
+//! by a literal or by a run of a [`ByteClass`]. The rules are compiled into a
+//! table indexed by the first byte of a token, so only the rules that could
+//! start at a position are tried. The longest match wins, with ties going to
+//! the rule listed first.
+//!
+//! Input that no rule matches is an [`ExpectedValid`] error with the reason
+//! [`Reason::UnexpectedChar`], spanning the unexpected char.
+//!
+//! Tokens are matched against the input as it is, so a run of a class that
+//! reaches the end of input is taken as complete.
+//!
+//! # Example
+//!
+//! ```
+//! use dangerous::input::AsciiClass;
+//! use dangerous::lexer::{ByteClass, Lexer, TokenRule};
+//! use dangerous::Invalid;
+//!
+//! #[derive(Debug, Copy, Clone, PartialEq)]
+//! enum Kind {
+//!     Ident,
+//!     Number,
+//!     Arrow,
+//!     Minus,
+//!     Whitespace,
+//! }
+//!
+//! fn ident_start(c: u8) -> bool {
+//!     c.is_ascii_alphabetic() || c == b'_'
+//! }
+//!
+//! fn ident_rest(c: u8) -> bool {
+//!     c.is_ascii_alphanumeric() || c == b'_'
+//! }
+//!
+//! let rules = [
+//!     TokenRule::run_after(Kind::Ident, ByteClass::Fn(ident_start), ByteClass::Fn(ident_rest)),
+//!     TokenRule::run(Kind::Number, AsciiClass::Digit),
+//!     TokenRule::literal(Kind::Arrow, "->"),
+//!     TokenRule::literal(Kind::Minus, "-"),
+//!     TokenRule::run(Kind::Whitespace, AsciiClass::Whitespace).skipped(),
+//! ];
+//! let lexer = Lexer::new(&rules);
+//!
+//! let tokens = lexer
+//!     .tokens::<_, Invalid>(dangerous::input("x_1 -> -42"))
+//!     .map(|token| token.map(|(kind, input)| (kind, input.as_dangerous())))
+//!     .collect::<Result<Vec<_>, _>>()?;
+//!
+//! assert_eq!(
+//!     tokens,
+//!     [
+//!         (Kind::Ident, "x_1"),
+//!         (Kind::Arrow, "->"),
+//!         (Kind::Minus, "-"),
+//!         (Kind::Number, "42"),
+//!     ]
+//! );
+//! # Ok::<(), Invalid>(())
+//! ```
+//!
+//! [`ExpectedValid`]: crate::error::ExpectedValid
+//! [`Reason::UnexpectedChar`]: crate::error::Reason::UnexpectedChar
+
+use core::str;
+
+use crate::error::{hook, CoreContext, CoreExpected, CoreOperation, ExpectedValid, Reason, Value};
+use crate::fmt;
+use crate::input::{AsciiClass, Input, PrivateExt, TokenType};
+use crate::util::fast;
+use crate::Reader;
+
+/// The maximum number of rules a [`Lexer`] can be built with.
+pub const MAX_RULES: usize = 64;
+
+/// A class of bytes a [`TokenRule`] can match runs of.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone)]
+pub enum ByteClass {
+    /// Bytes within an [`AsciiClass`], searched with SIMD where supported.
+    Ascii(AsciiClass),
+    /// A single byte.
+    Byte(u8),
+    /// Bytes the function returns `true` for.
+    Fn(fn(u8) -> bool),
+}
+
+impl ByteClass {
+    /// Returns `true` if the byte is within the class.
+    #[must_use]
+    #[inline]
+    pub fn matches(self, byte: u8) -> bool {
+        match self {
+            Self::Ascii(class) => class.matches(byte),
+            Self::Byte(other) => byte == other,
+            Self::Fn(f) => f(byte),
+        }
+    }
+
+    /// Returns the length of the run of bytes within the class at the start
+    /// of the bytes.
+    fn run_len(self, bytes: &[u8]) -> usize {
+        let reject = match self {
+            Self::Ascii(class) => fast::find_class_reject(class, bytes),
+            _ => bytes.iter().position(|&byte| !self.matches(byte)),
+        };
+        reject.unwrap_or(bytes.len())
+    }
+}
+
+impl From<AsciiClass> for ByteClass {
+    fn from(class: AsciiClass) -> Self {
+        Self::Ascii(class)
+    }
+}
+
+impl From<u8> for ByteClass {
+    fn from(byte: u8) -> Self {
+        Self::Byte(byte)
+    }
+}
+
+impl From<fn(u8) -> bool> for ByteClass {
+    fn from(f: fn(u8) -> bool) -> Self {
+        Self::Fn(f)
+    }
+}
+
+/// A rule matching a kind of token for a [`Lexer`].
+#[derive(Debug, Copy, Clone)]
+pub struct TokenRule<K> {
+    kind: K,
+    pattern: RulePattern,
+    skipped: bool,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum RulePattern {
+    Literal(&'static [u8]),
+    Run { first: ByteClass, rest: ByteClass },
+}
+
+impl<K> TokenRule<K> {
+    /// Matches a literal, such as a keyword or operator.
+    ///
+    /// An empty literal never matches.
+    pub fn literal(kind: K, literal: &'static str) -> Self {
+        Self::new(kind, RulePattern::Literal(literal.as_bytes()))
+    }
+
+    /// Matches a run of one or more bytes within a class.
+    pub fn run<C>(kind: K, class: C) -> Self
+    where
+        C: Into<ByteClass>,
+    {
+        let class = class.into();
+        Self::run_after(kind, class, class)
+    }
+
+    /// Matches a byte within the `first` class, followed by a run of zero or
+    /// more bytes within the `rest` class, such as an identifier.
+    pub fn run_after<F, R>(kind: K, first: F, rest: R) -> Self
+    where
+        F: Into<ByteClass>,
+        R: Into<ByteClass>,
+    {
+        Self::new(
+            kind,
+            RulePattern::Run {
+                first: first.into(),
+                rest: rest.into(),
+            },
+        )
+    }
+
+    /// Matched tokens are skipped rather than returned, such as whitespace
+    /// and comments.
+    #[must_use]
+    pub fn skipped(mut self) -> Self {
+        self.skipped = true;
+        self
+    }
+
+    fn new(kind: K, pattern: RulePattern) -> Self {
+        Self {
+            kind,
+            pattern,
+            skipped: false,
+        }
+    }
+
+    fn starts_with(&self, byte: u8) -> bool {
+        match self.pattern {
+            RulePattern::Literal(literal) => literal.first() == Some(&byte),
+            RulePattern::Run { first, .. } => first.matches(byte),
+        }
+    }
+
+    /// Returns the length of the match at the start of the bytes, which
+    /// the rule is known to start with.
+    fn match_len(&self, bytes: &[u8]) -> usize {
+        match self.pattern {
+            RulePattern::Literal(literal) if bytes.starts_with(literal) => literal.len(),
+            RulePattern::Literal(_) => 0,
+            RulePattern::Run { rest, .. } => 1 + rest.run_len(&bytes[1..]),
+        }
+    }
+}
+
+/// Splits input into tokens with a set of [`TokenRule`]s.
+pub struct Lexer<'r, K> {
+    rules: &'r [TokenRule<K>],
+    /// The rules that can start with each byte, as a bit set of indexes.
+    first: [u64; 256],
+}
+
+impl<'r, K> Lexer<'r, K>
+where
+    K: Copy,
+{
+    /// Compiles a lexer from a set of rules.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`MAX_RULES`] rules.
+    #[must_use]
+    pub fn new(rules: &'r [TokenRule<K>]) -> Self {
+        assert!(rules.len() <= MAX_RULES, "too many lexer rules");
+        let mut first = [0; 256];
+        for (byte, set) in (0..=u8::MAX).zip(first.iter_mut()) {
+            for (index, rule) in rules.iter().enumerate() {
+                if rule.starts_with(byte) {
+                    *set |= 1 << index;
+                }
+            }
+        }
+        Self { rules, first }
+    }
+
+    /// Returns an iterator over the tokens within the input.
+    ///
+    /// The iterator ends after the first error.
+    pub fn tokens<'i, I, E>(&self, input: I) -> Tokens<'_, 'r, I, K, E>
+    where
+        I: Input<'i>,
+        E: From<ExpectedValid<'i>>,
+    {
+        Tokens {
+            lexer: self,
+            origin: input.clone(),
+            input: Some(input),
+            error: core::marker::PhantomData,
+        }
+    }
+
+    /// Read the next token from the reader, or `None` if there is no input
+    /// left besides skipped tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no rule matches the input.
+    pub fn read<'i, I, E>(&self, r: &mut Reader<'i, I, E>) -> Result<Option<(K, I)>, E>
+    where
+        I: Input<'i>,
+        E: From<ExpectedValid<'i>>,
+    {
+        r.try_advance(|input| self.split(input.clone(), input))
+    }
+
+    /// Splits off the next token, with errors against the `origin` input.
+    fn split<'i, I, E>(&self, mut input: I, origin: I) -> Result<(Option<(K, I)>, I), E>
+    where
+        I: Input<'i>,
+        E: From<ExpectedValid<'i>>,
+    {
+        loop {
+            let bytes = input.as_dangerous_bytes();
+            let first = match bytes.first() {
+                Some(&first) => first,
+                None => return Ok((None, input)),
+            };
+            let mut best: Option<(&TokenRule<K>, usize)> = None;
+            let mut set = self.first[usize::from(first)];
+            while set != 0 {
+                let rule = &self.rules[set.trailing_zeros() as usize];
+                set &= set - 1;
+                let len = rule.match_len(bytes);
+                if len > best.map_or(0, |(_, best_len)| best_len) {
+                    best = Some((rule, len));
+                }
+            }
+            let split = best.and_then(|(rule, len)| {
+                let (token, rest) = input.clone().split_at_byte_opt(len)?;
+                Some((rule, token, rest))
+            });
+            match split {
+                Some((rule, _, rest)) if rule.skipped => input = rest,
+                Some((rule, token, rest)) => return Ok((Some((rule.kind, token)), rest)),
+                None => return Err(unexpected_char(&input, origin)),
+            }
+        }
+    }
+}
+
+impl<K> fmt::Debug for Lexer<'_, K>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lexer").field("rules", &self.rules).finish()
+    }
+}
+
+fn unexpected_char<'i, I, E>(input: &I, origin: I) -> E
+where
+    I: Input<'i>,
+    E: From<ExpectedValid<'i>>,
+{
+    let len = input
+        .clone()
+        .split_token_opt()
+        .map_or(0, |(_, rest)| input.byte_len() - rest.byte_len());
+    let bytes = &input.as_dangerous_bytes()[..len];
+    let found = match <I::Token as crate::input::Token>::TYPE {
+        TokenType::Char => fast::from_utf8(bytes).map_or_else(|_| Value::from(bytes), Value::from),
+        TokenType::Byte => Value::from(bytes),
+    };
+    hook::create(ExpectedValid {
+        retry_requirement: None,
+        reason: Some(Reason::UnexpectedChar),
+        found: Some(found),
+        context: CoreContext {
+            span: bytes.into(),
+            operation: CoreOperation::Lex,
+            expected: CoreExpected::Valid("token"),
+        },
+        input: origin.into_maybe_string(),
+    })
+}
+
+/// Iterator over the tokens within input created by [`Lexer::tokens()`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Tokens<'l, 'r, I, K, E> {
+    lexer: &'l Lexer<'r, K>,
+    origin: I,
+    input: Option<I>,
+    error: core::marker::PhantomData<fn() -> E>,
+}
+
+impl<I, K, E> Tokens<'_, '_, I, K, E> {
+    /// Returns the input that has not been split into tokens, or `None` if
+    /// the iterator ended with an error.
+    pub fn remaining(&self) -> Option<I>
+    where
+        I: Clone,
+    {
+        self.input.clone()
+    }
+}
+
+impl<'i, I, K, E> Iterator for Tokens<'_, '_, I, K, E>
+where
+    I: Input<'i>,
+    K: Copy,
+    E: From<ExpectedValid<'i>>,
+{
+    type Item = Result<(K, I), E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.lexer.split(self.input.take()?, self.origin.clone()) {
+            Ok((token, rest)) => {
+                self.input = Some(rest);
+                token.map(Ok)
+            }
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+impl<I, K, E> fmt::Debug for Tokens<'_, '_, I, K, E>
+where
+    I: fmt::Debug,
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tokens")
+            .field("lexer", &self.lexer)
+            .field("input", &self.input)
+            .finish()
+    }
+}
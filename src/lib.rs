@@ -22,9 +22,35 @@
 //! | `simd`           | **Enabled** | Enables all supported SIMD optimisations.          |
 //! | `unicode`        | **Enabled** | Enables improved unicode printing support.         |
 //! | `full-backtrace` | **Enabled** | Enables collection of all contexts for `Expected`. |
+//! | `color`          | _Disabled_  | Enables ANSI colored error output.                 |
+//! | `error-hook`     | _Disabled_  | Enables a global hook called on error creation.    |
+//! | `derive`         | _Disabled_  | Enables `#[derive(ReadBinary)]`.                   |
+//! | `recipes`        | _Disabled_  | Enables ready-made readers for common formats.     |
+//! | `codec`          | _Disabled_  | Enables hex, base32 and base64 codecs.             |
+//! | `const-fn`       | _Disabled_  | Enables `ConstReader` for parsing in `const fn`.   |
+//! | `unchecked-internals` | _Disabled_ | Skips re-checking slice bounds in release.   |
 //! | `zc`             | _Disabled_  | Enables `zc` crate support.                        |
-//! | `nom`            | _Disabled_  | Enables `nom` crate error support.                 |
+//! | `nom`            | _Disabled_  | Enables `nom` crate error support and adapters.    |
+//! | `winnow`         | _Disabled_  | Enables `winnow` stream and error support.         |
 //! | `regex`          | _Disabled_  | Enables `regex` pattern support.                   |
+//! | `simdutf8`       | _Disabled_  | Enables SIMD UTF-8 validation (requires std).      |
+//! | `tokio-util`     | _Disabled_  | Enables `tokio-util` codec support (requires std). |
+//! | `memmap2`        | _Disabled_  | Enables memory mapped file input (requires std).   |
+//! | `serde`          | _Disabled_  | Enables `serde` support for errors and `de`.       |
+//! | `miette`         | _Disabled_  | Enables `miette` diagnostics (requires std).       |
+//! | `defmt`          | _Disabled_  | Enables `defmt` formatting for errors.             |
+//! | `tracing`        | _Disabled_  | Enables `tracing` spans for reader contexts.       |
+//! | `heapless`       | _Disabled_  | Enables `HeaplessBacktrace` without alloc.         |
+//! | `smallvec`       | _Disabled_  | Enables `SmallVecBacktrace` (requires alloc).      |
+//! | `bytemuck`       | _Disabled_  | Enables reading `bytemuck::Pod` slices.            |
+//! | `encoding_rs`    | _Disabled_  | Enables decoding legacy text encodings.            |
+//! | `arbitrary`      | _Disabled_  | Enables `arbitrary` support.                       |
+//! | `proptest`       | _Disabled_  | Enables `proptest` strategies (requires std).      |
+//!
+//! Input is only ever sliced after its bounds are checked, and by default the
+//! slicing checks them a second time. The `unchecked-internals` feature drops
+//! that second check in builds without debug assertions, for those who need the
+//! last few percent and accept relying on the crate's own checks being correct.
 
 ///////////////////////////////////////////////////////////////////////////////
 // Library quirks & hacks
@@ -64,17 +90,58 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+mod macros;
 mod reader;
 mod support;
 mod util;
 
 pub mod display;
 pub mod error;
+pub mod grammar;
 pub mod input;
+pub mod lexer;
+pub mod stream;
+
+#[cfg(all(feature = "serde", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "alloc"))))]
+pub mod de;
+#[cfg(any(feature = "nom", feature = "winnow"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "nom", feature = "winnow"))))]
+pub mod interop;
+
+#[cfg(feature = "codec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "codec")))]
+pub mod codec;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod fuzz;
+#[cfg(feature = "recipes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "recipes")))]
+pub mod recipes;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod testing;
+
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod derive;
 
 pub use self::error::{Error, Expected, Fatal, Invalid, ToRetryRequirement};
 pub use self::input::{Bound, ByteArray, Bytes, Input, MaybeString, Span, String};
-pub use self::reader::{BytesReader, Peek, Reader, StringReader};
+pub use self::reader::{BytesReader, FromReader, Peek, Reader, StringReader};
+
+#[cfg(feature = "const-fn")]
+pub use self::reader::ConstReader;
+#[cfg(feature = "std")]
+pub use self::reader::IoReader;
+
+#[cfg(all(feature = "memmap2", feature = "std"))]
+pub use self::input::input_file;
+#[cfg(feature = "alloc")]
+pub use self::stream::read_all_retry;
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use dangerous_derive::ReadBinary;
 
 // Re-exported types from core::fmt along with `DisplayBase` and `Write`.
 // This is used crate wide with the exception of crate::display.
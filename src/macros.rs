@@ -0,0 +1,109 @@
+/// Read a sequence of fields from a reader.
+///
+/// Each field is read in order within a context named after the field, so an
+/// error reading `width` will show `width` in its context backtrace. The
+/// result is `Ok` with the struct (or a tuple of the fields if no struct path
+/// is given), or the first error hit.
+///
+/// A field is read with one of:
+///
+/// - A reader method call, such as `consume(b"PNG")` or `take(4)`.
+/// - A big-endian integer, such as `u32_be` or `i16_be`.
+/// - A type implementing [`FromReader`], such as `u8`, `u32` (little-endian)
+///   or `[u8; 4]`.
+/// - A closure taking the reader and returning a result, such as
+///   `|r| r.take_until(b'\0')`.
+///
+/// Fields already read are in scope for the fields after them, which allows
+/// for length prefixed values.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{Bytes, Input, Invalid};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Chunk<'i> {
+///     magic: (),
+///     len: u16,
+///     kind: [u8; 4],
+///     data: Bytes<'i>,
+/// }
+///
+/// let input = dangerous::input(b"PNG\x00\x03IHDRabc");
+/// let chunk = input.read_all::<_, _, Invalid>(|r| {
+///     dangerous::read!(r, Chunk {
+///         magic: consume(b"PNG"),
+///         len: u16_be,
+///         kind: [u8; 4],
+///         data: take(usize::from(len)),
+///     })
+/// })?;
+///
+/// assert_eq!(chunk.len, 3);
+/// assert_eq!(&chunk.kind, b"IHDR");
+/// assert_eq!(chunk.data, b"abc"[..]);
+/// # Ok::<(), Invalid>(())
+/// ```
+///
+/// [`FromReader`]: crate::FromReader
+#[macro_export]
+macro_rules! read {
+    ($r:expr, $($path:ident)::+ { $($fields:tt)* }) => {
+        $crate::__read_fields!(@munch $r ($($path)::+) [] $($fields)*)
+    };
+    ($r:expr, { $($fields:tt)* }) => {
+        $crate::__read_fields!(@munch $r () [] $($fields)*)
+    };
+}
+
+// Split the fields at top level commas and read them in nested matches, so
+// each field is in scope for the ones after it.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __read_fields {
+    (@munch $r:tt $ctor:tt [$($names:ident)*]) => {
+        $crate::__read_fields!(@build $ctor $($names)*)
+    };
+    (@munch $r:tt $ctor:tt [$($names:ident)*] $name:ident : $($rest:tt)*) => {
+        $crate::__read_fields!(@spec $r $ctor [$($names)*] $name [] $($rest)*)
+    };
+    (@spec $r:tt $ctor:tt [$($names:ident)*] $name:ident [$($spec:tt)+] , $($rest:tt)*) => {
+        match $r.context(stringify!($name), $crate::__read_fields!(@field $($spec)+)) {
+            ::core::result::Result::Ok($name) => {
+                $crate::__read_fields!(@munch $r $ctor [$($names)* $name] $($rest)*)
+            }
+            ::core::result::Result::Err(err) => ::core::result::Result::Err(err),
+        }
+    };
+    (@spec $r:tt $ctor:tt [$($names:ident)*] $name:ident [$($spec:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__read_fields!(@spec $r $ctor [$($names)*] $name [$($spec)* $next] $($rest)*)
+    };
+    (@spec $r:tt $ctor:tt [$($names:ident)*] $name:ident [$($spec:tt)+]) => {
+        $crate::__read_fields!(@spec $r $ctor [$($names)*] $name [$($spec)+] ,)
+    };
+    (@build () $($names:ident)*) => {
+        ::core::result::Result::Ok(($($names,)*))
+    };
+    (@build ($($path:ident)::+) $($names:ident)*) => {
+        ::core::result::Result::Ok($($path)::+ { $($names),* })
+    };
+    (@field | $($closure:tt)*) => { | $($closure)* };
+    (@field u16_be) => { $crate::__read_fields!(@be u16) };
+    (@field u32_be) => { $crate::__read_fields!(@be u32) };
+    (@field u64_be) => { $crate::__read_fields!(@be u64) };
+    (@field u128_be) => { $crate::__read_fields!(@be u128) };
+    (@field i16_be) => { $crate::__read_fields!(@be i16) };
+    (@field i32_be) => { $crate::__read_fields!(@be i32) };
+    (@field i64_be) => { $crate::__read_fields!(@be i64) };
+    (@field i128_be) => { $crate::__read_fields!(@be i128) };
+    (@field $method:ident ( $($args:tt)* )) => {
+        |r| r.$method($($args)*)
+    };
+    (@field $ty:ty) => {
+        |r| <$ty as $crate::FromReader<'_>>::from_reader(r)
+    };
+    (@be $int:ident) => {
+        |r| r.take_array().map(|bytes| $int::from_be_bytes($crate::ByteArray::into_dangerous(bytes)))
+    };
+}
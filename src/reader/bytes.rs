@@ -1,7 +1,19 @@
-use crate::error::{CoreOperation, ExpectedLength, ExpectedValid, WithContext};
+#[cfg(all(feature = "encoding_rs", feature = "alloc"))]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::error::Error;
+use crate::error::{CoreOperation, ExpectedLength, ExpectedValid, ExpectedValue, WithContext};
+#[cfg(all(feature = "encoding_rs", feature = "alloc"))]
+use crate::input::PrivateExt;
 use crate::input::{ByteArray, Bytes, String};
+use crate::util::fast;
 
 use super::BytesReader;
+#[cfg(feature = "alloc")]
+use super::FromReader;
 
 impl<'i, E> BytesReader<'i, E> {
     /// Read an array from input.
@@ -39,6 +51,121 @@ impl<'i, E> BytesReader<'i, E> {
         self.advance_opt(Bytes::split_array_opt)
     }
 
+    /// Read a slice of `count` plain old data values.
+    ///
+    /// The input is reinterpreted in place, so its byte order must already be
+    /// the native one and its start must be aligned for `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    ///
+    /// #[repr(align(4))]
+    /// struct Aligned([u8; 8]);
+    ///
+    /// let mut bytes = Aligned([0; 8]);
+    /// bytes.0[..4].copy_from_slice(&1u32.to_ne_bytes());
+    /// bytes.0[4..].copy_from_slice(&2u32.to_ne_bytes());
+    ///
+    /// let result: Result<_, Invalid> = dangerous::input(&bytes.0).read_all(|r| {
+    ///     r.take_pod_slice::<u32>(2)
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), [1, 2]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedLength`] if there is not enough input for `count`
+    /// values and [`ExpectedValid`] if the input is not aligned for `T`.
+    #[cfg(feature = "bytemuck")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+    pub fn take_pod_slice<T>(&mut self, count: usize) -> Result<&'i [T], E>
+    where
+        T: bytemuck::Pod,
+        E: From<ExpectedValid<'i>>,
+        E: From<ExpectedLength<'i>>,
+    {
+        self.try_advance(|input| input.split_pod_slice(count, CoreOperation::TakePodSlice))
+    }
+
+    /// Read a number of values with [`FromReader`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    ///
+    /// let result: Result<_, Invalid> = dangerous::input(&[1, 0, 2, 0]).read_all(|r| {
+    ///     r.read_vec::<u16>(2)
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), [1, 2]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the values could not be read.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn read_vec<T>(&mut self, len: usize) -> Result<Vec<T>, E>
+    where
+        E: Error<'i>,
+        T: FromReader<'i>,
+    {
+        // The length may not be trusted, so only reserve for as many values as
+        // could be left if each were a byte.
+        let mut values = Vec::with_capacity(len.min(self.remaining_bytes()));
+        for _ in 0..len {
+            values.push(T::from_reader(self)?);
+        }
+        Ok(values)
+    }
+
+    /// Read a length of input and decode it from a text encoding.
+    ///
+    /// See [`Bytes::to_dangerous_str_with()`] for how the input is decoded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    /// use encoding_rs::WINDOWS_1252;
+    ///
+    /// let result: Result<_, Invalid> = dangerous::input(b"caf\xe9!").read_all(|r| {
+    ///     let word = r.take_str_encoded(WINDOWS_1252, 4)?;
+    ///     r.consume(b'!')?;
+    ///     Ok(word)
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), "café");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedLength`] if there is not enough input for `len` and
+    /// [`ExpectedValid`] if the input is not valid in the encoding.
+    ///
+    /// [`Bytes::to_dangerous_str_with()`]: crate::Bytes::to_dangerous_str_with()
+    #[cfg(all(feature = "encoding_rs", feature = "alloc"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "encoding_rs", feature = "alloc"))))]
+    pub fn take_str_encoded(
+        &mut self,
+        encoding: &'static encoding_rs::Encoding,
+        len: usize,
+    ) -> Result<Cow<'i, str>, E>
+    where
+        E: From<ExpectedValid<'i>>,
+        E: From<ExpectedLength<'i>>,
+    {
+        self.try_advance(|input| {
+            let (head, tail) = input.split_at_for::<E>(len, CoreOperation::TakeStrEncoded)?;
+            head.decode_str(encoding, CoreOperation::TakeStrEncoded)
+                .map(|decoded| (decoded, tail))
+        })
+    }
+
     /// Read the remaining string input.
     ///
     /// # Errors
@@ -50,7 +177,7 @@ impl<'i, E> BytesReader<'i, E> {
         E: From<ExpectedValid<'i>>,
         E: From<ExpectedLength<'i>>,
     {
-        self.try_advance(|input| input.split_str_while(|_| true, CoreOperation::TakeRemainingStr))
+        self.try_advance(|input| input.split_str_remaining(CoreOperation::TakeRemainingStr))
     }
 
     /// Read a length of string input while a predicate check remains true.
@@ -85,6 +212,67 @@ impl<'i, E> BytesReader<'i, E> {
         self.try_advance(|input| input.try_split_str_while(pred, CoreOperation::TakeStrWhile))
     }
 
+    /// Skip input until a byte is found.
+    ///
+    /// This behaves as [`skip_until()`] with a `u8`, but only moves past the
+    /// skipped input instead of splitting it off, for hot paths that discard
+    /// framing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    ///
+    /// let result: Result<_, Invalid> = dangerous::input(b"junk\x7epayload").read_all(|r| {
+    ///     r.skip_until_byte(0x7e)?;
+    ///     r.consume(0x7e)?;
+    ///     Ok(r.take_remaining())
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), b"payload"[..]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValue`] if the byte could not be found.
+    ///
+    /// [`skip_until()`]: crate::Reader::skip_until()
+    #[inline]
+    pub fn skip_until_byte(&mut self, needle: u8) -> Result<(), E>
+    where
+        E: From<ExpectedValue<'i>>,
+    {
+        self.try_advance(|input| {
+            let index = fast::find_u8_match(needle, input.as_dangerous());
+            input
+                .skip_to_match(index, needle.into(), CoreOperation::SkipUntil)
+                .map(|next| ((), next))
+        })
+    }
+
+    /// Skip input until a slice is found.
+    ///
+    /// This behaves as [`skip_until()`] with a `&[u8]`, but only moves past
+    /// the skipped input instead of splitting it off.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValue`] if the slice could not be found.
+    ///
+    /// [`skip_until()`]: crate::Reader::skip_until()
+    #[inline]
+    pub fn skip_until_slice(&mut self, needle: &'i [u8]) -> Result<(), E>
+    where
+        E: From<ExpectedValue<'i>>,
+    {
+        self.try_advance(|input| {
+            let index = fast::find_slice_match(needle, input.as_dangerous());
+            input
+                .skip_to_match(index, needle.into(), CoreOperation::SkipUntil)
+                .map(|next| ((), next))
+        })
+    }
+
     /// Skip a length of string input while a predicate check remains true.
     ///
     /// # Errors
@@ -0,0 +1,228 @@
+use crate::error::Invalid;
+use crate::input::{Bound, Bytes};
+
+/// A reader over bytes that can be used in a `const fn`.
+///
+/// [`Reader`] is driven through `&mut` and traits, neither of which can be
+/// used in a `const fn`, so this covers the core primitives on their own:
+/// taking and skipping input, comparing tags and reading fixed-size integers.
+/// This allows magic numbers and tables known at compile time to be parsed
+/// and validated at compile time.
+///
+/// Each read takes the reader by value and returns it advanced. As all of the
+/// input is known up front, errors are always fatal [`Invalid`] errors.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::ConstReader;
+///
+/// const fn version(r: ConstReader<'_>) -> Result<u16, dangerous::Invalid> {
+///     let r = match r.consume(b"\x7fDNG") {
+///         Ok(r) => r,
+///         Err(err) => return Err(err),
+///     };
+///     match r.read_u16_be() {
+///         Ok((version, r)) if r.at_end() => Ok(version),
+///         Ok(_) => Err(dangerous::Invalid::fatal()),
+///         Err(err) => Err(err),
+///     }
+/// }
+///
+/// const VERSION: u16 = match version(ConstReader::new(b"\x7fDNG\x00\x02")) {
+///     Ok(version) => version,
+///     Err(_) => panic!("invalid header"),
+/// };
+///
+/// assert_eq!(VERSION, 2);
+/// ```
+///
+/// [`Reader`]: crate::Reader
+#[derive(Debug, Copy, Clone)]
+#[must_use = "reader must be consumed"]
+pub struct ConstReader<'i> {
+    bytes: &'i [u8],
+    start: usize,
+    end: usize,
+}
+
+macro_rules! impl_read_int {
+    ($($int:ident, $le:ident, $be:ident, $len:literal;)*) => {
+        $(
+            #[doc = concat!("Read a little-endian `", stringify!($int), "`.")]
+            ///
+            /// # Errors
+            ///
+            /// Returns [`Invalid`] if there was not enough input.
+            #[inline]
+            pub const fn $le(self) -> Result<($int, Self), Invalid> {
+                match self.take_array::<$len>() {
+                    Ok((bytes, r)) => Ok(($int::from_le_bytes(bytes), r)),
+                    Err(err) => Err(err),
+                }
+            }
+
+            #[doc = concat!("Read a big-endian `", stringify!($int), "`.")]
+            ///
+            /// # Errors
+            ///
+            /// Returns [`Invalid`] if there was not enough input.
+            #[inline]
+            pub const fn $be(self) -> Result<($int, Self), Invalid> {
+                match self.take_array::<$len>() {
+                    Ok((bytes, r)) => Ok(($int::from_be_bytes(bytes), r)),
+                    Err(err) => Err(err),
+                }
+            }
+        )*
+    };
+}
+
+impl<'i> ConstReader<'i> {
+    /// Creates a reader over bytes.
+    pub const fn new(bytes: &'i [u8]) -> Self {
+        Self {
+            bytes,
+            start: 0,
+            end: bytes.len(),
+        }
+    }
+
+    /// Returns the number of bytes left to read.
+    #[must_use]
+    #[inline]
+    pub const fn remaining_bytes(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns `true` if there is no more input to read.
+    #[must_use]
+    #[inline]
+    pub const fn at_end(&self) -> bool {
+        self.remaining_bytes() == 0
+    }
+
+    /// Returns `true` if the remaining input starts with a tag.
+    #[must_use]
+    pub const fn peek_eq(&self, tag: &[u8]) -> bool {
+        if tag.len() > self.remaining_bytes() {
+            return false;
+        }
+        let mut i = 0;
+        while i < tag.len() {
+            if self.bytes[self.start + i] != tag[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Consume a tag from the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Invalid`] if the input did not start with the tag.
+    #[inline]
+    pub const fn consume(self, tag: &[u8]) -> Result<Self, Invalid> {
+        if self.peek_eq(tag) {
+            Ok(self.advance(tag.len()))
+        } else {
+            Err(Invalid::fatal())
+        }
+    }
+
+    /// Take a length of input, returning it as its own reader along with the
+    /// rest.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Invalid`] if there was not enough input.
+    #[inline]
+    pub const fn take(self, len: usize) -> Result<(Self, Self), Invalid> {
+        if len > self.remaining_bytes() {
+            return Err(Invalid::fatal());
+        }
+        let taken = Self {
+            bytes: self.bytes,
+            start: self.start,
+            end: self.start + len,
+        };
+        Ok((taken, self.advance(len)))
+    }
+
+    /// Skip a length of input.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Invalid`] if there was not enough input.
+    #[inline]
+    pub const fn skip(self, len: usize) -> Result<Self, Invalid> {
+        if len > self.remaining_bytes() {
+            Err(Invalid::fatal())
+        } else {
+            Ok(self.advance(len))
+        }
+    }
+
+    /// Read a byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Invalid`] if there was no more input.
+    #[inline]
+    pub const fn read(self) -> Result<(u8, Self), Invalid> {
+        if self.at_end() {
+            Err(Invalid::fatal())
+        } else {
+            Ok((self.bytes[self.start], self.advance(1)))
+        }
+    }
+
+    /// Read an array of bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Invalid`] if there was not enough input.
+    pub const fn take_array<const N: usize>(self) -> Result<([u8; N], Self), Invalid> {
+        if N > self.remaining_bytes() {
+            return Err(Invalid::fatal());
+        }
+        let mut array = [0; N];
+        let mut i = 0;
+        while i < N {
+            array[i] = self.bytes[self.start + i];
+            i += 1;
+        }
+        Ok((array, self.advance(N)))
+    }
+
+    impl_read_int! {
+        u16, read_u16_le, read_u16_be, 2;
+        u32, read_u32_le, read_u32_be, 4;
+        u64, read_u64_le, read_u64_be, 8;
+    }
+
+    /// Returns the remaining input.
+    ///
+    /// This is not a `const fn`, as a slice can't be narrowed in one. The
+    /// readers only track where their input starts and ends until then.
+    pub fn into_input(self) -> Bytes<'i> {
+        Bytes::new(self.as_dangerous(), Bound::StartEnd)
+    }
+
+    /// Returns the remaining input as a byte slice.
+    ///
+    /// See [`Bytes::as_dangerous`] for naming.
+    #[must_use]
+    pub fn as_dangerous(&self) -> &'i [u8] {
+        self.bytes.get(self.start..self.end).unwrap_or_default()
+    }
+
+    /// Moves the start of the input forward, which must not pass its end.
+    #[inline(always)]
+    const fn advance(mut self, len: usize) -> Self {
+        self.start += len;
+        self
+    }
+}
@@ -0,0 +1,155 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::error::Error;
+use crate::input::ByteArray;
+
+use super::BytesReader;
+
+/// Implemented for types that can be read from a [`BytesReader`].
+///
+/// This is the common foundation for generic container readers such as
+/// [`BytesReader::read_vec()`]. The provided implementations use a fixed
+/// binary layout:
+///
+/// - Integers are little-endian.
+/// - Arrays and tuples are their values one after another.
+/// - `Option` is a tag byte of `0` for `None` or `1` for `Some`, followed by
+///   the value if present.
+/// - `Vec` is a little-endian `u32` count, followed by the values.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{BytesReader, Error, FromReader, Input, Invalid};
+///
+/// struct Point {
+///     x: u16,
+///     y: u16,
+/// }
+///
+/// impl<'i> FromReader<'i> for Point {
+///     fn from_reader<E>(r: &mut BytesReader<'i, E>) -> Result<Self, E>
+///     where
+///         E: Error<'i>,
+///     {
+///         let (x, y) = FromReader::from_reader(r)?;
+///         Ok(Self { x, y })
+///     }
+/// }
+///
+/// let point = dangerous::input(&[1, 0, 2, 0]).read_all::<_, _, Invalid>(Point::from_reader)?;
+///
+/// assert_eq!((point.x, point.y), (1, 2));
+/// # Ok::<(), Invalid>(())
+/// ```
+pub trait FromReader<'i>: Sized {
+    /// Read a value from the reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a valid value.
+    fn from_reader<E>(r: &mut BytesReader<'i, E>) -> Result<Self, E>
+    where
+        E: Error<'i>;
+}
+
+macro_rules! impl_int {
+    ($($ty:ty),*) => {
+        $(
+            impl<'i> FromReader<'i> for $ty {
+                #[inline]
+                fn from_reader<E>(r: &mut BytesReader<'i, E>) -> Result<Self, E>
+                where
+                    E: Error<'i>,
+                {
+                    r.take_array()
+                        .map(ByteArray::into_dangerous)
+                        .map(<$ty>::from_le_bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl<'i, T, const N: usize> FromReader<'i> for [T; N]
+where
+    T: FromReader<'i> + Default + Copy,
+{
+    fn from_reader<E>(r: &mut BytesReader<'i, E>) -> Result<Self, E>
+    where
+        E: Error<'i>,
+    {
+        let mut values = [T::default(); N];
+        for value in &mut values {
+            *value = T::from_reader(r)?;
+        }
+        Ok(values)
+    }
+}
+
+impl<'i, T> FromReader<'i> for Option<T>
+where
+    T: FromReader<'i>,
+{
+    fn from_reader<E>(r: &mut BytesReader<'i, E>) -> Result<Self, E>
+    where
+        E: Error<'i>,
+    {
+        let present = r.try_expect("option tag", |r| {
+            Ok(match r.read()? {
+                0 => Some(false),
+                1 => Some(true),
+                _ => None,
+            })
+        })?;
+        if present {
+            T::from_reader(r).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'i, T> FromReader<'i> for Vec<T>
+where
+    T: FromReader<'i>,
+{
+    fn from_reader<E>(r: &mut BytesReader<'i, E>) -> Result<Self, E>
+    where
+        E: Error<'i>,
+    {
+        let len = u32::from_reader(r)?;
+        // A count that does not fit will fail to be read.
+        r.read_vec(usize::try_from(len).unwrap_or(usize::MAX))
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident),+) => {
+        impl<'i, $($name),+> FromReader<'i> for ($($name,)+)
+        where
+            $($name: FromReader<'i>),+
+        {
+            #[inline]
+            fn from_reader<E>(r: &mut BytesReader<'i, E>) -> Result<Self, E>
+            where
+                E: Error<'i>,
+            {
+                Ok(($($name::from_reader(r)?,)+))
+            }
+        }
+    };
+}
+
+impl_tuple!(A);
+impl_tuple!(A, B);
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, F);
+impl_tuple!(A, B, C, D, F, G);
+impl_tuple!(A, B, C, D, F, G, H);
+impl_tuple!(A, B, C, D, F, G, H, I);
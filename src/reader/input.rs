@@ -1,11 +1,17 @@
+use crate::display::InputDisplay;
 use crate::input::{Input, Pattern, Prefix, PrivateExt};
 
 use crate::error::{
-    with_context, Context, CoreContext, CoreOperation, ExpectedLength, ExpectedValid,
-    ExpectedValue, External, Value, WithContext,
+    hook, with_context, Context, CoreContext, CoreExpected, CoreOperation, ExpectedLength,
+    ExpectedValid, ExpectedValue, External, Reason, Value, WithContext,
 };
 
-use super::{Peek, Reader};
+#[cfg(feature = "alloc")]
+use crate::error::MultiError;
+
+use super::{trace, Peek, Reader};
+
+const DISPLAY_WIDTH: usize = 80;
 
 impl<'i, I, E> Reader<'i, I, E>
 where
@@ -25,6 +31,32 @@ where
         self.input.byte_len()
     }
 
+    /// Returns a display of the input the `Reader` was created with, pointing
+    /// to where the reader is within it.
+    ///
+    /// The input left to read is the span of the display, so it is
+    /// highlighted or underlined while the input already consumed is not, and
+    /// input far from the position of the reader is elided. Useful when
+    /// stepping through a parser in tests or a debugger.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    ///
+    /// dangerous::input(b"GET /index.html").read_all::<_, _, Invalid>(|r| {
+    ///     r.consume(b"GET ")?;
+    ///     assert_eq!(r.display().str_hint().to_string(), r#""GET /index.html""#);
+    ///     assert_eq!(r.display().str_hint().underline().to_string(), "     ^^^^^^^^^^^ ");
+    ///     r.take_remaining();
+    ///     Ok(())
+    /// })?;
+    /// # Ok::<(), Invalid>(())
+    /// ```
+    pub fn display(&self) -> InputDisplay<'i> {
+        self.origin.display().span(self.input.span(), DISPLAY_WIDTH)
+    }
+
     /// Read all of the remaining input.
     #[inline(always)]
     pub fn take_remaining(&mut self) -> I {
@@ -33,6 +65,9 @@ where
 
     /// Use the `Reader` in a mutable context.
     ///
+    /// With the `tracing` feature, a `context` span recording the operation
+    /// and expected value of the context is entered while the function runs.
+    ///
     /// # Errors
     ///
     /// Returns any error returned by the provided function with the specified
@@ -43,6 +78,10 @@ where
         E: WithContext<'i>,
         F: FnOnce(&mut Self) -> Result<T, E>,
     {
+        let _span = trace::context(&context);
+        if E::NO_CONTEXT {
+            return f(self);
+        }
         with_context(context, self.input.clone(), || f(self))
     }
 
@@ -58,18 +97,222 @@ where
         E: WithContext<'i>,
         F: FnOnce(&Self) -> Result<T, E>,
     {
+        let _span = trace::context(&context);
+        if E::NO_CONTEXT {
+            return f(self);
+        }
         with_context(context, self.input.clone(), || f(self))
     }
 
+    /// Use the `Reader` one level of nesting deeper in a mutable context.
+    ///
+    /// Recursive parsers of nested structures, such as arrays within arrays,
+    /// should enter each level of nesting so that input nested deeper than
+    /// the maximum depth fails with an error instead of overflowing the
+    /// stack. The depth is carried over to the readers of sub-parses, such as
+    /// with [`try_expect()`](Self::try_expect()), but starts again for input
+    /// read with a new reader. See [`set_max_depth()`](Self::set_max_depth()).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::error::Reason;
+    /// use dangerous::{BytesReader, Expected, Input};
+    ///
+    /// fn read_list<'i>(r: &mut BytesReader<'i, Expected<'i>>) -> Result<usize, Expected<'i>> {
+    ///     r.enter("list", |r| {
+    ///         r.consume(b"[")?;
+    ///         let depth = if r.peek_eq(b"[") { read_list(r)? + 1 } else { 1 };
+    ///         r.consume(b"]")?;
+    ///         Ok(depth)
+    ///     })
+    /// }
+    ///
+    /// let result = dangerous::input(b"[[]]").read_all(|r| {
+    ///     r.set_max_depth(2);
+    ///     read_list(r)
+    /// });
+    /// assert_eq!(result.unwrap(), 2);
+    ///
+    /// let error = dangerous::input(b"[[[]]]")
+    ///     .read_all(|r| {
+    ///         r.set_max_depth(2);
+    ///         read_list(r)
+    ///     })
+    ///     .unwrap_err();
+    /// assert_eq!(error.reason(), Some(Reason::DepthExceeded));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValid`] if the maximum depth was reached, or any error
+    /// returned by the provided function, with the specified context
+    /// attached.
+    pub fn enter<F, T>(&mut self, context: impl Context, f: F) -> Result<T, E>
+    where
+        E: WithContext<'i>,
+        E: From<ExpectedValid<'i>>,
+        F: FnOnce(&mut Self) -> Result<T, E>,
+    {
+        self.context(context, |r| {
+            if r.limits.depth == r.limits.max_depth {
+                return Err(hook::create(ExpectedValid {
+                    retry_requirement: None,
+                    reason: Some(Reason::DepthExceeded),
+                    found: None,
+                    context: CoreContext {
+                        span: r.input.span().start(),
+                        operation: CoreOperation::Enter,
+                        expected: CoreExpected::MaxDepth(r.limits.max_depth),
+                    },
+                    input: r.input.clone().into_maybe_string(),
+                }));
+            }
+            r.limits.depth += 1;
+            let result = f(r);
+            r.limits.depth -= 1;
+            result
+        })
+    }
+
+    /// Returns the depth of nesting the `Reader` has entered.
+    ///
+    /// See [`enter()`](Self::enter()).
+    #[must_use]
+    #[inline(always)]
+    pub fn depth(&self) -> usize {
+        self.limits.depth
+    }
+
+    /// Sets the maximum depth of nesting the `Reader` can enter.
+    ///
+    /// See [`enter()`](Self::enter()). Defaults to `128`.
+    #[inline(always)]
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.limits.max_depth = max_depth;
+    }
+
+    /// Returns the iteration fuel the `Reader` has left to burn, or `None` if
+    /// it is unlimited.
+    ///
+    /// See [`set_fuel()`](Self::set_fuel()).
+    #[must_use]
+    #[inline(always)]
+    pub fn fuel(&self) -> Option<usize> {
+        self.limits.fuel
+    }
+
+    /// Sets the iteration fuel the `Reader` can burn.
+    ///
+    /// Fuel is burnt by [`repeat()`](Self::repeat()) for each iteration, and
+    /// by [`burn_fuel()`](Self::burn_fuel()) for hand written loops. Once the
+    /// fuel runs out, reading fails fast with an error instead of looping on
+    /// pathological input for as long as it allows. Like the depth, fuel is
+    /// carried over to the readers of sub-parses and what is burnt there is
+    /// burnt from the `Reader`. Fuel is unlimited by default.
+    #[inline(always)]
+    pub fn set_fuel(&mut self, fuel: usize) {
+        self.limits.fuel = Some(fuel);
+    }
+
+    /// Burns an amount of iteration fuel.
+    ///
+    /// Does nothing if fuel is unlimited. See [`set_fuel()`](Self::set_fuel()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValid`] if there is not enough fuel left to burn.
+    pub fn burn_fuel(&mut self, amount: usize) -> Result<(), E>
+    where
+        E: From<ExpectedValid<'i>>,
+    {
+        match self.limits.fuel {
+            None => Ok(()),
+            Some(fuel) if amount <= fuel => {
+                self.limits.fuel = Some(fuel - amount);
+                Ok(())
+            }
+            Some(_) => Err(hook::create(ExpectedValid {
+                retry_requirement: None,
+                reason: Some(Reason::FuelExhausted),
+                found: None,
+                context: CoreContext {
+                    span: self.input.span().start(),
+                    operation: CoreOperation::BurnFuel,
+                    expected: CoreExpected::Fuel(amount),
+                },
+                input: self.input.clone().into_maybe_string(),
+            })),
+        }
+    }
+
+    /// Repeats a read while it returns `true`, burning one unit of iteration
+    /// fuel before each.
+    ///
+    /// Returns the number of times the read returned `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::error::Reason;
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let items = dangerous::input(b"a,b,c").read_all::<_, _, Expected<'_>>(|r| {
+    ///     r.repeat(|r| {
+    ///         r.skip(1)?;
+    ///         Ok(r.consume_opt(b","))
+    ///     })
+    /// });
+    /// assert_eq!(items.unwrap(), 2);
+    ///
+    /// let error = dangerous::input(b"a,b,c")
+    ///     .read_all::<_, _, Expected<'_>>(|r| {
+    ///         r.set_fuel(2);
+    ///         r.repeat(|r| {
+    ///             r.skip(1)?;
+    ///             Ok(r.consume_opt(b","))
+    ///         })
+    ///     })
+    ///     .unwrap_err();
+    /// assert_eq!(error.reason(), Some(Reason::FuelExhausted));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValid`] if the fuel runs out, or any error returned by
+    /// the provided function.
+    pub fn repeat<F>(&mut self, mut f: F) -> Result<usize, E>
+    where
+        E: WithContext<'i>,
+        E: From<ExpectedValid<'i>>,
+        F: FnMut(&mut Self) -> Result<bool, E>,
+    {
+        let context = CoreContext::from_operation(CoreOperation::Repeat, self.input.span());
+        self.context(context, |r| {
+            let mut count = 0;
+            loop {
+                r.burn_fuel(1)?;
+                if !f(r)? {
+                    return Ok(count);
+                }
+                count += 1;
+            }
+        })
+    }
+
     /// Read a length of input that was successfully consumed from a sub-parse.
     pub fn take_consumed<F, T>(&mut self, consumer: F) -> (T, I)
     where
         F: FnOnce(&mut Self) -> T,
     {
-        self.advance(|input| {
+        let mut limits = self.limits;
+        let consumer = Self::nested(&mut limits, consumer);
+        let value = self.advance(|input| {
             let (value, head, tail) = input.split_consumed(consumer);
             ((value, head), tail)
-        })
+        });
+        self.limits = limits;
+        value
     }
 
     /// Try read a length of input that was successfully consumed from a
@@ -100,11 +343,15 @@ where
         E: WithContext<'i>,
         F: FnOnce(&mut Self) -> Result<T, E>,
     {
-        self.try_advance(|input| {
+        let mut limits = self.limits;
+        let consumer = Self::nested(&mut limits, consumer);
+        let value = self.try_advance(|input| {
             input
                 .try_split_consumed_for(consumer, CoreOperation::TakeConsumed)
                 .map(|(value, head, tail)| ((value, head), tail))
-        })
+        });
+        self.limits = limits;
+        value
     }
 
     /// Read and verify a value without returning it.
@@ -117,7 +364,9 @@ where
         F: FnOnce(&mut Self) -> bool,
         E: From<ExpectedValid<'i>>,
     {
-        self.try_advance(|input| {
+        let mut limits = self.limits;
+        let verifier = Self::nested(&mut limits, verifier);
+        let value = self.try_advance(|input| {
             input.split_expect_for(
                 |r: &mut Self| {
                     if verifier(r) {
@@ -129,7 +378,9 @@ where
                 expected,
                 CoreOperation::Verify,
             )
-        })
+        });
+        self.limits = limits;
+        value
     }
 
     /// Try read and verify a value without returning it.
@@ -143,7 +394,9 @@ where
         E: WithContext<'i>,
         E: From<ExpectedValid<'i>>,
     {
-        self.try_advance(|input| {
+        let mut limits = self.limits;
+        let verifier = Self::nested(&mut limits, verifier);
+        let value = self.try_advance(|input| {
             input.try_split_expect_for(
                 |r: &mut Self| match verifier(r) {
                     Ok(true) => Ok(Some(())),
@@ -153,7 +406,9 @@ where
                 expected,
                 CoreOperation::Verify,
             )
-        })
+        });
+        self.limits = limits;
+        value
     }
 
     /// Expect a value to be read and returned as `Some(T)`.
@@ -166,7 +421,12 @@ where
         F: FnOnce(&mut Self) -> Option<T>,
         E: From<ExpectedValid<'i>>,
     {
-        self.try_advance(|input| input.split_expect_for(f, expected, CoreOperation::Expect))
+        let mut limits = self.limits;
+        let f = Self::nested(&mut limits, f);
+        let value =
+            self.try_advance(|input| input.split_expect_for(f, expected, CoreOperation::Expect));
+        self.limits = limits;
+        value
     }
 
     /// Expect a value to be read successfully and returned as `Some(T)`.
@@ -181,7 +441,12 @@ where
         E: From<ExpectedValid<'i>>,
         F: FnOnce(&mut Self) -> Result<Option<T>, E>,
     {
-        self.try_advance(|input| input.try_split_expect_for(f, expected, CoreOperation::Expect))
+        let mut limits = self.limits;
+        let f = Self::nested(&mut limits, f);
+        let value = self
+            .try_advance(|input| input.try_split_expect_for(f, expected, CoreOperation::Expect));
+        self.limits = limits;
+        value
     }
 
     /// Tries to read an expected value with support for an external error.
@@ -270,6 +535,29 @@ where
         }
     }
 
+    /// Recovers from an error returning `Some(T)` if successful, or `None` if
+    /// an error occurred, collecting the error into `errors`.
+    ///
+    /// If an error is recovered from the `Reader`'s internal state is reset,
+    /// so the caller can resync to the next item before continuing.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn recover_into<F, T>(&mut self, errors: &mut MultiError<E>, f: F) -> Option<T>
+    where
+        E: WithContext<'i>,
+        F: FnOnce(&mut Self) -> Result<T, E>,
+    {
+        let checkpoint = self.input.clone();
+        match f(self) {
+            Ok(ok) => Some(ok),
+            Err(err) => {
+                errors.push(err.with_input(checkpoint.clone()));
+                self.input = checkpoint;
+                None
+            }
+        }
+    }
+
     /// Recovers from an error based on a predicate.
     ///
     /// If an error is recovered from the `Reader`'s internal state is reset.
@@ -347,11 +635,15 @@ where
     where
         F: FnOnce(&mut Reader<'i, I, S>) -> T,
     {
-        self.advance(|input| {
+        let mut limits = self.limits;
+        let f = Self::nested(&mut limits, f);
+        let value = self.advance(|input| {
             let mut sub = Reader::new(input);
             let ok = f(&mut sub);
             (ok, sub.input)
-        })
+        });
+        self.limits = limits;
+        value
     }
 
     /// Read a token.
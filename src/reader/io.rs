@@ -0,0 +1,80 @@
+use std::io;
+
+use crate::fmt;
+use crate::input::{Input, Private};
+
+use super::BytesReader;
+
+/// An [`io::Read`] and [`io::BufRead`] over the unconsumed input of a
+/// [`BytesReader`].
+///
+/// Created with [`BytesReader::io()`]. Reading advances the reader, so once
+/// the adapter is dropped the reader continues after what was read. With
+/// [`io::BufRead`] the unconsumed input is lent out directly without being
+/// copied.
+///
+/// The end of the unconsumed input is reported as the end of the stream, even
+/// if the input is not bound.
+pub struct IoReader<'r, 'i, E> {
+    reader: &'r mut BytesReader<'i, E>,
+}
+
+impl<'i, E> BytesReader<'i, E> {
+    /// Returns an [`io::Read`] over the unconsumed input.
+    ///
+    /// This allows the payload of a container to be handed to APIs that only
+    /// accept [`io::Read`], such as decompressors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Read;
+    /// use dangerous::{Input, Invalid};
+    ///
+    /// let result: Result<_, Invalid> = dangerous::input(b"\x05hello world").read_partial(|r| {
+    ///     let len = r.read()?;
+    ///     let mut payload = Vec::new();
+    ///     r.io().take(u64::from(len)).read_to_end(&mut payload).unwrap();
+    ///     Ok(payload)
+    /// });
+    ///
+    /// let (payload, remaining) = result.unwrap();
+    /// assert_eq!(payload, b"hello");
+    /// assert_eq!(remaining, b" world"[..]);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn io(&mut self) -> IoReader<'_, 'i, E> {
+        IoReader { reader: self }
+    }
+}
+
+impl<E> io::Read for IoReader<'_, '_, E> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut remaining = self.reader.input.as_dangerous();
+        let len = remaining.read(buf)?;
+        io::BufRead::consume(self, len);
+        Ok(len)
+    }
+}
+
+impl<E> io::BufRead for IoReader<'_, '_, E> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.reader.input.as_dangerous())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.reader
+            .advance(|input| match input.clone().split_at_opt(amt) {
+                Some((_, tail)) => ((), tail),
+                None => ((), input.end()),
+            });
+    }
+}
+
+impl<E> fmt::Debug for IoReader<'_, '_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IoReader")
+            .field("reader", &self.reader)
+            .finish()
+    }
+}
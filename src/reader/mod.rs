@@ -1,12 +1,23 @@
 mod bytes;
+#[cfg(feature = "const-fn")]
+mod const_reader;
+mod from_reader;
 mod input;
+#[cfg(feature = "std")]
+mod io;
 mod peek;
+mod trace;
 
 use core::marker::PhantomData;
 
 use crate::fmt;
 use crate::input::{Bytes, Input, String};
 
+#[cfg(feature = "const-fn")]
+pub use self::const_reader::ConstReader;
+pub use self::from_reader::FromReader;
+#[cfg(feature = "std")]
+pub use self::io::IoReader;
 pub use self::peek::Peek;
 
 /// [`Bytes`] specific [`Reader`].
@@ -15,6 +26,8 @@ pub type BytesReader<'i, E> = Reader<'i, Bytes<'i>, E>;
 /// [`String`] specific [`Reader`].
 pub type StringReader<'i, E> = Reader<'i, String<'i>, E>;
 
+const DEFAULT_MAX_DEPTH: usize = 128;
+
 /// Created from and consumes an [`Input`].
 ///
 /// You can only create a [`Reader`] from [`Input`] via [`Input::read_all()`],
@@ -94,9 +107,20 @@ pub type StringReader<'i, E> = Reader<'i, String<'i>, E>;
 /// [`RetryRequirement`]: crate::error::RetryRequirement  
 pub struct Reader<'i, I, E> {
     input: I,
+    /// The input the reader was created with.
+    origin: I,
+    limits: Limits,
     types: PhantomData<(&'i (), E)>,
 }
 
+/// The limits of a [`Reader`] carried over to the readers of its sub-parses.
+#[derive(Copy, Clone)]
+struct Limits {
+    depth: usize,
+    max_depth: usize,
+    fuel: Option<usize>,
+}
+
 impl<'i, I, E> Reader<'i, I, E>
 where
     I: Input<'i>,
@@ -104,11 +128,35 @@ where
     /// Create a `Reader` given `Input`.
     pub(crate) fn new(input: I) -> Self {
         Self {
+            origin: input.clone(),
             input,
+            limits: Limits {
+                depth: 0,
+                max_depth: DEFAULT_MAX_DEPTH,
+                fuel: None,
+            },
             types: PhantomData,
         }
     }
 
+    /// Returns `f` wrapped to carry the `limits` over to the sub-reader it is
+    /// called with, and what is left of them back once it returns.
+    #[inline(always)]
+    fn nested<'l, F, T, S>(
+        limits: &'l mut Limits,
+        f: F,
+    ) -> impl FnOnce(&mut Reader<'i, I, S>) -> T + 'l
+    where
+        F: FnOnce(&mut Reader<'i, I, S>) -> T + 'l,
+    {
+        move |r| {
+            r.limits = *limits;
+            let value = f(r);
+            *limits = r.limits;
+            value
+        }
+    }
+
     /// Advances the reader's input given an operation.
     #[inline(always)]
     fn advance<F, O>(&mut self, f: F) -> O
@@ -134,9 +182,22 @@ where
         }
     }
 
+    /// Returns the number of bytes consumed from the input the reader was
+    /// created with.
+    #[inline(always)]
+    pub(crate) fn consumed_bytes(&self) -> usize {
+        self.origin.byte_len().saturating_sub(self.input.byte_len())
+    }
+
+    /// Returns `true` if entering another level of nesting would fail.
+    #[inline(always)]
+    pub(crate) fn at_max_depth(&self) -> bool {
+        self.limits.depth == self.limits.max_depth
+    }
+
     /// Tries to advance the reader's input given an operation.
     #[inline(always)]
-    fn try_advance<F, SE, O>(&mut self, f: F) -> Result<O, SE>
+    pub(crate) fn try_advance<F, SE, O>(&mut self, f: F) -> Result<O, SE>
     where
         F: FnOnce(I) -> Result<(O, I), SE>,
     {
@@ -0,0 +1,58 @@
+use crate::error::Context;
+#[cfg(feature = "tracing")]
+use crate::fmt;
+
+/// Guard exiting the span entered for a reader operation when dropped.
+#[cfg(feature = "tracing")]
+pub(crate) type Entered = tracing::span::EnteredSpan;
+
+/// Without the `tracing` feature there is no span to exit.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Entered;
+
+/// Enters a `context` span for a reader context.
+///
+/// The operation and expected value of the context are recorded as fields,
+/// and are only written if the span is enabled.
+#[cfg(feature = "tracing")]
+#[inline(always)]
+pub(crate) fn context(context: &dyn Context) -> Entered {
+    tracing::trace_span!(
+        target: "dangerous",
+        "context",
+        operation = %ContextOperation(context),
+        expected = %ContextExpected(context),
+    )
+    .entered()
+}
+
+/// Without the `tracing` feature there is no span to enter.
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn context(_context: &dyn Context) -> Entered {
+    Entered
+}
+
+#[cfg(feature = "tracing")]
+struct ContextOperation<'a>(&'a dyn Context);
+
+#[cfg(feature = "tracing")]
+impl fmt::Display for ContextOperation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.operation().description(f)
+    }
+}
+
+#[cfg(feature = "tracing")]
+struct ContextExpected<'a>(&'a dyn Context);
+
+#[cfg(feature = "tracing")]
+impl fmt::Display for ContextExpected<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.has_expected() {
+            self.0.expected(f)
+        } else {
+            Ok(())
+        }
+    }
+}
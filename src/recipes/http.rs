@@ -0,0 +1,319 @@
+//! An HTTP/1.1 ([RFC 7230]) message head reader.
+//!
+//! Request lines, status lines and header fields are read following the
+//! grammar's token rules. Line endings must be `CRLF`, and obsolete line
+//! folding within header fields is rejected.
+//!
+//! # Streaming
+//!
+//! Running out of input is never fatal, so a head can be read from a buffer
+//! that has only partly arrived. An error that returns a
+//! [`RetryRequirement`] means the head is incomplete rather than invalid,
+//! and can be read again once more input arrives.
+//!
+//! ```
+//! use dangerous::recipes::http;
+//! use dangerous::{Input, Invalid, ToRetryRequirement};
+//!
+//! let message = b"GET /index.html HTTP/1.1\r\nHost: example.com\r\n\r\nbody";
+//!
+//! let err = dangerous::input(&message[..20])
+//!     .read_partial::<_, _, Invalid>(http::read_request_head)
+//!     .unwrap_err();
+//! assert!(err.to_retry_requirement().is_some());
+//!
+//! let (head, body) = dangerous::input(&message[..])
+//!     .read_partial::<_, _, Invalid>(http::read_request_head)?;
+//!
+//! assert_eq!(head.line.method, "GET");
+//! assert_eq!(head.line.target, "/index.html");
+//! assert_eq!(head.header("host"), Some(&b"example.com"[..]));
+//! assert_eq!(body, b"body"[..]);
+//! # Ok::<(), Invalid>(())
+//! ```
+//!
+//! [RFC 7230]: https://tools.ietf.org/html/rfc7230
+//! [`RetryRequirement`]: crate::error::RetryRequirement
+
+use alloc::vec::Vec;
+
+use crate::input::AsciiClass;
+use crate::{BytesReader, Error};
+
+/// An HTTP version.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    /// The major version.
+    pub major: u8,
+    /// The minor version.
+    pub minor: u8,
+}
+
+/// The first line of an HTTP request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestLine<'i> {
+    /// The request method, such as `GET`.
+    pub method: &'i str,
+    /// The request target, such as `/index.html`.
+    pub target: &'i str,
+    /// The HTTP version of the request.
+    pub version: Version,
+}
+
+/// The first line of an HTTP response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusLine<'i> {
+    /// The HTTP version of the response.
+    pub version: Version,
+    /// The three digit status code, such as `404`.
+    pub code: u16,
+    /// The reason phrase, which may contain non-ASCII bytes.
+    pub reason: &'i [u8],
+}
+
+/// An HTTP header field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderField<'i> {
+    /// The field name, as it was written.
+    pub name: &'i str,
+    /// The field value, without surrounding whitespace, which may contain
+    /// non-ASCII bytes.
+    pub value: &'i [u8],
+}
+
+/// The head of an HTTP request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestHead<'i> {
+    /// The request line.
+    pub line: RequestLine<'i>,
+    /// The header fields, in the order they were read.
+    pub headers: Vec<HeaderField<'i>>,
+}
+
+impl<'i> RequestHead<'i> {
+    /// Returns the value of the first header field with the name, ignoring
+    /// ASCII case.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&'i [u8]> {
+        find_header(&self.headers, name)
+    }
+}
+
+/// The head of an HTTP response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseHead<'i> {
+    /// The status line.
+    pub line: StatusLine<'i>,
+    /// The header fields, in the order they were read.
+    pub headers: Vec<HeaderField<'i>>,
+}
+
+impl<'i> ResponseHead<'i> {
+    /// Returns the value of the first header field with the name, ignoring
+    /// ASCII case.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&'i [u8]> {
+        find_header(&self.headers, name)
+    }
+}
+
+fn find_header<'i>(headers: &[HeaderField<'i>], name: &str) -> Option<&'i [u8]> {
+    headers
+        .iter()
+        .find(|field| field.name.eq_ignore_ascii_case(name))
+        .map(|field| field.value)
+}
+
+/// Read a request line and the header fields that follow, up to and
+/// including the empty line that ends the head.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid request head.
+pub fn read_request_head<'i, E>(r: &mut BytesReader<'i, E>) -> Result<RequestHead<'i>, E>
+where
+    E: Error<'i>,
+{
+    r.context("http request head", |r| {
+        let line = read_request_line(r)?;
+        let headers = read_header_fields(r)?;
+        Ok(RequestHead { line, headers })
+    })
+}
+
+/// Read a status line and the header fields that follow, up to and
+/// including the empty line that ends the head.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid response head.
+pub fn read_response_head<'i, E>(r: &mut BytesReader<'i, E>) -> Result<ResponseHead<'i>, E>
+where
+    E: Error<'i>,
+{
+    r.context("http response head", |r| {
+        let line = read_status_line(r)?;
+        let headers = read_header_fields(r)?;
+        Ok(ResponseHead { line, headers })
+    })
+}
+
+/// Read a request line, including its `CRLF`.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid request line.
+pub fn read_request_line<'i, E>(r: &mut BytesReader<'i, E>) -> Result<RequestLine<'i>, E>
+where
+    E: Error<'i>,
+{
+    r.context("http request line", |r| {
+        let method = read_token(r, "method")?;
+        r.consume(b' ')?;
+        let target = r.try_expect("request target", |r| {
+            r.peek_read()?;
+            let target = r.take_while(|c: u8| c.is_ascii_graphic());
+            Ok(if target.is_empty() {
+                None
+            } else {
+                Some(target)
+            })
+        })?;
+        r.consume(b' ')?;
+        let version = read_version(r)?;
+        r.consume(b"\r\n")?;
+        Ok(RequestLine {
+            method,
+            target: target.to_dangerous_str::<E>()?,
+            version,
+        })
+    })
+}
+
+/// Read a status line, including its `CRLF`.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid status line.
+pub fn read_status_line<'i, E>(r: &mut BytesReader<'i, E>) -> Result<StatusLine<'i>, E>
+where
+    E: Error<'i>,
+{
+    r.context("http status line", |r| {
+        let version = read_version(r)?;
+        r.consume(b' ')?;
+        let code = r.try_expect("status code", |r| {
+            let digits = r.take_array::<3>()?.into_dangerous();
+            Ok(digits.iter().try_fold(0_u16, |acc, &digit| {
+                let digit = char::from(digit).to_digit(10)?;
+                Some(acc * 10 + u16::try_from(digit).ok()?)
+            }))
+        })?;
+        r.consume(b' ')?;
+        let reason = r.take_while(is_field_char).as_dangerous();
+        r.consume(b"\r\n")?;
+        Ok(StatusLine {
+            version,
+            code,
+            reason,
+        })
+    })
+}
+
+/// Read header fields up to and including the empty line that ends them.
+///
+/// # Errors
+///
+/// Returns an error if the input is not valid header fields.
+pub fn read_header_fields<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Vec<HeaderField<'i>>, E>
+where
+    E: Error<'i>,
+{
+    let mut fields = Vec::new();
+    // A lone `CR` may be the start of the empty line, so is read as one to
+    // ask for more input rather than as an invalid field name.
+    while !r.peek_eq(b'\r') {
+        fields.push(read_header_field(r)?);
+    }
+    r.consume(b"\r\n")?;
+    Ok(fields)
+}
+
+/// Read a header field, including its `CRLF`.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid header field.
+pub fn read_header_field<'i, E>(r: &mut BytesReader<'i, E>) -> Result<HeaderField<'i>, E>
+where
+    E: Error<'i>,
+{
+    r.context("http header field", |r| {
+        let name = read_token(r, "field name")?;
+        r.consume(b':')?;
+        skip_whitespace(r);
+        let value = r.take_while(is_field_char).as_dangerous();
+        r.consume(b"\r\n")?;
+        // Values may contain whitespace, so only the end is trimmed once the
+        // whole value is known.
+        let len = value
+            .iter()
+            .rposition(|c| !matches!(c, b' ' | b'\t'))
+            .map_or(0, |index| index + 1);
+        Ok(HeaderField {
+            name,
+            value: &value[..len],
+        })
+    })
+}
+
+fn read_version<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Version, E>
+where
+    E: Error<'i>,
+{
+    r.context("http version", |r| {
+        r.consume(b"HTTP/")?;
+        let major = read_digit(r)?;
+        r.consume(b'.')?;
+        let minor = read_digit(r)?;
+        Ok(Version { major, minor })
+    })
+}
+
+fn read_digit<'i, E>(r: &mut BytesReader<'i, E>) -> Result<u8, E>
+where
+    E: Error<'i>,
+{
+    r.try_expect("digit", |r| {
+        r.read().map(|c| {
+            if c.is_ascii_digit() {
+                Some(c - b'0')
+            } else {
+                None
+            }
+        })
+    })
+}
+
+/// Reads a non-empty token, asking for more input if there is none rather
+/// than returning an empty one.
+fn read_token<'i, E>(r: &mut BytesReader<'i, E>, expected: &'static str) -> Result<&'i str, E>
+where
+    E: Error<'i>,
+{
+    let token = r.try_expect(expected, |r| {
+        r.peek_read()?;
+        let token = r.take_while(AsciiClass::Token);
+        Ok(if token.is_empty() { None } else { Some(token) })
+    })?;
+    token.to_dangerous_str()
+}
+
+/// `HTAB`, `SP`, `VCHAR` or `obs-text`.
+fn is_field_char(c: u8) -> bool {
+    c == b'\t' || c == b' ' || c.is_ascii_graphic() || c >= 0x80
+}
+
+fn skip_whitespace<E>(r: &mut BytesReader<'_, E>) {
+    r.skip_while(|c: u8| c == b' ' || c == b'\t');
+}
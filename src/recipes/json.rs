@@ -0,0 +1,378 @@
+//! A JSON ([RFC 8259]) value reader.
+//!
+//! Strings are borrowed from the input unless they contain escapes, and
+//! numbers are kept as their validated text so no precision is lost before
+//! the caller picks a type. Arrays and objects are read with
+//! [`Reader::enter()`], so the depth of nesting is limited by the reader.
+//!
+//! # Example
+//!
+//! ```
+//! use dangerous::recipes::json::{self, Value};
+//! use dangerous::Expected;
+//!
+//! let value = json::from_bytes::<Expected<'_>>(br#"{ "name": "bob", "age": 42 }"#)?;
+//!
+//! assert_eq!(value.get("name").and_then(Value::as_str), Some("bob"));
+//! assert_eq!(value.get("age").and_then(Value::as_number).and_then(|n| n.to_u64()), Some(42));
+//! # Ok::<(), Expected<'static>>(())
+//! ```
+//!
+//! [RFC 8259]: https://tools.ietf.org/html/rfc8259
+//! [`Reader::enter()`]: crate::Reader::enter()
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::input::AsciiClass;
+use crate::{BytesReader, Error, Input};
+
+/// A JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'i> {
+    /// `null`.
+    Null,
+    /// `true` or `false`.
+    Bool(bool),
+    /// A number.
+    Number(Number<'i>),
+    /// A string, borrowed from the input if it had no escapes.
+    String(Cow<'i, str>),
+    /// An array of values.
+    Array(Vec<Value<'i>>),
+    /// The members of an object, in the order they were read.
+    ///
+    /// Duplicate keys are kept as they were found.
+    Object(Vec<(Cow<'i, str>, Value<'i>)>),
+}
+
+impl<'i> Value<'i> {
+    /// Returns `true` if the value is `null`.
+    #[must_use]
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Returns the boolean if the value is one.
+    #[must_use]
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Self::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if the value is one.
+    #[must_use]
+    pub fn as_number(&self) -> Option<Number<'i>> {
+        match *self {
+            Self::Number(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if the value is one.
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the values if the value is an array.
+    #[must_use]
+    pub fn as_array(&self) -> Option<&[Value<'i>]> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the members if the value is an object.
+    #[must_use]
+    pub fn as_object(&self) -> Option<&[(Cow<'i, str>, Value<'i>)]> {
+        match self {
+            Self::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the first member with the key if the value is an
+    /// object.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value<'i>> {
+        self.as_object()?
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+}
+
+/// A JSON number, kept as the text it was read from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Number<'i>(&'i str);
+
+impl<'i> Number<'i> {
+    /// Returns the number as it was written.
+    #[must_use]
+    pub fn as_str(&self) -> &'i str {
+        self.0
+    }
+
+    /// Returns the number as the nearest `f64`.
+    ///
+    /// Numbers too large for an `f64` are returned as infinity.
+    #[must_use]
+    pub fn to_f64(&self) -> f64 {
+        // The grammar of a JSON number is a subset of what `f64` parses.
+        self.0.parse().unwrap_or(f64::NAN)
+    }
+
+    /// Returns the number as an `i64` if it is an integer within range.
+    ///
+    /// Integers written with a fraction or exponent return `None`.
+    #[must_use]
+    pub fn to_i64(&self) -> Option<i64> {
+        self.0.parse().ok()
+    }
+
+    /// Returns the number as a `u64` if it is a non-negative integer within
+    /// range.
+    ///
+    /// Integers written with a fraction or exponent return `None`.
+    #[must_use]
+    pub fn to_u64(&self) -> Option<u64> {
+        self.0.parse().ok()
+    }
+}
+
+/// Read a JSON value from all of the bytes.
+///
+/// # Errors
+///
+/// Returns an error if the bytes are not a valid JSON value, or if there is
+/// anything other than whitespace after it.
+pub fn from_bytes<'i, E>(bytes: &'i [u8]) -> Result<Value<'i>, E>
+where
+    E: Error<'i>,
+{
+    crate::input(bytes).read_all(read_value)
+}
+
+/// Read a JSON value from the reader, along with any whitespace around it.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid JSON value.
+pub fn read_value<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Value<'i>, E>
+where
+    E: Error<'i>,
+{
+    skip_whitespace(r);
+    let value = match r.peek_read()? {
+        b'{' => Value::Object(read_object(r)?),
+        b'[' => Value::Array(read_array(r)?),
+        b'"' => Value::String(read_string(r)?),
+        b'-' | b'0'..=b'9' => Value::Number(read_number(r)?),
+        b't' | b'f' | b'n' => read_literal(r)?,
+        _ => r.try_expect("json value", |r| r.read().map(|_| None))?,
+    };
+    skip_whitespace(r);
+    Ok(value)
+}
+
+fn read_object<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Vec<(Cow<'i, str>, Value<'i>)>, E>
+where
+    E: Error<'i>,
+{
+    r.enter("json object", |r| {
+        let mut members = Vec::new();
+        r.consume(b'{')?;
+        skip_whitespace(r);
+        if r.consume_opt(b'}') {
+            return Ok(members);
+        }
+        loop {
+            let key = r.context("json object key", read_string)?;
+            skip_whitespace(r);
+            r.consume(b':')?;
+            members.push((key, read_value(r)?));
+            if !r.consume_opt(b',') {
+                break;
+            }
+            skip_whitespace(r);
+        }
+        r.consume(b'}')?;
+        Ok(members)
+    })
+}
+
+fn read_array<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Vec<Value<'i>>, E>
+where
+    E: Error<'i>,
+{
+    r.enter("json array", |r| {
+        let mut values = Vec::new();
+        r.consume(b'[')?;
+        skip_whitespace(r);
+        if r.consume_opt(b']') {
+            return Ok(values);
+        }
+        loop {
+            values.push(read_value(r)?);
+            if !r.consume_opt(b',') {
+                break;
+            }
+        }
+        r.consume(b']')?;
+        Ok(values)
+    })
+}
+
+fn read_string<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Cow<'i, str>, E>
+where
+    E: Error<'i>,
+{
+    r.context("json string", |r| {
+        r.consume(b'"')?;
+        // Only allocated once an escape is found.
+        let mut owned: Option<String> = None;
+        loop {
+            // Runs end on ASCII, so always on a char boundary.
+            let run = r
+                .take_while(|c: u8| c >= 0x20 && c != b'"' && c != b'\\')
+                .to_dangerous_str::<E>()?;
+            let escaped = r.try_expect("json string character", |r| match r.read()? {
+                b'"' => Ok(Some(None)),
+                b'\\' => read_escape(r).map(|c| Some(Some(c))),
+                // Control characters must be escaped.
+                _ => Ok(None),
+            })?;
+            match escaped {
+                None => {
+                    return Ok(match owned {
+                        None => Cow::Borrowed(run),
+                        Some(mut s) => {
+                            s.push_str(run);
+                            Cow::Owned(s)
+                        }
+                    });
+                }
+                Some(c) => {
+                    let s = owned.get_or_insert_with(String::new);
+                    s.push_str(run);
+                    s.push(c);
+                }
+            }
+        }
+    })
+}
+
+fn read_escape<'i, E>(r: &mut BytesReader<'i, E>) -> Result<char, E>
+where
+    E: Error<'i>,
+{
+    r.try_expect("json escape", |r| {
+        let c = match r.read()? {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => return read_unicode_escape(r),
+            _ => return Ok(None),
+        };
+        Ok(Some(c))
+    })
+}
+
+/// Reads the hex digits of a `\u` escape, and the low surrogate that must
+/// follow a high one. Lone surrogates are not chars, so return `None`.
+fn read_unicode_escape<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Option<char>, E>
+where
+    E: Error<'i>,
+{
+    let high = read_hex_u16(r)?;
+    if !(0xD800..=0xDBFF).contains(&high) {
+        return Ok(char::from_u32(high.into()));
+    }
+    r.consume(b"\\u")?;
+    let low = read_hex_u16(r)?;
+    if !(0xDC00..=0xDFFF).contains(&low) {
+        return Ok(None);
+    }
+    let high = u32::from(high) - 0xD800;
+    let low = u32::from(low) - 0xDC00;
+    Ok(char::from_u32(0x10000 + (high << 10) + low))
+}
+
+fn read_hex_u16<'i, E>(r: &mut BytesReader<'i, E>) -> Result<u16, E>
+where
+    E: Error<'i>,
+{
+    r.try_expect("4 hex digits", |r| {
+        let digits = r.take_array::<4>()?.into_dangerous();
+        Ok(digits.iter().try_fold(0_u16, |acc, &digit| {
+            let digit = char::from(digit).to_digit(16)?;
+            Some((acc << 4) | u16::try_from(digit).ok()?)
+        }))
+    })
+}
+
+fn read_number<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Number<'i>, E>
+where
+    E: Error<'i>,
+{
+    r.context("json number", |r| {
+        let ((), text) = r.try_take_consumed(|r| {
+            r.consume_opt(b'-');
+            // Leading zeros are not allowed, so a zero ends the integer.
+            if !r.consume_opt(b'0') {
+                read_digits(r)?;
+            }
+            if r.consume_opt(b'.') {
+                read_digits(r)?;
+            }
+            if r.consume_opt(b'e') || r.consume_opt(b'E') {
+                if !r.consume_opt(b'+') {
+                    r.consume_opt(b'-');
+                }
+                read_digits(r)?;
+            }
+            Ok(())
+        })?;
+        Ok(Number(text.to_dangerous_str::<E>()?))
+    })
+}
+
+fn read_digits<'i, E>(r: &mut BytesReader<'i, E>) -> Result<(), E>
+where
+    E: Error<'i>,
+{
+    r.try_verify("digit", |r| r.read().map(|c| c.is_ascii_digit()))?;
+    r.skip_while(AsciiClass::Digit);
+    Ok(())
+}
+
+fn read_literal<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Value<'i>, E>
+where
+    E: Error<'i>,
+{
+    r.try_expect("json literal", |r| match r.peek_read()? {
+        b't' => r.consume(b"true").map(|()| Some(Value::Bool(true))),
+        b'f' => r.consume(b"false").map(|()| Some(Value::Bool(false))),
+        b'n' => r.consume(b"null").map(|()| Some(Value::Null)),
+        _ => Ok(None),
+    })
+}
+
+/// JSON whitespace, which unlike [`AsciiClass::Whitespace`] excludes form
+/// feeds.
+fn skip_whitespace<E>(r: &mut BytesReader<'_, E>) {
+    r.skip_while(|c: u8| matches!(c, b' ' | b'\t' | b'\n' | b'\r'));
+}
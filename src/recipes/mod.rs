@@ -0,0 +1,9 @@
+//! Readers for common formats built on the crate's own combinators.
+//!
+//! Each recipe is a complete reader that can be used as is, and doubles as a
+//! worked example of structuring a parser with contexts, so errors point at
+//! the part of the format that was being read.
+
+pub mod http;
+pub mod json;
+pub mod multipart;
@@ -0,0 +1,244 @@
+//! A MIME multipart ([RFC 2046]) body reader, as used for form data and file
+//! uploads ([RFC 7578]).
+//!
+//! Part bodies are returned as [`Bytes`] within the multipart body, so errors
+//! from reading them further keep spans relative to the whole body. Part
+//! headers are read as [HTTP header fields](super::http::HeaderField).
+//!
+//! # Example
+//!
+//! ```
+//! use dangerous::recipes::multipart;
+//! use dangerous::Invalid;
+//!
+//! let body = b"--XYZ\r\n\
+//!     Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n\
+//!     \r\n\
+//!     hello\r\n\
+//!     --XYZ--\r\n";
+//!
+//! let parts = multipart::from_bytes::<Invalid>(body, b"XYZ")?;
+//!
+//! assert_eq!(parts.len(), 1);
+//! assert_eq!(parts[0].name(), Some(&b"file"[..]));
+//! assert_eq!(parts[0].filename(), Some(&b"a.txt"[..]));
+//! assert_eq!(parts[0].body, b"hello"[..]);
+//! # Ok::<(), Invalid>(())
+//! ```
+//!
+//! # Streaming
+//!
+//! Parts can be read one at a time as the body arrives with
+//! [`read_preamble()`] and then [`read_part()`] until it returns `None`.
+//! Running out of input before a part is complete returns an error with a
+//! [`RetryRequirement`], so the read can be repeated once more input arrives.
+//!
+//! ```
+//! use dangerous::recipes::multipart;
+//! use dangerous::{Input, Invalid, ToRetryRequirement};
+//!
+//! let body = b"--XYZ\r\n\r\none\r\n--XYZ\r\n\r\ntwo\r\n--XYZ--";
+//!
+//! // Only the first part has fully arrived.
+//! let (_, remaining) = dangerous::input(&body[..20])
+//!     .read_partial::<_, _, Invalid>(|r| multipart::read_preamble(r, b"XYZ"))?;
+//! let (part, remaining) =
+//!     remaining.read_partial::<_, _, Invalid>(|r| multipart::read_part(r, b"XYZ"))?;
+//! assert_eq!(part.unwrap().body, b"one"[..]);
+//!
+//! let err = remaining
+//!     .read_partial::<_, _, Invalid>(|r| multipart::read_part(r, b"XYZ"))
+//!     .unwrap_err();
+//! assert!(err.to_retry_requirement().is_some());
+//! # Ok::<(), Invalid>(())
+//! ```
+//!
+//! [RFC 2046]: https://tools.ietf.org/html/rfc2046
+//! [RFC 7578]: https://tools.ietf.org/html/rfc7578
+//! [`RetryRequirement`]: crate::error::RetryRequirement
+
+use alloc::vec::Vec;
+
+use crate::error::{hook, CoreContext, CoreExpected, CoreOperation, ExpectedLength, Length};
+use crate::input::{AsciiClass, Bytes, Input, PrivateExt};
+use crate::{BytesReader, Error, Invalid};
+
+use super::http::{self, HeaderField};
+
+/// A part of a multipart body.
+#[derive(Debug, Clone)]
+pub struct Part<'i> {
+    /// The header fields of the part, in the order they were read.
+    pub headers: Vec<HeaderField<'i>>,
+    /// The body of the part.
+    pub body: Bytes<'i>,
+}
+
+impl<'i> Part<'i> {
+    /// Returns the value of the first header field with the name, ignoring
+    /// ASCII case.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&'i [u8]> {
+        self.headers
+            .iter()
+            .find(|field| field.name.eq_ignore_ascii_case(name))
+            .map(|field| field.value)
+    }
+
+    /// Returns the `name` parameter of the `Content-Disposition` header.
+    ///
+    /// Quoted values are returned without their quotes, but are otherwise as
+    /// they were written.
+    #[must_use]
+    pub fn name(&self) -> Option<&'i [u8]> {
+        self.disposition_param("name")
+    }
+
+    /// Returns the `filename` parameter of the `Content-Disposition` header.
+    ///
+    /// Quoted values are returned without their quotes, but are otherwise as
+    /// they were written.
+    #[must_use]
+    pub fn filename(&self) -> Option<&'i [u8]> {
+        self.disposition_param("filename")
+    }
+
+    fn disposition_param(&self, name: &str) -> Option<&'i [u8]> {
+        let value = self.header("content-disposition")?;
+        let mut found = None;
+        crate::input(value)
+            .read_all::<_, _, Invalid>(|r| {
+                r.skip_while(|c: u8| c != b';');
+                while r.consume_opt(b';') {
+                    skip_whitespace(r);
+                    let key = r.take_while(AsciiClass::Token);
+                    r.consume(b'=')?;
+                    let value = if r.consume_opt(b'"') {
+                        r.take_until_consume(b'"')?
+                    } else {
+                        r.take_while(AsciiClass::Token)
+                    };
+                    skip_whitespace(r);
+                    if found.is_none() && key.as_dangerous().eq_ignore_ascii_case(name.as_bytes()) {
+                        found = Some(value.as_dangerous());
+                    }
+                }
+                Ok(())
+            })
+            .ok()?;
+        found
+    }
+}
+
+/// Read the parts of a multipart body from all of the bytes.
+///
+/// # Errors
+///
+/// Returns an error if the bytes are not a valid multipart body with the
+/// boundary.
+pub fn from_bytes<'i, E>(bytes: &'i [u8], boundary: &[u8]) -> Result<Vec<Part<'i>>, E>
+where
+    E: Error<'i>,
+{
+    crate::input(bytes).read_all(|r| read_multipart(r, boundary))
+}
+
+/// Read the parts of a multipart body.
+///
+/// The preamble before the first delimiter and the epilogue after the last
+/// are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid multipart body with the
+/// boundary.
+pub fn read_multipart<'i, E>(
+    r: &mut BytesReader<'i, E>,
+    boundary: &[u8],
+) -> Result<Vec<Part<'i>>, E>
+where
+    E: Error<'i>,
+{
+    let mut parts = Vec::new();
+    read_preamble(r, boundary)?;
+    while let Some(part) = read_part(r, boundary)? {
+        parts.push(part);
+    }
+    let _epilogue = r.take_remaining();
+    Ok(parts)
+}
+
+/// Read up to and including the first delimiter of a multipart body.
+///
+/// # Errors
+///
+/// Returns an error if the first delimiter could not be found.
+pub fn read_preamble<'i, E>(r: &mut BytesReader<'i, E>, boundary: &[u8]) -> Result<(), E>
+where
+    E: Error<'i>,
+{
+    r.context("multipart preamble", |r| {
+        let dash_boundary = [&b"--"[..], boundary].concat();
+        // The first delimiter has no preceding line break if there is no
+        // preamble.
+        if r.peek_eq(&dash_boundary[..]) {
+            r.skip(dash_boundary.len())
+        } else {
+            read_body(r, &[&b"\r\n"[..], &dash_boundary].concat()).map(drop)
+        }
+    })
+}
+
+/// Read the part following a delimiter, up to and including the delimiter
+/// that ends it.
+///
+/// Returns `None` once the final delimiter is reached, leaving the epilogue
+/// unread.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid part with the boundary.
+pub fn read_part<'i, E>(r: &mut BytesReader<'i, E>, boundary: &[u8]) -> Result<Option<Part<'i>>, E>
+where
+    E: Error<'i>,
+{
+    r.context("multipart part", |r| {
+        if r.peek_read()? == b'-' {
+            r.consume(b"--")?;
+            return Ok(None);
+        }
+        // Transport padding may follow a delimiter.
+        skip_whitespace(r);
+        r.consume(b"\r\n")?;
+        let headers = http::read_header_fields(r)?;
+        let body = read_body(r, &[&b"\r\n--"[..], boundary].concat())?;
+        Ok(Some(Part { headers, body }))
+    })
+}
+
+/// Reads up to and including a delimiter, returning what came before it.
+fn read_body<'i, E>(r: &mut BytesReader<'i, E>, delimiter: &[u8]) -> Result<Bytes<'i>, E>
+where
+    E: Error<'i>,
+{
+    r.try_advance(
+        |input| match input.clone().split_until_consume_opt(delimiter) {
+            Some(split) => Ok(split),
+            // The delimiter may be yet to arrive, so more input is asked for
+            // rather than the body being invalid.
+            None => Err(hook::create(ExpectedLength {
+                len: Length::AtLeast(input.byte_len() + 1),
+                context: CoreContext {
+                    span: input.span(),
+                    operation: CoreOperation::TakeUntilConsume,
+                    expected: CoreExpected::EnoughInputFor("multipart delimiter"),
+                },
+                input: input.into_maybe_string(),
+            })),
+        },
+    )
+}
+
+fn skip_whitespace<E>(r: &mut BytesReader<'_, E>) {
+    r.skip_while(|c: u8| c == b' ' || c == b'\t');
+}
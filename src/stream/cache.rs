@@ -0,0 +1,157 @@
+use alloc::vec::Vec;
+
+use crate::error::{ExpectedLength, WithContext};
+use crate::fmt;
+use crate::input::{Input, Span};
+use crate::BytesReader;
+
+/// Caches values read from the leading parts of streamed input across
+/// retries.
+///
+/// When a read is retried once more input is available, everything before the
+/// point where more input was required is read again. For parses that do
+/// expensive validation (UTF-8, checksums), this cache keeps values that were
+/// already accepted keyed by their offset within the buffer, so a retry skips
+/// over them instead of validating them again.
+///
+/// Offsets are relative to the `base` [`Span`] passed to [`try_read()`],
+/// which must be the span of the buffer being read on each attempt. The
+/// buffer may grow between attempts, but the cache must be [`clear()`]ed when
+/// the bytes it covers are removed or changed.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::stream::PrefixCache;
+/// use dangerous::{BytesReader, Input, Invalid};
+///
+/// fn read_name<'i>(r: &mut BytesReader<'i, Invalid>) -> Result<String, Invalid> {
+///     let len = r.read()?;
+///     let name = r.take(len as usize)?.to_dangerous_str::<Invalid>()?;
+///     Ok(name.to_owned())
+/// }
+///
+/// let mut cache = PrefixCache::new();
+/// let mut buf = b"\x03bob\x05al".to_vec();
+///
+/// let input = dangerous::input(&buf[..]);
+/// let base = input.span();
+/// let result: Result<_, Invalid> = input.read_partial(|r| {
+///     Ok((cache.try_read(base, r, read_name)?, cache.try_read(base, r, read_name)?))
+/// });
+///
+/// assert!(result.is_err());
+/// assert_eq!(cache.len(), 1);
+///
+/// buf.extend_from_slice(b"ice");
+/// let input = dangerous::input(&buf[..]);
+/// let base = input.span();
+/// let ((first, second), _) = input.read_partial::<_, _, Invalid>(|r| {
+///     Ok((cache.try_read(base, r, read_name)?, cache.try_read(base, r, read_name)?))
+/// }).unwrap();
+///
+/// assert_eq!(first, "bob");
+/// assert_eq!(second, "alice");
+/// ```
+///
+/// [`try_read()`]: Self::try_read()
+/// [`clear()`]: Self::clear()
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct PrefixCache<T> {
+    entries: Vec<Entry<T>>,
+}
+
+struct Entry<T> {
+    offset: usize,
+    len: usize,
+    value: T,
+}
+
+impl<T> PrefixCache<T> {
+    /// Create a new empty `PrefixCache`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Returns the number of cached values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no cached values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all cached values.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Try read a value at the reader's current position, returning the
+    /// cached value if it was previously read at the same offset of `base`.
+    ///
+    /// If a cached value is returned, the length of input it was originally
+    /// read from is skipped. Otherwise the provided function is called and
+    /// its value is cached if it succeeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error the provided function does, or an error if the input
+    /// a cached value was read from is no longer available.
+    pub fn try_read<'i, E, F>(
+        &mut self,
+        base: Span,
+        r: &mut BytesReader<'i, E>,
+        f: F,
+    ) -> Result<T, E>
+    where
+        T: Clone,
+        E: WithContext<'i>,
+        E: From<ExpectedLength<'i>>,
+        F: FnOnce(&mut BytesReader<'i, E>) -> Result<T, E>,
+    {
+        let position = r.peek_opt(0).and_then(|here| here.span().range_of(base));
+        if let Some(position) = position {
+            if let Some(entry) = self.entries.iter().find(|e| e.offset == position.start) {
+                r.skip(entry.len)?;
+                return Ok(entry.value.clone());
+            }
+        }
+        let (value, consumed) = r.try_take_consumed(f)?;
+        if let Some(range) = consumed.span().range_of(base) {
+            self.entries.push(Entry {
+                offset: range.start,
+                len: range.len(),
+                value: value.clone(),
+            });
+        }
+        Ok(value)
+    }
+}
+
+impl<T> Default for PrefixCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for PrefixCache<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.entries
+                    .iter()
+                    .map(|e| (e.offset..e.offset + e.len, &e.value)),
+            )
+            .finish()
+    }
+}
@@ -0,0 +1,186 @@
+use core::marker::PhantomData;
+use std::io;
+
+use tokio_util::bytes::{Buf, BytesMut};
+use tokio_util::codec;
+
+use crate::error::{ToRetryRequirement, WithContext};
+use crate::fmt;
+use crate::input::Input;
+use crate::{BytesReader, Invalid};
+
+/// A [`tokio_util::codec::Decoder`] driven by a `dangerous` read function.
+///
+/// Each call to `decode` reads a partial length of the buffered input with the
+/// provided function. On success, the input consumed is removed from the
+/// buffer. Retryable errors are translated into `Ok(None)` with the buffer
+/// reserving the additional bytes required (taking into account any
+/// [`RetryRequirement::total_hint()`]), and fatal errors are returned as
+/// a [`DecodeError::Invalid`]. A read function that succeeds without consuming
+/// any input would be called for the same item forever, so this is returned as
+/// a [`DecodeError::NoProgress`].
+///
+/// [`RetryRequirement::total_hint()`]: crate::error::RetryRequirement::total_hint()
+///
+/// When the underlying stream reaches its end, the remaining input is read as
+/// bound so any incomplete frame results in a fatal error.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::stream::Decoder;
+/// use dangerous::{BytesReader, Invalid};
+/// use tokio_util::bytes::BytesMut;
+/// use tokio_util::codec::Decoder as _;
+///
+/// fn read_frame(r: &mut BytesReader<'_, Invalid>) -> Result<Vec<u8>, Invalid> {
+///     let len = r.read()?;
+///     let body = r.take(len as usize)?;
+///     Ok(body.as_dangerous().to_vec())
+/// }
+///
+/// let mut decoder = Decoder::new(read_frame);
+/// let mut buf = BytesMut::from(&[3, b'a', b'b'][..]);
+///
+/// // Not enough input yet.
+/// assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+///
+/// buf.extend_from_slice(b"c");
+/// assert_eq!(decoder.decode(&mut buf).unwrap(), Some(b"abc".to_vec()));
+/// assert!(buf.is_empty());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-util")))]
+pub struct Decoder<F, E = Invalid> {
+    read: F,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<F, E> Decoder<F, E> {
+    /// Create a new `Decoder` given a read function.
+    pub fn new(read: F) -> Self {
+        Self {
+            read,
+            error: PhantomData,
+        }
+    }
+
+    /// Unwrap the read function from the decoder.
+    pub fn into_inner(self) -> F {
+        self.read
+    }
+}
+
+impl<F, E, T> Decoder<F, E>
+where
+    F: for<'i> FnMut(&mut BytesReader<'i, E>) -> Result<T, E>,
+    E: for<'i> WithContext<'i> + ToRetryRequirement,
+{
+    fn decode_input(&mut self, src: &mut BytesMut, eof: bool) -> Result<Option<T>, DecodeError<E>> {
+        let decoded = decode_partial(src, eof, &mut self.read)?;
+        Ok(decoded.map(|(item, consumed)| {
+            src.advance(consumed);
+            item
+        }))
+    }
+}
+
+/// Reads an item from the start of the buffer, returning it along with the
+/// number of bytes it consumed, which are left in the buffer.
+///
+/// Retryable errors reserve the additional bytes required and return
+/// `Ok(None)`.
+pub(super) fn decode_partial<F, E, T>(
+    src: &mut BytesMut,
+    eof: bool,
+    read: F,
+) -> Result<Option<(T, usize)>, DecodeError<E>>
+where
+    F: for<'i> FnOnce(&mut BytesReader<'i, E>) -> Result<T, E>,
+    E: for<'i> WithContext<'i> + ToRetryRequirement,
+{
+    if src.is_empty() {
+        return Ok(None);
+    }
+    let input = crate::input(&src[..]);
+    let input = if eof { input.into_bound() } else { input };
+    match input.read_partial(read) {
+        Ok((_, remaining)) if remaining.len() == src.len() => Err(DecodeError::NoProgress),
+        Ok((item, remaining)) => Ok(Some((item, src.len() - remaining.len()))),
+        Err(err) => match err.to_retry_requirement() {
+            Some(requirement) => {
+                src.reserve(requirement.reserve_hint(src.len()));
+                Ok(None)
+            }
+            None => Err(DecodeError::Invalid(err)),
+        },
+    }
+}
+
+impl<F, E, T> codec::Decoder for Decoder<F, E>
+where
+    F: for<'i> FnMut(&mut BytesReader<'i, E>) -> Result<T, E>,
+    E: for<'i> WithContext<'i> + ToRetryRequirement,
+{
+    type Item = T;
+    type Error = DecodeError<E>;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode_input(src, false)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode_input(src, true)
+    }
+}
+
+impl<F, E> fmt::Debug for Decoder<F, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Decoder").finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// An error returned from a [`Decoder`].
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-util")))]
+pub enum DecodeError<E> {
+    /// An IO error from the underlying stream.
+    Io(io::Error),
+    /// A fatal error returned from the read function.
+    Invalid(E),
+    /// The read function succeeded without consuming any input.
+    NoProgress,
+}
+
+impl<E> From<io::Error> for DecodeError<E> {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl<E> fmt::Display for DecodeError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => fmt::Display::fmt(err, f),
+            Self::Invalid(err) => fmt::Display::fmt(err, f),
+            Self::NoProgress => f.write_str("read function consumed no input"),
+        }
+    }
+}
+
+impl<E> std::error::Error for DecodeError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Invalid(err) => Some(err),
+            Self::NoProgress => None,
+        }
+    }
+}
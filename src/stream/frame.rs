@@ -0,0 +1,217 @@
+use crate::error::Error;
+use crate::input::{ByteArray, Bytes};
+use crate::BytesReader;
+
+/// Width of a frame length prefix.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PrefixWidth {
+    /// A 1 byte length prefix.
+    U8,
+    /// A 2 byte length prefix.
+    U16,
+    /// A 4 byte length prefix.
+    U32,
+    /// An 8 byte length prefix.
+    U64,
+}
+
+impl PrefixWidth {
+    /// The number of bytes the prefix occupies.
+    #[must_use]
+    pub const fn byte_len(self) -> usize {
+        match self {
+            Self::U8 => 1,
+            Self::U16 => 2,
+            Self::U32 => 4,
+            Self::U64 => 8,
+        }
+    }
+}
+
+/// Byte order of a frame length prefix.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Endian {
+    /// Big endian (network order).
+    Big,
+    /// Little endian.
+    Little,
+}
+
+/// A description of a length-prefixed frame.
+///
+/// A frame consists of a length prefix (the header), followed by the payload.
+/// By default the length within the prefix describes the payload alone and
+/// there is no maximum payload length.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::stream::{Endian, FrameFormat, PrefixWidth};
+/// use dangerous::{Input, Invalid, ToRetryRequirement};
+///
+/// const FORMAT: FrameFormat = FrameFormat::new(PrefixWidth::U32, Endian::Big).max_len(1024);
+///
+/// let input = dangerous::input(&[0, 0, 0, 2, b'h', b'i', 0, 0]);
+/// let (frame, remaining) = input.read_partial::<_, _, Invalid>(|r| FORMAT.read_frame(r)).unwrap();
+///
+/// assert_eq!(frame, b"hi"[..]);
+///
+/// // Retry once more input is available.
+/// let err = remaining.read_partial::<_, _, Invalid>(|r| FORMAT.read_frame(r)).unwrap_err();
+///
+/// assert!(!err.is_fatal());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct FrameFormat {
+    width: PrefixWidth,
+    endian: Endian,
+    includes_header: bool,
+    max_len: Option<usize>,
+}
+
+impl FrameFormat {
+    /// Create a new `FrameFormat` given the prefix width and byte order.
+    pub const fn new(width: PrefixWidth, endian: Endian) -> Self {
+        Self {
+            width,
+            endian,
+            includes_header: false,
+            max_len: None,
+        }
+    }
+
+    /// Set whether the length within the prefix includes the length of the
+    /// prefix itself.
+    ///
+    /// Default is `false`.
+    pub const fn includes_header(mut self, value: bool) -> Self {
+        self.includes_header = value;
+        self
+    }
+
+    /// Set the maximum payload length, excluding the header.
+    ///
+    /// Frames declaring a longer payload are rejected without waiting for
+    /// the payload to be available.
+    pub const fn max_len(mut self, max: usize) -> Self {
+        self.max_len = Some(max);
+        self
+    }
+
+    /// The number of bytes the header occupies.
+    #[must_use]
+    pub const fn header_len(&self) -> usize {
+        self.width.byte_len()
+    }
+
+    /// Read a frame, returning the payload.
+    ///
+    /// Once the header is read, the length of the whole frame is known, so if
+    /// the payload is not yet available the retry requirement carries it as a
+    /// [`total_hint()`]. The hint is measured from the start of the input the
+    /// reader was created with, so it includes any frames read before.
+    ///
+    /// [`total_hint()`]: crate::error::RetryRequirement::total_hint()
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header or payload could not be read, or if the
+    /// length declared in the header was not valid for this format.
+    pub fn read_frame<'i, E>(&self, r: &mut BytesReader<'i, E>) -> Result<Bytes<'i>, E>
+    where
+        E: Error<'i>,
+    {
+        r.context("frame", |r| {
+            let start = r.consumed_bytes();
+            let len = r.try_expect("valid frame length", |r| {
+                self.read_prefix(r).map(|len| self.payload_len(len))
+            })?;
+            let total = start.saturating_add(self.header_len()).saturating_add(len);
+            r.take(len).map_err(|err: E| err.with_total_hint(total))
+        })
+    }
+
+    fn read_prefix<'i, E>(&self, r: &mut BytesReader<'i, E>) -> Result<u64, E>
+    where
+        E: Error<'i>,
+    {
+        macro_rules! read_int {
+            ($ty:ty) => {
+                r.take_array()
+                    .map(ByteArray::into_dangerous)
+                    .map(|bytes| match self.endian {
+                        Endian::Big => <$ty>::from_be_bytes(bytes),
+                        Endian::Little => <$ty>::from_le_bytes(bytes),
+                    })
+                    .map(u64::from)
+            };
+        }
+        match self.width {
+            PrefixWidth::U8 => r.read().map(u64::from),
+            PrefixWidth::U16 => read_int!(u16),
+            PrefixWidth::U32 => read_int!(u32),
+            PrefixWidth::U64 => read_int!(u64),
+        }
+    }
+
+    fn payload_len(&self, declared: u64) -> Option<usize> {
+        let declared = usize::try_from(declared).ok()?;
+        let len = if self.includes_header {
+            declared.checked_sub(self.header_len())?
+        } else {
+            declared
+        };
+        match self.max_len {
+            Some(max) if len > max => None,
+            _ => Some(len),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// tokio-util support
+
+#[cfg(all(feature = "tokio-util", feature = "std"))]
+mod codec {
+    use tokio_util::bytes::{Buf, BytesMut};
+    use tokio_util::codec::Decoder;
+
+    use crate::Invalid;
+
+    use super::super::decoder::decode_partial;
+    use super::super::DecodeError;
+    use super::FrameFormat;
+
+    impl FrameFormat {
+        fn decode_frame(
+            &self,
+            src: &mut BytesMut,
+            eof: bool,
+        ) -> Result<Option<BytesMut>, DecodeError<Invalid>> {
+            let decoded = decode_partial(src, eof, |r| self.read_frame::<Invalid>(r).map(drop))?;
+            Ok(decoded.map(|((), consumed)| {
+                let mut frame = src.split_to(consumed);
+                frame.advance(self.header_len());
+                frame
+            }))
+        }
+    }
+
+    /// Decodes frames, yielding the payloads.
+    ///
+    /// Retries reserve room for the whole frame once its header is read.
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-util")))]
+    impl Decoder for FrameFormat {
+        type Item = BytesMut;
+        type Error = DecodeError<Invalid>;
+
+        fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+            self.decode_frame(src, false)
+        }
+
+        fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+            self.decode_frame(src, true)
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::fmt;
+use crate::input::{Bytes, Input, PrivateExt};
+
+/// Returns an iterator over the complete lines within a buffer of streamed
+/// input.
+///
+/// Lines are terminated by `\n`, with a preceding `\r` stripped if present.
+/// A trailing line without a terminator is not yielded as more input may
+/// complete it. Once the iterator is exhausted, drop [`Lines::consumed()`]
+/// bytes from the front of the buffer, append the next chunk of data and call
+/// `lines` again. When the stream has ended, [`Lines::remaining()`] holds the
+/// final unterminated line, if any.
+///
+/// # Example
+///
+/// ```
+/// let mut buf = b"one\r\ntwo\nthr".to_vec();
+/// let mut lines = dangerous::stream::lines(&buf);
+///
+/// assert_eq!(lines.next().unwrap(), b"one"[..]);
+/// assert_eq!(lines.next().unwrap(), b"two"[..]);
+/// assert_eq!(lines.next(), None);
+/// assert_eq!(lines.remaining(), b"thr"[..]);
+///
+/// let consumed = lines.consumed();
+/// buf.drain(..consumed);
+/// buf.extend_from_slice(b"ee\n");
+///
+/// let mut lines = dangerous::stream::lines(&buf);
+///
+/// assert_eq!(lines.next().unwrap(), b"three"[..]);
+/// assert_eq!(lines.next(), None);
+/// assert_eq!(lines.consumed(), buf.len());
+/// ```
+pub fn lines(buf: &[u8]) -> Lines<'_> {
+    Lines {
+        input: crate::input(buf),
+        consumed: 0,
+    }
+}
+
+/// Iterator over complete lines within a buffer created by [`lines()`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Lines<'i> {
+    input: Bytes<'i>,
+    consumed: usize,
+}
+
+impl<'i> Lines<'i> {
+    /// Returns the input remaining after the last complete line yielded.
+    #[inline(always)]
+    pub fn remaining(&self) -> Bytes<'i> {
+        self.input.clone()
+    }
+
+    /// Returns the number of bytes consumed by the complete lines yielded,
+    /// including their terminators.
+    #[must_use]
+    #[inline(always)]
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+}
+
+impl<'i> Iterator for Lines<'i> {
+    type Item = Bytes<'i>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (line, remaining) = self.input.clone().split_until_consume_opt(b'\n')?;
+        self.consumed += self.input.byte_len() - remaining.byte_len();
+        self.input = remaining;
+        if line.last() == Some(b'\r') {
+            line.clone()
+                .split_at_opt(line.byte_len() - 1)
+                .map(|(line, _)| line)
+        } else {
+            Some(line)
+        }
+    }
+}
+
+impl fmt::Debug for Lines<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lines")
+            .field("input", &self.input)
+            .field("consumed", &self.consumed)
+            .finish()
+    }
+}
@@ -0,0 +1,27 @@
+//! Streaming support.
+//!
+//! `dangerous` signals that more input is required to continue processing via
+//! a [`RetryRequirement`]. The helpers within this module package up the
+//! common patterns of driving a read function over a growing buffer.
+//!
+//! [`RetryRequirement`]: crate::error::RetryRequirement
+
+#[cfg(feature = "alloc")]
+mod cache;
+#[cfg(all(feature = "tokio-util", feature = "std"))]
+mod decoder;
+mod frame;
+mod lines;
+#[cfg(feature = "alloc")]
+mod retry;
+mod utf8;
+
+#[cfg(feature = "alloc")]
+pub use self::cache::PrefixCache;
+#[cfg(all(feature = "tokio-util", feature = "std"))]
+pub use self::decoder::{DecodeError, Decoder};
+pub use self::frame::{Endian, FrameFormat, PrefixWidth};
+pub use self::lines::{lines, Lines};
+#[cfg(feature = "alloc")]
+pub use self::retry::read_all_retry;
+pub use self::utf8::Utf8StreamState;
@@ -0,0 +1,78 @@
+use alloc::vec::Vec;
+
+use crate::error::{Error, RetryRequirement};
+use crate::input::Input;
+use crate::BytesReader;
+
+/// Reads all of a buffer with a read function, refilling the buffer while the
+/// read fails with a retryable error.
+///
+/// Each attempt reads all of the buffer from the start with `read`. When the
+/// read fails with a [`RetryRequirement`], `refill` is called with the buffer
+/// and the requirement to extend the buffer with more input, returning `true`
+/// if it did. Once `refill` returns `false`, or doesn't grow the buffer, the
+/// end of the input was reached and a final attempt reads the buffer as bound
+/// so the read either completes or fails with a fatal error. A fatal error is
+/// returned as soon as it occurs.
+///
+/// The buffer is owned so the value returned can't borrow from it. Use
+/// `stream::Decoder` (with the `tokio-util` feature) when reading frames from
+/// an async stream.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{BytesReader, Invalid};
+///
+/// fn read_message(r: &mut BytesReader<'_, Invalid>) -> Result<Vec<u8>, Invalid> {
+///     let len = r.read()?;
+///     let body = r.take(len as usize)?;
+///     Ok(body.as_dangerous().to_vec())
+/// }
+///
+/// let mut chunks = vec![&b"c"[..], &b"de"[..]].into_iter();
+/// let message = dangerous::read_all_retry(
+///     vec![3, b'a', b'b'],
+///     |buf, requirement| {
+///         assert_eq!(requirement.continue_after(), 1);
+///         match chunks.next() {
+///             Some(chunk) => {
+///                 buf.extend_from_slice(chunk);
+///                 true
+///             }
+///             None => false,
+///         }
+///     },
+///     read_message,
+/// );
+///
+/// assert_eq!(message.unwrap(), b"abc");
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the read function fails with a fatal error, or with any
+/// error once the end of the input was reached.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn read_all_retry<T, E, R, F>(initial: Vec<u8>, mut refill: R, mut read: F) -> Result<T, E>
+where
+    R: FnMut(&mut Vec<u8>, RetryRequirement) -> bool,
+    F: for<'i> FnMut(&mut BytesReader<'i, E>) -> Result<T, E>,
+    E: for<'i> Error<'i>,
+{
+    let mut buf = initial;
+    loop {
+        let err = match crate::input(&buf[..]).read_all(&mut read) {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        let requirement = match err.to_retry_requirement() {
+            Some(requirement) => requirement,
+            None => return Err(err),
+        };
+        let len = buf.len();
+        if !refill(&mut buf, requirement) || buf.len() <= len {
+            return crate::input(&buf[..]).into_bound().read_all(&mut read);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::error::{CoreOperation, ExpectedLength, ExpectedValid};
+use crate::input::{Bytes, Input, String};
+use crate::util::fast;
+
+/// Incremental UTF-8 validation state for streamed input.
+///
+/// Tracks how much of a growing buffer has already been validated, so that
+/// when more input arrives only the new bytes (and any code point cut short at
+/// the end of the previous attempt) are validated again.
+///
+/// The state remembers where the input it validated starts. If the next input
+/// starts elsewhere, such as after the buffer was consumed or reallocated, it
+/// is validated from the start. Progress is only an optimisation for retries:
+/// the `str` returned is always checked in full, so an input that was changed
+/// in place can't be returned unchecked. Call [`reset()`](Self::reset()) if
+/// the buffer was changed in place to have errors reported against the new
+/// contents.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::stream::Utf8StreamState;
+/// use dangerous::{Invalid, ToRetryRequirement};
+///
+/// let mut state = Utf8StreamState::new();
+/// let mut buf = b"hello \xE2\x82".to_vec();
+///
+/// // The euro sign is cut short, so we need more input.
+/// let err = state.to_dangerous_str::<Invalid>(&dangerous::input(&buf[..])).unwrap_err();
+///
+/// assert!(!err.is_fatal());
+/// assert_eq!(state.valid_up_to(), 6);
+///
+/// buf.extend_from_slice(b"\xAC!");
+/// let text = state.to_dangerous_str::<Invalid>(&dangerous::input(&buf[..])).unwrap();
+///
+/// assert_eq!(text, "hello €!");
+/// assert_eq!(state.valid_up_to(), buf.len());
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Utf8StreamState {
+    /// Address of the start of the input validated.
+    start: usize,
+    valid_up_to: usize,
+}
+
+impl Utf8StreamState {
+    /// Create a new `Utf8StreamState` with nothing validated.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            start: 0,
+            valid_up_to: 0,
+        }
+    }
+
+    /// Returns the length of input known to be valid UTF-8.
+    #[must_use]
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    /// Forget all validation progress.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Decodes the input into a UTF-8 `str` slice, only validating the bytes
+    /// that were not already validated.
+    ///
+    /// See [`Bytes::to_dangerous_str()`] for naming.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValid`] if the input could never be valid UTF-8 and
+    /// [`ExpectedLength`] if a UTF-8 code point was cut short.
+    pub fn to_dangerous_str<'i, E>(&mut self, input: &Bytes<'i>) -> Result<&'i str, E>
+    where
+        E: From<ExpectedValid<'i>>,
+        E: From<ExpectedLength<'i>>,
+    {
+        let bytes = input.as_dangerous();
+        let start = bytes.as_ptr() as usize;
+        if start != self.start || bytes.len() < self.valid_up_to {
+            self.start = start;
+            self.valid_up_to = 0;
+        }
+        let validated = self.valid_up_to;
+        // Only the new bytes are validated while more input is required, the
+        // whole input is checked once they are valid.
+        let (valid_up_to, error_len) = match fast::from_utf8(&bytes[validated..]) {
+            Ok(_) => match fast::from_utf8(bytes) {
+                Ok(s) => {
+                    self.valid_up_to = bytes.len();
+                    return Ok(s);
+                }
+                Err(err) => (err.valid_up_to(), err.error_len()),
+            },
+            Err(err) => (validated + err.valid_up_to(), err.error_len()),
+        };
+        self.valid_up_to = valid_up_to;
+        Err(input
+            .clone()
+            .map_utf8_error(error_len, valid_up_to, CoreOperation::IntoString))
+    }
+
+    /// Converts the input into a [`String`], only validating the bytes that
+    /// were not already validated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValid`] if the input could never be valid UTF-8 and
+    /// [`ExpectedLength`] if a UTF-8 code point was cut short.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn into_string<'i, E>(&mut self, input: Bytes<'i>) -> Result<String<'i>, E>
+    where
+        E: From<ExpectedValid<'i>>,
+        E: From<ExpectedLength<'i>>,
+    {
+        let s = self.to_dangerous_str::<E>(&input)?;
+        Ok(String::new(s, input.bound()))
+    }
+}
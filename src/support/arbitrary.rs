@@ -0,0 +1,33 @@
+use arbitrary::{size_hint, Arbitrary, Result, Unstructured};
+
+use crate::{Bound, Bytes, String};
+
+/// Input is generated as it would be created from a slice, with the end
+/// either open for retries or closed.
+fn arbitrary_bound(u: &mut Unstructured<'_>) -> Result<Bound> {
+    Ok(if u.arbitrary()? {
+        Bound::StartEnd
+    } else {
+        Bound::Start
+    })
+}
+
+impl<'a> Arbitrary<'a> for Bytes<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Bytes::new(u.arbitrary()?, arbitrary_bound(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(<&[u8]>::size_hint(depth), bool::size_hint(depth))
+    }
+}
+
+impl<'a> Arbitrary<'a> for String<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(String::new(u.arbitrary()?, arbitrary_bound(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(<&str>::size_hint(depth), bool::size_hint(depth))
+    }
+}
@@ -0,0 +1,74 @@
+use defmt::{write, Format, Formatter};
+
+use crate::error::{
+    Backtrace, CoreOperation, ErrorKind, Expected, Fatal, Invalid, RetryRequirement,
+    ToRetryRequirement,
+};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl Format for RetryRequirement {
+    fn format(&self, f: Formatter<'_>) {
+        match self.continue_after() {
+            1 => write!(f, "1 byte more"),
+            n => write!(f, "{=usize} bytes more", n),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl Format for Invalid {
+    fn format(&self, f: Formatter<'_>) {
+        if let Some(retry_requirement) = self.to_retry_requirement() {
+            write!(f, "invalid input: needs {}", retry_requirement);
+        } else {
+            write!(f, "invalid input");
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl Format for Fatal {
+    fn format(&self, f: Formatter<'_>) {
+        write!(f, "invalid input");
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl Format for ErrorKind {
+    fn format(&self, f: Formatter<'_>) {
+        let kind = match self {
+            Self::Value => "value",
+            Self::Valid => "valid",
+            Self::Length => "length",
+        };
+        write!(f, "{=str}", kind);
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl Format for CoreOperation {
+    fn format(&self, f: Formatter<'_>) {
+        write!(f, "{=str}", self.description());
+    }
+}
+
+/// Formats a truncated form of the error.
+///
+/// Only the root operation, the kind of error, the span offsets and the
+/// retry requirement are written. The input, expected value and backtrace
+/// are left out, as they would need `core::fmt` to be written.
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl<S> Format for Expected<'_, S>
+where
+    S: Backtrace,
+{
+    fn format(&self, f: Formatter<'_>) {
+        write!(f, "failed to {} ({} error)", self.operation(), self.kind());
+        if let Some(span) = self.span_range() {
+            write!(f, " at {=usize}..{=usize}", span.start, span.end);
+        }
+        if let Some(retry_requirement) = self.to_retry_requirement() {
+            write!(f, ": needs {}", retry_requirement);
+        }
+    }
+}
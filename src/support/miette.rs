@@ -0,0 +1,69 @@
+use std::boxed::Box;
+use std::iter;
+use std::string::String;
+
+use miette::{Diagnostic, LabeledSpan, MietteError, SourceCode, SourceSpan, SpanContents};
+
+use crate::error::{Backtrace, Context, Details, Expected, InputSpan};
+use crate::fmt;
+use crate::input::MaybeString;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "miette")))]
+impl SourceCode for MaybeString<'_> {
+    fn read_span<'a>(
+        &'a self,
+        span: &SourceSpan,
+        context_lines_before: usize,
+        context_lines_after: usize,
+    ) -> Result<Box<dyn SpanContents<'a> + 'a>, MietteError> {
+        self.as_dangerous_bytes()
+            .read_span(span, context_lines_before, context_lines_after)
+    }
+}
+
+impl SourceCode for InputSpan<'_> {
+    fn read_span<'a>(
+        &'a self,
+        span: &SourceSpan,
+        context_lines_before: usize,
+        context_lines_after: usize,
+    ) -> Result<Box<dyn SpanContents<'a> + 'a>, MietteError> {
+        self.as_dangerous_bytes()
+            .read_span(span, context_lines_before, context_lines_after)
+    }
+}
+
+/// The input is provided as the source code, labeled with the description at
+/// the error span. The help text is what was expected.
+#[cfg_attr(docsrs, doc(cfg(feature = "miette")))]
+impl<S> Diagnostic for Expected<'_, S>
+where
+    S: Backtrace,
+{
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let mut help = String::from("expected ");
+        if let Some(value) = self.expected() {
+            fmt::DisplayBase::fmt(&value.display(), &mut help).ok()?;
+        } else {
+            let root = self.backtrace().root();
+            if !root.has_expected() {
+                return None;
+            }
+            root.expected(&mut help).ok()?;
+        }
+        Some(Box::new(help))
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(self.input_span())
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let root = self.backtrace().root();
+        let range = root.span.range_of(self.input_span().span())?;
+        let mut description = String::new();
+        self.description(&mut description).ok()?;
+        let label = LabeledSpan::new_primary_with_span(Some(description), range);
+        Some(Box::new(iter::once(label)))
+    }
+}
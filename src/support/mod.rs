@@ -1,6 +1,14 @@
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 mod core;
+#[cfg(feature = "defmt")]
+mod defmt;
+#[cfg(all(feature = "miette", feature = "std"))]
+mod miette;
 #[cfg(feature = "nom")]
 mod nom;
+#[cfg(feature = "serde")]
+mod serde;
 #[cfg(feature = "std")]
 mod std;
 #[cfg(feature = "zc")]
@@ -72,6 +72,10 @@ impl Context for NomContext {
     fn operation(&self) -> &dyn Operation {
         &self.kind
     }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "nom")))]
@@ -124,6 +128,10 @@ impl Context for NomVerboseContext {
         &self.kind
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn has_expected(&self) -> bool {
         match self.kind {
             VerboseErrorKind::Char(_) | VerboseErrorKind::Context(_) => true,
@@ -135,7 +143,9 @@ impl Context for NomVerboseContext {
         match self.kind {
             VerboseErrorKind::Char(c) => {
                 w.write_str("character '")?;
-                w.write_char(c)?;
+                for c in c.escape_debug() {
+                    w.write_char(c)?;
+                }
                 w.write_char('\'')
             }
             VerboseErrorKind::Context(c) => w.write_str(c),
@@ -0,0 +1,143 @@
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+#[cfg(feature = "alloc")]
+use crate::error::{
+    Backtrace, ErrorKind, Expected, Operation, OwnedExpected, Report, ReportContext, ReportValue,
+};
+use crate::error::{Fatal, Invalid, RetryRequirement, ToRetryRequirement};
+use crate::input::SpanOffsets;
+
+/// Serializes the offsets as a struct of `start` and `end`, the same as a
+/// [`Range`](core::ops::Range).
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for SpanOffsets {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        self.range().serialize(serializer)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for SpanOffsets {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        core::ops::Range::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for RetryRequirement {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        let mut s = serializer.serialize_struct("RetryRequirement", 3)?;
+        s.serialize_field("continue_after", &self.continue_after())?;
+        s.serialize_field("total_hint", &self.total_hint())?;
+        s.serialize_field("exact", &self.is_exact())?;
+        s.end()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for Invalid {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        let mut s = serializer.serialize_struct("Invalid", 1)?;
+        s.serialize_field("retry_requirement", &self.to_retry_requirement())?;
+        s.end()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for Fatal {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        serializer.serialize_unit_struct("Fatal")
+    }
+}
+
+/// Serializes the report as a struct of its fields, in the order they are
+/// declared.
+///
+/// - `kind` is written as `"value"`, `"valid"` or `"length"`.
+/// - `reason` and `operation` are written as their descriptions.
+/// - `expected_value` and `found_value` are written as a string if known to
+///   be UTF-8, otherwise as a byte sequence.
+/// - Spans are written with their `start` and `end` offsets.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "alloc"))))]
+impl Serialize for Report {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        let mut operation = alloc::string::String::new();
+        // Failing to write the description leaves what was written.
+        let _ = Operation::description(&self.operation, &mut operation);
+        let mut s = serializer.serialize_struct("Report", 11)?;
+        s.serialize_field("kind", &self.kind.map(ErrorKind::name))?;
+        s.serialize_field("reason", &self.reason.map(SerializeDisplay))?;
+        s.serialize_field("operation", &*operation)?;
+        s.serialize_field("description", &*self.description)?;
+        s.serialize_field("expected", &self.expected.as_deref())?;
+        s.serialize_field("expected_value", &self.expected_value)?;
+        s.serialize_field("found_value", &self.found_value)?;
+        s.serialize_field("span", &self.span)?;
+        s.serialize_field("input_len", &self.input_len)?;
+        s.serialize_field("retry_requirement", &self.retry_requirement)?;
+        s.serialize_field("contexts", &self.contexts[..])?;
+        s.end()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "alloc"))))]
+impl Serialize for ReportContext {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        let mut s = serializer.serialize_struct("ReportContext", 4)?;
+        s.serialize_field("depth", &self.depth)?;
+        s.serialize_field("operation", &*self.operation)?;
+        s.serialize_field("expected", &self.expected.as_deref())?;
+        s.serialize_field("span", &self.span)?;
+        s.end()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "alloc"))))]
+impl Serialize for ReportValue {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        match self {
+            Self::Bytes(bytes) => serializer.serialize_bytes(bytes),
+            Self::String(string) => serializer.serialize_str(string),
+        }
+    }
+}
+
+/// Serializes the [`Report`] of the error.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "alloc"))))]
+impl<S> Serialize for Expected<'_, S>
+where
+    S: Backtrace,
+{
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        self.to_report().serialize(serializer)
+    }
+}
+
+/// Serializes the [`Report`] of the error.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "alloc"))))]
+impl Serialize for OwnedExpected {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        self.to_report().serialize(serializer)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg(feature = "alloc")]
+struct SerializeDisplay<T>(T);
+
+#[cfg(feature = "alloc")]
+impl<T> Serialize for SerializeDisplay<T>
+where
+    T: core::fmt::Display,
+{
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
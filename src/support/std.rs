@@ -13,8 +13,29 @@ impl<'i> crate::error::External<'i> for std::net::AddrParseError {
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl std::error::Error for crate::error::Invalid {}
 
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for crate::error::InvalidHint {}
+
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl std::error::Error for crate::error::Fatal {}
 
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl<'i, S> std::error::Error for crate::error::Expected<'i, S> where S: crate::error::Backtrace {}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for crate::error::ExpectedLength<'_> {}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for crate::error::ExpectedValid<'_> {}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for crate::error::ExpectedValue<'_> {}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for crate::error::OwnedExpected {}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<'i, E> std::error::Error for crate::error::MultiError<E> where
+    E: crate::error::Details<'i> + std::fmt::Debug
+{
+}
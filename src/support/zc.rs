@@ -9,6 +9,18 @@ unsafe impl<'o> zc::Dependant<'o> for crate::error::FullBacktrace {
     type Static = crate::error::FullBacktrace;
 }
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "zc", feature = "alloc"))))]
+unsafe impl<'o, const MAX: usize> zc::Dependant<'o> for crate::error::LimitedBacktrace<MAX> {
+    type Static = crate::error::LimitedBacktrace<MAX>;
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "zc", feature = "alloc"))))]
+unsafe impl<'o, const N: usize> zc::Dependant<'o> for crate::error::PrunedBacktrace<N> {
+    type Static = crate::error::PrunedBacktrace<N>;
+}
+
 #[cfg_attr(docsrs, doc(cfg(feature = "zc")))]
 unsafe impl<'o> zc::Dependant<'o> for crate::error::CoreContext {
     type Static = crate::error::CoreContext;
@@ -0,0 +1,133 @@
+//! Helpers for testing parsers.
+//!
+//! These panic with the error and input involved when a check fails, so they
+//! can be used directly within tests and fuzz targets.
+//!
+//! Parsers are passed as closures over a reader with [`Expected`] errors, so
+//! values borrowing from the input can be read.
+
+use alloc::vec::Vec;
+
+use crate::error::Details;
+use crate::fuzz::Mutation;
+use crate::{BytesReader, Expected, Input};
+
+/// Assert reading and serializing a value is stable.
+///
+/// The provided function reads a value and returns it serialized. The input
+/// is read and serialized, and the serialized bytes are then read and
+/// serialized again, which must result in the same bytes.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{BytesReader, Error};
+///
+/// fn decode<'i, E>(r: &mut BytesReader<'i, E>) -> Result<&'i [u8], E>
+/// where
+///     E: Error<'i>,
+/// {
+///     let len = r.read()?;
+///     r.take(len.into()).map(|bytes| bytes.as_dangerous())
+/// }
+///
+/// fn encode(value: &[u8]) -> Vec<u8> {
+///     let mut bytes = vec![value.len() as u8];
+///     bytes.extend_from_slice(value);
+///     bytes
+/// }
+///
+/// dangerous::testing::assert_round_trip(b"\x02hi", |r| decode(r).map(encode));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the input or the serialized bytes could not be read, or if the
+/// serialized bytes changed when read and serialized again.
+pub fn assert_round_trip<F>(input: &[u8], mut read_and_serialize: F)
+where
+    F: for<'i> FnMut(&mut BytesReader<'i, Expected<'i>>) -> Result<Vec<u8>, Expected<'i>>,
+{
+    let first = match crate::input(input).read_all(&mut read_and_serialize) {
+        Ok(first) => first,
+        Err(err) => panic!("failed to read the input:\n{:#}", err),
+    };
+    let second = match crate::input(first.as_slice()).read_all(&mut read_and_serialize) {
+        Ok(second) => second,
+        Err(err) => panic!("failed to read the serialized input:\n{:#}", err),
+    };
+    assert!(
+        first == second,
+        "serialized value changed when read and serialized again\n first: {}\nsecond: {}",
+        crate::input(first.as_slice()).display(),
+        crate::input(second.as_slice()).display(),
+    );
+}
+
+/// Assert errors from reading mutations of valid input have spans within it.
+///
+/// The valid input is cut short at every offset, and every byte of it is
+/// adjusted by one either side, removed and duplicated. Any error returned
+/// reading these must point within the input read, and if linked to a parent
+/// input, originate from within the parent. Off by one errors in spans, such
+/// as an origin calculated from the wrong side of a field, are found by this.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{BytesReader, Error};
+///
+/// fn decode<'i, E>(r: &mut BytesReader<'i, E>) -> Result<(), E>
+/// where
+///     E: Error<'i>,
+/// {
+///     let len = r.read()?;
+///     r.take(len.into())?;
+///     r.consume(b';')
+/// }
+///
+/// dangerous::testing::assert_error_spans(b"\x02hi;", |r| decode(r));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the valid input could not be read, or if an error is returned
+/// with a span or origin outside of its input.
+pub fn assert_error_spans<F>(input: &[u8], mut read: F)
+where
+    F: for<'i> FnMut(&mut BytesReader<'i, Expected<'i>>) -> Result<(), Expected<'i>>,
+{
+    if let Err(err) = crate::input(input).read_all(&mut read) {
+        panic!("failed to read the valid input:\n{:#}", err);
+    }
+    let mutations = (0..input.len()).flat_map(|offset| {
+        let duplicate = input[offset];
+        [
+            Mutation::Truncate(offset),
+            Mutation::Adjust(offset, 1),
+            Mutation::Adjust(offset, -1),
+            Mutation::Remove(offset),
+            Mutation::Insert(offset, duplicate),
+        ]
+    });
+    for mutation in mutations {
+        let mut bytes = input.to_vec();
+        mutation.apply(&mut bytes);
+        if let Err(err) = crate::input(bytes.as_slice()).read_all(&mut read) {
+            assert!(
+                err.span_range().is_some(),
+                "error span is outside of the input after {:?}:\n{:#}",
+                mutation,
+                err,
+            );
+            if let Some((parent, origin)) = err.parent() {
+                assert!(
+                    origin.is_within(parent.span()),
+                    "error origin is outside of the parent input after {:?}:\n{:#}",
+                    mutation,
+                    err,
+                );
+            }
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use core::str;
+
+use crate::input::AsciiClass;
 use crate::util::utf8::CharBytes;
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -10,8 +13,18 @@ pub(crate) fn count_u8(needle: u8, haystack: &[u8]) -> usize {
 }
 
 #[cfg(not(feature = "bytecount"))]
+#[inline(always)]
 pub(crate) fn count_u8(needle: u8, haystack: &[u8]) -> usize {
-    haystack.iter().copied().filter(|b| *b == needle).count()
+    swar::count_any(&[needle], haystack)
+}
+
+#[inline(always)]
+pub(crate) fn count_any_u8(needles: &[u8], haystack: &[u8]) -> usize {
+    match needles {
+        [] => 0,
+        [needle] => count_u8(*needle, haystack),
+        _ => swar::count_any(needles, haystack),
+    }
 }
 
 #[cfg(feature = "memchr")]
@@ -25,9 +38,31 @@ pub(crate) fn find_u8_match(needle: u8, haystack: &[u8]) -> Option<usize> {
     haystack.iter().copied().position(|b| b == needle)
 }
 
-// FIXME: impl SIMD variant
+#[inline(always)]
 pub(crate) fn find_u8_reject(needle: u8, haystack: &[u8]) -> Option<usize> {
-    haystack.iter().copied().position(|b| b != needle)
+    swar::find_range(needle, needle, haystack, true)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// str
+
+/// Reports `valid_up_to()` and `error_len()` the same as [`str::Utf8Error`].
+#[cfg(feature = "simdutf8")]
+pub(crate) type Utf8Error = simdutf8::compat::Utf8Error;
+
+#[cfg(not(feature = "simdutf8"))]
+pub(crate) type Utf8Error = str::Utf8Error;
+
+#[cfg(feature = "simdutf8")]
+#[inline(always)]
+pub(crate) fn from_utf8(bytes: &[u8]) -> Result<&str, Utf8Error> {
+    simdutf8::compat::from_utf8(bytes)
+}
+
+#[cfg(not(feature = "simdutf8"))]
+#[inline(always)]
+pub(crate) fn from_utf8(bytes: &[u8]) -> Result<&str, Utf8Error> {
+    str::from_utf8(bytes)
 }
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -42,7 +77,8 @@ pub(crate) fn num_chars(s: &str) -> usize {
 #[cfg(not(feature = "bytecount"))]
 #[inline(always)]
 pub(crate) fn num_chars(s: &str) -> usize {
-    s.chars().count()
+    // Every char has exactly one byte that isn't a continuation byte.
+    s.len() - swar::count_range(0x80, 0xBF, s.as_bytes())
 }
 
 #[inline(always)]
@@ -98,3 +134,248 @@ pub(crate) fn find_slice_reject(needle: &[u8], haystack: &[u8]) -> Option<usize>
             }
         })
 }
+
+///////////////////////////////////////////////////////////////////////////////
+// ascii class
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+pub(crate) fn find_class_match(class: AsciiClass, haystack: &[u8]) -> Option<usize> {
+    sse2::find_class(class, haystack, false)
+}
+
+#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+pub(crate) fn find_class_match(class: AsciiClass, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|b| class.matches(*b))
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+pub(crate) fn find_class_reject(class: AsciiClass, haystack: &[u8]) -> Option<usize> {
+    sse2::find_class(class, haystack, true)
+}
+
+#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+pub(crate) fn find_class_reject(class: AsciiClass, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|b| !class.matches(*b))
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// byte range
+
+#[inline(always)]
+pub(crate) fn find_range_match(start: u8, end: u8, haystack: &[u8]) -> Option<usize> {
+    swar::find_range(start, end, haystack, false)
+}
+
+#[inline(always)]
+pub(crate) fn find_range_reject(start: u8, end: u8, haystack: &[u8]) -> Option<usize> {
+    swar::find_range(start, end, haystack, true)
+}
+
+/// SSE2 is part of the `x86_64` baseline, so no runtime detection is needed.
+///
+/// FIXME: add a NEON variant once the MSRV allows `core::arch::aarch64`.
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod sse2 {
+    use core::arch::x86_64::{
+        __m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_min_epu8, _mm_movemask_epi8, _mm_or_si128,
+        _mm_set1_epi8, _mm_sub_epi8,
+    };
+
+    use super::AsciiClass;
+
+    const LANES: usize = 16;
+
+    /// Token punctuation as inclusive ranges.
+    const TOKEN_PUNCT: [(u8, u8); 7] = [
+        (b'!', b'!'),
+        (b'#', b'\''),
+        (b'*', b'+'),
+        (b'-', b'.'),
+        (b'^', b'`'),
+        (b'|', b'|'),
+        (b'~', b'~'),
+    ];
+
+    pub(super) fn find_class(class: AsciiClass, haystack: &[u8], reject: bool) -> Option<usize> {
+        let mut chunks = haystack.chunks_exact(LANES);
+        let mut offset = 0;
+        for chunk in &mut chunks {
+            // SAFETY: SSE2 is always available on x86_64 and the chunk is
+            // exactly 16 bytes long.
+            let mut mask = unsafe { class_mask(class, load(chunk)) };
+            if reject {
+                mask = !mask & 0xFFFF;
+            }
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+            offset += LANES;
+        }
+        chunks
+            .remainder()
+            .iter()
+            .position(|b| class.matches(*b) != reject)
+            .map(|index| offset + index)
+    }
+
+    /// # Safety
+    ///
+    /// The chunk must be at least 16 bytes long.
+    #[inline(always)]
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe fn load(chunk: &[u8]) -> __m128i {
+        _mm_loadu_si128(chunk.as_ptr().cast::<__m128i>())
+    }
+
+    /// Returns a bitmask with a bit set for each byte within the class.
+    #[inline(always)]
+    unsafe fn class_mask(class: AsciiClass, v: __m128i) -> u32 {
+        let matched = match class {
+            AsciiClass::Whitespace => _mm_or_si128(
+                _mm_or_si128(in_range(v, b'\t', b'\n'), in_range(v, 0x0C, b'\r')),
+                in_range(v, b' ', b' '),
+            ),
+            AsciiClass::Digit => in_range(v, b'0', b'9'),
+            AsciiClass::HexDigit => _mm_or_si128(in_range(v, b'0', b'9'), {
+                in_range(_mm_or_si128(v, splat(0x20)), b'a', b'f')
+            }),
+            AsciiClass::Alphabetic => alphabetic(v),
+            AsciiClass::Alphanumeric => _mm_or_si128(in_range(v, b'0', b'9'), alphabetic(v)),
+            AsciiClass::Token => TOKEN_PUNCT.iter().fold(
+                _mm_or_si128(in_range(v, b'0', b'9'), alphabetic(v)),
+                |acc, &(lo, hi)| _mm_or_si128(acc, in_range(v, lo, hi)),
+            ),
+        };
+        u32::from_ne_bytes(_mm_movemask_epi8(matched).to_ne_bytes())
+    }
+
+    #[inline(always)]
+    unsafe fn alphabetic(v: __m128i) -> __m128i {
+        in_range(_mm_or_si128(v, splat(0x20)), b'a', b'z')
+    }
+
+    /// Sets each byte to `0xFF` if within `lo..=hi` and `0x00` otherwise.
+    ///
+    /// Subtracting `lo` wraps bytes below the range above it, so a single
+    /// unsigned comparison against `hi - lo` checks both bounds.
+    #[inline(always)]
+    unsafe fn in_range(v: __m128i, lo: u8, hi: u8) -> __m128i {
+        let shifted = _mm_sub_epi8(v, splat(lo));
+        let width = splat(hi - lo);
+        _mm_cmpeq_epi8(_mm_min_epu8(shifted, width), shifted)
+    }
+
+    #[inline(always)]
+    unsafe fn splat(byte: u8) -> __m128i {
+        _mm_set1_epi8(i8::from_ne_bytes([byte]))
+    }
+}
+
+/// SIMD within a register: bytes are compared a machine word at a time with
+/// plain integer arithmetic, so this works on every target.
+mod swar {
+    const WORD: usize = core::mem::size_of::<usize>();
+    /// The low seven bits of every byte.
+    const LOW: usize = usize::MAX / 0xFF * 0x7F;
+    /// The high bit of every byte.
+    const HIGH: usize = usize::MAX / 0xFF * 0x80;
+
+    pub(super) fn find_range(start: u8, end: u8, haystack: &[u8], reject: bool) -> Option<usize> {
+        let mut chunks = haystack.chunks_exact(WORD);
+        let mut offset = 0;
+        for chunk in &mut chunks {
+            let mut bytes = [0; WORD];
+            bytes.copy_from_slice(chunk);
+            // Loading as little-endian puts the first byte in the lowest bits
+            // on every target.
+            let word = usize::from_le_bytes(bytes);
+            let mut mask = in_range(word, start, end);
+            if reject {
+                mask = !mask & HIGH;
+            }
+            if mask != 0 {
+                return Some(offset + (mask.trailing_zeros() / 8) as usize);
+            }
+            offset += WORD;
+        }
+        chunks
+            .remainder()
+            .iter()
+            .position(|b| (start <= *b && *b <= end) != reject)
+            .map(|index| offset + index)
+    }
+
+    pub(super) fn count_any(needles: &[u8], haystack: &[u8]) -> usize {
+        count(
+            haystack,
+            |word| {
+                needles
+                    .iter()
+                    .fold(0, |mask, needle| mask | in_range(word, *needle, *needle))
+            },
+            |byte| needles.contains(&byte),
+        )
+    }
+
+    #[cfg_attr(feature = "bytecount", allow(dead_code))]
+    pub(super) fn count_range(start: u8, end: u8, haystack: &[u8]) -> usize {
+        count(
+            haystack,
+            |word| in_range(word, start, end),
+            |byte| start <= byte && byte <= end,
+        )
+    }
+
+    /// Counts the bytes matched, given the high bit mask of the matches in a
+    /// word and the same check for a single byte.
+    #[inline(always)]
+    fn count<M, B>(haystack: &[u8], word_mask: M, byte_matches: B) -> usize
+    where
+        M: Fn(usize) -> usize,
+        B: Fn(u8) -> bool,
+    {
+        let mut chunks = haystack.chunks_exact(WORD);
+        let mut count = 0;
+        for chunk in &mut chunks {
+            let mut bytes = [0; WORD];
+            bytes.copy_from_slice(chunk);
+            count += word_mask(usize::from_ne_bytes(bytes)).count_ones() as usize;
+        }
+        count
+            + chunks
+                .remainder()
+                .iter()
+                .filter(|byte| byte_matches(**byte))
+                .count()
+    }
+
+    /// Sets the high bit of each byte within `start..=end`.
+    #[inline(always)]
+    fn in_range(word: usize, start: u8, end: u8) -> usize {
+        let below_end = match end.checked_add(1) {
+            Some(after) => !at_least(word, after) & HIGH,
+            None => HIGH,
+        };
+        at_least(word, start) & below_end
+    }
+
+    /// Sets the high bit of each byte greater than or equal to `min`.
+    ///
+    /// The low seven bits are compared by subtracting from each byte with its
+    /// high bit set, which can't borrow from the byte above. The high bits
+    /// then decide the comparison unless they are equal.
+    #[inline(always)]
+    fn at_least(word: usize, min: u8) -> usize {
+        let low = ((word & LOW) | HIGH) - splat(min & 0x7F);
+        if min & 0x80 == 0 {
+            (word | low) & HIGH
+        } else {
+            word & low & HIGH
+        }
+    }
+
+    #[inline(always)]
+    fn splat(byte: u8) -> usize {
+        usize::MAX / 0xFF * usize::from(byte)
+    }
+}
@@ -1,9 +1,15 @@
+//! Slicing of input, with bounds already proven by the caller.
+//!
+//! By default the bounds are checked again, so a bug in the crate's own checks
+//! panics rather than reading out of bounds. With the `unchecked-internals`
+//! feature in a build without debug assertions, the second check is skipped.
+
 /// Returns an end slice of the slice provided (always empty).
 #[inline(always)]
 pub(crate) fn end<T>(slice: &[T]) -> &[T] {
     // SAFETY: This is always valid as we a getting a new slice from its own
     // length.
-    unsafe { slice.get_unchecked(slice.len()..) }
+    unsafe { get_from(slice, slice.len()) }
 }
 
 /// Splits a slice at `mid`.
@@ -22,8 +28,10 @@ pub(crate) fn split_at_opt<T>(slice: &[T], mid: usize) -> Option<(&[T], &[T])> {
 /// Returns the first item in a slice without bounds checking.
 #[inline(always)]
 pub(crate) unsafe fn first_unchecked<T: Copy>(slice: &[T]) -> T {
-    debug_assert!(!slice.is_empty());
-    *slice.get_unchecked(0)
+    #[cfg(all(feature = "unchecked-internals", not(debug_assertions)))]
+    return *slice.get_unchecked(0);
+    #[cfg(not(all(feature = "unchecked-internals", not(debug_assertions))))]
+    return slice[0];
 }
 
 /// Splits a slice at `mid` without bounds checking.
@@ -33,8 +41,10 @@ pub(crate) unsafe fn first_unchecked<T: Copy>(slice: &[T]) -> T {
 /// Caller has to check that `0 <= mid <= slice.len()`
 #[inline(always)]
 pub(crate) unsafe fn split_at_unchecked<T>(slice: &[T], mid: usize) -> (&[T], &[T]) {
-    debug_assert!(mid <= slice.len());
-    (slice.get_unchecked(..mid), slice.get_unchecked(mid..))
+    #[cfg(all(feature = "unchecked-internals", not(debug_assertions)))]
+    return (slice.get_unchecked(..mid), slice.get_unchecked(mid..));
+    #[cfg(not(all(feature = "unchecked-internals", not(debug_assertions))))]
+    return slice.split_at(mid);
 }
 
 /// Splits a str slice at `mid` without bounds checking.
@@ -45,9 +55,10 @@ pub(crate) unsafe fn split_at_unchecked<T>(slice: &[T], mid: usize) -> (&[T], &[
 /// char boundary.
 #[inline(always)]
 pub(crate) unsafe fn split_str_at_unchecked(slice: &str, mid: usize) -> (&str, &str) {
-    debug_assert!(mid <= slice.len());
-    debug_assert!(slice.is_char_boundary(mid));
-    (slice.get_unchecked(..mid), slice.get_unchecked(mid..))
+    #[cfg(all(feature = "unchecked-internals", not(debug_assertions)))]
+    return (slice.get_unchecked(..mid), slice.get_unchecked(mid..));
+    #[cfg(not(all(feature = "unchecked-internals", not(debug_assertions))))]
+    return slice.split_at(mid);
 }
 
 /// Returns the slice as a reference to an array.
@@ -57,7 +68,22 @@ pub(crate) unsafe fn split_str_at_unchecked(slice: &str, mid: usize) -> (&str, &
 /// Caller has to check that `slice.len() == N`.
 #[inline(always)]
 pub(crate) unsafe fn slice_to_array_unchecked<T, const N: usize>(slice: &[T]) -> &[T; N] {
-    debug_assert!(slice.len() == N);
+    #[cfg(all(feature = "unchecked-internals", not(debug_assertions)))]
     // Cast the slice pointer to an array pointer and reborrow.
-    &*slice.as_ptr().cast::<[T; N]>()
+    return &*slice.as_ptr().cast::<[T; N]>();
+    #[cfg(not(all(feature = "unchecked-internals", not(debug_assertions))))]
+    return <&[T; N]>::try_from(slice).expect("slice length does not match the array");
+}
+
+/// Returns the slice from `start` onwards.
+///
+/// # Safety
+///
+/// Caller has to check that `start <= slice.len()`.
+#[inline(always)]
+unsafe fn get_from<T>(slice: &[T], start: usize) -> &[T] {
+    #[cfg(all(feature = "unchecked-internals", not(debug_assertions)))]
+    return slice.get_unchecked(start..);
+    #[cfg(not(all(feature = "unchecked-internals", not(debug_assertions))))]
+    return &slice[start..];
 }
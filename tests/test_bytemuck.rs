@@ -0,0 +1,64 @@
+use dangerous::error::{Expected, Reason};
+use dangerous::{Input, ToRetryRequirement};
+
+#[repr(align(4))]
+struct Aligned([u8; 12]);
+
+fn aligned() -> Aligned {
+    let mut bytes = Aligned([0; 12]);
+    for (value, chunk) in (1u32..).zip(bytes.0.chunks_mut(4)) {
+        chunk.copy_from_slice(&value.to_ne_bytes());
+    }
+    bytes
+}
+
+#[test]
+fn test_take_pod_slice() {
+    let bytes = aligned();
+    let (values, remaining) = dangerous::input(&bytes.0)
+        .read_partial::<_, _, Expected<'_>>(|r| r.take_pod_slice::<u32>(2))
+        .unwrap();
+    assert_eq!(values, [1, 2]);
+    assert_eq!(remaining, 3u32.to_ne_bytes()[..]);
+}
+
+#[test]
+fn test_take_pod_slice_empty() {
+    let values = dangerous::input(b"")
+        .read_all::<_, _, Expected<'_>>(|r| r.take_pod_slice::<u64>(0))
+        .unwrap();
+    assert!(values.is_empty());
+}
+
+#[test]
+fn test_take_pod_slice_misaligned() {
+    let bytes = aligned();
+    let err = dangerous::input(&bytes.0)
+        .read_all::<_, _, Expected<'_>>(|r| {
+            r.skip(1)?;
+            r.take_pod_slice::<u32>(2)?;
+            r.skip(3)
+        })
+        .unwrap_err();
+    assert_eq!(err.reason(), Some(Reason::Misaligned));
+    assert_eq!(err.span_range(), Some(1..9));
+}
+
+#[test]
+fn test_take_pod_slice_too_short() {
+    let bytes = aligned();
+    let err = dangerous::input(&bytes.0)
+        .read_all::<_, _, Expected<'_>>(|r| r.take_pod_slice::<u32>(4))
+        .unwrap_err();
+    assert!(!err.is_fatal());
+    assert_eq!(err.reason(), None);
+}
+
+#[test]
+fn test_take_pod_slice_overflowing_count() {
+    let bytes = aligned();
+    let err = dangerous::input(&bytes.0)
+        .read_all::<_, _, Expected<'_>>(|r| r.take_pod_slice::<u32>(usize::MAX))
+        .unwrap_err();
+    assert!(!err.is_fatal());
+}
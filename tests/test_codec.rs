@@ -0,0 +1,142 @@
+use dangerous::codec::{base32, base64, hex, Decoded};
+use dangerous::{Expected, Input, ToRetryRequirement};
+
+type Decode = for<'i> fn(dangerous::Bytes<'i>) -> Result<Decoded, Expected<'i>>;
+
+const VECTORS: [&[u8]; 7] = [b"", b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar"];
+
+fn decode_ok(decode: Decode, encoded: &str) -> Vec<u8> {
+    decode(dangerous::input(encoded.as_bytes()).into_bound())
+        .unwrap()
+        .into_vec()
+}
+
+fn decode_err(decode: Decode, encoded: &str) -> Option<std::ops::Range<usize>> {
+    let err = decode(dangerous::input(encoded.as_bytes()).into_bound()).unwrap_err();
+    assert!(err.is_fatal(), "{:?}", encoded);
+    err.span_range()
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// RFC 4648 test vectors
+
+#[test]
+fn test_hex_vectors() {
+    let expected = [
+        "",
+        "66",
+        "666f",
+        "666f6f",
+        "666f6f62",
+        "666f6f6261",
+        "666f6f626172",
+    ];
+    for (bytes, encoded) in VECTORS.iter().zip(expected) {
+        assert_eq!(hex::encode(bytes), encoded);
+        assert_eq!(hex::encode_upper(bytes), encoded.to_ascii_uppercase());
+        assert_eq!(decode_ok(|i| hex::decode(i), encoded), *bytes);
+        assert_eq!(
+            decode_ok(|i| hex::decode(i), &encoded.to_ascii_uppercase()),
+            *bytes
+        );
+    }
+}
+
+#[test]
+fn test_base32_vectors() {
+    let expected = [
+        "",
+        "MY======",
+        "MZXQ====",
+        "MZXW6===",
+        "MZXW6YQ=",
+        "MZXW6YTB",
+        "MZXW6YTBOI======",
+    ];
+    for (bytes, encoded) in VECTORS.iter().zip(expected) {
+        assert_eq!(base32::encode(bytes), encoded);
+        assert_eq!(decode_ok(|i| base32::decode(i), encoded), *bytes);
+        assert_eq!(
+            decode_ok(|i| base32::decode(i), &encoded.to_ascii_lowercase()),
+            *bytes
+        );
+    }
+}
+
+#[test]
+fn test_base64_vectors() {
+    let expected = [
+        "", "Zg==", "Zm8=", "Zm9v", "Zm9vYg==", "Zm9vYmE=", "Zm9vYmFy",
+    ];
+    for (bytes, encoded) in VECTORS.iter().zip(expected) {
+        assert_eq!(base64::encode(bytes), encoded);
+        assert_eq!(decode_ok(|i| base64::decode(i), encoded), *bytes);
+        let url = encoded.trim_end_matches('=');
+        assert_eq!(base64::encode_url(bytes), url);
+        assert_eq!(decode_ok(|i| base64::decode_url(i), url), *bytes);
+    }
+}
+
+#[test]
+fn test_round_trips() {
+    let bytes: Vec<u8> = (0..=255).collect();
+    for len in 0..40 {
+        let bytes = &bytes[bytes.len() - len..];
+        assert_eq!(decode_ok(|i| hex::decode(i), &hex::encode(bytes)), bytes);
+        assert_eq!(
+            decode_ok(|i| base32::decode(i), &base32::encode(bytes)),
+            bytes
+        );
+        assert_eq!(
+            decode_ok(|i| base64::decode(i), &base64::encode(bytes)),
+            bytes
+        );
+        assert_eq!(
+            decode_ok(|i| base64::decode_url(i), &base64::encode_url(bytes)),
+            bytes
+        );
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Invalid
+
+#[test]
+fn test_invalid_chars() {
+    assert_eq!(decode_err(|i| hex::decode(i), "0g"), Some(1..2));
+    assert_eq!(decode_err(|i| base32::decode(i), "MY1====="), Some(2..3));
+    assert_eq!(decode_err(|i| base64::decode(i), "Zm-v"), Some(2..3));
+    assert_eq!(decode_err(|i| base64::decode_url(i), "Zm+v"), Some(2..3));
+}
+
+#[test]
+fn test_invalid_lengths() {
+    decode_err(|i| hex::decode(i), "666");
+    decode_err(|i| base32::decode(i), "M=======");
+    decode_err(|i| base32::decode(i), "MY");
+    decode_err(|i| base32::decode(i), "MY=====");
+    decode_err(|i| base64::decode(i), "Z===");
+    decode_err(|i| base64::decode(i), "Zg");
+    decode_err(|i| base64::decode(i), "Zg=");
+    decode_err(|i| base64::decode(i), "Zg===");
+    decode_err(|i| base64::decode_url(i), "Z");
+    decode_err(|i| base64::decode_url(i), "Zg==");
+}
+
+#[test]
+fn test_retry_requirements() {
+    let err = hex::decode::<Expected<'_>>(dangerous::input(b"666")).unwrap_err();
+    assert!(err.to_retry_requirement().is_none());
+    let err = base64::decode::<Expected<'_>>(dangerous::input(b"Zg=")).unwrap_err();
+    assert!(err.to_retry_requirement().is_some());
+}
+
+#[test]
+fn test_decoded_input_is_bound() {
+    let decoded = hex::decode::<Expected<'_>>(dangerous::input(b"0102")).unwrap();
+    let err = decoded
+        .input()
+        .read_all::<_, _, Expected<'_>>(|r| r.take(3))
+        .unwrap_err();
+    assert!(err.is_fatal());
+}
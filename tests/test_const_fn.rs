@@ -0,0 +1,90 @@
+use dangerous::{ConstReader, Invalid};
+
+#[derive(Debug)]
+struct Header {
+    version: u16,
+    flags: u8,
+    len: u32,
+}
+
+const fn parse_header(r: ConstReader<'_>) -> Result<(Header, ConstReader<'_>), Invalid> {
+    let r = match r.consume(b"HDR") {
+        Ok(r) => r,
+        Err(err) => return Err(err),
+    };
+    let (version, r) = match r.read_u16_le() {
+        Ok(read) => read,
+        Err(err) => return Err(err),
+    };
+    let (flags, r) = match r.read() {
+        Ok(read) => read,
+        Err(err) => return Err(err),
+    };
+    match r.read_u32_be() {
+        Ok((len, r)) => Ok((
+            Header {
+                version,
+                flags,
+                len,
+            },
+            r,
+        )),
+        Err(err) => Err(err),
+    }
+}
+
+const HEADER: Header = match parse_header(ConstReader::new(b"HDR\x02\x00\x01\x00\x00\x01\x00")) {
+    Ok((header, r)) if r.at_end() => header,
+    _ => panic!("invalid header"),
+};
+
+#[test]
+fn test_const_parse() {
+    assert_eq!(HEADER.version, 2);
+    assert_eq!(HEADER.flags, 1);
+    assert_eq!(HEADER.len, 256);
+}
+
+#[test]
+fn test_const_parse_errors() {
+    assert!(parse_header(ConstReader::new(b"")).is_err());
+    assert!(parse_header(ConstReader::new(b"HDX\x02\x00\x01\x00\x00\x01\x00")).is_err());
+    assert_eq!(
+        parse_header(ConstReader::new(b"HDR\x02\x00\x01\x00\x00\x01")).unwrap_err(),
+        Invalid::fatal()
+    );
+}
+
+#[test]
+fn test_take() {
+    const SPLIT: (ConstReader<'_>, ConstReader<'_>) = match ConstReader::new(b"abcdef").take(4) {
+        Ok(split) => split,
+        Err(_) => panic!(),
+    };
+    let (head, tail) = SPLIT;
+    assert_eq!(head.remaining_bytes(), 4);
+    assert_eq!(head.as_dangerous(), b"abcd");
+    assert_eq!(head.into_input(), b"abcd"[..]);
+    assert_eq!(tail.as_dangerous(), b"ef");
+    assert!(head.peek_eq(b"abc"));
+    assert!(!head.peek_eq(b"abcde"));
+
+    let head = head.skip(3).unwrap();
+    assert_eq!(head.read().unwrap().0, b'd');
+    assert!(head.skip(2).is_err());
+    assert!(head.take(2).is_err());
+    assert!(head.read_u16_be().is_err());
+}
+
+#[test]
+fn test_read_ints() {
+    let r = ConstReader::new(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(r.read_u16_le().unwrap().0, 0x0201);
+    assert_eq!(r.read_u16_be().unwrap().0, 0x0102);
+    assert_eq!(r.read_u32_le().unwrap().0, 0x0403_0201);
+    assert_eq!(r.read_u32_be().unwrap().0, 0x0102_0304);
+    assert_eq!(r.read_u64_le().unwrap().0, 0x0807_0605_0403_0201);
+    assert_eq!(r.read_u64_be().unwrap().0, 0x0102_0304_0506_0708);
+    assert_eq!(r.take_array::<3>().unwrap().0, [1, 2, 3]);
+    assert!(r.read_u64_le().unwrap().1.at_end());
+}
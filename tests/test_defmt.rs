@@ -0,0 +1,29 @@
+//! Formatting with `defmt` needs a global logger, which is only available on
+//! embedded targets, so these only check the implementations exist.
+
+use dangerous::error::{
+    CoreOperation, ErrorKind, Expected, Fatal, FullBacktrace, Invalid, RetryRequirement,
+    RootBacktrace,
+};
+use defmt::Format;
+
+fn assert_format<T: Format + ?Sized>() {}
+
+#[test]
+fn test_retry_requirement() {
+    assert_format::<RetryRequirement>();
+}
+
+#[test]
+fn test_invalid() {
+    assert_format::<Invalid>();
+    assert_format::<Fatal>();
+}
+
+#[test]
+fn test_expected() {
+    assert_format::<Expected<'_, RootBacktrace>>();
+    assert_format::<Expected<'_, FullBacktrace>>();
+    assert_format::<ErrorKind>();
+    assert_format::<CoreOperation>();
+}
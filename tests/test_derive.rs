@@ -0,0 +1,126 @@
+#[macro_use]
+mod common;
+
+use common::*;
+
+///////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq, ReadBinary)]
+#[dangerous(magic = b"HDR", endian = "big")]
+struct Header<'a> {
+    #[dangerous(eq = 2)]
+    version: u8,
+    #[dangerous(endian = "little")]
+    flags: u16,
+    #[dangerous(pad_before = 1)]
+    kind: Kind,
+    count: u8,
+    #[dangerous(len = count)]
+    values: Vec<u8>,
+    #[dangerous(prefix = u16)]
+    name: String<'a>,
+}
+
+#[derive(Debug, PartialEq, ReadBinary)]
+#[dangerous(tag = u16, endian = "big")]
+enum Kind {
+    #[dangerous(tag = 1)]
+    Empty,
+    #[dangerous(tag = 2)]
+    Point(u16, u16),
+    #[dangerous(tag = 3)]
+    Sized {
+        #[dangerous(pad_after = 2)]
+        len: u32,
+    },
+}
+
+#[derive(Debug, PartialEq, ReadBinary)]
+struct Wrapper<T>(T, Option<T>);
+
+#[test]
+fn test_struct() {
+    let header = read_all_ok!(
+        b"HDR\x02\x01\x00\xFF\x00\x02\x00\x01\x00\x02\x02\x02\x03\x00\x02hi",
+        Header::from_reader
+    );
+    assert_eq!(header.version, 2);
+    assert_eq!(header.flags, 1);
+    assert_eq!(header.kind, Kind::Point(1, 2));
+    assert_eq!(header.values, [2, 3]);
+    assert_eq!(header.name, "hi");
+}
+
+#[test]
+fn test_enum() {
+    assert_eq!(read_all_ok!(b"\x00\x01", Kind::from_reader), Kind::Empty);
+    assert_eq!(
+        read_all_ok!(b"\x00\x03\x00\x00\x00\x07\xFF\xFF", Kind::from_reader),
+        Kind::Sized { len: 7 }
+    );
+}
+
+#[test]
+fn test_generic() {
+    assert_eq!(
+        read_all_ok!(b"\x01\x00\x01\x02\x00", Wrapper::<u16>::from_reader),
+        Wrapper(1, Some(2))
+    );
+}
+
+#[test]
+fn test_invalid_magic() {
+    let error = read_all_err!(b"HDX\x02", Header::from_reader);
+    assert_eq!(error.span_range(), Some(0..3));
+}
+
+#[test]
+fn test_invalid_eq() {
+    let error = read_all_err!(b"HDR\x03\x01\x00", Header::from_reader);
+    assert_eq!(error.span_range(), Some(3..4));
+    assert_str_eq!(
+        format!("{:#}\n", error),
+        indoc! {r#"
+            failed to read and expect a value: expected 2
+            > "HDR\u{3}\u{1}\0"
+                  ^^^^^        
+            additional:
+              error line: 1, error column: 4, error offset: 3, input length: 6
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected Header)
+              3. `<context>` (expected version)
+              4. `read and expect a value` (expected 2)
+        "#}
+    );
+}
+
+#[test]
+fn test_invalid_tag() {
+    let error = read_all_err!(b"\x00\x09", Kind::from_reader);
+    assert_eq!(error.span_range(), Some(0..2));
+    assert_str_eq!(
+        format!("{:#}\n", error),
+        indoc! {r#"
+            failed to read and expect a value: expected Kind tag
+            > "\0\t"
+               ^^^^ 
+            additional:
+              error line: 1, error column: 1, error offset: 0, input length: 2
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected Kind)
+              3. `<context>` (expected tag)
+              4. `read and expect a value` (expected Kind tag)
+        "#}
+    );
+}
+
+#[test]
+fn test_retry() {
+    let error = read_all_err!(
+        b"HDR\x02\x01\x00\xFF\x00\x02\x00\x01\x00",
+        Header::from_reader
+    );
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::exactly(1));
+}
@@ -7,7 +7,7 @@ use std::fmt;
 macro_rules! assert_input_display_eq {
     ($input:expr, $format:expr, $expected:expr) => {
         assert_eq!(
-            format!($format, input!(<&[u8]>::from($input.as_ref()))),
+            format!($format, input!(AsRef::<[u8]>::as_ref($input))),
             $expected
         );
     };
@@ -59,6 +59,7 @@ fn test_invalid_span_does_nothing() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_format_with_mut_ref_write() {
     use dangerous::display::{DisplayBase, Write};
 
@@ -94,4 +95,915 @@ fn test_preferred_format_debug() {
     assert_eq!(format!("{:?}", PreferredFormat::StrCjk), "StrCjk");
     assert_eq!(format!("{:?}", PreferredFormat::Bytes), "Bytes");
     assert_eq!(format!("{:?}", PreferredFormat::BytesAscii), "BytesAscii");
+    assert_eq!(format!("{:?}", PreferredFormat::StrLossy), "StrLossy");
+    assert_eq!(format!("{:?}", PreferredFormat::ByteStr), "ByteStr");
+}
+
+#[test]
+#[cfg(feature = "color")]
+fn test_color_span() {
+    let full = "hello world";
+    let input = input!(full.as_bytes());
+    let span = full[6..].as_bytes().into();
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(span, 80)
+            .color(true)
+            .to_string(),
+        "\"hello \x1b[1;31mworld\x1b[0m\""
+    );
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(span, 80)
+            .underline()
+            .color(true)
+            .to_string(),
+        "       \x1b[1;31m^^^^^ \x1b[0m"
+    );
+    assert_eq!(
+        input
+            .display()
+            .span(full[..1].as_bytes().into(), 80)
+            .color(true)
+            .to_string(),
+        "[\x1b[1;31m68\x1b[0m 65 6c 6c 6f 20 77 6f 72 6c 64]"
+    );
+    assert_eq!(
+        input.display().str_hint().span(span, 80).to_string(),
+        "\"hello world\""
+    );
+}
+
+#[test]
+fn test_hexdump_head_tail() {
+    let full: Vec<u8> = (0..=0x4f).collect();
+    let input = input!(&full[..]);
+    assert_eq!(
+        input.display().hexdump().head_tail(32).to_string(),
+        indoc! {"
+            00000000  00 01 02 03 04 05 06 07  08 09 0a 0b 0c 0d 0e 0f  |................|
+            ...
+            00000040  40 41 42 43 44 45 46 47  48 49 4a 4b 4c 4d 4e 4f  |@ABCDEFGHIJKLMNO|"
+        }
+    );
+    assert_eq!(
+        input.display().hexdump().full().to_string().lines().count(),
+        5
+    );
+    assert_eq!(input!(b"").display().hexdump().to_string(), "");
+}
+
+#[test]
+#[cfg(feature = "color")]
+fn test_hexdump_color_span() {
+    let full = b"hello";
+    let input = input!(&full[..]);
+    assert_eq!(
+        input
+            .display()
+            .hexdump()
+            .span(full[4..].into(), 16)
+            .color(true)
+            .to_string(),
+        "00000000  68 65 6c 6c \x1b[1;31m6f\x1b[0m                                    \
+         |hell\x1b[1;31mo\x1b[0m|"
+    );
+}
+
+#[test]
+fn test_offsets() {
+    let full = b"hello world";
+    let input = input!(&full[..]);
+    assert_eq!(
+        input.display().str_hint().offsets(true).to_string(),
+        r#""hello world" (offsets 0..11 of 11)"#
+    );
+    assert_eq!(
+        input.display().head_tail(16).offsets(true).to_string(),
+        "[68 65 .. 6c 64] (offsets 0..2 and 9..11 of 11)"
+    );
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(full[6..7].into(), 16)
+            .offsets(true)
+            .to_string(),
+        r#""hello world" (offsets 0..11 of 11, span 6..7)"#
+    );
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(full[6..7].into(), 16)
+            .underline()
+            .offsets(true)
+            .to_string(),
+        "       ^     "
+    );
+}
+
+#[test]
+fn test_str_lossy() {
+    use dangerous::display::PreferredFormat;
+    let full = b"hello \xffworld\xe2\x99\xa5";
+    let input = input!(&full[..]);
+    assert_eq!(
+        input
+            .display()
+            .format(PreferredFormat::StrLossy)
+            .to_string(),
+        r#""hello \xffworld♥""#
+    );
+    assert_eq!(
+        input
+            .display()
+            .format(PreferredFormat::StrLossy)
+            .head_tail(16)
+            .to_string(),
+        r#""hell" .. "rld♥""#
+    );
+    assert_eq!(
+        input
+            .display()
+            .format(PreferredFormat::StrLossy)
+            .span(full[6..7].into(), 80)
+            .underline()
+            .to_string(),
+        "       ^^^^       "
+    );
+    assert_eq!(
+        input!(&b"\xe2\x99"[..])
+            .display()
+            .format(PreferredFormat::StrLossy)
+            .to_string(),
+        r#""\xe2\x99""#
+    );
+}
+
+#[test]
+fn test_byte_str() {
+    use dangerous::display::PreferredFormat;
+    let full = b"GET /\"a\\b\" \x00\xff\r\n\tHTTP/1.1";
+    let input = input!(&full[..]);
+    assert_eq!(
+        input.display().format(PreferredFormat::ByteStr).to_string(),
+        r#"b"GET /\"a\\b\" \0\xff\r\n\tHTTP/1.1""#
+    );
+    assert_eq!(
+        input
+            .display()
+            .format(PreferredFormat::ByteStr)
+            .head_tail(24)
+            .to_string(),
+        r#"b"GET /\"a" .. b"TP/1.1""#
+    );
+    assert_eq!(
+        input
+            .display()
+            .format(PreferredFormat::ByteStr)
+            .span(full[12..13].into(), 24)
+            .to_string(),
+        r#".. b"b\" \0\xff\r\n" .."#
+    );
+    assert_eq!(
+        input
+            .display()
+            .format(PreferredFormat::ByteStr)
+            .span(full[12..13].into(), 24)
+            .underline()
+            .to_string(),
+        "           ^^^^        "
+    );
+}
+
+#[test]
+fn test_span_around() {
+    let full = "hello world, how are you";
+    let input = input!(full.as_bytes());
+    let span = full[6..11].as_bytes().into();
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span_around(span, 2, 5)
+            .to_string(),
+        r#".. "o world, how" .."#
+    );
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span_around(span, 2, 5)
+            .underline()
+            .to_string(),
+        "      ^^^^^         "
+    );
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span_around(span, 64, 64)
+            .to_string(),
+        r#""hello world, how are you""#
+    );
+    let full = "♥♥x♥♥";
+    let input = input!(full.as_bytes());
+    let span = full[6..7].as_bytes().into();
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span_around(span, 2, 4)
+            .to_string(),
+        r#".. "x♥" .."#
+    );
+}
+
+#[test]
+fn test_hexdump_span_around() {
+    let full: Vec<u8> = (0..=0x4f).collect();
+    let input = input!(&full[..]);
+    let span = full[0x21..0x22].into();
+    let display = input
+        .display()
+        .hexdump()
+        .span_around(span, 2, 16)
+        .to_string();
+    let offsets: Vec<&str> = display
+        .lines()
+        .filter(|line| !line.starts_with(' '))
+        .filter_map(|line| line.split(' ').next())
+        .collect();
+    assert_eq!(offsets, ["...", "00000010", "00000020", "00000030", "..."]);
+}
+
+#[test]
+fn test_span_around_chars() {
+    use dangerous::display::PreferredFormat;
+    let full = "♥♥♥x♥♥♥";
+    let input = input!(full.as_bytes());
+    let span = full[9..10].as_bytes().into();
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span_around(span, 4, 4)
+            .to_string(),
+        r#".. "♥x♥" .."#
+    );
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span_around_chars(span, 1, 3)
+            .to_string(),
+        r#".. "♥x♥♥♥""#
+    );
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span_around_chars(span, 1, 3)
+            .underline()
+            .to_string(),
+        "     ^    "
+    );
+    let full = b"ab\xffcd\xe2\x99\xa5";
+    let input = input!(&full[..]);
+    assert_eq!(
+        input
+            .display()
+            .format(PreferredFormat::StrLossy)
+            .span_around_chars(full[3..4].into(), 1, 2)
+            .to_string(),
+        r#".. "\xffcd♥""#
+    );
+}
+
+#[test]
+fn test_label() {
+    let full = "(hello) [world";
+    let input = input!(full.as_bytes());
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(full[1..6].as_bytes().into(), 80)
+            .secondary_span(full[8..9].as_bytes().into())
+            .label("here")
+            .underline()
+            .to_string(),
+        "  ^^^^^ here"
+    );
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(full[14..].as_bytes().into(), 80)
+            .label("unclosed")
+            .underline()
+            .to_string(),
+        "               ^ unclosed"
+    );
+    assert_eq!(
+        input
+            .display()
+            .span(full[8..9].as_bytes().into(), 80)
+            .label("here")
+            .to_string(),
+        "[28 68 65 6c 6c 6f 29 20 5b 77 6f 72 6c 64]"
+    );
+}
+
+#[test]
+fn test_html() {
+    let full = "if a < b && c > \"d\"";
+    let input = input!(full.as_bytes());
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(full[5..6].as_bytes().into(), 80)
+            .secondary_span(full[10..11].as_bytes().into())
+            .html(true)
+            .to_string(),
+        "<pre class=\"dangerous-input\">&quot;if a <span class=\"dangerous-highlight\">&lt;</span> b &amp;<span class=\"dangerous-secondary\">&amp;</span> c &gt; \\&quot;d\\&quot;&quot;</pre>"
+    );
+    assert_eq!(
+        input
+            .display()
+            .span(full[5..6].as_bytes().into(), 20)
+            .uppercase(true)
+            .html(true)
+            .to_string(),
+        "<pre class=\"dangerous-input\">[.. 61 20 <span class=\"dangerous-highlight\">3C</span> 20 ..]</pre>"
+    );
+}
+
+#[test]
+fn test_preset() {
+    use dangerous::display::Preset;
+    let full: Vec<u8> = (0..24).collect();
+    let input = input!(&full[..]);
+    assert_eq!(
+        input.display().head(16).preset(Preset::Full).to_string(),
+        "[00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f 10 11 12 13 14 15 16 17] (offsets 0..24 of 24)"
+    );
+    assert_eq!(
+        input.display().preset(Preset::Compact).to_string(),
+        "[00 01 02 03 04 .. 14 15 16 17]"
+    );
+    assert_eq!(
+        input
+            .display()
+            .span_around(full[20..21].into(), 1, 1)
+            .offsets(true)
+            .preset(Preset::Compact)
+            .to_string(),
+        "[.. 0f 10 11 12 13 14 15 16 17]"
+    );
+    assert_eq!(
+        input.display().preset(Preset::Hexdump).to_string(),
+        input.display().hexdump().to_string()
+    );
+    // Options set after a preset override it
+    assert_eq!(
+        input
+            .display()
+            .preset(Preset::Full)
+            .offsets(false)
+            .to_string(),
+        input.display().full().to_string()
+    );
+}
+
+#[test]
+fn test_ruler() {
+    let full: Vec<u8> = (0..=0xff).chain(0..0x10).collect();
+    let input = input!(&full[..]);
+    assert_eq!(
+        input
+            .display()
+            .span(full[0xfc..0xfd].into(), 40)
+            .ruler(true)
+            .to_string(),
+        "    f8                      0100\n[.. f8 f9 fa fb fc fd fe ff 00 01 02 ..]"
+    );
+    assert_eq!(
+        input
+            .display()
+            .span(full[0x104..0x105].into(), 40)
+            .ruler(true)
+            .to_string(),
+        "    0100                    0108\n[.. 00 01 02 03 04 05 06 07 08 09 0a ..]"
+    );
+    assert_eq!(
+        input.display().head(24).ruler(true).underline().to_string(),
+        "                      "
+    );
+    assert_eq!(
+        input!(b"hello")
+            .display()
+            .str_hint()
+            .ruler(true)
+            .to_string(),
+        r#""hello""#
+    );
+}
+
+#[test]
+fn test_group_bytes() {
+    use dangerous::display::ByteSeparator;
+    let full: Vec<u8> = (0..16).collect();
+    let input = input!(&full[..]);
+    assert_eq!(
+        input
+            .display()
+            .group_bytes(2, ByteSeparator::Dash)
+            .to_string(),
+        "[0001-0203-0405-0607-0809-0a0b-0c0d-0e0f]"
+    );
+    assert_eq!(
+        input
+            .display()
+            .group_bytes(4, ByteSeparator::None)
+            .to_string(),
+        "[000102030405060708090a0b0c0d0e0f]"
+    );
+    assert_eq!(
+        input
+            .display()
+            .group_bytes(4, ByteSeparator::Space)
+            .span(full[5..6].into(), 16)
+            .to_string(),
+        "[.. 040506 ..]"
+    );
+    assert_eq!(
+        input
+            .display()
+            .group_bytes(4, ByteSeparator::Space)
+            .span(full[5..6].into(), 16)
+            .underline()
+            .to_string(),
+        "      ^^      "
+    );
+}
+
+#[test]
+fn test_uppercase() {
+    use dangerous::display::PreferredFormat;
+    let full = b"\xab\xcd ok";
+    let input = input!(&full[..]);
+    assert_eq!(
+        input.display().uppercase(true).to_string(),
+        "[AB CD 20 6F 6B]"
+    );
+    assert_eq!(
+        input
+            .display()
+            .format(PreferredFormat::StrLossy)
+            .uppercase(true)
+            .to_string(),
+        r#""\xAB\xCD ok""#
+    );
+    assert_eq!(
+        input!(&[0xEE; 0xA2][..])
+            .display()
+            .hexdump()
+            .tail(1)
+            .uppercase(true)
+            .to_string(),
+        "...\n000000A0  EE EE                                             |..|"
+    );
+}
+
+#[test]
+fn test_str_escapes_non_printable() {
+    let full = "a\tb\n\x1b[31mc\u{202e}";
+    let input = input!(full.as_bytes());
+    assert_eq!(
+        input.display().str_hint().to_string(),
+        r#""a\tb\n\u{1b}[31mc\u{202e}""#
+    );
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(full[4..5].as_bytes().into(), 80)
+            .underline()
+            .to_string(),
+        "       ^^^^^^              "
+    );
+}
+
+#[test]
+fn test_secondary_span() {
+    let full = "(hello) [world";
+    let input = input!(full.as_bytes());
+    let primary = full[14..].as_bytes().into();
+    let secondary = full[8..9].as_bytes().into();
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(primary, 80)
+            .secondary_span(secondary)
+            .underline()
+            .to_string(),
+        "         -     ^"
+    );
+    assert_eq!(
+        input
+            .display()
+            .span(primary, 80)
+            .secondary_span(secondary)
+            .underline()
+            .to_string(),
+        "                         --               ^"
+    );
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(primary, 80)
+            .secondary_span(secondary)
+            .to_string(),
+        r#""(hello) [world""#
+    );
+}
+
+#[test]
+#[cfg(feature = "color")]
+fn test_color_secondary_span() {
+    let full = "(hello) [world";
+    let input = input!(full.as_bytes());
+    let primary = full[9..].as_bytes().into();
+    let secondary = full[8..9].as_bytes().into();
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(primary, 80)
+            .secondary_span(secondary)
+            .color(true)
+            .to_string(),
+        "\"(hello) \x1b[1;34m[\x1b[1;31mworld\x1b[0m\""
+    );
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(primary, 80)
+            .secondary_span(secondary)
+            .underline()
+            .color(true)
+            .to_string(),
+        "         \x1b[1;34m-\x1b[1;31m^^^^^ \x1b[0m"
+    );
+}
+
+#[test]
+fn test_theme() {
+    use dangerous::display::{Ellipsis, Theme};
+    let full = "(hello) [world";
+    let input = input!(full.as_bytes());
+    let primary = full[9..].as_bytes().into();
+    let secondary = full[8..9].as_bytes().into();
+    let theme = Theme::new()
+        .underline('~')
+        .secondary_underline('=')
+        .delimiters('<', '>')
+        .quote('`')
+        .ellipsis(Ellipsis::Char);
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(primary, 80)
+            .secondary_span(secondary)
+            .theme(theme)
+            .to_string(),
+        "`(hello) [world`"
+    );
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(primary, 80)
+            .secondary_span(secondary)
+            .theme(theme)
+            .underline()
+            .to_string(),
+        "         =~~~~~ "
+    );
+    assert_eq!(
+        input.display().head_tail(16).theme(theme).to_string(),
+        "<28 68 … 6c 64>"
+    );
+    assert_eq!(
+        input
+            .display()
+            .hexdump()
+            .span(primary, 80)
+            .theme(theme)
+            .to_string(),
+        "00000000  28 68 65 6c 6c 6f 29 20  5b 77 6f 72 6c 64        |(hello) [world|\n                                      ~~ ~~ ~~ ~~ ~~                  ~~~~~"
+    );
+}
+
+#[test]
+#[cfg(feature = "color")]
+fn test_color_theme() {
+    use dangerous::display::Theme;
+    let full = "(hello) [world";
+    let input = input!(full.as_bytes());
+    let theme = Theme::new()
+        .highlight_style("\x1b[4m")
+        .secondary_style("\x1b[2m");
+    let display = input
+        .display()
+        .str_hint()
+        .span(full[9..].as_bytes().into(), 80)
+        .secondary_span(full[8..9].as_bytes().into())
+        .theme(theme);
+    assert_eq!(
+        display.clone().color(true).to_string(),
+        "\"(hello) \x1b[2m[\x1b[4mworld\x1b[0m\""
+    );
+    assert_eq!(
+        display.html(true).to_string(),
+        "<pre class=\"dangerous-input\">&quot;(hello) \
+         <span class=\"dangerous-secondary\">[</span>\
+         <span class=\"dangerous-highlight\">world</span>&quot;</pre>"
+    );
+}
+
+#[test]
+#[cfg(feature = "color")]
+fn test_write_style() {
+    use dangerous::display::{DisplayBase, Style, Write};
+
+    #[derive(Default)]
+    struct Styles(std::string::String);
+
+    impl Write for Styles {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0.push_str(s);
+            Ok(())
+        }
+
+        fn write_char(&mut self, c: char) -> fmt::Result {
+            self.0.push(c);
+            Ok(())
+        }
+
+        fn write_usize(&mut self, v: usize) -> fmt::Result {
+            self.0.push_str(&v.to_string());
+            Ok(())
+        }
+
+        fn write_style(&mut self, style: Option<Style>, _escape: &str) -> fmt::Result {
+            match style {
+                Some(style) => self.0.push_str(&format!("<{:?}>", style)),
+                None => self.0.push_str("</>"),
+            }
+            Ok(())
+        }
+    }
+
+    let full = "hello";
+    let input = input!(full.as_bytes());
+    let display = input
+        .display()
+        .str_hint()
+        .span(full[1..4].as_bytes().into(), 80)
+        .color(true);
+    let mut styles = Styles::default();
+    DisplayBase::fmt(&display, &mut styles).unwrap();
+    assert_eq!(styles.0, "\"h<Highlight>ell</>o\"");
+}
+
+#[test]
+fn test_ellipsis() {
+    use dangerous::display::Ellipsis;
+    let full: Vec<u8> = (0..64).collect();
+    let input = input!(&full[..]);
+    assert_eq!(
+        input
+            .display()
+            .head_tail(16)
+            .ellipsis(Ellipsis::Char)
+            .to_string(),
+        "[00 01 … 3e 3f]"
+    );
+    assert_eq!(
+        input
+            .display()
+            .head_tail(40)
+            .ellipsis(Ellipsis::Count)
+            .to_string(),
+        "[00 01 02 <+58 bytes> 3d 3e 3f]"
+    );
+    assert_eq!(
+        input
+            .display()
+            .span(full[32..33].into(), 40)
+            .ellipsis(Ellipsis::Count)
+            .to_string(),
+        "[<+30 bytes> 1e 1f 20 21 22 <+29 bytes>]"
+    );
+    assert_eq!(
+        input
+            .display()
+            .span(full[32..33].into(), 40)
+            .ellipsis(Ellipsis::Count)
+            .underline()
+            .to_string(),
+        "                   ^^                   "
+    );
+    assert_eq!(
+        input!(b"hello world, how are you")
+            .display()
+            .str_hint()
+            .tail(32)
+            .ellipsis(Ellipsis::Count)
+            .to_string(),
+        r#"<+13 bytes> "how are you""#
+    );
+}
+
+#[test]
+#[cfg(feature = "unicode")]
+fn test_unicode_width_underline() {
+    fn underline(full: &str) -> std::string::String {
+        let span = full[full.len() - 1..].as_bytes().into();
+        input!(full.as_bytes())
+            .display()
+            .str_hint()
+            .span(span, 80)
+            .underline()
+            .to_string()
+    }
+    // wide chars
+    assert_eq!(underline("a😀b"), "    ^ ");
+    assert_eq!(underline("你好x"), "     ^ ");
+    // emoji with a skin tone modifier
+    assert_eq!(underline("👍🏽x"), "   ^ ");
+    // combining and zero-width chars are escaped
+    assert_eq!(underline("e\u{301}x"), "         ^ ");
+    assert_eq!(underline("a\u{200b}b"), "          ^ ");
+}
+
+#[test]
+fn test_write_to() {
+    use std::io;
+
+    struct Full;
+
+    impl io::Write for Full {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::WriteZero.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let input = input!(b"hello");
+    let mut out = Vec::new();
+    input.display().str_hint().write_to(&mut out).unwrap();
+    assert_eq!(out, br#""hello""#);
+    assert_eq!(
+        input.display().write_to(&mut Full).unwrap_err().kind(),
+        io::ErrorKind::WriteZero
+    );
+}
+
+#[test]
+fn test_render() {
+    use dangerous::display::{PreferredFormat, SectionRenderer};
+
+    #[derive(Default)]
+    struct Events(Vec<std::string::String>, Option<PreferredFormat>);
+
+    impl SectionRenderer for Events {
+        fn start(&mut self, format: PreferredFormat) {
+            self.1 = Some(format);
+        }
+
+        fn visible(&mut self, bytes: &[u8], offset: usize, in_span: bool) {
+            self.0.push(format!("{:?}@{}:{}", bytes, offset, in_span));
+        }
+
+        fn more(&mut self, elided: usize, in_span: bool) {
+            self.0.push(format!("+{}:{}", elided, in_span));
+        }
+    }
+
+    let full: Vec<u8> = (0..64).collect();
+    let input = input!(&full[..]);
+
+    let mut events = Events::default();
+    input.display().head_tail(16).render(&mut events);
+    assert_eq!(
+        events.0,
+        ["[0, 1]@0:false", "+60:false", "[62, 63]@62:false"]
+    );
+    assert_eq!(events.1, Some(PreferredFormat::Bytes));
+
+    let mut events = Events::default();
+    input
+        .display()
+        .span(full[31..33].into(), 16)
+        .render(&mut events);
+    assert_eq!(
+        events.0,
+        [
+            "+30:false",
+            "[30]@30:false",
+            "[31, 32]@31:true",
+            "+31:false"
+        ]
+    );
+
+    let mut events = Events::default();
+    input
+        .display()
+        .span(full[64..].into(), 16)
+        .render(&mut events);
+    assert_eq!(
+        events.0,
+        ["+60:false", "[60, 61, 62, 63]@60:false", "[]@64:true"]
+    );
+
+    let mut events = Events::default();
+    input!(b"\xff").display().str_hint().render(&mut events);
+    assert_eq!(events.1, Some(PreferredFormat::BytesAscii));
+}
+
+#[test]
+fn test_unit_widths() {
+    use dangerous::display::{self, PreferredFormat, UnitWidth};
+
+    let full = "a\tb♥c";
+    let input = input!(full);
+    let span = full[6..].as_bytes().into();
+    let underline = input.display().span(span, 80).underline().to_string();
+    let before = display::display_width(full[..6].as_bytes(), PreferredFormat::Str);
+    assert_eq!(underline.find('^'), Some(before + 1));
+
+    let underline = input
+        .display()
+        .format(PreferredFormat::BytesAscii)
+        .span(span, 80)
+        .underline()
+        .to_string();
+    let before = display::display_width(full[..6].as_bytes(), PreferredFormat::BytesAscii);
+    assert_eq!(underline.find('^'), Some(before + 2));
+
+    assert_eq!(
+        display::unit_widths(b"\xffa", PreferredFormat::Str).collect::<Vec<_>>(),
+        [
+            UnitWidth { len: 1, width: 4 },
+            UnitWidth { len: 1, width: 1 }
+        ]
+    );
+    assert_eq!(
+        display::unit_widths(b"\xffa", PreferredFormat::ByteStr)
+            .rev()
+            .collect::<Vec<_>>(),
+        [
+            UnitWidth { len: 1, width: 1 },
+            UnitWidth { len: 1, width: 4 }
+        ]
+    );
+}
+
+#[test]
+fn test_wrap() {
+    let full = "hello 你好 world";
+    let input = input!(full.as_bytes());
+    assert_eq!(
+        input.display().str_hint().wrap(8).to_string(),
+        "\"hello \n你好 wor\nld\""
+    );
+    assert_eq!(
+        input
+            .display()
+            .str_hint()
+            .span(full[6..9].as_bytes().into(), 80)
+            .wrap(8)
+            .to_string(),
+        "\"hello \n你好 wor\n^^      \nld\""
+    );
+    assert_eq!(
+        input!(b"ab").display().wrap(0).to_string(),
+        "[\n6\n1\n \n6\n2\n]"
+    );
 }
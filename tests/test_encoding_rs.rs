@@ -0,0 +1,81 @@
+use std::borrow::Cow;
+
+use dangerous::error::{Details, Expected, Reason};
+use dangerous::{Input, ToRetryRequirement};
+use encoding_rs::{SHIFT_JIS, UTF_8, WINDOWS_1252};
+
+const HELLO_SHIFT_JIS: &[u8] = b"\x82\xb1\x82\xf1\x82\xc9\x82\xbf\x82\xcd";
+
+#[test]
+fn test_to_dangerous_str_with() {
+    let decoded = dangerous::input(HELLO_SHIFT_JIS)
+        .to_dangerous_str_with::<Expected<'_>>(SHIFT_JIS)
+        .unwrap();
+    assert_eq!(decoded, "こんにちは");
+    assert!(matches!(decoded, Cow::Owned(_)));
+}
+
+#[test]
+fn test_to_dangerous_str_with_ascii_borrowed() {
+    let decoded = dangerous::input(b"hello")
+        .to_dangerous_str_with::<Expected<'_>>(SHIFT_JIS)
+        .unwrap();
+    assert!(matches!(decoded, Cow::Borrowed("hello")));
+}
+
+#[test]
+fn test_to_dangerous_str_with_utf8() {
+    let decoded = dangerous::input("héllo".as_bytes())
+        .to_dangerous_str_with::<Expected<'_>>(UTF_8)
+        .unwrap();
+    assert!(matches!(decoded, Cow::Borrowed("héllo")));
+
+    let err = dangerous::input(b"h\xc3")
+        .to_dangerous_str_with::<Expected<'_>>(UTF_8)
+        .unwrap_err();
+    assert_eq!(err.span_range(), Some(1..2));
+    assert!(err.to_retry_requirement().is_some());
+}
+
+#[test]
+fn test_to_dangerous_str_with_malformed() {
+    let err = dangerous::input(b"\x82\xb1\xff\x82\xf1")
+        .to_dangerous_str_with::<Expected<'_>>(SHIFT_JIS)
+        .unwrap_err();
+    assert_eq!(err.span_range(), Some(2..3));
+    assert_eq!(err.reason(), Some(Reason::InvalidEncoding));
+    assert_eq!(
+        err.to_string().lines().next(),
+        Some("failed to convert input into string: expected Shift_JIS")
+    );
+}
+
+#[test]
+fn test_take_str_encoded() {
+    let (word, remaining) = dangerous::input(b"caf\xe9!")
+        .read_partial::<_, _, Expected<'_>>(|r| r.take_str_encoded(WINDOWS_1252, 4))
+        .unwrap();
+    assert_eq!(word, "café");
+    assert_eq!(remaining, b"!"[..]);
+}
+
+#[test]
+fn test_take_str_encoded_malformed() {
+    let err = dangerous::input(b"ab\x82\xb1\x82!")
+        .read_all::<_, _, Expected<'_>>(|r| {
+            r.skip(2)?;
+            r.take_str_encoded(SHIFT_JIS, 3)?;
+            r.consume(b'!')
+        })
+        .unwrap_err();
+    assert_eq!(err.span_range(), Some(4..5));
+    assert_eq!(err.reason(), Some(Reason::InvalidEncoding));
+}
+
+#[test]
+fn test_take_str_encoded_too_short() {
+    let err = dangerous::input(HELLO_SHIFT_JIS)
+        .read_all::<_, _, Expected<'_>>(|r| r.take_str_encoded(SHIFT_JIS, 12))
+        .unwrap_err();
+    assert_eq!(err.to_retry_requirement().unwrap().continue_after(), 2);
+}
@@ -0,0 +1,37 @@
+use std::sync::Mutex;
+
+use dangerous::error::{set_error_hook, CoreOperation, ErrorEvent, ErrorKind};
+use dangerous::{Expected, Input, Invalid};
+
+static EVENTS: Mutex<Vec<(ErrorKind, CoreOperation, bool)>> = Mutex::new(Vec::new());
+
+fn record(event: &ErrorEvent) {
+    EVENTS
+        .lock()
+        .unwrap()
+        .push((event.kind, event.context.operation, event.fatal));
+}
+
+// The hook is global, so all cases run within the one test.
+#[test]
+fn test_error_hook() {
+    set_error_hook(Some(record));
+
+    let value = dangerous::input(b"ab").read_all::<_, _, Expected<'_>>(|r| {
+        let _ = r.recover(|r| r.consume(b"b"));
+        r.take(2)
+    });
+    let _ = dangerous::input(b"").read_all::<_, _, Invalid>(|r| r.read());
+
+    set_error_hook(None);
+    let _ = dangerous::input(b"").read_all::<_, _, Invalid>(|r| r.read());
+
+    assert_eq!(value.unwrap().as_dangerous(), b"ab");
+    assert_eq!(
+        *EVENTS.lock().unwrap(),
+        [
+            (ErrorKind::Value, CoreOperation::Consume, true),
+            (ErrorKind::Length, CoreOperation::ReadByte, false),
+        ]
+    );
+}
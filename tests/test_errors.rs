@@ -28,14 +28,14 @@ fn test_invalid_retry_1_more() {
         .unwrap_err();
 
     assert!(!error.is_fatal());
-    assert_eq!(error.to_retry_requirement(), RetryRequirement::new(1));
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::exactly(1));
     assert_str_eq!(
         format!("{}", error),
         "invalid input: needs 1 byte more to continue processing"
     );
     assert_str_eq!(
         format!("{:?}", error),
-        "Invalid { retry_requirement: Some(RetryRequirement(1)) }"
+        "Invalid { retry_requirement: Some(RetryRequirement { continue_after: 1, exact: true }) }"
     );
 }
 
@@ -46,17 +46,32 @@ fn test_invalid_retry_2_more() {
         .unwrap_err();
 
     assert!(!error.is_fatal());
-    assert_eq!(error.to_retry_requirement(), RetryRequirement::new(2));
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::exactly(2));
     assert_str_eq!(
         format!("{}", error),
         "invalid input: needs 2 bytes more to continue processing"
     );
     assert_str_eq!(
         format!("{:?}", error),
-        "Invalid { retry_requirement: Some(RetryRequirement(2)) }"
+        "Invalid { retry_requirement: Some(RetryRequirement { continue_after: 2, exact: true }) }"
     );
 }
 
+#[test]
+fn test_invalid_retry_exact() {
+    let error = input!(b"a")
+        .read_all::<_, _, Invalid>(|r| r.take(3))
+        .unwrap_err();
+    assert!(error.to_retry_requirement().unwrap().is_exact());
+
+    let error = input!("")
+        .read_all::<_, _, Invalid>(|r| r.read())
+        .unwrap_err();
+    let requirement = error.to_retry_requirement().unwrap();
+    assert!(!requirement.is_exact());
+    assert_eq!(requirement, RetryRequirement::new(1).unwrap());
+}
+
 #[test]
 fn test_invalid_fatal() {
     let error = input!(b"2")
@@ -72,6 +87,76 @@ fn test_invalid_fatal() {
     );
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// InvalidHint
+
+#[test]
+fn test_invalid_hint_retry() {
+    use dangerous::error::{CoreOperation, ErrorKind, InvalidHint};
+
+    let error = input!(b"")
+        .read_all::<_, _, InvalidHint>(|r| r.context("hi", |r| r.take(2)))
+        .unwrap_err();
+
+    assert!(!error.is_fatal());
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::exactly(2));
+    assert_eq!(error.kind(), ErrorKind::Length);
+    assert_eq!(error.operation(), CoreOperation::Take);
+    assert_str_eq!(
+        format!("{}", error),
+        "invalid input: failed to take a length of input \
+         (expected enough input for split): needs 2 bytes more to continue processing"
+    );
+    assert_eq!(
+        Invalid::from(error).to_retry_requirement(),
+        RetryRequirement::exactly(2)
+    );
+}
+
+#[test]
+fn test_invalid_hint_fatal() {
+    use dangerous::error::{CoreOperation, ErrorKind, InvalidHint};
+
+    let error: InvalidHint = trigger_expected_valid();
+
+    assert!(error.is_fatal());
+    assert_eq!(error.kind(), ErrorKind::Valid);
+    assert_eq!(error.operation(), CoreOperation::TakeStrWhile);
+    assert_str_eq!(
+        format!("{}", error),
+        "invalid input: failed to take UTF-8 input while a condition remains true \
+         (expected utf-8 code point)"
+    );
+
+    let error = InvalidHint::from(trigger_expected_value::<Expected<'_>>());
+    assert_eq!(error.kind(), ErrorKind::Value);
+    assert_eq!(error.operation(), CoreOperation::Consume);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// NoContext
+
+#[test]
+fn test_no_context() {
+    use dangerous::error::{InvalidHint, NoContext, WithContext};
+
+    fn parse<'i, E: NoContext<'i> + dangerous::Error<'i>>(input: &'i [u8]) -> Result<u8, E> {
+        dangerous::input(input).read_all(|r| r.context("value", |r| r.read()))
+    }
+
+    assert!(<Invalid as WithContext<'_>>::NO_CONTEXT);
+    assert!(<InvalidHint as WithContext<'_>>::NO_CONTEXT);
+    assert!(<Fatal as WithContext<'_>>::NO_CONTEXT);
+    assert!(!<Expected<'_> as WithContext<'_>>::NO_CONTEXT);
+
+    assert_eq!(parse::<Invalid>(b"\x01").unwrap(), 1);
+    assert_eq!(
+        parse::<Invalid>(b"").unwrap_err().to_retry_requirement(),
+        RetryRequirement::exactly(1)
+    );
+    assert!(parse::<Fatal>(b"\x01\x02").is_err());
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Expected support
 
@@ -156,6 +241,550 @@ fn trigger_expected_value_str<E: Error<'static>>() -> E {
         .unwrap_err()
 }
 
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_expected_label_underline() {
+    let error = trigger_expected_valid::<Expected<'_>>();
+    assert_str_eq!(
+        format!("{}", error.display().str_hint().label_underline(true)),
+        indoc! {"
+            failed to take UTF-8 input while a condition remains true: expected utf-8 code point
+            > ['h' 'e' 'l' 'l' 'o' 20 'w' 'o' 'r' 'l' 'd' c2 20]
+                                                          ^^ expected utf-8 code point (offset 11)
+            additional:
+              error line: 1, error column: 12, error offset: 11, input length: 13
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected hi)
+              3. `take UTF-8 input while a condition remains true` (expected utf-8 code point)"
+        }
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_expected_span_summary() {
+    let error = trigger_expected_valid::<Expected<'_>>();
+    assert_str_eq!(
+        format!("{}", error.display().str_hint().span_summary(true)),
+        indoc! {"
+            failed to take UTF-8 input while a condition remains true: expected utf-8 code point
+            > ['h' 'e' 'l' 'l' 'o' 20 'w' 'o' 'r' 'l' 'd' c2 20]
+                                                          ^^    
+            additional:
+              error line: 1, error column: 12, error offset: 11, input length: 13
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected hi)
+              3. `take UTF-8 input while a condition remains true` (expected utf-8 code point)
+            span: bytes 11..12 of 13"
+        }
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_expected_html() {
+    let error = trigger_expected_value::<Expected<'_>>();
+    assert_str_eq!(
+        format!("{}", error.display().str_hint().html(true)),
+        indoc! {r#"
+            <pre class="dangerous-error"><span class="dangerous-header">failed to consume input: found a different value to the exact expected</span>
+            expected:
+            <span class="dangerous-expected">&gt; &quot;123&quot;
+            </span>found:
+            &gt; &quot;hel&quot;
+            in:
+            &gt; &quot;<span class="dangerous-highlight">hel</span>lo world&quot;
+            additional:
+              error line: 1, error column: 1, error offset: 0, input length: 11
+            backtrace:
+              1. `<span class="dangerous-operation">read all input</span>`
+              2. `<span class="dangerous-operation">&lt;context&gt;</span>` (expected <span class="dangerous-expected">hi</span>)
+              3. `<span class="dangerous-operation">consume input</span>` (expected <span class="dangerous-expected">exact value</span>)</pre>"#
+        }
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_expected_preset() {
+    use dangerous::display::Preset;
+    let error = trigger_expected_value::<Expected<'_>>();
+    assert_str_eq!(
+        format!("{}", error.display().preset(Preset::Hexdump)),
+        indoc! {"
+            failed to consume input: found a different value to the exact expected
+            expected:
+            00000000  31 32 33                                          |123|
+            found:
+            00000000  68 65 6c                                          |hel|
+            in:
+            00000000  68 65 6c 6c 6f 20 77 6f  72 6c 64                 |hello world|
+                      ^^ ^^ ^^                                           ^^^
+            additional:
+              error offset: 0, input length: 11
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected hi)
+              3. `consume input` (expected exact value)"
+        }
+    );
+    assert_str_eq!(
+        format!("{}", error.display().preset(Preset::Compact)),
+        format!("{}", error.display().compact(true))
+    );
+    assert_str_eq!(
+        format!(
+            "{}",
+            error.display().input_max_width(16).preset(Preset::Full)
+        ),
+        format!("{}", error.display())
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Expected color
+
+#[test]
+#[cfg(all(feature = "color", feature = "full-backtrace"))]
+fn test_expected_color() {
+    let error = trigger_expected_value::<Expected<'_>>();
+    assert_str_eq!(
+        format!("{}", error.display().str_hint().color(true)),
+        indoc! {"
+            \x1b[1mfailed to consume input: found a different value to the exact expected\x1b[0m
+            expected:
+            \x1b[32m> \"123\"
+            \x1b[0mfound:
+            > \"hel\"
+            in:
+            > \"\x1b[1;31mhel\x1b[0mlo world\"
+               \x1b[1;31m^^^         \x1b[0m
+            additional:
+              error line: 1, error column: 1, error offset: 0, input length: 11
+            backtrace:
+              1. `\x1b[36mread all input\x1b[0m`
+              2. `\x1b[36m<context>\x1b[0m` (expected \x1b[32mhi\x1b[0m)
+              3. `\x1b[36mconsume input\x1b[0m` (expected \x1b[32mexact value\x1b[0m)"
+        }
+    );
+}
+
+#[test]
+#[cfg(all(feature = "color", feature = "full-backtrace"))]
+fn test_expected_color_inline_highlight() {
+    let error = trigger_expected_value::<Expected<'_>>();
+    assert_str_eq!(
+        format!(
+            "{}",
+            error
+                .display()
+                .str_hint()
+                .color(true)
+                .inline_highlight(true)
+        ),
+        indoc! {"
+            \x1b[1mfailed to consume input: found a different value to the exact expected\x1b[0m
+            expected:
+            \x1b[32m> \"123\"
+            \x1b[0mfound:
+            > \"hel\"
+            in:
+            > \"\x1b[1;31mhel\x1b[0mlo world\"
+            additional:
+              error line: 1, error column: 1, error offset: 0, input length: 11
+            backtrace:
+              1. `\x1b[36mread all input\x1b[0m`
+              2. `\x1b[36m<context>\x1b[0m` (expected \x1b[32mhi\x1b[0m)
+              3. `\x1b[36mconsume input\x1b[0m` (expected \x1b[32mexact value\x1b[0m)"
+        }
+    );
+    // Falls back to the underline without color
+    assert_str_eq!(
+        error.display().inline_highlight(true).to_string(),
+        error.display().to_string()
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Expected kind
+
+#[test]
+fn test_expected_kind() {
+    let error = trigger_expected_valid::<Expected<'_>>();
+    assert_eq!(error.kind(), ErrorKind::Valid);
+    assert_eq!(error.operation(), CoreOperation::TakeStrWhile);
+
+    let error = trigger_expected_length::<Expected<'_>>();
+    assert_eq!(error.kind(), ErrorKind::Length);
+    assert_eq!(error.operation(), CoreOperation::Take);
+
+    let error = trigger_expected_value::<Expected<'_>>();
+    assert_eq!(error.kind(), ErrorKind::Value);
+    assert_eq!(error.operation(), CoreOperation::Consume);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Expected line and column
+
+#[test]
+fn test_expected_reason() {
+    let error = read_all_err!(b"\xc2 ", |r| r.take_remaining_str());
+    assert_eq!(error.reason(), Some(Reason::InvalidUtf8));
+
+    let error = read_all_err!("é", |r| r.try_external("half", |_| Ok::<_, ()>((1, ()))));
+    assert_eq!(error.reason(), Some(Reason::InvalidTokenBoundary));
+    assert_eq!(
+        error.into_owned().reason(),
+        Some(Reason::InvalidTokenBoundary)
+    );
+
+    let error = read_all_err!(b"a", |r| r
+        .expect("b", |r| r.read_opt().filter(|b| *b == b'b')));
+    assert_eq!(error.kind(), ErrorKind::Valid);
+    assert_eq!(error.reason(), None);
+}
+
+#[test]
+fn test_expected_line_column() {
+    let error = input!("one\ntwo\nthrée four")
+        .read_all::<_, _, Expected<'_>>(|r| {
+            let before = r.take_until_consume("thrée ")?;
+            assert_eq!(before, "one\ntwo\n");
+            r.consume("five")
+        })
+        .unwrap_err();
+    let position = error.line_column().unwrap();
+    assert_eq!(position, LineColumn { line: 3, column: 7 });
+    assert_str_eq!(position.to_string(), "line 3, column 7");
+    assert!(format!("{}", error).contains("error line: 3, error column: 7,"));
+    assert_eq!(error.into_owned().line_column(), Some(position));
+}
+
+#[test]
+fn test_expected_line_column_bytes() {
+    let error = input!(b"a\n\xff\xffb")
+        .read_all::<_, _, Expected<'_>>(|r| {
+            r.skip(4)?;
+            r.consume(b"c")
+        })
+        .unwrap_err();
+    assert_eq!(error.line_column(), Some(LineColumn { line: 2, column: 3 }));
+}
+
+#[test]
+fn test_expected_span_range() {
+    let error = trigger_expected_valid::<Expected<'_>>();
+    assert_eq!(error.span_range(), Some(11..12));
+    assert_eq!(error.into_owned().span_range(), Some(11..12));
+
+    let error = trigger_expected_length::<Expected<'_>>();
+    assert_eq!(error.span_range(), Some(0..11));
+
+    let error = trigger_expected_value::<Expected<'_>>();
+    assert_eq!(error.span_range(), Some(0..3));
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_expected_with_parent() {
+    let outer = input!(b"key=313233;");
+    let (_, encoded) = outer.clone().split_at::<Invalid>(4).unwrap();
+    let (encoded, _) = encoded.split_at::<Invalid>(6).unwrap();
+    let decoded = b"123".to_vec();
+    let parse = || {
+        dangerous::input(&decoded[..])
+            .read_all::<_, _, Expected<'_>>(|r| r.consume(b"124"))
+            .unwrap_err()
+    };
+    let error = parse().with_parent(outer.clone(), encoded.span());
+    assert_str_eq!(
+        format!("{:#}", error),
+        indoc! {r#"
+            failed to consume input: found a different value to the exact expected
+            expected:
+            > "124"
+            found:
+            > "123"
+            in:
+            > "123"
+               ^^^ 
+            derived from:
+            > "key=313233;"
+                   ^^^^^^  
+            additional:
+              error line: 1, error column: 1, error offset: 0, input length: 3
+            backtrace:
+              1. `read all input`
+              2. `consume input` (expected exact value)"#
+        }
+    );
+    assert_eq!(error, parse().with_parent(outer.clone(), encoded.span()));
+    assert_ne!(error, parse());
+}
+
+#[test]
+fn test_expected_eq() {
+    fn parse(input: &[u8]) -> Expected<'_> {
+        dangerous::input(input)
+            .read_all(|r| {
+                r.context("greeting", |r| {
+                    r.consume(b"hello ")?;
+                    r.consume(b"world")
+                })
+            })
+            .unwrap_err()
+    }
+
+    let a = b"hello there".to_vec();
+    let b = a.clone();
+    assert_eq!(parse(&a), parse(&b));
+    assert_ne!(parse(&a), parse(b"hello thorn"));
+    assert_ne!(parse(&a), parse(b"hello  there"));
+    assert_ne!(
+        parse(&a),
+        dangerous::input(&a[..])
+            .read_all::<_, _, Expected<'_>>(|r| {
+                r.consume(b"hello ")?;
+                r.consume(b"world")
+            })
+            .unwrap_err()
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_expected_eq_context_text() {
+    use core::any::Any;
+    use core::fmt;
+    use dangerous::display::Write;
+    use dangerous::error::{Context, Operation};
+
+    #[derive(Clone)]
+    struct Field(&'static str, &'static str);
+
+    impl Context for Field {
+        fn operation(&self) -> &dyn Operation {
+            self
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    impl Operation for Field {
+        fn description(&self, w: &mut dyn Write) -> fmt::Result {
+            w.write_str(self.0)?;
+            w.write_str(self.1)
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    fn parse(field: Field) -> Expected<'static> {
+        dangerous::input(b"a")
+            .read_all(|r| r.context(field, |r| r.take(2)))
+            .unwrap_err()
+    }
+
+    assert_eq!(parse(Field("read ", "name")), parse(Field("read na", "me")));
+    assert_eq!(parse(Field("", "read name")), parse(Field("read name", "")));
+    assert_ne!(
+        parse(Field("read ", "name")),
+        parse(Field("read ", "names"))
+    );
+    assert_ne!(parse(Field("read ", "name")), parse(Field("read ", "nam")));
+    assert_ne!(parse(Field("read ", "name")), parse(Field("read ", "nane")));
+}
+
+#[test]
+fn test_expected_kinds_eq() {
+    assert!(matches!(
+        (trigger_expected_value(), trigger_expected_value()),
+        (ExpectedKind::Value(a), ExpectedKind::Value(b)) if a == b
+    ));
+    assert!(matches!(
+        (trigger_expected_valid(), trigger_expected_valid()),
+        (ExpectedKind::Valid(a), ExpectedKind::Valid(b)) if a == b
+    ));
+    assert!(matches!(
+        (trigger_expected_length(), trigger_expected_length()),
+        (ExpectedKind::Length(a), ExpectedKind::Length(b)) if a == b
+    ));
+    assert!(matches!(
+        (
+            trigger_expected_value(),
+            input!(b"hello world").read_all(|r| r.consume(b"124")).unwrap_err()
+        ),
+        (ExpectedKind::Value(a), ExpectedKind::Value(b)) if a != b
+    ));
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Expected report
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_expected_to_report() {
+    let error = trigger_expected_value::<Expected<'_>>();
+    let report = error.to_report();
+    assert_eq!(report.kind, Some(ErrorKind::Value));
+    assert_eq!(report.reason, None);
+    assert_eq!(report.operation, CoreOperation::Consume);
+    assert_str_eq!(
+        report.description,
+        "found a different value to the exact expected"
+    );
+    assert_eq!(report.expected.as_deref(), Some("exact value"));
+    assert_eq!(
+        report.expected_value,
+        Some(ReportValue::Bytes(b"123".to_vec()))
+    );
+    assert_eq!(
+        report.found_value,
+        Some(ReportValue::Bytes(b"hel".to_vec()))
+    );
+    assert_eq!(report.span, Some(0..3));
+    assert_eq!(report.input_len, 11);
+    assert_eq!(report.retry_requirement, None);
+    let contexts: Vec<_> = report
+        .contexts
+        .iter()
+        .map(|c| {
+            (
+                c.depth,
+                c.operation.as_str(),
+                c.expected.as_deref(),
+                c.span.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        contexts,
+        [
+            (1, "read all input", None, Some(0..11)),
+            (2, "<context>", Some("hi"), None),
+            (3, "consume input", Some("exact value"), Some(0..3)),
+        ]
+    );
+    assert_eq!(error.into_owned().to_report(), report);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// std::error::Error
+
+#[test]
+#[cfg(feature = "std")]
+fn test_std_error() {
+    fn boxed<'i, E>(error: E) -> Box<dyn std::error::Error + 'i>
+    where
+        E: std::error::Error + 'i,
+    {
+        Box::new(error)
+    }
+
+    assert_eq!(
+        boxed(Invalid::fatal()).to_string(),
+        Invalid::fatal().to_string()
+    );
+    assert_eq!(boxed(Fatal).to_string(), Fatal.to_string());
+    assert!(boxed(trigger_expected_value::<Expected<'_>>())
+        .source()
+        .is_none());
+    for kind in [
+        trigger_expected_valid::<ExpectedKind<'_>>(),
+        trigger_expected_length::<ExpectedKind<'_>>(),
+        trigger_expected_value::<ExpectedKind<'_>>(),
+    ] {
+        let (display, error) = match kind {
+            ExpectedKind::Value(e) => (e.to_string(), boxed(e)),
+            ExpectedKind::Valid(e) => (e.to_string(), boxed(e)),
+            ExpectedKind::Length(e) => (e.to_string(), boxed(e)),
+        };
+        assert_eq!(error.to_string(), display);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Owned expected
+
+#[test]
+fn test_expected_into_owned() {
+    fn assert_send_sync_static<T: Send + Sync + 'static>(_: &T) {}
+
+    for (error, owned) in [
+        (
+            trigger_expected_valid::<Expected<'_>>(),
+            trigger_expected_valid::<Expected<'_>>().into_owned(),
+        ),
+        (
+            trigger_expected_length::<Expected<'_>>(),
+            trigger_expected_length::<Expected<'_>>().into_owned(),
+        ),
+        (
+            trigger_expected_value::<Expected<'_>>(),
+            trigger_expected_value::<Expected<'_>>().into_owned(),
+        ),
+    ] {
+        assert_send_sync_static(&owned);
+        assert_eq!(error.kind(), owned.kind());
+        assert_eq!(error.operation(), owned.operation());
+        assert_eq!(error.to_retry_requirement(), owned.to_retry_requirement());
+        assert_str_eq!(format!("{}", owned), format!("{}", error));
+        assert_str_eq!(format!("{:#}", owned), format!("{:#}", error));
+        assert_str_eq!(format!("{:?}", owned), format!("{:?}", error));
+    }
+}
+
+#[test]
+fn test_expected_into_owned_outlives_input() {
+    let owned = {
+        let buf = b"hello world".to_vec();
+        input!(&buf[..])
+            .read_all::<_, _, Expected<'_>>(|r| r.context("hi", |r| r.consume(b"123")))
+            .unwrap_err()
+            .into_owned()
+    };
+    assert_str_eq!(
+        format!("{:#}\n", owned),
+        indoc! {r#"
+            failed to consume input: found a different value to the exact expected
+            expected:
+            > "123"
+            found:
+            > "hel"
+            in:
+            > "hello world"
+               ^^^         
+            additional:
+              error line: 1, error column: 1, error offset: 0, input length: 11
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected hi)
+              3. `consume input` (expected exact value)
+        "#}
+    );
+}
+
+#[test]
+fn test_expected_into_owned_clone() {
+    let cloned = {
+        let owned = input!(b"hello world")
+            .read_all::<_, _, Expected<'_>>(|r| r.context("hi", |r| r.consume(b"123")))
+            .unwrap_err()
+            .into_owned();
+        let cloned = owned.clone();
+        assert_str_eq!(format!("{:#}", cloned), format!("{:#}", owned));
+        cloned
+    };
+    assert_eq!(cloned.span_range(), Some(0..3));
+    assert!(format!("{:#}", cloned).contains("^^^"));
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Expected valid
 
@@ -173,6 +802,7 @@ fn test_expected_valid_variant() {
         indoc! {r#"
             ExpectedValid {
                 retry_requirement: None,
+                reason: InvalidUtf8,
                 context: CoreContext {
                     span: Span(
                         [c2],
@@ -192,157 +822,597 @@ fn test_expected_valid_variant() {
 }
 
 #[test]
-fn test_expected_valid_root() {
-    let error: Expected<RootBacktrace> = trigger_expected_valid();
+fn test_expected_valid_root() {
+    let error: Expected<RootBacktrace> = trigger_expected_valid();
+
+    assert!(error.is_fatal());
+    assert_eq!(error.to_retry_requirement(), None);
+    assert_str_eq!(
+        format!("{}\n", error),
+        indoc! {r#"
+            failed to take UTF-8 input while a condition remains true: expected utf-8 code point
+            > [68 65 6c 6c 6f 20 77 6f 72 6c 64 c2 20]
+                                                ^^    
+            additional:
+              error offset: 11, input length: 13
+            backtrace:
+              1. `take UTF-8 input while a condition remains true` (expected utf-8 code point)
+        "#}
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_expected_valid_full() {
+    let error: Expected = trigger_expected_valid();
+
+    assert!(error.is_fatal());
+    assert_eq!(error.to_retry_requirement(), None);
+    println!("{}\n", error);
+    assert_str_eq!(
+        format!("{}\n", error),
+        indoc! {r#"
+            failed to take UTF-8 input while a condition remains true: expected utf-8 code point
+            > [68 65 6c 6c 6f 20 77 6f 72 6c 64 c2 20]
+                                                ^^    
+            additional:
+              error offset: 11, input length: 13
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected hi)
+              3. `take UTF-8 input while a condition remains true` (expected utf-8 code point)
+        "#}
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_expected_valid_with_empty_span_full() {
+    let error: Expected = trigger_expected_valid_with_empty_span();
+
+    assert!(error.is_fatal());
+    assert_eq!(error.to_retry_requirement(), None);
+    println!("{}\n", error);
+    assert_str_eq!(
+        format!("{}\n", error),
+        indoc! {r#"
+            failed to read and expect a value: expected value
+            > [68 65 6c 6c 6f 20 77 6f 72 6c 64]
+                              ^^                
+            additional:
+              error offset: 5, input length: 11
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected hi)
+              3. `read and expect a value` (expected value)
+        "#}
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_expected_valid_full_boxed() {
+    let error: Expected = trigger_expected_valid();
+    let error_boxed: Box<Expected> = trigger_expected_valid();
+
+    assert!(error.is_fatal());
+    assert_eq!(error.to_retry_requirement(), None);
+    assert_str_eq!(format!("{:#?}", error), format!("{:#?}", error_boxed));
+}
+
+#[test]
+fn test_boxed_expected() {
+    use dangerous::error::{BoxedExpected, Details, InvalidHint};
+
+    fn span_range<'i, E: Details<'i>>(error: &E) -> Option<core::ops::Range<usize>> {
+        error.backtrace().root().span.range_of(error.input().span())
+    }
+
+    fn boxed() -> BoxedExpected<'static> {
+        input!(b"hello")
+            .read_all(|r| r.consume(b"world"))
+            .unwrap_err()
+    }
+
+    let error = read_all_err!(b"hello", |r| r.consume(b"world"));
+    assert_eq!(*boxed(), error);
+    assert_eq!(span_range(&boxed()), span_range(&error));
+    assert_eq!(
+        Invalid::from(boxed()).to_retry_requirement(),
+        error.to_retry_requirement()
+    );
+    assert_eq!(InvalidHint::from(boxed()), InvalidHint::from(error));
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Expected length
+
+#[test]
+fn test_expected_length_variant() {
+    let error = match trigger_expected_length() {
+        ExpectedKind::Length(error) => error,
+        _ => unreachable!(),
+    };
+    assert!(!error.input().is_string());
+    assert_eq!(error.input().into_bytes(), b"hello world"[..]);
+    assert_eq!(error.len(), Length::AtLeast(13));
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::exactly(2));
+    assert_str_eq!(
+        format!("{:#?}\n", error),
+        indoc! {r#"
+            ExpectedLength {
+                len: AtLeast(
+                    13,
+                ),
+                context: CoreContext {
+                    span: Span(
+                        "hello world",
+                    ),
+                    operation: Take,
+                    expected: EnoughInputFor(
+                        "split",
+                    ),
+                },
+                input: Bytes {
+                    bound: Start,
+                    value: "hello world",
+                },
+            }
+        "#}
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_external_error_deep_child() {
+    struct DeepExternalError;
+
+    impl<'i> External<'i> for DeepExternalError {
+        fn push_backtrace<E>(self, error: E) -> E
+        where
+            E: WithContext<'i>,
+        {
+            error.with_context("a").with_context("b").with_context("c")
+        }
+    }
+
+    let error = read_all_err!("hello world", |r| {
+        r.try_external("value", |_| {
+            Result::<(usize, ()), DeepExternalError>::Err(DeepExternalError)
+        })
+    });
+
+    assert!(error.is_fatal());
+    assert_str_eq!(
+        format!("{}\n", error),
+        indoc! {r#"
+            failed to read and expect an external value: expected value
+            > "hello world"
+               ^^^^^^^^^^^ 
+            additional:
+              error line: 1, error column: 1, error offset: 0, input length: 11
+            backtrace:
+              1. `read all input`
+              2. `read and expect an external value` (expected value)
+                1. `<context>` (expected c)
+                2. `<context>` (expected b)
+                3. `<context>` (expected a)
+        "#}
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_external_source() {
+    let error = read_all_err!("hello world", |r| {
+        r.try_external("value", |i| {
+            let s = i.as_dangerous();
+            let span = Span::from(&s[5..]);
+            u8::try_from(s.len() * 100)
+                .map(|v| (s.len(), v))
+                .map_err(|err| ExternalSource::new(err).span(span).reason(Reason::Overflow))
+        })
+    });
+
+    assert!(error.is_fatal());
+    assert_eq!(error.reason(), Some(Reason::Overflow));
+    assert_str_eq!(
+        format!("{}\n", error),
+        indoc! {r#"
+            failed to read and expect an external value: expected value
+            > "hello world"
+                    ^^^^^^ 
+            additional:
+              error line: 1, error column: 6, error offset: 5, input length: 11
+            backtrace:
+              1. `read all input`
+              2. `read and expect an external value` (expected value)
+                1. `out of range integral type conversion attempted`
+        "#}
+    );
+}
+
+#[test]
+fn test_external_found() {
+    struct Marker<'i>(Span, &'i [u8]);
+
+    impl<'i> External<'i> for Marker<'i> {
+        fn span(&self) -> Option<Span> {
+            Some(self.0)
+        }
+
+        fn found(&self) -> Option<Value<'i>> {
+            Some(Value::from(self.1))
+        }
+    }
+
+    let error = read_all_err!(b"\xff\xd8rest", |r| {
+        r.try_external("PNG header", |i| {
+            let bytes = i.as_dangerous();
+            Err::<(usize, ()), _>(Marker(Span::from(&bytes[..2]), &bytes[..2]))
+        })
+    });
+
+    assert_eq!(error.found().unwrap().as_bytes(), b"\xff\xd8");
+    assert_str_eq!(
+        format!("{}\n", error),
+        indoc! {r#"
+            failed to read and expect an external value: expected PNG header, found [ff d8]
+            > [ff d8 72 65 73 74]
+               ^^ ^^             
+            additional:
+              error offset: 0, input length: 6
+            backtrace:
+              1. `read all input`
+              2. `read and expect an external value` (expected PNG header)
+        "#}
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_expected_limited_backtrace() {
+    type Error<'i> = Expected<'i, LimitedBacktrace<2>>;
+
+    fn nested<'i>(r: &mut StringReader<'i, Error<'i>>) -> Result<(), Error<'i>> {
+        r.context("nested", |r| {
+            if r.at_end() {
+                r.consume("end")
+            } else {
+                r.skip(1)?;
+                nested(r)
+            }
+        })
+    }
+
+    let error = input!("ab").read_all(nested).unwrap_err();
+    assert_str_eq!(
+        format!("{}\n", error),
+        indoc! {r#"
+            failed to consume input: not enough input to match expected value
+            expected:
+            > "end"
+            in:
+            > "ab"
+                 ^
+            additional:
+              error line: 1, error column: 3, error offset: 2, input length: 2
+            backtrace:
+              1. `<truncated 2 contexts>`
+              2. `<context>` (expected nested) (repeated 2 times)
+              4. `consume input` (expected exact value)
+        "#}
+    );
+
+    let error = input!("").read_all::<_, _, Error<'_>>(nested).unwrap_err();
+    assert_eq!(error.backtrace().count(), 3);
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_expected_typed_contexts() {
+    use dangerous::error::{CoreContext, CoreOperation, ExternalContext};
+
+    let error: Expected = input!("a")
+        .read_all(|r| r.context("outer", |r| r.context("inner", |r| r.take(2))))
+        .unwrap_err();
+    let backtrace = error.backtrace();
+
+    assert_eq!(backtrace.contexts_of::<&'static str>(), ["outer", "inner"]);
+    assert_eq!(backtrace.find_context::<&'static str>(), Some("outer"));
+    assert_eq!(backtrace.contexts_of::<CoreContext>().len(), 2);
+    assert_eq!(
+        backtrace.find_context::<CoreContext>().unwrap().operation,
+        CoreOperation::ReadAll
+    );
+    assert!(backtrace
+        .find_context::<ExternalContext<CoreOperation, &'static str>>()
+        .is_none());
+    let mut depths = Vec::new();
+    backtrace.walk_of(&mut |depth, _: &&'static str| {
+        depths.push(depth);
+        true
+    });
+    assert_eq!(depths, [2, 3]);
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_expected_operation_ids() {
+    use dangerous::error::{CoreOperation, ExternalContext, OperationId};
+
+    let error: Expected = input!("a")
+        .read_all(|r| {
+            let context = ExternalContext {
+                operation: Some("header"),
+                expected: None::<&'static str>,
+            };
+            r.context(context, |r| r.context("inner", |r| r.take(2)))
+        })
+        .unwrap_err();
+    let mut ids = Vec::new();
+    error.backtrace().walk(&mut |_, context| {
+        ids.push(context.operation().id());
+        true
+    });
+
+    assert_eq!(
+        ids,
+        [
+            OperationId::Core(CoreOperation::ReadAll),
+            OperationId::Custom("header"),
+            OperationId::Core(CoreOperation::Context),
+            OperationId::Core(CoreOperation::Take),
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_expected_pruned_backtrace() {
+    use dangerous::error::PrunedBacktrace;
+
+    type Error<'i> = Expected<'i, PrunedBacktrace<2>>;
+
+    fn nested<'i>(r: &mut StringReader<'i, Error<'i>>) -> Result<(), Error<'i>> {
+        r.context("nested", |r| {
+            if r.at_end() {
+                r.consume("end")
+            } else {
+                r.skip(1)?;
+                r.context("inner", nested)
+            }
+        })
+    }
+
+    let error = input!("abc").read_all(nested).unwrap_err();
+    assert_eq!(error.backtrace().count(), 6);
+    assert_str_eq!(
+        format!("{}", error.display().stable(true)),
+        indoc! {r#"
+            error: failed to consume input: not enough input to match expected value
+            expected: "end"
+            found: ""
+            span: 3..3
+            input length: 3
+            context:
+              1. read all input
+              2. <context> (expected nested)
+              3. <truncated 4 contexts>
+              4. <context> (expected inner)
+              5. <context> (expected nested)
+              6. consume input (expected exact value)
+        "#}
+    );
+
+    let error = input!("").read_all::<_, _, Error<'_>>(nested).unwrap_err();
+    assert_eq!(error.backtrace().count(), 3);
+}
+
+#[test]
+#[cfg(feature = "heapless")]
+fn test_expected_heapless_backtrace() {
+    use dangerous::error::{ExternalContext, HeaplessBacktrace};
+
+    type Error<'i> = Expected<'i, HeaplessBacktrace<3>>;
+
+    fn nested<'i>(r: &mut StringReader<'i, Error<'i>>) -> Result<(), Error<'i>> {
+        r.context("nested", |r| {
+            if r.at_end() {
+                r.context(
+                    ExternalContext {
+                        operation: Some("read end"),
+                        expected: None::<&str>,
+                    },
+                    |r| r.consume("end"),
+                )
+            } else {
+                r.skip(1)?;
+                nested(r)
+            }
+        })
+    }
+
+    let error = input!("ab").read_all(nested).unwrap_err();
+    assert_eq!(error.backtrace().count(), 5);
+    assert_str_eq!(
+        format!("{}", error.display().stable(true)),
+        indoc! {r#"
+            error: failed to consume input: not enough input to match expected value
+            expected: "end"
+            found: ""
+            span: 2..2
+            input length: 2
+            context:
+              1. <truncated 2 contexts>
+              2. <context> (expected nested)
+              3. <context> (expected nested)
+              4. read end
+              5. consume input (expected exact value)
+        "#}
+    );
+
+    let error = input!("").read_all::<_, _, Error<'_>>(nested).unwrap_err();
+    assert_eq!(error.backtrace().count(), 4);
+}
+
+#[test]
+#[cfg(feature = "smallvec")]
+fn test_expected_smallvec_backtrace() {
+    use dangerous::error::{BacktraceBuilder, ExternalContext, SmallVecBacktrace};
 
-    assert!(error.is_fatal());
-    assert_eq!(error.to_retry_requirement(), None);
+    type Error<'i> = Expected<'i, SmallVecBacktrace<3>>;
+
+    fn nested<'i>(r: &mut StringReader<'i, Error<'i>>) -> Result<(), Error<'i>> {
+        r.context("nested", |r| {
+            if r.at_end() {
+                r.context(
+                    ExternalContext {
+                        operation: Some("read end"),
+                        expected: None::<&str>,
+                    },
+                    |r| r.consume("end"),
+                )
+            } else {
+                r.skip(1)?;
+                nested(r)
+            }
+        })
+    }
+
+    let error = input!("ab").read_all(nested).unwrap_err();
+    assert_eq!(error.backtrace().count(), 6);
     assert_str_eq!(
-        format!("{}\n", error),
+        format!("{}", error.display().stable(true)),
         indoc! {r#"
-            failed to take UTF-8 input while a condition remains true: expected utf-8 code point
-            > [68 65 6c 6c 6f 20 77 6f 72 6c 64 c2 20]
-                                                ^^    
-            additional:
-              error offset: 11, input length: 13
-            backtrace:
-              1. `take UTF-8 input while a condition remains true` (expected utf-8 code point)
+            error: failed to consume input: not enough input to match expected value
+            expected: "end"
+            found: ""
+            span: 2..2
+            input length: 2
+            context:
+              1. read all input
+              2. <context> (expected nested)
+              3. <context> (expected nested)
+              4. <context> (expected nested)
+              5. read end
+              6. consume input (expected exact value)
         "#}
     );
+
+    let error = input!("").read_all::<_, _, Error<'_>>(nested).unwrap_err();
+    assert_eq!(error.backtrace().count(), 4);
+
+    let mut backtrace = SmallVecBacktrace::<1>::from_root(error.backtrace().root());
+    backtrace.push("first");
+    assert!(!backtrace.spilled());
+    backtrace.push("second");
+    assert!(backtrace.spilled());
+    assert_eq!(backtrace.count(), 3);
 }
 
 #[test]
-#[cfg(feature = "full-backtrace")]
-fn test_expected_valid_full() {
-    let error: Expected = trigger_expected_valid();
+fn test_expected_repeated_contexts() {
+    fn nested<'i>(r: &mut StringReader<'i, Expected<'i>>) -> Result<(), Expected<'i>> {
+        r.context("nested", |r| {
+            if r.at_end() {
+                r.consume("end")
+            } else {
+                r.skip(1)?;
+                nested(r)
+            }
+        })
+    }
 
-    assert!(error.is_fatal());
-    assert_eq!(error.to_retry_requirement(), None);
-    println!("{}\n", error);
+    let error = input!("ab").read_all(nested).unwrap_err();
     assert_str_eq!(
         format!("{}\n", error),
         indoc! {r#"
-            failed to take UTF-8 input while a condition remains true: expected utf-8 code point
-            > [68 65 6c 6c 6f 20 77 6f 72 6c 64 c2 20]
-                                                ^^    
+            failed to consume input: not enough input to match expected value
+            expected:
+            > "end"
+            in:
+            > "ab"
+                 ^
             additional:
-              error offset: 11, input length: 13
+              error line: 1, error column: 3, error offset: 2, input length: 2
             backtrace:
               1. `read all input`
-              2. `<context>` (expected hi)
-              3. `take UTF-8 input while a condition remains true` (expected utf-8 code point)
+              2. `<context>` (expected nested) (repeated 3 times)
+              5. `consume input` (expected exact value)
         "#}
     );
-}
-
-#[test]
-#[cfg(feature = "full-backtrace")]
-fn test_expected_valid_with_empty_span_full() {
-    let error: Expected = trigger_expected_valid_with_empty_span();
-
-    assert!(error.is_fatal());
-    assert_eq!(error.to_retry_requirement(), None);
-    println!("{}\n", error);
     assert_str_eq!(
-        format!("{}\n", error),
+        format!("{}\n", error.display().collapse_repeated(false)),
         indoc! {r#"
-            failed to read and expect a value: expected value
-            > [68 65 6c 6c 6f 20 77 6f 72 6c 64]
-                              ^^                
+            failed to consume input: not enough input to match expected value
+            expected:
+            > "end"
+            in:
+            > "ab"
+                 ^
             additional:
-              error offset: 5, input length: 11
+              error line: 1, error column: 3, error offset: 2, input length: 2
             backtrace:
               1. `read all input`
-              2. `<context>` (expected hi)
-              3. `read and expect a value` (expected value)
+              2. `<context>` (expected nested)
+              3. `<context>` (expected nested)
+              4. `<context>` (expected nested)
+              5. `consume input` (expected exact value)
         "#}
     );
 }
 
 #[test]
-#[cfg(feature = "full-backtrace")]
-fn test_expected_valid_full_boxed() {
-    let error: Expected = trigger_expected_valid();
-    let error_boxed: Box<Expected> = trigger_expected_valid();
-
-    assert!(error.is_fatal());
-    assert_eq!(error.to_retry_requirement(), None);
-    assert_str_eq!(format!("{:#?}", error), format!("{:#?}", error_boxed));
-}
-
-///////////////////////////////////////////////////////////////////////////////
-// Expected length
+#[cfg(feature = "alloc")]
+fn test_multi_error() {
+    use dangerous::error::MultiError;
 
-#[test]
-fn test_expected_length_variant() {
-    let error = match trigger_expected_length() {
-        ExpectedKind::Length(error) => error,
-        _ => unreachable!(),
-    };
-    assert!(!error.input().is_string());
-    assert_eq!(error.input().into_bytes(), b"hello world"[..]);
-    assert_eq!(error.len(), Length::AtLeast(13));
-    assert_eq!(error.to_retry_requirement(), RetryRequirement::new(2));
-    assert_str_eq!(
-        format!("{:#?}\n", error),
-        indoc! {r#"
-            ExpectedLength {
-                len: AtLeast(
-                    13,
-                ),
-                context: CoreContext {
-                    span: Span(
-                        "hello world",
-                    ),
-                    operation: Take,
-                    expected: EnoughInputFor(
-                        "split",
-                    ),
-                },
-                input: Bytes {
-                    bound: Start,
-                    value: "hello world",
-                },
+    let input = input!("a,1,b,2");
+    let mut errors = MultiError::<Expected<'_>>::new();
+    let letters = input
+        .clone()
+        .read_all(|r| {
+            let mut letters = Vec::new();
+            loop {
+                letters.extend(r.recover_into(&mut errors, |r| {
+                    r.context("item", |r| {
+                        r.expect("letter", |r| r.read_opt().filter(char::is_ascii_alphabetic))
+                    })
+                }));
+                r.skip_until_opt(',');
+                if !r.consume_opt(',') {
+                    return Ok(letters);
+                }
             }
-        "#}
-    );
-}
-
-#[test]
-#[cfg(feature = "full-backtrace")]
-fn test_external_error_deep_child() {
-    struct DeepExternalError;
-
-    impl<'i> External<'i> for DeepExternalError {
-        fn push_backtrace<E>(self, error: E) -> E
-        where
-            E: WithContext<'i>,
-        {
-            error.with_context("a").with_context("b").with_context("c")
-        }
-    }
-
-    let error = read_all_err!("hello world", |r| {
-        r.try_external("value", |_| {
-            Result::<(usize, ()), DeepExternalError>::Err(DeepExternalError)
         })
-    });
+        .unwrap();
+    let errors = errors.with_input(input);
 
-    assert!(error.is_fatal());
+    assert_eq!(letters, ['a', 'b']);
+    assert_eq!(errors.len(), 2);
     assert_str_eq!(
-        format!("{}\n", error),
+        format!("{}\n", errors),
         indoc! {r#"
-            failed to read and expect an external value: expected value
-            > "hello world"
-               ^^^^^^^^^^^ 
+            error 1 of 2:
+            failed to read and expect a value: expected letter
+            > "a,1,b,2"
+                 ^     
             additional:
-              error line: 1, error offset: 0, input length: 11
+              error line: 1, error column: 3, error offset: 2, input length: 7
             backtrace:
-              1. `read all input`
-              2. `read and expect an external value` (expected value)
-                1. `<context>` (expected c)
-                2. `<context>` (expected b)
-                3. `<context>` (expected a)
+              1. `<context>` (expected item)
+              2. `read and expect a value` (expected letter)
+
+            error 2 of 2:
+            failed to read and expect a value: expected letter
+            > "a,1,b,2"
+                     ^ 
+            additional:
+              error line: 1, error column: 7, error offset: 6, input length: 7
+            backtrace:
+              1. `<context>` (expected item)
+              2. `read and expect a value` (expected letter)
         "#}
     );
 }
@@ -352,7 +1422,7 @@ fn test_expected_length_root() {
     let error: Expected<RootBacktrace> = trigger_expected_length();
 
     assert!(!error.is_fatal());
-    assert_eq!(error.to_retry_requirement(), RetryRequirement::new(2));
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::exactly(2));
     assert_str_eq!(
         format!("{}\n", error),
         indoc! {r#"
@@ -373,7 +1443,7 @@ fn test_expected_length_full() {
     let error: Expected = trigger_expected_length();
 
     assert!(!error.is_fatal());
-    assert_eq!(error.to_retry_requirement(), RetryRequirement::new(2));
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::exactly(2));
     assert_str_eq!(
         format!("{}\n", error),
         indoc! {r#"
@@ -439,6 +1509,8 @@ fn test_expected_value_root() {
             failed to consume input: found a different value to the exact expected
             expected:
             > [31 32 33]
+            found:
+            > [68 65 6c]
             in:
             > [68 65 6c 6c 6f 20 77 6f 72 6c 64]
                ^^ ^^ ^^                         
@@ -463,6 +1535,8 @@ fn test_expected_value_full() {
             failed to consume input: found a different value to the exact expected
             expected:
             > [31 32 33]
+            found:
+            > [68 65 6c]
             in:
             > [68 65 6c 6c 6f 20 77 6f 72 6c 64]
                ^^ ^^ ^^                         
@@ -492,6 +1566,8 @@ fn test_error_max_input_len() {
             failed to consume input: found a different value to the exact expected
             expected:
             > [31 32 33]
+            found:
+            > [68 65 6c]
             in:
             > [68 65 6c 6c 6f ..]
                ^^ ^^ ^^          
@@ -518,11 +1594,13 @@ fn test_error_display_str() {
             failed to consume input: found a different value to the exact expected
             expected:
             > "123"
+            found:
+            > "hel"
             in:
             > "hello world"
                ^^^         
             additional:
-              error line: 1, error offset: 0, input length: 11
+              error line: 1, error column: 1, error offset: 0, input length: 11
             backtrace:
               1. `read all input`
               2. `<context>` (expected hi)
@@ -544,11 +1622,13 @@ fn test_error_display_str_hint() {
             failed to consume input: found a different value to the exact expected
             expected:
             > "123"
+            found:
+            > "hel"
             in:
             > "hello world"
                ^^^         
             additional:
-              error line: 1, error offset: 0, input length: 11
+              error line: 1, error column: 1, error offset: 0, input length: 11
             backtrace:
               1. `read all input`
               2. `<context>` (expected hi)
@@ -557,6 +1637,176 @@ fn test_error_display_str_hint() {
     );
 }
 
+#[test]
+fn test_error_display_compact() {
+    let error: Expected = trigger_expected_value();
+
+    assert_str_eq!(
+        error.display().compact(true).to_string(),
+        "failed to consume input: found a different value to the exact expected \
+         (expected [31 32 33], at offset 0, in [68 65 6c 6c 6f 20 77 6f 72 ..])"
+    );
+    assert_str_eq!(
+        error.display().compact(true).str_hint().to_string(),
+        "failed to consume input: found a different value to the exact expected \
+         (expected \"123\", at offset 0, in \"hello world\")"
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_error_display_json() {
+    let error: Expected = trigger_expected_value();
+
+    assert_str_eq!(
+        error.display().json(true).str_hint().to_string(),
+        concat!(
+            r#"{"kind":"value","reason":null,"operation":"consume input","#,
+            r#""description":"found a different value to the exact expected","#,
+            r#""expected":"exact value","expected_value":"\"123\"","found_value":"\"hel\"","#,
+            r#""span":{"start":0,"end":3},"input_len":11,"retry_requirement":null,"contexts":["#,
+            r#"{"depth":1,"operation":"read all input","expected":null,"span":{"start":0,"end":11}},"#,
+            r#"{"depth":2,"operation":"<context>","expected":"hi","span":null},"#,
+            r#"{"depth":3,"operation":"consume input","expected":"exact value","span":{"start":0,"end":3}}]}"#,
+        )
+    );
+}
+
+#[test]
+fn test_error_display_mode() {
+    use dangerous::display::OutputMode;
+
+    let error: Expected = trigger_expected_value();
+    assert_str_eq!(
+        error.display().mode(OutputMode::Stable).to_string(),
+        error.display().stable(true).to_string()
+    );
+    assert_str_eq!(
+        error.display().compact(true).html(false).to_string(),
+        error.display().compact(true).to_string()
+    );
+    assert_str_eq!(
+        error.display().compact(true).compact(false).to_string(),
+        error.display().to_string()
+    );
+    assert_str_eq!(
+        error.display().stable(true).compact(true).to_string(),
+        error.display().compact(true).to_string()
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_error_display_terminal_width() {
+    let error: Expected = trigger_expected_value();
+    let expected = error.display().input_max_width(28).to_string();
+
+    std::env::set_var("COLUMNS", "30");
+    assert_str_eq!(error.display().terminal_width().to_string(), expected);
+    std::env::set_var("COLUMNS", "invalid");
+    assert_str_eq!(
+        error.display().terminal_width().to_string(),
+        error.display().to_string()
+    );
+    std::env::remove_var("COLUMNS");
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_error_display_stable() {
+    let error: Expected = trigger_expected_valid();
+    assert_str_eq!(
+        error.display().stable(true).to_string(),
+        indoc! {r#"
+            error: failed to take UTF-8 input while a condition remains true: expected utf-8 code point
+            expected: utf-8 code point
+            span: 11..12
+            input length: 13
+            context:
+              1. read all input
+              2. <context> (expected hi)
+              3. take UTF-8 input while a condition remains true (expected utf-8 code point)
+        "#}
+    );
+
+    let error: Expected = trigger_expected_length();
+    assert_str_eq!(
+        error.display().stable(true).input_max_width(4).to_string(),
+        indoc! {r#"
+            error: failed to take a length of input: found 11 bytes when at least 13 bytes was expected
+            expected: enough input for split
+            span: 0..11
+            input length: 11
+            context:
+              1. read all input
+              2. <context> (expected hi)
+              3. take a length of input (expected enough input for split)
+        "#}
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_error_display_max_frames() {
+    fn nested<'i>(r: &mut BytesReader<'i, Expected<'i>>) -> Result<(), Expected<'i>> {
+        r.context("array", |r| {
+            r.consume(b"[")?;
+            if r.peek_eq(b"[") {
+                nested(r)
+            } else {
+                r.consume(b"]")
+            }
+        })
+    }
+
+    let input = b"[".repeat(200);
+    let error = dangerous::input(&input[..]).read_all(nested).unwrap_err();
+    assert_eq!(error.backtrace().count(), 202);
+    assert_str_eq!(
+        error.display().max_frames(3).to_string(),
+        indoc! {r#"
+            failed to consume input: not enough input to match expected value
+            expected:
+            > [5d]
+            in:
+            > [.. 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b 5b]
+                                                                                            ^
+            additional:
+              error offset: 200, input length: 200
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected array) (repeated 2 times)
+              ... 199 more contexts omitted"#
+        }
+    );
+    assert_str_eq!(
+        error.display().stable(true).max_frames(2).to_string(),
+        indoc! {r#"
+            error: failed to consume input: not enough input to match expected value
+            expected: [5d]
+            found: []
+            span: 200..200
+            input length: 200
+            context:
+              1. read all input
+              2. <context> (expected array)
+              ... 200 more contexts omitted
+        "#}
+    );
+    assert_str_eq!(
+        error.display().json(true).max_frames(1).to_string(),
+        concat!(
+            r#"{"kind":"value","reason":null,"operation":"consume input","#,
+            r#""description":"not enough input to match expected value","#,
+            r#""expected":"exact value","expected_value":"[5d]","found_value":"[]","#,
+            r#""span":{"start":200,"end":200},"input_len":200,"#,
+            r#""retry_requirement":{"continue_after":1,"total_hint":null,"exact":true},"#,
+            r#""contexts":[{"depth":1,"operation":"read all input","expected":null,"span":{"start":0,"end":200}}],"#,
+            r#""omitted_contexts":201}"#,
+        )
+    );
+}
+
 #[test]
 fn test_invalid_error_details_span() {
     use dangerous::Input;
@@ -590,3 +1840,92 @@ fn test_invalid_error_details_span() {
         "#}
     );
 }
+
+#[test]
+fn test_error_display_uppercase() {
+    let error: Expected = trigger_expected_value();
+
+    assert_str_eq!(
+        error.display().compact(true).uppercase(true).to_string(),
+        "failed to consume input: found a different value to the exact expected \
+         (expected [31 32 33], at offset 0, in [68 65 6C 6C 6F 20 77 6F 72 ..])"
+    );
+}
+
+#[test]
+fn test_error_display_escapes_non_printable() {
+    let error: Expected = input!("ab\n\x1b[31mX\u{202e}")
+        .read_all(|r| {
+            r.skip_while(|c: char| c.is_ascii_alphabetic());
+            r.consume('?')
+        })
+        .unwrap_err();
+
+    assert_str_eq!(
+        format!("{}", error),
+        indoc! {r#"
+            failed to consume input: found a different value to the exact expected
+            expected:
+            > "?"
+            found:
+            > "\n"
+            in:
+            > "ab\n\u{1b}[31mX\u{202e}"
+                 ^^                    
+            additional:
+              error line: 1, error column: 3, error offset: 2, input length: 12
+            backtrace:
+              1. `read all input`
+              2. `consume input` (expected exact value)"#}
+    );
+}
+
+#[test]
+fn test_error_display_write_to() {
+    let error: Expected = trigger_expected_value();
+    let mut out = Vec::new();
+    error.display().compact(true).write_to(&mut out).unwrap();
+
+    assert_str_eq!(
+        std::str::from_utf8(&out).unwrap(),
+        error.display().compact(true).to_string()
+    );
+}
+
+#[test]
+fn test_error_write_to_buf() {
+    let error: Expected = trigger_expected_value();
+    let message = error.display().compact(true).to_string();
+
+    let mut buf = [0; 256];
+    let len = error.write_to_buf(&mut buf);
+    assert_str_eq!(std::str::from_utf8(&buf[..len]).unwrap(), message);
+
+    let mut buf = [0; 16];
+    let len = error.write_to_buf(&mut buf);
+    assert_eq!(len, 16);
+    assert_str_eq!(
+        std::str::from_utf8(&buf[..len]).unwrap(),
+        format!("{}...", &message[..13])
+    );
+}
+
+#[test]
+fn test_error_write_to_buf_char_boundary() {
+    let error = input!("é")
+        .read_all::<_, _, Expected<'_>>(|r| r.consume("a"))
+        .unwrap_err();
+    let message = error.display().str_hint().to_string();
+    let end = message.find('é').unwrap() + 1;
+
+    let mut buf = vec![0; end + 3];
+    let len = error.display().str_hint().write_to_buf(&mut buf);
+    assert_str_eq!(
+        std::str::from_utf8(&buf[..len]).unwrap(),
+        format!("{}...", &message[..end - 1])
+    );
+
+    let mut buf = [0; 2];
+    assert_eq!(error.write_to_buf(&mut buf), 2);
+    assert_eq!(error.write_to_buf(&mut []), 0);
+}
@@ -0,0 +1,102 @@
+#[macro_use]
+mod common;
+
+use arbitrary::{Arbitrary, Unstructured};
+use common::*;
+use dangerous::fuzz::Mutation;
+use proptest::prelude::*;
+
+///////////////////////////////////////////////////////////////////////////////
+// Mutation
+
+#[test]
+fn test_mutation_apply() {
+    let cases = [
+        (Mutation::Truncate(2), &[1, 2][..]),
+        (Mutation::Truncate(4), &[][..]),
+        (Mutation::Adjust(1, -3), &[1, 255, 3][..]),
+        (Mutation::Replace(5, 9), &[1, 2, 9][..]),
+        (Mutation::Insert(3, 9), &[1, 2, 3, 9][..]),
+        (Mutation::Remove(4), &[1, 3][..]),
+        (Mutation::Append(vec![4, 5]), &[1, 2, 3, 4, 5][..]),
+    ];
+    for (mutation, expected) in cases {
+        let mut bytes = vec![1, 2, 3];
+        mutation.apply(&mut bytes);
+        assert_eq!(bytes, expected, "{:?}", mutation);
+    }
+}
+
+#[test]
+fn test_mutation_apply_empty() {
+    for mutation in [
+        Mutation::Truncate(1),
+        Mutation::Adjust(1, 1),
+        Mutation::Replace(1, 1),
+        Mutation::Remove(1),
+    ] {
+        let mut bytes = Vec::new();
+        mutation.apply(&mut bytes);
+        assert!(bytes.is_empty());
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// arbitrary
+
+#[test]
+fn test_arbitrary_input() {
+    let data = [3, 1, 2, 3, 1, 0xFF];
+    let mut u = Unstructured::new(&data);
+    let bytes = Bytes::arbitrary(&mut u).unwrap();
+    assert!(bytes.as_dangerous().len() <= data.len());
+    let _ = Mutation::arbitrary(&mut u).unwrap();
+
+    let mut u = Unstructured::new(b"\x02hi\x00");
+    let string = String::arbitrary(&mut u).unwrap();
+    assert!(string.as_dangerous().len() <= 4);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// proptest
+
+fn encode(values: &[u16]) -> Vec<u8> {
+    let mut bytes = vec![values.len() as u8];
+    for value in values {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+fn decode<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Vec<u16>, E>
+where
+    E: Error<'i>,
+{
+    let len = r.read()?;
+    r.read_vec(len.into())
+}
+
+proptest! {
+    #[test]
+    fn test_mutated_does_not_panic(
+        bytes in dangerous::fuzz::mutated(
+            prop::collection::vec(any::<u16>(), 0..8).prop_map(|values| encode(&values))
+        )
+    ) {
+        let _ = dangerous::input(bytes.as_slice()).read_all::<_, _, Expected<'_>>(decode);
+    }
+
+    #[test]
+    fn test_truncated_is_retried(
+        values in prop::collection::vec(any::<u16>(), 1..8),
+        offset in any::<usize>(),
+    ) {
+        let mut bytes = encode(&values);
+        let len = bytes.len();
+        Mutation::Truncate(offset % len).apply(&mut bytes);
+        let error = dangerous::input(bytes.as_slice())
+            .read_all::<_, _, Expected<'_>>(decode)
+            .unwrap_err();
+        prop_assert!(error.to_retry_requirement().is_some());
+    }
+}
@@ -0,0 +1,121 @@
+#[macro_use]
+mod common;
+
+use common::*;
+use dangerous::grammar::{self, Rule, RuleContext};
+
+type Sum<'i> = Rule<'i, Bytes<'i>, u32, Expected<'i>>;
+
+fn value<'i>() -> Sum<'i> {
+    Rule::new("value", |r| {
+        grammar::choice(r, "digit or list", &[digit(), list()])
+    })
+}
+
+fn digit<'i>() -> Sum<'i> {
+    Rule::new("digit", |r| {
+        r.try_expect("digit", |r| Ok(char::from(r.read()?).to_digit(10)))
+    })
+}
+
+fn list<'i>() -> Sum<'i> {
+    Rule::recursive("list", |r| {
+        r.consume(b'[')?;
+        let mut sum = 0;
+        while !r.peek_eq(b']') {
+            sum += value().read(r)?;
+            r.consume_opt(b',');
+        }
+        r.consume(b']')?;
+        Ok(sum)
+    })
+}
+
+fn rule_names(error: &Expected<'_>) -> Vec<&'static str> {
+    error
+        .backtrace()
+        .contexts_of::<RuleContext>()
+        .into_iter()
+        .map(|context| context.name)
+        .collect()
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(value().parse(input(b"7")).unwrap(), 7);
+    assert_eq!(value().parse(input(b"[1,[2,[3]],4]")).unwrap(), 10);
+}
+
+#[test]
+fn test_rule_backtrace() {
+    let error = value().parse(input(b"[1,[x]]")).unwrap_err();
+    assert_eq!(error.span_range(), Some(4..4));
+    assert_eq!(
+        rule_names(&error),
+        ["value", "list", "value", "list", "value"]
+    );
+    assert_str_eq!(
+        format!("{:#}\n", error),
+        indoc! {r#"
+            failed to read and expect a value: expected digit or list
+            > "[1,[x]]"
+                   ^   
+            additional:
+              error line: 1, error column: 5, error offset: 4, input length: 7
+            backtrace:
+              1. `read all input`
+              2. `read a grammar rule` (expected value)
+              3. `read and expect a value` (expected digit or list)
+              4. `recover if a condition returns true`
+              5. `read a grammar rule` (expected list)
+              6. `read a grammar rule` (expected value)
+              7. `read and expect a value` (expected digit or list)
+              8. `recover if a condition returns true`
+              9. `read a grammar rule` (expected list)
+              10. `read a grammar rule` (expected value)
+              11. `read and expect a value` (expected digit or list)
+        "#}
+    );
+}
+
+#[test]
+fn test_choice_retry() {
+    let error = value().parse(input(b"[1,")).unwrap_err();
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::exactly(1));
+}
+
+#[test]
+fn test_recursive_depth() {
+    let error = input(b"[[[1]]]")
+        .read_all::<_, _, Expected<'_>>(|r| {
+            r.set_max_depth(2);
+            value().read(r)
+        })
+        .unwrap_err();
+    assert_eq!(error.reason(), Some(Reason::DepthExceeded));
+    assert_eq!(
+        rule_names(&error),
+        ["value", "list", "value", "list", "value", "list"]
+    );
+}
+
+#[test]
+fn test_read_opt() {
+    read_all_ok!(b"[1,x", |r| {
+        assert_eq!(list().read_opt(r), None);
+        assert_eq!(r.take_remaining(), b"[1,x"[..]);
+        Ok(())
+    });
+}
+
+#[test]
+fn test_rule_debug() {
+    let rule = list();
+    assert_eq!(rule.name(), "list");
+    assert!(rule.is_recursive());
+    assert!(!digit().is_recursive());
+    assert_eq!(
+        format!("{:?}", rule),
+        r#"Rule { name: "list", recursive: true }"#
+    );
+}
@@ -11,6 +11,39 @@ fn test_as_dangerous() {
     assert_eq!(input!(b"hello").as_dangerous(), b"hello");
 }
 
+#[test]
+fn test_count() {
+    let bytes: Vec<u8> = (0..100u8).map(|i| i % 7).collect();
+    for needle in 0..8u8 {
+        let expected = bytes.iter().filter(|b| **b == needle).count();
+        assert_eq!(dangerous::input(&bytes[..]).count(needle), expected);
+    }
+}
+
+#[test]
+fn test_count_any() {
+    let bytes: Vec<u8> = (0..=u8::MAX).chain(0x70..0x90).collect();
+    for len in [0, 1, 7, 8, 9, 100, bytes.len()] {
+        let bytes = &bytes[..len];
+        let expected = bytes.iter().filter(|b| b",;\x80\xff".contains(b)).count();
+        assert_eq!(dangerous::input(bytes).count_any(b",;\x80\xff"), expected);
+        assert_eq!(dangerous::input(bytes).count_any(b""), 0);
+        assert_eq!(
+            dangerous::input(bytes).count_any(b"\x80"),
+            dangerous::input(bytes).count(0x80)
+        );
+    }
+}
+
+#[test]
+fn test_num_chars() {
+    let text = "aé€😀\n".repeat(5);
+    for (end, _) in text.char_indices() {
+        let s = &text[..end];
+        assert_eq!(dangerous::input(s).num_chars(), s.chars().count());
+    }
+}
+
 #[test]
 fn test_into_non_empty() {
     // Valid
@@ -44,12 +77,12 @@ fn test_to_dangerous_str_expected_length() {
     let err = input!(&[0b1101_1111])
         .to_dangerous_str::<Expected>()
         .unwrap_err();
-    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(1));
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::exactly(1));
     // Length 3
     let err = input!(&[0b1110_1111])
         .to_dangerous_str::<Expected>()
         .unwrap_err();
-    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(2));
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::exactly(2));
     // Invalid
     let err = input!(&[0b1111_0111])
         .to_dangerous_str::<Expected>()
@@ -57,6 +90,31 @@ fn test_to_dangerous_str_expected_length() {
     assert_eq!(err.to_retry_requirement(), None);
 }
 
+#[test]
+fn test_to_dangerous_str_long() {
+    // Long enough to be validated in blocks with `simdutf8`.
+    let mut bytes = "é".repeat(100).into_bytes();
+    bytes.extend_from_slice(b"\xffabc");
+    assert_eq!(
+        input!(&bytes[..200])
+            .to_dangerous_str::<Expected>()
+            .unwrap(),
+        "é".repeat(100)
+    );
+    // Invalid
+    let err = input!(bytes.as_slice())
+        .to_dangerous_str::<Expected>()
+        .unwrap_err();
+    assert_eq!(err.span_range(), Some(200..201));
+    assert_eq!(err.to_retry_requirement(), None);
+    // Cut short
+    let err = input!(&bytes[..199])
+        .to_dangerous_str::<Expected>()
+        .unwrap_err();
+    assert_eq!(err.span_range(), Some(198..199));
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::exactly(1));
+}
+
 #[test]
 fn test_read_all() {
     // Valid
@@ -73,7 +131,7 @@ fn test_read_all() {
     );
     assert_eq!(
         read_all_err!(b"hello", |r| { r.take(10) }).to_retry_requirement(),
-        RetryRequirement::new(5)
+        RetryRequirement::exactly(5)
     );
 }
 
@@ -95,7 +153,7 @@ fn test_read_partial() {
     // Invalid
     assert_eq!(
         read_partial_err!(b"hello", |r| { r.take(10) }).to_retry_requirement(),
-        RetryRequirement::new(5)
+        RetryRequirement::exactly(5)
     );
 }
 
@@ -114,3 +172,83 @@ fn test_read_infallible() {
         (input!(b"hello"), input!(b"1"))
     );
 }
+
+#[test]
+#[cfg(feature = "std")]
+fn test_vectored_input() {
+    use dangerous::input::VectoredInput;
+    use std::borrow::Cow;
+    use std::io::IoSlice;
+
+    let slices = [IoSlice::new(b"hel"), IoSlice::new(b""), IoSlice::new(b"lo")];
+    let vectored = VectoredInput::new(&slices);
+    assert_eq!(vectored.len(), 5);
+    assert_eq!(&*vectored.to_contiguous(), b"hello");
+    assert!(matches!(vectored.to_contiguous(), Cow::Owned(_)));
+
+    let slices: [&[u8]; 2] = [b"", b"hello"];
+    let vectored = VectoredInput::new(&slices);
+    match vectored.to_contiguous() {
+        Cow::Borrowed(buf) => assert_eq!(buf.as_ptr(), slices[1].as_ptr()),
+        Cow::Owned(_) => panic!("copied a single slice"),
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_vectored_reader() {
+    use dangerous::input::VectoredInput;
+
+    let slices: [&[u8]; 4] = [b"ab", b"", b"cd", b"e"];
+    let vectored = VectoredInput::new(&slices);
+    let mut r = vectored.reader();
+    assert_eq!(r.remaining_len(), 5);
+
+    let first = r.read_partial::<_, _, Invalid>(|r| r.take(2)).unwrap();
+    assert_eq!(first.as_dangerous().as_ptr(), slices[0].as_ptr());
+
+    // A read continuing into the next slice is fatal and consumes nothing.
+    let err = r.read_partial::<_, _, Invalid>(|r| r.take(3)).unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(r.remaining_len(), 3);
+
+    let second = r.read_partial::<_, _, Invalid>(|r| r.take(2)).unwrap();
+    assert_eq!(second.as_dangerous().as_ptr(), slices[2].as_ptr());
+
+    // Errors in the last slice are fatal.
+    let err = r.read_partial::<_, _, Invalid>(|r| r.take(2)).unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(r.read_partial::<_, _, Invalid>(|r| r.read()), Ok(b'e'));
+    assert!(r.at_end());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_vectored_reader_frame_across_slices() {
+    use dangerous::input::VectoredInput;
+
+    fn read_frame<'i>(r: &mut BytesReader<'i, Expected<'i>>) -> Result<Bytes<'i>, Expected<'i>> {
+        let len = r.read()?;
+        r.take(len.into())
+    }
+
+    let slices: [&[u8]; 2] = [b"\x03a", b"bc"];
+    let vectored = VectoredInput::new(&slices);
+    let mut r = vectored.reader();
+
+    // The frame is all there, so the read fails rather than asking for more.
+    let err = r.read_partial(read_frame).unwrap_err();
+    assert!(err.is_fatal());
+    assert!(err
+        .backtrace()
+        .contexts_of::<CoreContext>()
+        .iter()
+        .any(|context| context.operation == CoreOperation::ReadSlice));
+    assert_eq!(r.remaining_len(), 4);
+
+    let joined = vectored.to_contiguous();
+    let frame = dangerous::input(&*joined)
+        .read_all(|r| read_frame(r))
+        .unwrap();
+    assert_eq!(frame, b"abc"[..]);
+}
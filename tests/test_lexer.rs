@@ -0,0 +1,135 @@
+use dangerous::error::{Expected, Reason};
+use dangerous::input::AsciiClass;
+use dangerous::lexer::{ByteClass, Lexer, TokenRule, MAX_RULES};
+use dangerous::Input;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Kind {
+    Ident,
+    Keyword,
+    Number,
+    Eq,
+    EqEq,
+    Space,
+    Upper,
+}
+
+fn rules() -> [TokenRule<Kind>; 6] {
+    [
+        TokenRule::run_after(
+            Kind::Ident,
+            AsciiClass::Alphabetic,
+            AsciiClass::Alphanumeric,
+        ),
+        TokenRule::literal(Kind::Keyword, "let"),
+        TokenRule::run(Kind::Number, AsciiClass::Digit),
+        TokenRule::literal(Kind::Eq, "="),
+        TokenRule::literal(Kind::EqEq, "=="),
+        TokenRule::run(Kind::Space, b' ').skipped(),
+    ]
+}
+
+fn lex<'i>(lexer: &Lexer<'_, Kind>, input: &'i str) -> Result<Vec<(Kind, &'i str)>, Expected<'i>> {
+    lexer
+        .tokens(dangerous::input(input))
+        .map(|token| token.map(|(kind, token)| (kind, token.as_dangerous())))
+        .collect()
+}
+
+#[test]
+fn test_tokens() {
+    let rules = rules();
+    let lexer = Lexer::new(&rules);
+    assert_eq!(
+        lex(&lexer, " x1 == 42 ").unwrap(),
+        [
+            (Kind::Ident, "x1"),
+            (Kind::EqEq, "=="),
+            (Kind::Number, "42")
+        ]
+    );
+    assert_eq!(lex(&lexer, "").unwrap(), []);
+    assert_eq!(lex(&lexer, "   ").unwrap(), []);
+}
+
+#[test]
+fn test_longest_match_then_first_rule() {
+    let rules = rules();
+    let lexer = Lexer::new(&rules);
+    // `==` is longer than `=`, and `letter` is longer than `let`.
+    assert_eq!(
+        lex(&lexer, "===letter").unwrap(),
+        [(Kind::EqEq, "=="), (Kind::Eq, "="), (Kind::Ident, "letter")]
+    );
+    // `let` matches the identifier rule listed first at the same length.
+    assert_eq!(lex(&lexer, "let").unwrap(), [(Kind::Ident, "let")]);
+}
+
+#[test]
+fn test_byte_tokens() {
+    let rules = rules();
+    let lexer = Lexer::new(&rules);
+    let tokens: Vec<_> = lexer
+        .tokens::<_, Expected<'_>>(dangerous::input(b"a = 1"))
+        .map(|token| token.unwrap())
+        .collect();
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(tokens[2].0, Kind::Number);
+    assert_eq!(tokens[2].1, b"1"[..]);
+}
+
+#[test]
+fn test_unexpected_char() {
+    let rules = rules();
+    let lexer = Lexer::new(&rules);
+    let err = lex(&lexer, "a = é").unwrap_err();
+    assert_eq!(err.reason(), Some(Reason::UnexpectedChar));
+    assert_eq!(err.span_range(), Some(4..6));
+    assert!(format!("{:#}", err).contains("lex a token"));
+
+    let mut tokens = lexer.tokens::<_, Expected<'_>>(dangerous::input(b"1\xff"));
+    assert!(tokens.next().unwrap().is_ok());
+    let err = tokens.next().unwrap().unwrap_err();
+    assert_eq!(err.span_range(), Some(1..2));
+    assert!(tokens.next().is_none());
+    assert!(tokens.remaining().is_none());
+}
+
+#[test]
+fn test_class_split_within_char() {
+    // A class matching only the first byte of `é` must not split the char.
+    let rules = [TokenRule::run(Kind::Upper, ByteClass::Byte(0xc3))];
+    let lexer = Lexer::new(&rules);
+    let err = lex(&lexer, "é").unwrap_err();
+    assert_eq!(err.span_range(), Some(0..2));
+    // The same bytes are fine as bytes.
+    let mut tokens = lexer.tokens::<_, Expected<'_>>(dangerous::input("é".as_bytes()));
+    assert_eq!(tokens.next().unwrap().unwrap().1, b"\xc3"[..]);
+}
+
+#[test]
+fn test_read() {
+    let rules = rules();
+    let lexer = Lexer::new(&rules);
+    let (kinds, remaining) = dangerous::input("let x = 1;")
+        .read_partial::<_, _, Expected<'_>>(|r| {
+            let mut kinds = Vec::new();
+            while let Some((kind, _)) = lexer.read(r)? {
+                kinds.push(kind);
+                if kind == Kind::Number {
+                    break;
+                }
+            }
+            Ok(kinds)
+        })
+        .unwrap();
+    assert_eq!(kinds, [Kind::Ident, Kind::Ident, Kind::Eq, Kind::Number]);
+    assert_eq!(remaining, ";"[..]);
+}
+
+#[test]
+#[should_panic(expected = "too many lexer rules")]
+fn test_too_many_rules() {
+    let rules = vec![TokenRule::literal(Kind::Eq, "="); MAX_RULES + 1];
+    let _ = Lexer::new(&rules);
+}
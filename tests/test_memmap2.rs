@@ -0,0 +1,47 @@
+use std::fs;
+use std::path::PathBuf;
+
+use dangerous::input::MappedFile;
+use dangerous::{Input, Invalid};
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("dangerous-{}-{}.bin", name, std::process::id()));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_input_file() {
+    let path = temp_file("input-file", b"hello world");
+    let file = unsafe { dangerous::input_file(&path).unwrap() };
+    assert_eq!(file.path(), path);
+    assert_eq!(file.offset(), 0);
+    assert_eq!(file.len(), 11);
+    assert!(file.input().is_bound());
+    let value: Result<_, Invalid> = file.input().read_all(|r| {
+        let hello = r.take(5)?;
+        r.skip(1)?;
+        Ok((hello, r.take_remaining()))
+    });
+    let (hello, world) = value.unwrap();
+    assert_eq!(hello, b"hello"[..]);
+    assert_eq!(world, b"world"[..]);
+    drop(file);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_input_file_range() {
+    let path = temp_file("input-file-range", b"hello world");
+    let file = unsafe { MappedFile::open_range(&path, 6, 3).unwrap() };
+    assert_eq!(file.offset(), 6);
+    assert_eq!(file.input(), b"wor"[..]);
+    drop(file);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_input_file_missing() {
+    let path = std::env::temp_dir().join("dangerous-does-not-exist.bin");
+    assert!(unsafe { dangerous::input_file(path) }.is_err());
+}
@@ -0,0 +1,49 @@
+use dangerous::{Expected, Input};
+use miette::{Diagnostic, SourceSpan};
+
+fn expected_error(input: &str) -> Expected<'_> {
+    dangerous::input(input)
+        .read_all(|r| {
+            r.context("greeting", |r| {
+                r.consume("hello ")?;
+                r.consume("world")
+            })
+        })
+        .unwrap_err()
+}
+
+#[test]
+fn test_diagnostic_labels() {
+    let error = expected_error("hello there");
+    let labels: Vec<_> = error.labels().unwrap().collect();
+    assert_eq!(labels.len(), 1);
+    assert!(labels[0].primary());
+    assert_eq!(labels[0].inner(), &SourceSpan::from(6..11));
+    assert_eq!(
+        labels[0].label(),
+        Some("found a different value to the exact expected")
+    );
+}
+
+#[test]
+fn test_diagnostic_help() {
+    let error = expected_error("hello there");
+    assert_eq!(error.help().unwrap().to_string(), "expected \"world\"");
+
+    let error = dangerous::input("")
+        .read_all::<_, _, Expected<'_>>(|r| r.read())
+        .unwrap_err();
+    assert_eq!(
+        error.help().unwrap().to_string(),
+        "expected enough input for token"
+    );
+}
+
+#[test]
+fn test_diagnostic_source_code() {
+    let error = expected_error("hello\nthere");
+    let source = error.source_code().unwrap();
+    let contents = source.read_span(&SourceSpan::from(6..11), 0, 0).unwrap();
+    assert_eq!(contents.data(), b"there");
+    assert_eq!(contents.line(), 1);
+}
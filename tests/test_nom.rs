@@ -118,10 +118,9 @@ fn test_parse_color_too_short() {
             > "#2F14D"
                     ^ 
             additional:
-              error line: 1, error offset: 5, input length: 6
+              error line: 1, error column: 6, error offset: 5, input length: 6
             backtrace:
-              1. `read all input`
-              2. `read all input`
+              1. `read all input` (repeated 2 times)
               3. `read and expect an external value` (expected hex color)
                 1. `TakeWhileMN`
         "##}
@@ -139,10 +138,9 @@ fn test_parse_verbose_err() {
             > "err"
                ^^^ 
             additional:
-              error line: 1, error offset: 0, input length: 3
+              error line: 1, error column: 1, error offset: 0, input length: 3
             backtrace:
-              1. `read all input`
-              2. `read all input`
+              1. `read all input` (repeated 2 times)
               3. `read and expect an external value` (expected value)
                 1. `<context>` (expected a)
                 2. `<context>` (expected b)
@@ -163,11 +161,79 @@ fn test_parse_verbose_err_retry() {
             > "f"
                ^ 
             additional:
-              error line: 1, error offset: 0, input length: 1
+              error line: 1, error column: 1, error offset: 0, input length: 1
             backtrace:
-              1. `read all input`
-              2. `read all input`
+              1. `read all input` (repeated 2 times)
               3. `read and expect an external value` (expected value)
         "##}
     );
 }
+
+///////////////////////////////////////////////////////////////////////////////
+// Adapters
+
+fn read_color<'i, E>(r: &mut StringReader<'i, E>) -> Result<color::Value, E>
+where
+    E: Error<'i>,
+{
+    dangerous::interop::read_nom(r, "hex color", color::parse)
+}
+
+fn read_hex<'i, E>(r: &mut StringReader<'i, E>) -> Result<String<'i>, E>
+where
+    E: Error<'i>,
+{
+    r.try_take_while(|c| Ok(c.is_ascii_hexdigit()))
+        .and_then(|hex| r.consume(';').map(|()| hex))
+}
+
+#[test]
+fn test_read_adapter_ok() {
+    let value = read_all_ok!("#2F14DF", |r| {
+        let value = read_color(r)?;
+        assert!(r.at_end());
+        Ok(value)
+    });
+    assert_eq!(value.red, 47);
+}
+
+#[test]
+fn test_read_adapter_partial() {
+    let error = read_all_err!("#2F14DFF", read_color);
+    assert_eq!(error.span_range(), Some(7..8));
+}
+
+#[test]
+fn test_read_adapter_retry() {
+    let error = read_all_err!("f", |r| dangerous::interop::read_nom(
+        r,
+        "value",
+        verbose::parse
+    ));
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::new(5));
+}
+
+#[test]
+fn test_parser_adapter() {
+    use nom::{character::complete::char, error::ErrorKind, sequence::preceded, IResult};
+
+    fn parse(i: &str) -> IResult<&str, dangerous::String<'_>> {
+        preceded(
+            char('#'),
+            dangerous::interop::nom_parser::<_, _, _, Expected<'_>, _>(read_hex),
+        )(i)
+    }
+
+    let (remaining, hex) = parse("#2F;rest").unwrap();
+    assert_eq!(remaining, "rest");
+    assert_eq!(hex, "2F");
+
+    assert_eq!(parse("#2F"), Err(nom::Err::Incomplete(nom::Needed::new(1))));
+    assert_eq!(
+        parse("#2F!"),
+        Err(nom::Err::Error(nom::error::Error::new(
+            "2F!",
+            ErrorKind::Verify
+        )))
+    );
+}
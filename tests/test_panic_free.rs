@@ -0,0 +1,329 @@
+//! Every reader primitive is run against every prefix of inputs mixing single
+//! and multi-byte characters, with every length up to and past the end of the
+//! input. Any errors returned are also displayed, so the formatting of spans
+//! at each boundary is covered. None of it may panic.
+
+use dangerous::{BytesReader, Expected, Input, StringReader};
+
+/// Single, two, three and four byte chars followed by a line break.
+const TEXT: &str = "aé€😀\nb";
+const MAX_LEN: usize = TEXT.len() + 2;
+
+fn byte_inputs() -> impl Iterator<Item = &'static [u8]> {
+    let bytes = TEXT.as_bytes();
+    (0..=bytes.len())
+        .map(move |end| &bytes[..end])
+        .chain((0..bytes.len()).map(move |start| &bytes[start..]))
+        .chain([&b"\xff"[..], b"a\xc3", b"\xf0\x9f\x98"])
+}
+
+fn str_inputs() -> impl Iterator<Item = &'static str> {
+    (0..=TEXT.len())
+        .filter(|&end| TEXT.is_char_boundary(end))
+        .map(|end| &TEXT[..end])
+        .chain(
+            (0..TEXT.len())
+                .filter(|&start| TEXT.is_char_boundary(start))
+                .map(|start| &TEXT[start..]),
+        )
+}
+
+fn display(error: &Expected<'_>) {
+    let _ = error.to_string();
+    let _ = format!("{:#}", error);
+    let _ = error.display().compact(true).to_string();
+    let _ = error.display().stable(true).to_string();
+    let _ = error.span_range();
+    let _ = error.line_column();
+}
+
+fn check_bytes<F>(mut f: F)
+where
+    F: for<'i> FnMut(&mut BytesReader<'i, Expected<'i>>) -> Result<(), Expected<'i>>,
+{
+    for input in byte_inputs() {
+        for result in [
+            dangerous::input(input).read_all(&mut f),
+            dangerous::input(input).read_partial(&mut f).map(drop),
+        ] {
+            if let Err(error) = result {
+                display(&error);
+            }
+        }
+    }
+}
+
+fn check_str<F>(mut f: F)
+where
+    F: for<'i> FnMut(&mut StringReader<'i, Expected<'i>>) -> Result<(), Expected<'i>>,
+{
+    for input in str_inputs() {
+        for result in [
+            dangerous::input(input).read_all(&mut f),
+            dangerous::input(input).read_partial(&mut f).map(drop),
+        ] {
+            if let Err(error) = result {
+                display(&error);
+            }
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Lengths
+
+#[test]
+fn test_bytes_lengths() {
+    for len in 0..=MAX_LEN {
+        check_bytes(|r| r.take(len).map(drop));
+        check_bytes(|r| r.skip(len));
+        check_bytes(|r| r.peek(len).map(drop));
+        check_bytes(|r| r.read_vec::<u8>(len).map(drop));
+        check_bytes(|r| {
+            let _ = r.take_opt(len);
+            let _ = r.skip_opt(len);
+            let _ = r.peek_opt(len);
+            Ok(())
+        });
+    }
+    check_bytes(|r| r.read().map(drop));
+    check_bytes(|r| r.peek_read().map(drop));
+    check_bytes(|r| r.take_array::<1>().map(drop));
+    check_bytes(|r| r.take_array::<4>().map(drop));
+    check_bytes(|r| r.take_remaining_str().map(drop));
+    check_bytes(|r| {
+        let _ = r.read_opt();
+        let _ = r.peek_read_opt();
+        let _ = r.take_array_opt::<3>();
+        let _ = r.take_remaining();
+        Ok(())
+    });
+}
+
+#[test]
+fn test_str_lengths() {
+    for len in 0..=MAX_LEN {
+        check_str(|r| r.take(len).map(drop));
+        check_str(|r| r.skip(len));
+        check_str(|r| r.peek(len).map(drop));
+        check_str(|r| {
+            let _ = r.take_opt(len);
+            let _ = r.skip_opt(len);
+            let _ = r.peek_opt(len);
+            Ok(())
+        });
+    }
+    check_str(|r| r.read().map(drop));
+    check_str(|r| r.peek_read().map(drop));
+    check_str(|r| {
+        let _ = r.read_opt();
+        let _ = r.peek_read_opt();
+        let _ = r.take_remaining();
+        Ok(())
+    });
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Patterns
+
+#[test]
+fn test_bytes_patterns() {
+    let bytes = TEXT.as_bytes();
+    let mut slices = vec![&b""[..], b"\xff"];
+    for start in 0..bytes.len() {
+        for end in start + 1..=(start + 4).min(bytes.len()) {
+            slices.push(&bytes[start..end]);
+        }
+    }
+    for &byte in bytes.iter().chain(&[0xff]) {
+        check_bytes(|r| r.take_until(byte).map(drop));
+        check_bytes(|r| r.take_until_consume(byte).map(drop));
+        check_bytes(|r| r.skip_until(byte));
+        check_bytes(|r| r.skip_until_consume(byte));
+        check_bytes(|r| r.skip_until_byte(byte));
+        check_bytes(|r| r.consume(byte));
+        check_bytes(|r| {
+            let _ = r.take_until_opt(byte);
+            let _ = r.take_until_consume_opt(byte);
+            r.skip_until_opt(byte);
+            r.skip_until_consume_opt(byte);
+            let _ = r.take_while(|b: u8| b != byte);
+            r.skip_while(byte);
+            let _ = r.peek_eq(byte);
+            let _ = r.consume_opt(byte);
+            Ok(())
+        });
+    }
+    for slice in slices {
+        check_bytes(|r| r.take_until(slice).map(drop));
+        check_bytes(|r| r.take_until_consume(slice).map(drop));
+        check_bytes(|r| r.skip_until(slice));
+        check_bytes(|r| r.skip_until_consume(slice));
+        check_bytes(|r| r.skip_until_slice(slice));
+        check_bytes(|r| r.consume(slice));
+        check_bytes(|r| {
+            let _ = r.take_until_opt(slice);
+            let _ = r.take_until_consume_opt(slice);
+            r.skip_until_opt(slice);
+            r.skip_until_consume_opt(slice);
+            let _ = r.take_while(slice);
+            let _ = r.peek_eq(slice);
+            let _ = r.consume_opt(slice);
+            Ok(())
+        });
+    }
+}
+
+#[test]
+fn test_str_patterns() {
+    let mut strs = vec![""];
+    for (start, _) in TEXT.char_indices() {
+        for (end, c) in TEXT[start..].char_indices().take(3) {
+            strs.push(&TEXT[start..start + end + c.len_utf8()]);
+        }
+    }
+    for c in TEXT.chars().chain(['x']) {
+        check_str(|r| r.take_until(c).map(drop));
+        check_str(|r| r.take_until_consume(c).map(drop));
+        check_str(|r| r.skip_until(c));
+        check_str(|r| r.skip_until_consume(c));
+        check_str(|r| r.consume(c));
+        check_str(|r| {
+            let _ = r.take_until_opt(c);
+            let _ = r.take_until_consume_opt(c);
+            r.skip_until_opt(c);
+            r.skip_until_consume_opt(c);
+            let _ = r.take_while(|other: char| other != c);
+            r.skip_while(c);
+            let _ = r.peek_eq(c);
+            let _ = r.consume_opt(c);
+            Ok(())
+        });
+    }
+    for s in strs {
+        check_str(|r| r.take_until(s).map(drop));
+        check_str(|r| r.take_until_consume(s).map(drop));
+        check_str(|r| r.skip_until(s));
+        check_str(|r| r.skip_until_consume(s));
+        check_str(|r| r.consume(s));
+        check_str(|r| {
+            let _ = r.take_until_opt(s);
+            let _ = r.take_until_consume_opt(s);
+            r.skip_until_opt(s);
+            r.skip_until_consume_opt(s);
+            let _ = r.peek_eq(s);
+            let _ = r.consume_opt(s);
+            Ok(())
+        });
+    }
+}
+
+#[test]
+fn test_bytes_str_predicates() {
+    for c in TEXT.chars() {
+        check_bytes(|r| r.take_str_while(|other| other != c).map(drop));
+        check_bytes(|r| r.skip_str_while(|other| other != c));
+        check_bytes(|r| r.try_take_str_while(|other| Ok(other != c)).map(drop));
+        check_bytes(|r| r.try_skip_str_while(|other| Ok(other != c)));
+        check_bytes(|r| {
+            r.try_take_str_while(|other| if other == c { inner_error() } else { Ok(true) })
+                .map(drop)
+        });
+    }
+}
+
+fn inner_error<'i>() -> Result<bool, Expected<'i>> {
+    dangerous::input(b"").read_all(|r| r.consume(b"error").map(|()| true))
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Combinators
+
+#[test]
+fn test_combinators() {
+    for len in 0..=MAX_LEN {
+        check_bytes(|r| {
+            let _ = r.take_consumed(|r| r.skip_opt(len));
+            Ok(())
+        });
+        check_bytes(|r| r.try_take_consumed(|r| r.skip(len)).map(drop));
+        check_bytes(|r| r.verify("length", |r| r.skip_opt(len)));
+        check_bytes(|r| r.try_verify("length", |r| r.skip(len).map(|()| true)));
+        check_bytes(|r| r.expect("length", |r| r.take_opt(len)).map(drop));
+        check_bytes(|r| r.try_expect("length", |r| r.take(len).map(Some)).map(drop));
+        check_bytes(|r| {
+            r.try_external("length", |i: dangerous::Bytes<'_>| {
+                if i.len() >= len {
+                    Ok((len, ()))
+                } else {
+                    Err(())
+                }
+            })
+        });
+        check_bytes(|r| {
+            let _ = r.recover(|r| r.take(len));
+            r.recover_if(|r| r.take(len), |_| true).map(drop)
+        });
+        check_bytes(|r| r.context("length", |r| r.skip(len)));
+        check_bytes(|r| r.peek_context("length", |r| r.peek(len).map(drop)));
+        check_bytes(|r| {
+            r.set_max_depth(len);
+            r.enter("depth", |r| r.enter("depth", |r| r.skip(len)))
+        });
+        check_bytes(|r| {
+            r.set_fuel(len);
+            r.repeat(|r| Ok(r.skip_opt(1))).map(drop)
+        });
+        check_str(|r| r.try_take_consumed(|r| r.skip(len)).map(drop));
+        check_str(|r| r.try_expect("length", |r| r.take(len).map(Some)).map(drop));
+        check_str(|r| r.context("length", |r| r.skip(len)));
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Input
+
+#[test]
+fn test_input_splits() {
+    for input in byte_inputs() {
+        let input = dangerous::input(input);
+        for len in 0..=MAX_LEN {
+            let _ = input
+                .clone()
+                .split_at::<Expected<'_>>(len)
+                .map_err(|e| display(&e));
+            let _ = input
+                .clone()
+                .split_at_byte::<Expected<'_>>(len)
+                .map_err(|e| display(&e));
+            let _ = input.clone().split_at_opt(len);
+            let _ = input.clone().split_at_byte_opt(len);
+            let _ = input.nth(len);
+        }
+        let _ = input.first();
+        let _ = input.last();
+        let _ = input
+            .clone()
+            .into_non_empty::<Expected<'_>>()
+            .map_err(|e| display(&e));
+        let _ = input
+            .to_dangerous_str::<Expected<'_>>()
+            .map_err(|e| display(&e));
+        let _ = input.display().to_string();
+    }
+    for input in str_inputs() {
+        let input = dangerous::input(input);
+        for len in 0..=MAX_LEN {
+            let _ = input
+                .clone()
+                .split_at::<Expected<'_>>(len)
+                .map_err(|e| display(&e));
+            let _ = input
+                .clone()
+                .split_at_byte::<Expected<'_>>(len)
+                .map_err(|e| display(&e));
+            let _ = input.nth(len);
+        }
+        let _ = input.display().to_string();
+    }
+}
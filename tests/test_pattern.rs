@@ -2,6 +2,7 @@
 mod common;
 
 use common::*;
+use dangerous::input::{AsciiClass, BytePattern};
 
 ///////////////////////////////////////////////////////////////////////////////
 // reject: bytes function
@@ -369,6 +370,184 @@ fn test_match_string_empty() {
     });
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// byte pattern
+
+const BYTE_PATTERNS: [BytePattern; 9] = [
+    BytePattern::Eq(0x00),
+    BytePattern::Eq(0x7F),
+    BytePattern::Eq(0x80),
+    BytePattern::Eq(0xFF),
+    BytePattern::Range(b'0', b'9'),
+    BytePattern::Range(0x70, 0x90),
+    BytePattern::Range(0x80, 0xFF),
+    BytePattern::Range(0x00, 0xFF),
+    BytePattern::Range(b'9', b'0'),
+];
+
+#[test]
+fn test_byte_pattern_matches() {
+    for byte in 0..=u8::MAX {
+        assert_eq!(BytePattern::Eq(b'a').matches(byte), byte == b'a');
+        assert_eq!(
+            BytePattern::Range(b'0', b'9').matches(byte),
+            byte.is_ascii_digit()
+        );
+        assert!(!BytePattern::Range(b'9', b'0').matches(byte));
+    }
+}
+
+#[test]
+fn test_reject_bytes_byte_pattern() {
+    for pattern in BYTE_PATTERNS {
+        let filler = match (0..=u8::MAX).find(|b| pattern.matches(*b)) {
+            Some(filler) => filler,
+            None => continue,
+        };
+        for byte in 0..=u8::MAX {
+            for offset in [0, 1, 3, 4, 7, 8, 9, 15, 16, 17, 31, 40, 47] {
+                let mut input = vec![filler; 48];
+                input[offset] = byte;
+                let expected = if pattern.matches(byte) { 48 } else { offset };
+                let (taken, _) = read_partial_ok!(&input[..], |r| Ok(r.take_while(pattern)));
+                let len = taken.len();
+                assert_eq!(len, expected, "{:?} {:#04x} at {}", pattern, byte, offset);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_match_bytes_byte_pattern() {
+    for pattern in BYTE_PATTERNS {
+        let filler = match (0..=u8::MAX).find(|b| !pattern.matches(*b)) {
+            Some(filler) => filler,
+            None => continue,
+        };
+        for byte in 0..=u8::MAX {
+            for offset in [0, 1, 3, 4, 7, 8, 9, 15, 16, 17, 31, 40, 47] {
+                let mut input = vec![filler; 48];
+                input[offset] = byte;
+                let expected = if pattern.matches(byte) { offset } else { 48 };
+                let (taken, _) = read_partial_ok!(&input[..], |r| Ok(r.take_until_opt(pattern)));
+                let len = taken.len();
+                assert_eq!(len, expected, "{:?} {:#04x} at {}", pattern, byte, offset);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_reject_bytes_u8_word_boundaries() {
+    for offset in 0..48 {
+        let mut input = vec![b'a'; 48];
+        input[offset] = b'b';
+        let (taken, _) = read_partial_ok!(&input[..], |r| Ok(r.take_while(b'a')));
+        assert_eq!(taken.len(), offset);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// ascii class
+
+const CLASSES: [AsciiClass; 6] = [
+    AsciiClass::Whitespace,
+    AsciiClass::Digit,
+    AsciiClass::HexDigit,
+    AsciiClass::Alphabetic,
+    AsciiClass::Alphanumeric,
+    AsciiClass::Token,
+];
+
+#[test]
+fn test_ascii_class_matches() {
+    for byte in 0..=u8::MAX {
+        assert_eq!(
+            AsciiClass::Whitespace.matches(byte),
+            byte.is_ascii_whitespace()
+        );
+        assert_eq!(AsciiClass::Digit.matches(byte), byte.is_ascii_digit());
+        assert_eq!(AsciiClass::HexDigit.matches(byte), byte.is_ascii_hexdigit());
+        assert_eq!(
+            AsciiClass::Alphabetic.matches(byte),
+            byte.is_ascii_alphabetic()
+        );
+        assert_eq!(
+            AsciiClass::Alphanumeric.matches(byte),
+            byte.is_ascii_alphanumeric()
+        );
+        assert_eq!(
+            AsciiClass::Token.matches(byte),
+            byte.is_ascii_graphic() && !b"\"(),/:;<=>?@[\\]{}".contains(&byte)
+        );
+    }
+}
+
+#[test]
+fn test_reject_bytes_ascii_class() {
+    for class in CLASSES {
+        let filler = (0..=u8::MAX).find(|b| class.matches(*b)).unwrap();
+        for byte in 0..=u8::MAX {
+            for offset in [0, 1, 15, 16, 17, 31, 40] {
+                let mut input = vec![filler; 48];
+                input[offset] = byte;
+                let expected = if class.matches(byte) { 48 } else { offset };
+                let (taken, _) = read_partial_ok!(&input[..], |r| Ok(r.take_while(class)));
+                let len = taken.len();
+                assert_eq!(len, expected, "{:?} {:#04x} at {}", class, byte, offset);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_match_bytes_ascii_class() {
+    for class in CLASSES {
+        let filler = (0..=u8::MAX).find(|b| !class.matches(*b)).unwrap();
+        for byte in 0..=u8::MAX {
+            for offset in [0, 1, 15, 16, 17, 31, 40] {
+                let mut input = vec![filler; 48];
+                input[offset] = byte;
+                let expected = if class.matches(byte) {
+                    (offset, true)
+                } else {
+                    (48, false)
+                };
+                let ((taken, found), _) =
+                    read_partial_ok!(&input[..], |r| Ok(r.take_until_consume_opt(class)));
+                let len = (taken.len(), found);
+                assert_eq!(len, expected, "{:?} {:#04x} at {}", class, byte, offset);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_reject_string_ascii_class() {
+    let input = "  \t\r\n  \x0c  \t\r\n  \x0c  \t\r\n  \x0c  é  ";
+    assert_eq!(
+        read_all_ok!(input, |r| {
+            r.skip_while(AsciiClass::Whitespace);
+            let v = r.take_remaining();
+            Ok(v)
+        }),
+        "é  "[..]
+    );
+}
+
+#[test]
+fn test_match_string_ascii_class() {
+    let input = "ééééééééééééééé€€€€€€-x";
+    assert_eq!(
+        read_all_ok!(input, |r| {
+            let v = r.take_until_opt(AsciiClass::Token);
+            r.consume("-x")?;
+            Ok(v)
+        }),
+        "ééééééééééééééé€€€€€€"[..]
+    );
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // reject: bytes regex
 
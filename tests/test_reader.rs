@@ -46,6 +46,101 @@ fn test_context() {
     });
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// Reader::enter
+
+fn read_nested<'i>(r: &mut BytesReader<'i, Expected<'i>>) -> Result<usize, Expected<'i>> {
+    r.enter("nested", |r| {
+        r.consume(b"(")?;
+        let depth = if r.peek_eq(b"(") {
+            r.try_expect("nested", |r| read_nested(r).map(Some))?
+        } else {
+            r.depth()
+        };
+        r.consume(b")")?;
+        Ok(depth)
+    })
+}
+
+#[test]
+fn test_enter_within_max_depth() {
+    assert_eq!(read_all_ok!(b"((()))", read_nested), 3);
+    assert_eq!(
+        read_all_ok!(b"(())", |r| {
+            r.set_max_depth(2);
+            read_nested(r)
+        }),
+        2
+    );
+}
+
+#[test]
+fn test_enter_exceeds_max_depth() {
+    use dangerous::error::Reason;
+
+    let input = b"(".repeat(200);
+    let err = dangerous::input(&input[..])
+        .read_all(read_nested)
+        .unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(err.reason(), Some(Reason::DepthExceeded));
+    assert_eq!(err.operation(), CoreOperation::Enter);
+    assert_eq!(err.span_range(), Some(128..128));
+
+    let err = read_all_err!(b"((()))", |r| {
+        r.set_max_depth(2);
+        read_nested(r)
+    });
+    assert_eq!(err.reason(), Some(Reason::DepthExceeded));
+    assert_eq!(err.span_range(), Some(2..2));
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::repeat
+
+fn read_items<'i>(r: &mut BytesReader<'i, Expected<'i>>) -> Result<usize, Expected<'i>> {
+    r.repeat(|r| {
+        r.skip_while(|b: u8| b != b',');
+        Ok(r.consume_opt(b","))
+    })
+}
+
+#[test]
+fn test_repeat_unlimited_fuel() {
+    assert_eq!(read_all_ok!(b"a,b,,c", read_items), 3);
+}
+
+#[test]
+fn test_repeat_within_fuel() {
+    assert_eq!(
+        read_all_ok!(b"a,b", |r| {
+            r.set_fuel(3);
+            let items = r.try_expect("items", |r| read_items(r).map(Some))?;
+            assert_eq!(r.fuel(), Some(1));
+            Ok(items)
+        }),
+        1
+    );
+}
+
+#[test]
+fn test_repeat_exhausts_fuel() {
+    use dangerous::error::Reason;
+
+    let input = b",".repeat(1000);
+    let err = dangerous::input(&input[..])
+        .read_all(|r| {
+            r.set_fuel(100);
+            // Fuel burnt by sub-parses is burnt from the reader.
+            r.try_expect("items", |r| read_items(r).map(Some))
+        })
+        .unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(err.reason(), Some(Reason::FuelExhausted));
+    assert_eq!(err.operation(), CoreOperation::BurnFuel);
+    assert_eq!(err.span_range(), Some(100..100));
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Reader::skip
 
@@ -357,7 +452,7 @@ fn test_consume_exact_same() {
 fn test_consume_same_value_different_len() {
     assert_eq!(
         read_all_err!(b"hell", |r| { r.consume(b"hello") }).to_retry_requirement(),
-        RetryRequirement::new(1)
+        RetryRequirement::exactly(1)
     );
 }
 
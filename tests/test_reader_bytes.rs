@@ -35,6 +35,31 @@ fn test_bytes_input_pretty_debug() {
     });
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// Reader::display
+
+#[test]
+fn test_bytes_reader_display() {
+    read_all_ok!(b"hello world", |r| {
+        assert_eq!(
+            r.display().str_hint().underline().to_string(),
+            " ^^^^^^^^^^^ "
+        );
+        r.consume(b"hello ")?;
+        assert_eq!(r.display().str_hint().to_string(), r#""hello world""#);
+        assert_eq!(
+            r.display().str_hint().underline().to_string(),
+            "       ^^^^^ "
+        );
+        r.consume(b"world")?;
+        assert_eq!(
+            r.display().str_hint().underline().to_string(),
+            "            ^"
+        );
+        Ok(())
+    });
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Reader::consume (u8)
 
@@ -55,7 +80,7 @@ fn test_consume_u8_same_len_different_value() {
 fn test_consume_u8_different_len_and_value() {
     assert_eq!(
         read_all_err!(b"", |r| { r.consume(b'1') }).to_retry_requirement(),
-        RetryRequirement::new(1)
+        RetryRequirement::exactly(1)
     );
 }
 
@@ -137,6 +162,44 @@ fn test_peek_read_opt() {
     }));
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// Reader::skip_until_byte
+
+#[test]
+fn test_skip_until_byte() {
+    read_all_ok!(b"hello\n", |r| {
+        r.skip_until_byte(b'\n')?;
+        r.consume(b'\n')
+    })
+}
+
+#[test]
+fn test_skip_until_byte_err() {
+    let error = read_all_err!(b"hello", |r| { r.skip_until_byte(b'\n') });
+    let expected = read_all_err!(b"hello", |r| { r.skip_until(b'\n') });
+    assert_eq!(error, expected);
+    assert!(error.is_fatal());
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::skip_until_slice
+
+#[test]
+fn test_skip_until_slice() {
+    read_all_ok!(b"hello\r\n", |r| {
+        r.skip_until_slice(b"\r\n")?;
+        r.consume(b"\r\n")
+    })
+}
+
+#[test]
+fn test_skip_until_slice_err() {
+    let error = read_all_err!(b"hello\r", |r| { r.skip_until_slice(b"\r\n") });
+    let expected = read_all_err!(b"hello\r", |r| { r.skip_until(&b"\r\n"[..]) });
+    assert_eq!(error, expected);
+    assert_eq!(error.span_range(), Some(0..6));
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Reader::skip_str_while
 
@@ -173,10 +236,10 @@ fn test_take_str_while_utf8_retry() {
     );
     // Length 2
     let err = read_all_err!(&[0b1101_1111], |r| r.take_str_while(|_| true));
-    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(1));
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::exactly(1));
     // Length 3
     let err = read_all_err!(&[0b1110_1111], |r| r.take_str_while(|_| true));
-    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(2));
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::exactly(2));
     // Invalid
     let err = read_all_err!(&[0b1111_0111], |r| r.take_str_while(|_| true));
     assert_eq!(err.to_retry_requirement(), None);
@@ -208,3 +271,179 @@ fn test_try_take_str_while() {
         "hello"[..]
     );
 }
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::read_vec
+
+#[test]
+fn test_read_vec() {
+    assert_eq!(
+        read_all_ok!(&[1, 0, 2, 0, 3, 0], |r| { r.read_vec::<u16>(3) }),
+        [1, 2, 3]
+    );
+}
+
+#[test]
+fn test_read_vec_retry() {
+    let error = read_all_err!(&[1, 0, 2], |r| { r.read_vec::<u16>(2) });
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::exactly(1));
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::io
+
+#[test]
+fn test_io_read() {
+    use std::io::Read;
+
+    let (chunk, remaining) = read_partial_ok!(b"hello world", |r| {
+        let mut chunk = [0; 5];
+        r.io().read_exact(&mut chunk).unwrap();
+        Ok(chunk)
+    });
+    assert_eq!(&chunk, b"hello");
+    assert_eq!(remaining, b" world"[..]);
+
+    read_all_ok!(b"hello", |r| {
+        let mut all = Vec::new();
+        assert_eq!(r.io().read_to_end(&mut all).unwrap(), 5);
+        assert_eq!(all, b"hello");
+        assert!(r.at_end());
+        Ok(())
+    });
+}
+
+#[test]
+fn test_io_buf_read() {
+    use std::io::BufRead;
+
+    let bytes = b"line 1\nline 2";
+    read_all_ok!(bytes, |r| {
+        let mut io = r.io();
+        assert_eq!(io.fill_buf().unwrap().as_ptr(), bytes.as_ptr());
+        let mut line = std::string::String::new();
+        io.read_line(&mut line).unwrap();
+        assert_eq!(line, "line 1\n");
+        io.consume(100);
+        assert!(r.at_end());
+        Ok(())
+    });
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// FromReader
+
+#[test]
+fn test_from_reader_int() {
+    assert_eq!(
+        read_all_ok!(&[0xFE, 0xFF, 0xFF, 0xFF], i32::from_reader),
+        -2
+    );
+}
+
+#[test]
+fn test_from_reader_array_and_tuple() {
+    assert_eq!(
+        read_all_ok!(&[1, 2, 3, 4], <([u8; 2], u16)>::from_reader),
+        ([1, 2], 0x0403)
+    );
+}
+
+#[test]
+fn test_from_reader_option() {
+    assert_eq!(
+        read_all_ok!(&[0, 1, 7], <(Option<u8>, Option<u8>)>::from_reader),
+        (None, Some(7))
+    );
+    let error = read_all_err!(&[2, 7], Option::<u8>::from_reader);
+    assert_eq!(error.span_range(), Some(0..1));
+}
+
+#[test]
+fn test_from_reader_vec() {
+    assert_eq!(
+        read_all_ok!(&[2, 0, 0, 0, 1, 2], Vec::<u8>::from_reader),
+        [1, 2]
+    );
+    let error = read_all_err!(&[0xFF, 0xFF, 0xFF, 0xFF, 1], Vec::<u8>::from_reader);
+    assert!(error.to_retry_requirement().is_some());
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// read!
+
+#[derive(Debug, PartialEq)]
+struct Record<'i> {
+    magic: (),
+    len: u16,
+    kind: [u8; 2],
+    data: Bytes<'i>,
+}
+
+#[test]
+fn test_read_macro_struct() {
+    let record = read_all_ok!(b"REC\x00\x02ABhi", |r| {
+        read!(
+            r,
+            Record {
+                magic: consume(b"REC"),
+                len: u16_be,
+                kind: [u8; 2],
+                data: take(usize::from(len)),
+            }
+        )
+    });
+    assert_eq!(
+        record,
+        Record {
+            magic: (),
+            len: 2,
+            kind: *b"AB",
+            data: input(b"hi"),
+        }
+    );
+}
+
+#[test]
+fn test_read_macro_tuple() {
+    assert_eq!(
+        read_all_ok!(b"\x01\x00\x00\x02abc", |r| {
+            read!(r, {
+                little: u16,
+                big: i16_be,
+                rest: |r| Ok(r.take_while(|b: u8| b.is_ascii_alphabetic()))
+            })
+        }),
+        (1, 2, input(b"abc"))
+    );
+}
+
+#[test]
+fn test_read_macro_context() {
+    let error = read_all_err!(b"REC\x00\x05ABhi", |r| {
+        read!(
+            r,
+            Record {
+                magic: consume(b"REC"),
+                len: u16_be,
+                kind: [u8; 2],
+                data: take(usize::from(len)),
+            }
+        )
+    });
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::exactly(3));
+    assert_str_eq!(
+        format!("{:#}\n", error),
+        indoc! {r#"
+            failed to take a length of input: found 2 bytes when at least 5 bytes was expected
+            > "REC\0\u{5}ABhi"
+                           ^^ 
+            additional:
+              error line: 1, error column: 8, error offset: 7, input length: 9
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected data)
+              3. `take a length of input` (expected enough input for split)
+        "#}
+    );
+}
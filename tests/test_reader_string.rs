@@ -55,7 +55,7 @@ fn test_consume_char_same_len_different_value() {
 fn test_consume_char_different_len_and_value() {
     assert_eq!(
         read_all_err!("", |r| { r.consume('1') }).to_retry_requirement(),
-        RetryRequirement::new(1)
+        RetryRequirement::exactly(1)
     );
 }
 
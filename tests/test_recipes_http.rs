@@ -0,0 +1,151 @@
+use dangerous::recipes::http::{self, HeaderField, Version};
+use dangerous::{BytesReader, Expected, Input, ToRetryRequirement};
+
+const REQUEST: &[u8] = b"POST /submit?a=1 HTTP/1.1\r\n\
+    Host: example.com\r\n\
+    Content-Type:text/plain \t\r\n\
+    X-Empty:\r\n\
+    X-Opaque: caf\xc3\xa9 \xff\r\n\
+    \r\n\
+    body";
+
+const RESPONSE: &[u8] = b"HTTP/1.0 404 Not Found\r\nContent-Length: 0\r\n\r\n";
+
+fn read_partial<'i, T>(
+    input: &'i [u8],
+    f: fn(&mut BytesReader<'i, Expected<'i>>) -> Result<T, Expected<'i>>,
+) -> Result<(T, &'i [u8]), Expected<'i>> {
+    dangerous::input(input)
+        .read_partial(f)
+        .map(|(value, remaining)| (value, remaining.as_dangerous()))
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Heads
+
+#[test]
+fn test_request_head() {
+    let (head, body) = read_partial(REQUEST, http::read_request_head).unwrap();
+    assert_eq!(head.line.method, "POST");
+    assert_eq!(head.line.target, "/submit?a=1");
+    assert_eq!(head.line.version, Version { major: 1, minor: 1 });
+    assert_eq!(
+        head.headers,
+        [
+            HeaderField {
+                name: "Host",
+                value: b"example.com",
+            },
+            HeaderField {
+                name: "Content-Type",
+                value: b"text/plain",
+            },
+            HeaderField {
+                name: "X-Empty",
+                value: b"",
+            },
+            HeaderField {
+                name: "X-Opaque",
+                value: b"caf\xc3\xa9 \xff",
+            },
+        ]
+    );
+    assert_eq!(head.header("content-type"), Some(&b"text/plain"[..]));
+    assert_eq!(head.header("accept"), None);
+    assert_eq!(body, b"body");
+}
+
+#[test]
+fn test_response_head() {
+    let (head, body) = read_partial(RESPONSE, http::read_response_head).unwrap();
+    assert_eq!(head.line.version, Version { major: 1, minor: 0 });
+    assert_eq!(head.line.code, 404);
+    assert_eq!(head.line.reason, b"Not Found");
+    assert_eq!(head.header("Content-Length"), Some(&b"0"[..]));
+    assert!(body.is_empty());
+}
+
+#[test]
+fn test_status_line_empty_reason() {
+    let (line, _) = read_partial(b"HTTP/1.1 204 \r\n", http::read_status_line).unwrap();
+    assert_eq!(line.code, 204);
+    assert_eq!(line.reason, b"");
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Streaming
+
+#[test]
+fn test_incomplete_heads_are_retryable() {
+    let head_len = REQUEST.len() - b"body".len();
+    for len in 0..head_len {
+        let err = read_partial(&REQUEST[..len], http::read_request_head).unwrap_err();
+        assert!(err.to_retry_requirement().is_some(), "{}", len);
+    }
+    for len in 0..RESPONSE.len() {
+        let err = read_partial(&RESPONSE[..len], http::read_response_head).unwrap_err();
+        assert!(err.to_retry_requirement().is_some(), "{}", len);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Invalid
+
+#[test]
+fn test_invalid_request_lines() {
+    for input in [
+        &b" / HTTP/1.1\r\n"[..],
+        b"GET  / HTTP/1.1\r\n",
+        b"G(T / HTTP/1.1\r\n",
+        b"GET / HTTP/1.1\n",
+        b"GET / HTTP/1.1\r\r",
+        b"GET / HTTP/11\r\n",
+        b"GET / http/1.1\r\n",
+        b"GET /\xff HTTP/1.1\r\n",
+        b"GET / HTTP/1.1 \r\n",
+    ] {
+        let err = read_partial(input, http::read_request_line).unwrap_err();
+        assert!(err.is_fatal(), "{:?}", String::from_utf8_lossy(input));
+    }
+}
+
+#[test]
+fn test_invalid_status_lines() {
+    for input in [
+        &b"HTTP/1.1 20 OK\r\n"[..],
+        b"HTTP/1.1 2x0 OK\r\n",
+        b"HTTP/1.1 200OK\r\n",
+        b"HTTP/1.1 200 O\x00K\r\n",
+    ] {
+        let err = read_partial(input, http::read_status_line).unwrap_err();
+        assert!(err.is_fatal(), "{:?}", String::from_utf8_lossy(input));
+    }
+}
+
+#[test]
+fn test_invalid_header_fields() {
+    for input in [
+        &b"Host : example.com\r\n\r\n"[..],
+        b": example.com\r\n\r\n",
+        b"Host example.com\r\n\r\n",
+        b"Host: example.com\n\r\n",
+        b"Host: exa\x7fmple.com\r\n\r\n",
+        b"X-Folded: a\r\n b\r\n\r\n",
+        b"\r\r",
+    ] {
+        let err = read_partial(input, http::read_header_fields).unwrap_err();
+        assert!(err.is_fatal(), "{:?}", String::from_utf8_lossy(input));
+    }
+}
+
+#[test]
+fn test_error_context() {
+    let err = read_partial(
+        b"GET / HTTP/1.1\r\nHost example.com\r\n\r\n",
+        http::read_request_head,
+    )
+    .unwrap_err();
+    let display = format!("{:#}", err);
+    assert!(display.contains("http request head"), "{}", display);
+    assert!(display.contains("http header field"), "{}", display);
+}
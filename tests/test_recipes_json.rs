@@ -0,0 +1,161 @@
+use std::borrow::Cow;
+
+use dangerous::error::Reason;
+use dangerous::recipes::json::{self, Value};
+use dangerous::{Expected, ToRetryRequirement};
+
+fn parse(input: &str) -> Result<Value<'_>, Expected<'_>> {
+    json::from_bytes(input.as_bytes())
+}
+
+fn assert_invalid(input: &str) {
+    if let Ok(value) = parse(input) {
+        panic!("expected {:?} to be invalid, was {:?}", input, value);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Values
+
+#[test]
+fn test_literals() {
+    assert_eq!(parse("null").unwrap(), Value::Null);
+    assert_eq!(parse("true").unwrap(), Value::Bool(true));
+    assert_eq!(parse(" \t\r\nfalse \n").unwrap(), Value::Bool(false));
+}
+
+#[test]
+fn test_numbers() {
+    for (input, expected) in [
+        ("0", 0.0),
+        ("-0", 0.0),
+        ("42", 42.0),
+        ("-1.5", -1.5),
+        ("1e3", 1000.0),
+        ("1E+3", 1000.0),
+        ("25e-1", 2.5),
+        ("0.125", 0.125),
+    ] {
+        let number = parse(input).unwrap().as_number().unwrap();
+        assert_eq!(number.as_str(), input);
+        assert!((number.to_f64() - expected).abs() < f64::EPSILON);
+    }
+    let number = parse("18446744073709551615").unwrap().as_number().unwrap();
+    assert_eq!(number.to_u64(), Some(u64::MAX));
+    assert_eq!(number.to_i64(), None);
+    let number = parse("-9223372036854775808").unwrap().as_number().unwrap();
+    assert_eq!(number.to_i64(), Some(i64::MIN));
+    assert_eq!(parse("1.0").unwrap().as_number().unwrap().to_i64(), None);
+}
+
+#[test]
+fn test_strings() {
+    assert_eq!(parse(r#""""#).unwrap(), Value::String(Cow::Borrowed("")));
+    assert_eq!(
+        parse(r#""héllo""#).unwrap(),
+        Value::String(Cow::Borrowed("héllo"))
+    );
+    let value = parse(r#""a\"b\\c\/d\be\ff\ng\rh\ti""#).unwrap();
+    assert_eq!(value.as_str(), Some("a\"b\\c/d\u{8}e\u{c}f\ng\rh\ti"));
+    assert!(matches!(value, Value::String(Cow::Owned(_))));
+    assert_eq!(parse(r#""\u00e9\u20AC""#).unwrap().as_str(), Some("é€"));
+    assert_eq!(parse(r#""\ud83d\ude00!""#).unwrap().as_str(), Some("😀!"));
+}
+
+#[test]
+fn test_arrays() {
+    assert_eq!(parse("[]").unwrap(), Value::Array(vec![]));
+    assert_eq!(parse("[ ]").unwrap(), Value::Array(vec![]));
+    assert_eq!(
+        parse("[ null , [true] ,[] ]").unwrap(),
+        Value::Array(vec![
+            Value::Null,
+            Value::Array(vec![Value::Bool(true)]),
+            Value::Array(vec![]),
+        ])
+    );
+}
+
+#[test]
+fn test_objects() {
+    assert_eq!(parse("{}").unwrap(), Value::Object(vec![]));
+    let value = parse(r#"{ "a" : 1, "b": { "c": [] }, "a": null }"#).unwrap();
+    assert_eq!(value.as_object().unwrap().len(), 3);
+    assert_eq!(
+        value.get("a").unwrap().as_number().unwrap().to_u64(),
+        Some(1)
+    );
+    assert_eq!(
+        value.get("b").unwrap().get("c").unwrap().as_array(),
+        Some(&[][..])
+    );
+    assert_eq!(value.get("d"), None);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Invalid
+
+#[test]
+fn test_invalid() {
+    for input in [
+        "",
+        " ",
+        "nul",
+        "True",
+        "NaN",
+        "01",
+        "-",
+        "+1",
+        "1.",
+        ".5",
+        "1e",
+        "1e+",
+        "0x10",
+        "[1,]",
+        "[1 2]",
+        "[,1]",
+        "{\"a\":1,}",
+        "{\"a\" 1}",
+        "{a:1}",
+        "{1:2}",
+        "\"abc",
+        "\"\\x\"",
+        "\"\\u12\"",
+        "\"\\u+123\"",
+        "\"\\ud800\"",
+        "\"\\ud800\\u0041\"",
+        "\"\\udc00\"",
+        "\"\t\"",
+        "\"\u{0}\"",
+        "1 2",
+        "[1]]",
+    ] {
+        assert_invalid(input);
+    }
+    assert!(json::from_bytes::<Expected<'_>>(b"\"\xff\"").is_err());
+}
+
+#[test]
+fn test_incomplete_is_not_fatal() {
+    for input in ["[1, 2", "{\"a\": tr", "\"abc", "\"\\u00"] {
+        let err = parse(input).unwrap_err();
+        assert!(!err.is_fatal(), "{:?}", input);
+    }
+}
+
+#[test]
+fn test_max_depth() {
+    let input = "[".repeat(1000);
+    let err = parse(&input).unwrap_err();
+    assert_eq!(err.reason(), Some(Reason::DepthExceeded));
+}
+
+#[test]
+fn test_error_context() {
+    let err = parse(r#"{ "a": [1, x] }"#).unwrap_err();
+    let display = format!("{:#}", err);
+    assert!(display.contains("json object"), "{}", display);
+    assert!(display.contains("json array"), "{}", display);
+    assert!(display.contains("expected json value"), "{}", display);
+    assert_eq!(err.span_range(), Some(11..12));
+}
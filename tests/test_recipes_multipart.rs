@@ -0,0 +1,106 @@
+use dangerous::recipes::multipart;
+use dangerous::{Expected, Input, ToRetryRequirement};
+
+const BOUNDARY: &[u8] = b"frontier";
+
+const BODY: &[u8] = b"preamble\r\n\
+    --frontier  \r\n\
+    Content-Disposition: form-data; name=\"title\"\r\n\
+    \r\n\
+    hello\r\n\
+    --frontier\r\n\
+    Content-Disposition: form-data; name=\"upload\"; filename=\"a;b.txt\"\r\n\
+    Content-Type: text/plain\r\n\
+    \r\n\
+    line one\r\n\
+    --front line two\r\n\
+    \r\n\
+    --frontier\r\n\
+    \r\n\
+    \r\n\
+    --frontier--\r\n\
+    epilogue";
+
+#[test]
+fn test_parts() {
+    let parts = multipart::from_bytes::<Expected<'_>>(BODY, BOUNDARY).unwrap();
+    assert_eq!(parts.len(), 3);
+
+    assert_eq!(parts[0].name(), Some(&b"title"[..]));
+    assert_eq!(parts[0].filename(), None);
+    assert_eq!(parts[0].body, b"hello"[..]);
+
+    assert_eq!(parts[1].name(), Some(&b"upload"[..]));
+    assert_eq!(parts[1].filename(), Some(&b"a;b.txt"[..]));
+    assert_eq!(parts[1].header("content-type"), Some(&b"text/plain"[..]));
+    assert_eq!(parts[1].body, b"line one\r\n--front line two\r\n"[..]);
+
+    assert!(parts[2].headers.is_empty());
+    assert_eq!(parts[2].name(), None);
+    assert_eq!(parts[2].body, b""[..]);
+}
+
+#[test]
+fn test_body_spans() {
+    let parts = multipart::from_bytes::<Expected<'_>>(BODY, BOUNDARY).unwrap();
+    let span = parts[0].body.span();
+    let start = BODY.windows(5).position(|w| w == b"hello").unwrap();
+    assert_eq!(span.of(BODY), Some(&BODY[start..start + 5]));
+}
+
+#[test]
+fn test_no_preamble_or_parts() {
+    let parts = multipart::from_bytes::<Expected<'_>>(b"--frontier--", BOUNDARY).unwrap();
+    assert!(parts.is_empty());
+}
+
+#[test]
+fn test_incomplete_is_retryable() {
+    let end = BODY.len() - b"--\r\nepilogue".len();
+    for len in 0..end {
+        let err = dangerous::input(&BODY[..len])
+            .read_partial(|r| multipart::read_multipart::<Expected<'_>>(r, BOUNDARY))
+            .unwrap_err();
+        assert!(err.to_retry_requirement().is_some(), "{}", len);
+    }
+}
+
+#[test]
+fn test_streaming_parts() {
+    let (_, mut remaining) = dangerous::input(BODY)
+        .read_partial(|r| multipart::read_preamble::<Expected<'_>>(r, BOUNDARY))
+        .unwrap();
+    let mut bodies = Vec::new();
+    loop {
+        let (part, next) = remaining
+            .read_partial(|r| multipart::read_part::<Expected<'_>>(r, BOUNDARY))
+            .unwrap();
+        remaining = next;
+        match part {
+            Some(part) => bodies.push(part.body),
+            None => break,
+        }
+    }
+    assert_eq!(bodies.len(), 3);
+    assert_eq!(remaining, b"\r\nepilogue"[..]);
+}
+
+#[test]
+fn test_invalid() {
+    for body in [
+        &b"--frontier\r\nBad Header\r\n\r\nbody\r\n--frontier--"[..],
+        b"--frontierx\r\n\r\nbody\r\n--frontier--",
+    ] {
+        let err = dangerous::input(body)
+            .into_bound()
+            .read_all(|r| multipart::read_multipart::<Expected<'_>>(r, BOUNDARY))
+            .unwrap_err();
+        assert!(err.is_fatal());
+    }
+    let err = dangerous::input(&b"--frontier\r\n\r\nbody"[..])
+        .into_bound()
+        .read_all(|r| multipart::read_multipart::<Expected<'_>>(r, BOUNDARY))
+        .unwrap_err();
+    assert!(err.is_fatal());
+    assert!(format!("{:#}", err).contains("multipart delimiter"));
+}
@@ -0,0 +1,271 @@
+use dangerous::de::{self, Format, Item};
+use dangerous::error::Reason;
+use dangerous::input::SpanOffsets;
+use dangerous::{BytesReader, Error, Expected, Fatal, Input, Invalid, Span};
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+fn read_tag<'i>(r: &mut BytesReader<'i, Expected<'i>>) -> Result<(), Expected<'i>> {
+    r.context("tag", |r| r.consume(b"<a>"))
+}
+
+fn expected_error() -> Expected<'static> {
+    dangerous::input(b"<b>")
+        .read_all(|r| r.context("document", read_tag))
+        .unwrap_err()
+}
+
+#[test]
+fn test_serialize_expected() {
+    let value = serde_json::to_value(expected_error()).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "kind": "value",
+            "reason": null,
+            "operation": "consume input",
+            "description": "found a different value to the exact expected",
+            "expected": "exact value",
+            "expected_value": [60, 97, 62],
+            "found_value": [60, 98, 62],
+            "span": { "start": 0, "end": 3 },
+            "input_len": 3,
+            "retry_requirement": null,
+            "contexts": [
+                { "depth": 1, "operation": "read all input", "expected": null, "span": { "start": 0, "end": 3 } },
+                { "depth": 2, "operation": "<context>", "expected": "document", "span": null },
+                { "depth": 3, "operation": "<context>", "expected": "tag", "span": null },
+                { "depth": 4, "operation": "consume input", "expected": "exact value", "span": { "start": 0, "end": 3 } },
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_serialize_expected_retry() {
+    let error = dangerous::input(b"<a")
+        .read_all::<_, _, Expected<'_>>(read_tag)
+        .unwrap_err();
+    let value = serde_json::to_value(error).unwrap();
+    assert_eq!(
+        value["retry_requirement"],
+        json!({ "continue_after": 1, "total_hint": null, "exact": true })
+    );
+}
+
+#[test]
+fn test_serialize_expected_str() {
+    let error = dangerous::input(b"a")
+        .read_all::<_, _, Expected<'_>>(|r| r.consume('b'))
+        .unwrap_err();
+    let value = serde_json::to_value(error).unwrap();
+    assert_eq!(value["expected_value"], json!("b"));
+    assert_eq!(value["found_value"], json!([97]));
+}
+
+#[test]
+fn test_serialize_owned_expected() {
+    let error = expected_error();
+    let borrowed = serde_json::to_value(&error).unwrap();
+    let owned = serde_json::to_value(error.into_owned()).unwrap();
+    assert_eq!(borrowed, owned);
+}
+
+#[test]
+fn test_serialize_invalid() {
+    let error = dangerous::input(b"")
+        .read_all::<_, _, Invalid>(|r| r.consume(b"12"))
+        .unwrap_err();
+    assert_eq!(
+        serde_json::to_value(error).unwrap(),
+        json!({ "retry_requirement": { "continue_after": 2, "total_hint": null, "exact": true } })
+    );
+    assert_eq!(
+        serde_json::to_value(Invalid::fatal()).unwrap(),
+        json!({ "retry_requirement": null })
+    );
+}
+
+#[test]
+fn test_serialize_fatal() {
+    assert_eq!(serde_json::to_value(Fatal).unwrap(), Value::Null);
+}
+
+#[test]
+fn test_span_offsets_round_trip() {
+    let input = dangerous::input(b"hello world");
+    let world = Span::from(&input.as_dangerous()[6..]);
+    let offsets = world.offsets_in(input.span()).unwrap();
+
+    let value = serde_json::to_value(offsets).unwrap();
+    assert_eq!(value, json!({ "start": 6, "end": 11 }));
+
+    let offsets: SpanOffsets = serde_json::from_value(value).unwrap();
+    assert_eq!(offsets.span_in(&input), Some(world));
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// de
+
+fn unsigned(v: u64) -> Vec<u8> {
+    let mut bytes = vec![0x03];
+    bytes.extend_from_slice(&v.to_le_bytes());
+    bytes
+}
+
+fn signed(v: i64) -> Vec<u8> {
+    let mut bytes = vec![0x04];
+    bytes.extend_from_slice(&v.to_le_bytes());
+    bytes
+}
+
+fn header(tag: u8, len: usize) -> Vec<u8> {
+    let mut bytes = vec![tag];
+    bytes.extend_from_slice(&u32::try_from(len).unwrap().to_le_bytes());
+    bytes
+}
+
+fn string(v: &str) -> Vec<u8> {
+    let mut bytes = header(0x06, v.len());
+    bytes.extend_from_slice(v.as_bytes());
+    bytes
+}
+
+fn bytes(v: &[u8]) -> Vec<u8> {
+    let mut bytes = header(0x07, v.len());
+    bytes.extend_from_slice(v);
+    bytes
+}
+
+fn map(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+    let mut bytes = header(0x0B, entries.len());
+    for (key, value) in entries {
+        bytes.extend(string(key));
+        bytes.extend_from_slice(value);
+    }
+    bytes
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+enum Kind {
+    Ping,
+    Data(u64),
+    Pair { a: bool, b: bool },
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Packet<'a> {
+    id: u8,
+    offset: i64,
+    name: &'a str,
+    data: &'a [u8],
+    tags: Vec<String>,
+    parent: Option<u64>,
+    kinds: Vec<Kind>,
+}
+
+fn packet(id: u64) -> Vec<u8> {
+    let mut tags = header(0x0A, 2);
+    tags.extend(string("a"));
+    tags.extend(string("b"));
+    let mut kinds = header(0x0A, 3);
+    kinds.extend(string("Ping"));
+    kinds.extend(map(&[("Data", unsigned(7))]));
+    kinds.extend(map(&[(
+        "Pair",
+        map(&[("a", vec![0x02]), ("b", vec![0x01])]),
+    )]));
+    map(&[
+        ("id", unsigned(id)),
+        ("offset", signed(-2)),
+        ("name", string("hello")),
+        ("data", bytes(&[1, 2])),
+        ("tags", tags),
+        ("parent", [&[0x09][..], &unsigned(3)].concat()),
+        ("kinds", kinds),
+    ])
+}
+
+#[test]
+fn test_deserialize_struct() {
+    let input = packet(1);
+    let value: Packet<'_> = de::from_bytes::<_, Expected<'_>>(&input).unwrap();
+    assert_eq!(
+        value,
+        Packet {
+            id: 1,
+            offset: -2,
+            name: "hello",
+            data: &[1, 2],
+            tags: vec!["a".into(), "b".into()],
+            parent: Some(3),
+            kinds: vec![Kind::Ping, Kind::Data(7), Kind::Pair { a: true, b: false }],
+        }
+    );
+}
+
+#[test]
+fn test_deserialize_rejected_value() {
+    let input = packet(300);
+    let error = de::from_bytes::<Packet<'_>, Expected<'_>>(&input).unwrap_err();
+    let message = error.to_string();
+    assert_eq!(error.span_range(), Some(12..21));
+    assert!(message.contains("(expected Packet)"));
+    assert!(message.contains("invalid value: integer `300`, expected u8"));
+}
+
+#[test]
+fn test_deserialize_missing_field() {
+    let input = map(&[("id", unsigned(1))]);
+    let error = de::from_bytes::<Packet<'_>, Expected<'_>>(&input).unwrap_err();
+    assert_eq!(error.span_range(), Some(5..21));
+    assert!(error.to_string().contains("missing field `offset`"));
+}
+
+#[test]
+fn test_deserialize_invalid_tag() {
+    let error = de::from_bytes::<u64, Expected<'_>>(b"\x0c").unwrap_err();
+    assert_eq!(error.span_range(), Some(0..1));
+    assert!(error.to_string().contains("expected value tag"));
+}
+
+#[test]
+fn test_deserialize_depth() {
+    let input = [&[0x09; 16][..], &[0x00]].concat();
+    let error = dangerous::input(&input[..])
+        .read_all::<_, _, Expected<'_>>(|r| {
+            r.set_max_depth(8);
+            de::from_reader::<IgnoredAny, _>(r)
+        })
+        .unwrap_err();
+    assert_eq!(error.reason(), Some(Reason::DepthExceeded));
+}
+
+/// Digits are numbers and `[n` is a sequence of `n` values.
+struct Digits;
+
+impl<'i> Format<'i> for Digits {
+    fn read_item<E>(&mut self, r: &mut BytesReader<'i, E>) -> Result<Item<'i>, E>
+    where
+        E: Error<'i>,
+    {
+        if r.consume_opt(b'[') {
+            r.expect("count", |r| r.read_opt().filter(u8::is_ascii_digit))
+                .map(|c| Item::Seq(usize::from(c - b'0')))
+        } else {
+            r.expect("digit", |r| r.read_opt().filter(u8::is_ascii_digit))
+                .map(|c| Item::Unsigned(u64::from(c - b'0')))
+        }
+    }
+}
+
+#[test]
+fn test_deserialize_custom_format() {
+    let value = dangerous::input(b"[31[10[0")
+        .read_all::<_, _, Expected<'_>>(|r| {
+            de::from_reader_with::<(u8, Vec<u8>, Vec<u8>), _, _>(r, Digits)
+        })
+        .unwrap();
+    assert_eq!(value, (1, vec![0], vec![]));
+}
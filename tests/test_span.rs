@@ -66,3 +66,17 @@ fn test_of_input_string_invalid() {
     let non_span = Span::from(&parent.as_dangerous().as_bytes()[0..1]);
     assert_eq!(non_span.of(parent), None);
 }
+
+#[test]
+fn test_offsets_in_input_string() {
+    let parent = dangerous::input("a♥b");
+    let sub = &parent.as_dangerous()[1..4];
+
+    let offsets = Span::from(sub).offsets_in(parent.span()).unwrap();
+    assert_eq!(offsets, input::SpanOffsets::from(1..4));
+    assert_eq!(offsets.span_in(&parent), Some(Span::from(sub)));
+
+    assert_eq!(Span::from("b♥a").offsets_in(parent.span()), None);
+    assert_eq!(input::SpanOffsets::from(1..2).span_in(&parent), None);
+    assert_eq!(input::SpanOffsets::from(4..6).span_in(&parent), None);
+}
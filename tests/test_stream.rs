@@ -0,0 +1,309 @@
+use dangerous::error::RetryRequirement;
+use dangerous::stream::{self, Endian, FrameFormat, PrefixWidth};
+use dangerous::{Input, Invalid, ToRetryRequirement};
+
+///////////////////////////////////////////////////////////////////////////////
+// lines
+
+fn collect_chunked_lines(chunks: &[&[u8]]) -> Vec<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut out = Vec::new();
+    for chunk in chunks {
+        buf.extend_from_slice(chunk);
+        let mut lines = stream::lines(&buf);
+        for line in &mut lines {
+            out.push(line.as_dangerous().to_vec());
+        }
+        let consumed = lines.consumed();
+        buf.drain(..consumed);
+    }
+    let last = stream::lines(&buf).remaining();
+    if !last.is_empty() {
+        out.push(last.as_dangerous().to_vec());
+    }
+    out
+}
+
+#[test]
+fn test_lines_empty() {
+    let mut lines = stream::lines(b"");
+    assert_eq!(lines.next(), None);
+    assert_eq!(lines.consumed(), 0);
+    assert!(lines.remaining().is_empty());
+}
+
+#[test]
+fn test_lines_empty_lines() {
+    let lines: Vec<_> = stream::lines(b"\n\r\n").collect();
+    assert_eq!(lines, [&b""[..], &b""[..]]);
+}
+
+#[test]
+fn test_lines_split_across_chunks() {
+    assert_eq!(
+        collect_chunked_lines(&[b"{\"a\":", b"1}\n{\"b\"", b":2}\r", b"\n{}"]),
+        [&b"{\"a\":1}"[..], &b"{\"b\":2}"[..], &b"{}"[..]]
+    );
+}
+
+#[test]
+fn test_lines_byte_at_a_time() {
+    let data = b"hello\nworld\r\n!";
+    let chunks: Vec<&[u8]> = data.chunks(1).collect();
+    assert_eq!(
+        collect_chunked_lines(&chunks),
+        [&b"hello"[..], &b"world"[..], &b"!"[..]]
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// frame
+
+fn read_frame(format: FrameFormat, bytes: &[u8]) -> Result<(&[u8], &[u8]), Invalid> {
+    dangerous::input(bytes)
+        .read_partial(|r| format.read_frame(r))
+        .map(|(frame, remaining)| (frame.as_dangerous(), remaining.as_dangerous()))
+}
+
+#[test]
+fn test_frame_prefix_widths() {
+    let format = FrameFormat::new(PrefixWidth::U8, Endian::Big);
+    assert_eq!(read_frame(format, b"\x01ab"), Ok((&b"a"[..], &b"b"[..])));
+    let format = FrameFormat::new(PrefixWidth::U16, Endian::Little);
+    assert_eq!(read_frame(format, b"\x01\x00a"), Ok((&b"a"[..], &b""[..])));
+    let format = FrameFormat::new(PrefixWidth::U32, Endian::Big);
+    assert_eq!(
+        read_frame(format, b"\x00\x00\x00\x01a"),
+        Ok((&b"a"[..], &b""[..]))
+    );
+    let format = FrameFormat::new(PrefixWidth::U64, Endian::Little);
+    assert_eq!(
+        read_frame(format, b"\x01\x00\x00\x00\x00\x00\x00\x00a"),
+        Ok((&b"a"[..], &b""[..]))
+    );
+}
+
+#[test]
+fn test_frame_includes_header() {
+    let format = FrameFormat::new(PrefixWidth::U16, Endian::Big).includes_header(true);
+    assert_eq!(
+        read_frame(format, b"\x00\x03abc"),
+        Ok((&b"a"[..], &b"bc"[..]))
+    );
+    assert!(read_frame(format, b"\x00\x01").unwrap_err().is_fatal());
+}
+
+#[test]
+fn test_frame_max_len() {
+    let format = FrameFormat::new(PrefixWidth::U32, Endian::Big).max_len(4);
+    assert_eq!(
+        read_frame(format, b"\x00\x00\x00\x04abcd"),
+        Ok((&b"abcd"[..], &b""[..]))
+    );
+    assert!(read_frame(format, b"\x00\x00\x00\x05")
+        .unwrap_err()
+        .is_fatal());
+}
+
+#[test]
+fn test_frame_retry() {
+    let format = FrameFormat::new(PrefixWidth::U32, Endian::Big);
+    assert_eq!(
+        read_frame(format, b"\x00\x00")
+            .unwrap_err()
+            .to_retry_requirement(),
+        RetryRequirement::exactly(2)
+    );
+    let requirement = read_frame(format, b"\x00\x00\x00\x08abc")
+        .unwrap_err()
+        .to_retry_requirement()
+        .unwrap();
+    assert_eq!(requirement.continue_after(), 5);
+    assert!(requirement.is_exact());
+    assert_eq!(requirement.total_hint(), Some(12));
+    assert_eq!(requirement.reserve_hint(7), 5);
+    assert_eq!(requirement.reserve_hint(4), 8);
+}
+
+#[test]
+fn test_frame_retry_total_hint_expected() {
+    let format = FrameFormat::new(PrefixWidth::U16, Endian::Little);
+    let error = dangerous::input(b"\x0a\x00ab")
+        .read_partial::<_, _, dangerous::Expected<'_>>(|r| format.read_frame(r))
+        .unwrap_err();
+    let requirement = error.to_retry_requirement().unwrap();
+    assert_eq!(requirement.continue_after(), 8);
+    assert_eq!(requirement.total_hint(), Some(12));
+}
+
+#[test]
+fn test_frame_retry_total_hint_after_frame() {
+    let format = FrameFormat::new(PrefixWidth::U16, Endian::Little);
+    let error = dangerous::input(b"\x01\x00a\x0a\x00ab")
+        .read_partial::<_, _, Invalid>(|r| {
+            format.read_frame(r)?;
+            format.read_frame(r)
+        })
+        .unwrap_err();
+    let requirement = error.to_retry_requirement().unwrap();
+    assert_eq!(requirement.continue_after(), 8);
+    assert_eq!(requirement.total_hint(), Some(15));
+    assert_eq!(requirement.reserve_hint(7), 8);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// prefix cache
+
+#[test]
+fn test_prefix_cache_skips_validated() {
+    use dangerous::stream::PrefixCache;
+    use std::cell::Cell;
+
+    let calls = Cell::new(0);
+    let read_field = |r: &mut dangerous::BytesReader<'_, Invalid>| {
+        calls.set(calls.get() + 1);
+        let len = r.read()?;
+        r.take(len as usize).map(|i| i.as_dangerous().to_vec())
+    };
+
+    let mut cache = PrefixCache::new();
+    let mut buf = b"\x02ab\x02c".to_vec();
+    for _ in 0..2 {
+        let input = dangerous::input(&buf[..]);
+        let base = input.span();
+        let err = input
+            .read_partial::<_, _, Invalid>(|r| {
+                let first = cache.try_read(base, r, read_field)?;
+                let second = cache.try_read(base, r, read_field)?;
+                Ok((first, second))
+            })
+            .unwrap_err();
+        assert_eq!(err.to_retry_requirement(), RetryRequirement::exactly(1));
+    }
+    assert_eq!(calls.get(), 3);
+    assert_eq!(cache.len(), 1);
+
+    buf.push(b'd');
+    let input = dangerous::input(&buf[..]);
+    let base = input.span();
+    let (value, _) = input
+        .read_partial::<_, _, Invalid>(|r| {
+            let first = cache.try_read(base, r, read_field)?;
+            let second = cache.try_read(base, r, read_field)?;
+            Ok((first, second))
+        })
+        .unwrap();
+    assert_eq!(value, (b"ab".to_vec(), b"cd".to_vec()));
+    assert_eq!(calls.get(), 4);
+    assert_eq!(cache.len(), 2);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// utf8
+
+#[test]
+fn test_utf8_stream_state_invalid() {
+    let mut state = stream::Utf8StreamState::new();
+    let err = state
+        .to_dangerous_str::<Invalid>(&dangerous::input(b"ab\xffcd"))
+        .unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(state.valid_up_to(), 2);
+}
+
+#[test]
+fn test_utf8_stream_state_into_string() {
+    let mut state = stream::Utf8StreamState::new();
+    let string = state
+        .into_string::<Invalid>(dangerous::input("héllo".as_bytes()))
+        .unwrap();
+    assert_eq!(string, "héllo");
+    assert_eq!(state.valid_up_to(), 6);
+}
+
+#[test]
+fn test_utf8_stream_state_shorter_input() {
+    let mut state = stream::Utf8StreamState::new();
+    state
+        .to_dangerous_str::<Invalid>(&dangerous::input(b"hello"))
+        .unwrap();
+    // A shorter input than what was validated is validated from the start.
+    assert!(state
+        .to_dangerous_str::<Invalid>(&dangerous::input(b"\xff"))
+        .is_err());
+    state.reset();
+    assert_eq!(state.valid_up_to(), 0);
+}
+
+#[test]
+fn test_utf8_stream_state_different_input() {
+    let mut state = stream::Utf8StreamState::new();
+    state
+        .to_dangerous_str::<Invalid>(&dangerous::input(b"hello"))
+        .unwrap();
+    // A longer input elsewhere is validated from the start.
+    let err = state
+        .to_dangerous_str::<Invalid>(&dangerous::input(b"\xff\xfe\xfd\xfc\xfbok"))
+        .unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(state.valid_up_to(), 0);
+
+    // A buffer changed in place is still checked before being returned.
+    let mut buf = *b"hello ok";
+    state
+        .to_dangerous_str::<Invalid>(&dangerous::input(&buf[..5]))
+        .unwrap();
+    buf[0] = 0xff;
+    assert!(state
+        .to_dangerous_str::<Invalid>(&dangerous::input(&buf[..]))
+        .is_err());
+    assert_eq!(state.valid_up_to(), 0);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// read_all_retry
+
+fn read_message(r: &mut dangerous::BytesReader<'_, Invalid>) -> Result<Vec<u8>, Invalid> {
+    let len = r.read()?;
+    let body = r.take(len as usize)?;
+    Ok(body.as_dangerous().to_vec())
+}
+
+#[test]
+fn test_read_all_retry_refills() {
+    let mut requirements = Vec::new();
+    let mut chunks = vec![&b"\x04"[..], b"ab", b"cd"].into_iter();
+    let message = dangerous::read_all_retry(
+        Vec::new(),
+        |buf, requirement| {
+            requirements.push(requirement.continue_after());
+            chunks
+                .next()
+                .map(|chunk| buf.extend_from_slice(chunk))
+                .is_some()
+        },
+        read_message,
+    );
+    assert_eq!(message.unwrap(), b"abcd");
+    assert_eq!(requirements, [1, 4, 2]);
+}
+
+#[test]
+fn test_read_all_retry_end_of_input() {
+    let error = dangerous::read_all_retry(vec![3, b'a'], |_, _| false, read_message).unwrap_err();
+    assert!(error.is_fatal());
+
+    let error = dangerous::read_all_retry(vec![3, b'a'], |_, _| true, read_message).unwrap_err();
+    assert!(error.is_fatal());
+}
+
+#[test]
+fn test_read_all_retry_fatal() {
+    let error = dangerous::read_all_retry(
+        vec![1, b'a', b'b'],
+        |_, _| panic!("refilled on a fatal error"),
+        read_message,
+    )
+    .unwrap_err();
+    assert!(error.is_fatal());
+}
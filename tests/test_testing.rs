@@ -0,0 +1,80 @@
+#[macro_use]
+mod common;
+
+use common::*;
+use dangerous::testing::{assert_error_spans, assert_round_trip};
+
+///////////////////////////////////////////////////////////////////////////////
+
+fn decode<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Vec<&'i [u8]>, E>
+where
+    E: Error<'i>,
+{
+    let count = r.read()?;
+    let mut values = Vec::new();
+    for _ in 0..count {
+        let len = r.read()?;
+        values.push(r.take(len.into())?.as_dangerous());
+    }
+    Ok(values)
+}
+
+fn encode(values: &[&[u8]]) -> Vec<u8> {
+    let mut bytes = vec![values.len() as u8];
+    for value in values {
+        bytes.push(value.len() as u8);
+        bytes.extend_from_slice(value);
+    }
+    bytes
+}
+
+static OTHER: [u8; 1] = [0];
+
+/// Returns errors linked to the wrong origin within the parent input.
+fn decode_wrong_origin<'i>(r: &mut BytesReader<'i, Expected<'i>>) -> Result<(), Expected<'i>> {
+    let (value, consumed) = r.try_take_consumed(|r| r.take(2))?;
+    value
+        .read_all::<_, _, Expected<'i>>(|r| r.consume(b"ok"))
+        .map_err(|err| err.with_parent(consumed, Span::from(&OTHER[..])))
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// assert_round_trip
+
+#[test]
+fn test_round_trip() {
+    assert_round_trip(b"\x02\x01a\x02bc", |r| {
+        decode(r).map(|values| encode(&values))
+    });
+}
+
+#[test]
+#[should_panic(expected = "failed to read the input")]
+fn test_round_trip_invalid() {
+    assert_round_trip(b"\x02\x01a", |r| decode(r).map(|values| encode(&values)));
+}
+
+#[test]
+#[should_panic(expected = "serialized value changed")]
+fn test_round_trip_unstable() {
+    assert_round_trip(b"\x01\x01a", |r| {
+        decode(r).map(|mut values| {
+            values.push(b"b");
+            encode(&values)
+        })
+    });
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// assert_error_spans
+
+#[test]
+fn test_error_spans() {
+    assert_error_spans(b"\x02\x01a\x02bc", |r| decode(r).map(drop));
+}
+
+#[test]
+#[should_panic(expected = "error origin is outside of the parent input")]
+fn test_error_spans_wrong_origin() {
+    assert_error_spans(b"ok", |r| decode_wrong_origin(r));
+}
@@ -0,0 +1,144 @@
+use dangerous::error::RetryRequirement;
+use dangerous::stream::{DecodeError, Decoder};
+use dangerous::{BytesReader, Error, Invalid};
+use tokio_util::bytes::BytesMut;
+use tokio_util::codec::Decoder as _;
+
+///////////////////////////////////////////////////////////////////////////////
+
+fn read_frame<E>(r: &mut BytesReader<'_, E>) -> Result<Vec<u8>, E>
+where
+    E: for<'i> Error<'i>,
+{
+    r.context("frame", |r| {
+        r.consume(b'>')?;
+        let len = r.read()?;
+        let body = r.take(len as usize)?;
+        Ok(body.as_dangerous().to_vec())
+    })
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn test_decode_empty() {
+    let mut decoder = Decoder::<_, Invalid>::new(read_frame);
+    let mut buf = BytesMut::new();
+    assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+    assert_eq!(decoder.decode_eof(&mut buf).unwrap(), None);
+}
+
+#[test]
+fn test_decode_frames() {
+    let mut decoder = Decoder::<_, Invalid>::new(read_frame);
+    let mut buf = BytesMut::from(&b">\x02ab>\x01c>"[..]);
+    assert_eq!(decoder.decode(&mut buf).unwrap(), Some(b"ab".to_vec()));
+    assert_eq!(decoder.decode(&mut buf).unwrap(), Some(b"c".to_vec()));
+    assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+    assert_eq!(&buf[..], b">");
+    buf.extend_from_slice(b"\x03def");
+    assert_eq!(decoder.decode(&mut buf).unwrap(), Some(b"def".to_vec()));
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_decode_retry_reserves() {
+    let mut decoder = Decoder::<_, Invalid>::new(read_frame);
+    let mut buf = BytesMut::from(&b">\x10"[..]);
+    assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+    assert_eq!(&buf[..], b">\x10");
+    let required = RetryRequirement::new(16).unwrap().continue_after();
+    assert!(buf.capacity() - buf.len() >= required);
+}
+
+#[test]
+fn test_decode_fatal() {
+    let mut decoder = Decoder::<_, Invalid>::new(read_frame);
+    let mut buf = BytesMut::from(&b"<\x01a"[..]);
+    match decoder.decode(&mut buf) {
+        Err(DecodeError::Invalid(err)) => assert_eq!(err, Invalid::fatal()),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    // Nothing is consumed on error.
+    assert_eq!(&buf[..], b"<\x01a");
+}
+
+#[test]
+fn test_decode_eof_incomplete() {
+    let mut decoder = Decoder::<_, Invalid>::new(read_frame);
+    let mut buf = BytesMut::from(&b">\x02a"[..]);
+    assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+    assert!(matches!(
+        decoder.decode_eof(&mut buf),
+        Err(DecodeError::Invalid(_))
+    ));
+}
+
+#[test]
+fn test_decode_no_progress() {
+    let mut decoder = Decoder::new(|r: &mut BytesReader<'_, Invalid>| Ok(r.peek_read()?));
+    let mut buf = BytesMut::from(&b"a"[..]);
+    assert!(matches!(
+        decoder.decode(&mut buf),
+        Err(DecodeError::NoProgress)
+    ));
+    assert_eq!(&buf[..], b"a");
+}
+
+#[test]
+fn test_decode_error_display() {
+    let err = DecodeError::<Invalid>::from(std::io::Error::new(
+        std::io::ErrorKind::Other,
+        "broken pipe",
+    ));
+    assert_eq!(err.to_string(), "broken pipe");
+    let err = DecodeError::Invalid(Invalid::fatal());
+    assert_eq!(err.to_string(), Invalid::fatal().to_string());
+    let err = DecodeError::<Invalid>::NoProgress;
+    assert_eq!(err.to_string(), "read function consumed no input");
+}
+
+#[test]
+fn test_decode_retry_reserves_total_hint() {
+    let mut decoder = Decoder::new(|r: &mut BytesReader<'_, Invalid>| {
+        let len = r.read()?;
+        if r.peek_read_opt().is_none() {
+            let req = RetryRequirement::new(1)
+                .unwrap()
+                .with_total_hint(len as usize + 1);
+            return Err(Invalid::retry(req));
+        }
+        r.take(len as usize).map(|i| i.as_dangerous().to_vec())
+    });
+    let mut buf = BytesMut::from(&b"\x40"[..]);
+    assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+    assert!(buf.capacity() - buf.len() >= 0x40);
+}
+
+#[test]
+fn test_frame_format_decoder() {
+    use dangerous::stream::{Endian, FrameFormat, PrefixWidth};
+
+    let mut decoder = FrameFormat::new(PrefixWidth::U16, Endian::Big).max_len(8);
+    let mut buf = BytesMut::from(&b"\x00\x02ab\x00\x03c"[..]);
+    assert_eq!(decoder.decode(&mut buf).unwrap().unwrap(), &b"ab"[..]);
+    assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+    buf.extend_from_slice(b"de");
+    assert_eq!(decoder.decode(&mut buf).unwrap().unwrap(), &b"cde"[..]);
+    assert!(buf.is_empty());
+    buf.extend_from_slice(b"\x00\x09");
+    assert!(matches!(
+        decoder.decode(&mut buf),
+        Err(DecodeError::Invalid(_))
+    ));
+}
+
+#[test]
+fn test_frame_format_decoder_reserves_frame() {
+    use dangerous::stream::{Endian, FrameFormat, PrefixWidth};
+
+    let mut decoder = FrameFormat::new(PrefixWidth::U32, Endian::Big);
+    let mut buf = BytesMut::from(&b"\x00\x00\x01\x00ab"[..]);
+    assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+    assert!(buf.capacity() >= 4 + 0x100);
+}
@@ -0,0 +1,85 @@
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use dangerous::{Input, Invalid};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::with_default;
+use tracing::{Event, Metadata, Subscriber};
+
+/// Records each span created as its name and fields.
+#[derive(Clone, Default)]
+struct Spans(Arc<Mutex<Vec<String>>>);
+
+impl Spans {
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push_str(&format!(" {}={:?}", field.name(), value));
+    }
+}
+
+impl Subscriber for Spans {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields(span.metadata().name().to_owned());
+        span.record(&mut fields);
+        let mut spans = self.0.lock().unwrap();
+        spans.push(fields.0);
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn test_context_spans() {
+    let spans = Spans::default();
+    let result = with_default(spans.clone(), || {
+        dangerous::input(b"hello")
+            .read_all::<_, _, Invalid>(|r| r.context("greeting", |r| r.consume(b"hello")))
+    });
+    assert_eq!(result, Ok(()));
+    assert_eq!(
+        spans.take(),
+        [
+            "context operation=read all input expected=",
+            "context operation=<context> expected=greeting",
+        ]
+    );
+}
+
+#[test]
+fn test_expect_spans() {
+    let spans = Spans::default();
+    let result = with_default(spans.clone(), || {
+        dangerous::input(b"1").read_all::<_, _, Invalid>(|r| {
+            r.try_expect("digit", |r| Ok(r.read()?.checked_sub(b'0')))
+        })
+    });
+    assert_eq!(result, Ok(1));
+    assert_eq!(
+        spans.take(),
+        [
+            "context operation=read all input expected=",
+            "context operation=read and expect a value expected=digit",
+        ]
+    );
+}
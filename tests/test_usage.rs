@@ -14,7 +14,7 @@ fn test_usage_with_fn() {
 
     assert_eq!(
         input.read_all(do_thing).unwrap_err().to_retry_requirement(),
-        RetryRequirement::new(1)
+        RetryRequirement::exactly(1)
     );
 }
 
@@ -45,7 +45,7 @@ fn test_streaming_usage_with_valid_requirement() {
 
     assert_eq!(
         result.unwrap_err().to_retry_requirement(),
-        RetryRequirement::new(2)
+        RetryRequirement::exactly(2)
     );
 }
 
@@ -68,7 +68,7 @@ fn test_retry_unbound_spent_reader() {
 
     assert_eq!(
         result.unwrap_err().to_retry_requirement(),
-        RetryRequirement::new(1),
+        RetryRequirement::exactly(1),
     );
 }
 
@@ -0,0 +1,122 @@
+#[macro_use]
+mod common;
+
+use common::*;
+use winnow::ascii::{digit1, space0};
+use winnow::combinator::{delimited, separated};
+use winnow::token::{literal, take, take_until};
+use winnow::{Parser, Partial};
+
+///////////////////////////////////////////////////////////////////////////////
+
+fn read_number<'i>(r: &mut BytesReader<'i, Expected<'i>>) -> Result<u8, Expected<'i>> {
+    dangerous::interop::read_winnow(
+        r,
+        digit1
+            .try_map(|digits: Bytes<'i>| digits.to_dangerous_str::<Invalid>().unwrap().parse())
+            .context("number"),
+    )
+}
+
+#[test]
+fn test_read_bytes() {
+    let numbers: Vec<u8> = read_all_ok!(b"[1, 2, 3]", |r| {
+        dangerous::interop::read_winnow(
+            r,
+            delimited(
+                '[',
+                separated(1.., delimited(space0, digit1, space0), ','),
+                ']',
+            ),
+        )
+        .map(|numbers: Vec<Bytes<'_>>| {
+            numbers
+                .into_iter()
+                .map(|number| number.as_dangerous()[0] - b'0')
+                .collect()
+        })
+    });
+    assert_eq!(numbers, [1, 2, 3]);
+}
+
+#[test]
+fn test_read_string() {
+    let value = dangerous::input(b"key: value;rest")
+        .into_string::<Expected<'_>>()
+        .unwrap()
+        .read_all::<_, _, Expected<'_>>(|r| {
+            let value = dangerous::interop::read_winnow(
+                r,
+                (literal("key: "), take_until(0.., ';'), ';')
+                    .map(|(_, value, _): (String<'_>, String<'_>, char)| value),
+            )?;
+            r.consume("rest")?;
+            Ok(value)
+        })
+        .unwrap();
+    assert_eq!(value, "value");
+}
+
+#[test]
+fn test_read_advances() {
+    let number = read_all_ok!(b"12!", |r| {
+        let number = read_number(r)?;
+        r.consume(b'!')?;
+        Ok(number)
+    });
+    assert_eq!(number, 12);
+}
+
+#[test]
+fn test_read_external_error() {
+    let error = read_all_err!(b"!300", |r| {
+        r.consume(b'!')?;
+        read_number(r)
+    });
+    assert_eq!(error.span_range(), Some(1..4));
+    assert_str_eq!(
+        format!("{:#}\n", error),
+        indoc! {r#"
+            failed to read and expect an external value: expected value
+            > "!300"
+                ^^^ 
+            additional:
+              error line: 1, error column: 2, error offset: 1, input length: 4
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected number)
+              3. `read and expect an external value` (expected value)
+                1. `parse from string` (expected integer)
+        "#}
+    );
+}
+
+#[test]
+fn test_read_error() {
+    let error = read_all_err!(b"a", read_number);
+    assert_eq!(error.span_range(), Some(0..0));
+    assert!(error.to_retry_requirement().is_none());
+}
+
+#[test]
+fn test_read_invalid() {
+    let error = dangerous::input(b"a")
+        .read_all::<_, _, Invalid>(|r| dangerous::interop::read_winnow(r, digit1))
+        .unwrap_err();
+    assert!(error.to_retry_requirement().is_none());
+}
+
+#[test]
+fn test_partial_incomplete() {
+    let mut stream = Partial::new(dangerous::input(b"ab"));
+    let error = take::<_, _, Expected<'_>>(4usize)
+        .parse_next(&mut stream)
+        .unwrap_err();
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::exactly(2));
+
+    let mut stream = Partial::new(dangerous::input(b"ab"));
+    let error = take::<_, _, Invalid>(4usize)
+        .parse_next(&mut stream)
+        .unwrap_err();
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::exactly(2));
+}